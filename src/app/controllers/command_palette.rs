@@ -0,0 +1,119 @@
+//! Command palette (Ctrl+Shift+P) — run any menu action by name.
+//!
+//! The command list is taken from the menu bar itself (see
+//! `menu::palette_commands`), so every action that has a menu item shows
+//! up, with its effective shortcut. Plugin menu items are sent directly as
+//! `PluginMenuAction`. Recently used commands are kept in the settings and
+//! listed first.
+
+use crate::app::services::fuzzy::fuzzy_score;
+
+/// How many recently used commands are remembered
+pub const MAX_RECENT_COMMANDS: usize = 10;
+
+/// Score bonus for the most recently used command; it drops with each older one
+const BONUS_RECENT: i32 = 30;
+
+#[derive(Debug, Clone)]
+pub enum CommandAction {
+    /// Trigger the menu item at this path (its callback sends the message)
+    Menu(String),
+    /// Send `PluginMenuAction` for a plugin's menu item
+    Plugin { plugin_name: String, action: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteCommand {
+    /// Stable id for the history: the menu path, or the plugin command id
+    pub id: String,
+    /// Shown in the list, e.g. "File: Save As..."
+    pub label: String,
+    /// Effective shortcut ("" when unbound)
+    pub shortcut: String,
+    pub action: CommandAction,
+}
+
+/// Palette label for a menu path: "View/Split Editor Right" becomes
+/// "View: Split Editor Right"
+pub fn command_label(path: &str) -> String {
+    path.split('/')
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(": ")
+}
+
+/// Indices of the commands matching `query`, best first. With an empty
+/// query the recently used commands come first, then the rest in menu order.
+pub fn rank_commands(commands: &[PaletteCommand], recent: &[String], query: &str) -> Vec<usize> {
+    let recency = |id: &str| recent.iter().position(|r| r == id);
+    let mut scored: Vec<(i32, usize)> = commands
+        .iter()
+        .enumerate()
+        .filter_map(|(i, cmd)| {
+            let score = fuzzy_score(query, &cmd.label)?;
+            let bonus = recency(&cmd.id).map_or(0, |r| (BONUS_RECENT - r as i32).max(1));
+            Some((score + bonus, i))
+        })
+        .collect();
+    // Stable sort keeps menu order among equal scores
+    scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+/// Move `id` to the front of the recently used list
+pub fn remember_command(recent: &mut Vec<String>, id: &str) {
+    recent.retain(|r| r != id);
+    recent.insert(0, id.to_string());
+    recent.truncate(MAX_RECENT_COMMANDS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(path: &str) -> PaletteCommand {
+        PaletteCommand {
+            id: path.to_string(),
+            label: command_label(path),
+            shortcut: String::new(),
+            action: CommandAction::Menu(path.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_command_label() {
+        assert_eq!(command_label("File/Save As..."), "File: Save As...");
+        assert_eq!(
+            command_label("Plugins/General/Run All Checks"),
+            "Plugins: General: Run All Checks"
+        );
+    }
+
+    #[test]
+    fn test_rank_commands_recent_first() {
+        let commands = vec![
+            command("File/Save"),
+            command("File/Save As..."),
+            command("Edit/Select All"),
+        ];
+        let recent = vec!["Edit/Select All".to_string()];
+        assert_eq!(rank_commands(&commands, &recent, ""), vec![2, 0, 1]);
+        assert_eq!(rank_commands(&commands, &[], "save as"), vec![1]);
+
+        // Recency breaks ties between equally good matches
+        let recent = vec!["File/Save As...".to_string()];
+        assert_eq!(rank_commands(&commands, &recent, "fsa")[0], 1);
+    }
+
+    #[test]
+    fn test_remember_command() {
+        let mut recent = Vec::new();
+        for i in 0..MAX_RECENT_COMMANDS + 2 {
+            remember_command(&mut recent, &format!("cmd{}", i));
+        }
+        remember_command(&mut recent, "cmd5");
+        assert_eq!(recent.len(), MAX_RECENT_COMMANDS);
+        assert_eq!(recent[0], "cmd5");
+        assert_eq!(recent.iter().filter(|r| *r == "cmd5").count(), 1);
+    }
+}
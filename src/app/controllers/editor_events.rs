@@ -0,0 +1,195 @@
+//! Debounced editor events for plugin hooks.
+//!
+//! Cursor moves, selection changes, tab activations, window focus, session
+//! switches and file renames are collected here and delivered to plugins in
+//! one batch 300ms after the first event (same cadence as `DoTextChangeHook`).
+//! Rapid bursts (holding an arrow key, cycling tabs) collapse into a single
+//! hook call carrying the latest state.
+
+use crate::app::domain::document::DocumentId;
+
+/// Editor events waiting for the next `DoEditorEventHooks` flush.
+#[derive(Debug, Default, PartialEq)]
+pub struct PendingEditorEvents {
+    /// Latest cursor position in the active document
+    pub cursor: Option<i32>,
+    /// Latest selection (start, end); start == end means cleared
+    pub selection: Option<(i32, i32)>,
+    /// Active tab changed
+    pub tab_activated: bool,
+    /// Window regained focus
+    pub focus_gained: bool,
+    /// Name of the session switched to
+    pub session_switched: Option<String>,
+    /// (old_path, new_path) for each rename, in order
+    pub renamed: Vec<(String, String)>,
+}
+
+impl PendingEditorEvents {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Tracks cursor/selection state between event loop iterations and
+/// accumulates pending events for the debounced flush.
+#[derive(Debug, Default)]
+pub struct EditorEventTracker {
+    /// Last observed (document, cursor position)
+    last_cursor: Option<(DocumentId, i32)>,
+    /// Last observed (document, selection); None = no selection
+    last_selection: Option<(DocumentId, Option<(i32, i32)>)>,
+    pending: PendingEditorEvents,
+    /// Whether a DoEditorEventHooks timer is active
+    timer_active: bool,
+}
+
+impl EditorEventTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the current cursor position. Moves caused by switching documents
+    /// only reset the baseline (the tab activation is reported instead).
+    /// Returns true if a flush timer needs to be started.
+    pub fn observe_cursor(&mut self, doc: DocumentId, position: i32) -> bool {
+        let previous = self.last_cursor.replace((doc, position));
+        match previous {
+            Some((prev_doc, prev_pos)) if prev_doc == doc && prev_pos != position => {
+                self.pending.cursor = Some(position);
+                self.arm_timer()
+            }
+            _ => false,
+        }
+    }
+
+    /// Record the current selection (None when nothing is selected).
+    /// Returns true if a flush timer needs to be started.
+    pub fn observe_selection(&mut self, doc: DocumentId, selection: Option<(i32, i32)>) -> bool {
+        let previous = self.last_selection.replace((doc, selection));
+        match previous {
+            Some((prev_doc, prev_sel)) if prev_doc == doc && prev_sel != selection => {
+                let (start, end) = selection.unwrap_or((0, 0));
+                self.pending.selection = Some((start, end));
+                self.arm_timer()
+            }
+            _ => false,
+        }
+    }
+
+    /// Queue a tab activation. Returns true if a flush timer needs to be started.
+    pub fn tab_activated(&mut self) -> bool {
+        self.pending.tab_activated = true;
+        self.arm_timer()
+    }
+
+    /// Queue a window focus event. Returns true if a flush timer needs to be started.
+    pub fn focus_gained(&mut self) -> bool {
+        self.pending.focus_gained = true;
+        self.arm_timer()
+    }
+
+    /// Queue a session switch. Returns true if a flush timer needs to be started.
+    pub fn session_switched(&mut self, name: &str) -> bool {
+        self.pending.session_switched = Some(name.to_string());
+        self.last_cursor = None;
+        self.last_selection = None;
+        self.arm_timer()
+    }
+
+    /// Queue a file rename. Returns true if a flush timer needs to be started.
+    pub fn file_renamed(&mut self, old_path: String, new_path: String) -> bool {
+        self.pending.renamed.push((old_path, new_path));
+        self.arm_timer()
+    }
+
+    /// Take all pending events and clear the timer flag. Every
+    /// `DoEditorEventHooks` tick must reach this, or no later event arms
+    /// the timer again.
+    pub fn take(&mut self) -> PendingEditorEvents {
+        self.timer_active = false;
+        std::mem::take(&mut self.pending)
+    }
+
+    fn arm_timer(&mut self) -> bool {
+        if self.timer_active {
+            false
+        } else {
+            self.timer_active = true;
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_moves_coalesce_into_one_flush() {
+        let doc = DocumentId(1);
+        let mut tracker = EditorEventTracker::new();
+
+        // First observation only sets the baseline
+        assert!(!tracker.observe_cursor(doc, 0));
+        assert!(tracker.observe_cursor(doc, 5), "first move arms the timer");
+        assert!(!tracker.observe_cursor(doc, 9), "timer already armed");
+        assert!(!tracker.observe_cursor(doc, 9), "no move, no event");
+
+        let events = tracker.take();
+        assert_eq!(events.cursor, Some(9));
+        assert!(tracker.take().is_empty());
+    }
+
+    #[test]
+    fn test_flush_with_no_tabs_rearms_timer() {
+        let mut tracker = EditorEventTracker::new();
+        assert!(tracker.focus_gained(), "first event arms the timer");
+        assert!(!tracker.session_switched("work"), "timer already armed");
+
+        // The tick arrives while no tab is open: hooks fire without a path
+        let events = tracker.take();
+        assert!(events.focus_gained);
+        assert_eq!(events.session_switched.as_deref(), Some("work"));
+
+        assert!(tracker.tab_activated(), "next event arms the timer again");
+    }
+
+    #[test]
+    fn test_document_switch_resets_baseline() {
+        let mut tracker = EditorEventTracker::new();
+        tracker.observe_cursor(DocumentId(1), 10);
+        tracker.observe_selection(DocumentId(1), None);
+
+        assert!(!tracker.observe_cursor(DocumentId(2), 40));
+        assert!(!tracker.observe_selection(DocumentId(2), Some((3, 8))));
+        assert!(tracker.take().is_empty());
+    }
+
+    #[test]
+    fn test_selection_cleared_reports_empty_range() {
+        let doc = DocumentId(1);
+        let mut tracker = EditorEventTracker::new();
+        tracker.observe_selection(doc, Some((2, 6)));
+        assert!(tracker.observe_selection(doc, None));
+        assert_eq!(tracker.take().selection, Some((0, 0)));
+    }
+
+    #[test]
+    fn test_discrete_events_accumulate() {
+        let mut tracker = EditorEventTracker::new();
+        assert!(tracker.tab_activated());
+        assert!(!tracker.focus_gained());
+        assert!(!tracker.file_renamed("/a.txt".into(), "/b.txt".into()));
+        assert!(!tracker.session_switched("work"));
+
+        let events = tracker.take();
+        assert!(events.tab_activated);
+        assert!(events.focus_gained);
+        assert_eq!(events.session_switched.as_deref(), Some("work"));
+        assert_eq!(
+            events.renamed,
+            vec![("/a.txt".to_string(), "/b.txt".to_string())]
+        );
+    }
+}
@@ -12,8 +12,11 @@ use crate::app::domain::settings::AppSettings;
 use crate::app::infrastructure::buffer::buffer_text_no_leak;
 use crate::app::plugins::{HookResult, PluginHook, PluginManager};
 use crate::app::services::file_size::{
-    FileSizeCheck, TAIL_LINE_COUNT, check_file_size, read_chunk, read_tail, save_partial,
+    FileSizeCheck, FileStamp, OpenRange, TAIL_LINE_COUNT, check_file_size, line_range_bytes,
+    ranges_overlap, read_byte_range, read_tail, save_partial, shift_range,
 };
+use crate::app::services::hex::detect_binary;
+use crate::ui::dialogs::hex_viewer::{BinaryFileAction, show_binary_file_prompt, show_hex_viewer};
 use crate::ui::dialogs::large_file::{
     StreamLoadResult, TooLargeAction, load_to_buffer_with_progress, show_file_too_large_dialog,
     show_large_file_warning,
@@ -47,6 +50,13 @@ pub enum FileAction {
     },
     /// Process lint result from save hooks.
     ProcessLintResult(Box<HookResult>),
+    /// Notify plugins that a document's path changed (Save As to a new path).
+    NotifyRenamed {
+        old_path: String,
+        new_path: String,
+    },
+    /// Add a file to the recently used history.
+    RecordRecent(String),
     /// Update markdown preview file if applicable.
     UpdatePreviewFile {
         doc_id: u64,
@@ -90,6 +100,18 @@ impl FileController {
             self.last_open_directory = Some(parent.to_string_lossy().to_string());
         }
 
+        // Binary files go to the hex viewer unless the user insists on text
+        if detect_binary(path_ref).unwrap_or(false) {
+            match show_binary_file_prompt(path_ref) {
+                BinaryFileAction::HexView => {
+                    Self::show_hex_view(path_ref, settings, theme_bg);
+                    return vec![];
+                }
+                BinaryFileAction::OpenAsText => {}
+                BinaryFileAction::Cancel => return vec![],
+            }
+        }
+
         // Pre-flight size check to prevent crashes on huge files
         let warning_mb = settings.borrow().large_file_warning_mb as u64;
         let max_editable_mb = settings.borrow().max_editable_size_mb as u64;
@@ -111,15 +133,20 @@ impl FileController {
                         if let Some(req) = crate::ui::dialogs::readonly_viewer::show_readonly_viewer(
                             path_ref, theme_bg, code_font, code_size,
                         ) {
+                            let range = (req.start_byte, req.end_byte);
+                            if tabs_enabled
+                                && let Some(actions) =
+                                    Self::range_conflict(&path, range, tab_manager)
+                            {
+                                return actions;
+                            }
                             // User clicked "Open" — content already extracted from mmap
                             return self.open_chunk_content(
                                 path,
                                 req.content,
-                                &filename,
-                                req.start_line,
-                                req.end_line,
-                                req.start_byte,
-                                req.end_byte,
+                                format!("{} (lines {}-{})", filename, req.start_line, req.end_line),
+                                range,
+                                Some(req.stamp),
                                 tab_manager,
                                 tabs_enabled,
                             );
@@ -150,30 +177,12 @@ impl FileController {
                         }
                     }
                     TooLargeAction::OpenChunk(start_line, end_line) => {
-                        let filename = path_ref
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("file")
-                            .to_string();
-                        match read_chunk(path_ref, start_line, end_line) {
-                            Ok((content, start_byte, end_byte)) => {
-                                return self.open_chunk_content(
-                                    path,
-                                    content,
-                                    &filename,
-                                    start_line,
-                                    end_line,
-                                    start_byte,
-                                    end_byte,
-                                    tab_manager,
-                                    tabs_enabled,
-                                );
-                            }
-                            Err(e) => {
-                                dialog::alert_default(&format!("Failed to read file chunk: {}", e));
-                                return vec![];
-                            }
-                        }
+                        return self.open_range(
+                            path,
+                            OpenRange::Lines(start_line, end_line),
+                            tab_manager,
+                            tabs_enabled,
+                        );
                     }
                 }
             }
@@ -258,6 +267,62 @@ impl FileController {
         }
     }
 
+    /// Choose a file, then a line or byte range of it to open for editing
+    pub fn file_open_range(
+        &mut self,
+        tab_manager: &mut TabManager,
+        theme_bg: (u8, u8, u8),
+        tabs_enabled: bool,
+    ) -> Vec<FileAction> {
+        let Some(path) = native_open_dialog(self.last_open_directory.as_deref()) else {
+            return vec![];
+        };
+        let path_ref = std::path::Path::new(&path);
+        if let Some(parent) = path_ref.parent() {
+            self.last_open_directory = Some(parent.to_string_lossy().to_string());
+        }
+        let size = match fs::metadata(path_ref) {
+            Ok(m) => m.len(),
+            Err(e) => {
+                dialog::alert_default(&format!("Error opening file: {}", e));
+                return vec![];
+            }
+        };
+        let filename = path_ref
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("file")
+            .to_string();
+        match crate::ui::dialogs::open_range::show_open_range_dialog(&filename, size, theme_bg) {
+            Some(range) => self.open_range(path, range, tab_manager, tabs_enabled),
+            None => vec![],
+        }
+    }
+
+    /// Choose a file and show it in the hex viewer
+    pub fn file_open_hex(&mut self, settings: &Rc<RefCell<AppSettings>>, theme_bg: (u8, u8, u8)) {
+        let Some(path) = native_open_dialog(self.last_open_directory.as_deref()) else {
+            return;
+        };
+        let path_ref = std::path::Path::new(&path);
+        if let Some(parent) = path_ref.parent() {
+            self.last_open_directory = Some(parent.to_string_lossy().to_string());
+        }
+        Self::show_hex_view(path_ref, settings, theme_bg);
+    }
+
+    fn show_hex_view(
+        path: &std::path::Path,
+        settings: &Rc<RefCell<AppSettings>>,
+        theme_bg: (u8, u8, u8),
+    ) {
+        let (code_font, code_size) = {
+            let s = settings.borrow();
+            (s.current_font(), s.font_size as i32)
+        };
+        show_hex_viewer(path, theme_bg, code_font, code_size);
+    }
+
     pub fn file_save(
         &mut self,
         tab_manager: &mut TabManager,
@@ -285,6 +350,35 @@ impl FileController {
             });
             let text_to_save = hook_result.modified_content.unwrap_or(text);
 
+            // The bytes a partial document replaces, and the file before
+            let path_ref = std::path::Path::new(path);
+            let splice = match &partial_info {
+                PartialFileInfo::Full => None,
+                PartialFileInfo::Tail { start_byte } => Some((
+                    *start_byte,
+                    fs::metadata(path).map(|m| m.len()).unwrap_or(*start_byte),
+                )),
+                PartialFileInfo::Chunk {
+                    start_byte,
+                    end_byte,
+                    ..
+                } => Some((*start_byte, *end_byte)),
+            };
+            let stamp_before = FileStamp::of(path_ref).ok();
+            if let PartialFileInfo::Chunk {
+                stamp: Some(stamp), ..
+            } = &partial_info
+                && stamp_before != Some(*stamp)
+            {
+                dialog::alert_default(&format!(
+                    "'{}' changed on disk since this range was opened.\n\n\
+                     Saving now would write the range over the wrong bytes. \
+                     Copy your changes, then open the range again.",
+                    path
+                ));
+                return vec![];
+            }
+
             let save_result = match &partial_info {
                 PartialFileInfo::Full => fs::write(path, &text_to_save),
                 PartialFileInfo::Tail { start_byte } => {
@@ -299,6 +393,7 @@ impl FileController {
                 PartialFileInfo::Chunk {
                     start_byte,
                     end_byte,
+                    ..
                 } => save_partial(
                     std::path::Path::new(path),
                     &text_to_save,
@@ -311,7 +406,17 @@ impl FileController {
                 Ok(_) => {
                     if let Some(doc) = tab_manager.active_doc_mut() {
                         doc.mark_clean();
-                        doc.disk_mtime = fs::metadata(path).ok().and_then(|m| m.modified().ok());
+                        doc.record_disk_state(path);
+                    }
+                    if let Some(splice) = splice {
+                        Self::update_ranges_after_save(
+                            tab_manager,
+                            DocumentId(doc_id),
+                            path,
+                            splice,
+                            text_to_save.len() as u64,
+                            stamp_before,
+                        );
                     }
 
                     // Call lint hook after successful save
@@ -361,13 +466,13 @@ impl FileController {
             }
             match fs::write(&path, &text) {
                 Ok(_) => {
+                    let mut old_path = None;
                     let id = {
                         if let Some(doc) = tab_manager.active_doc_mut() {
-                            doc.file_path = Some(path.clone());
+                            old_path = doc.file_path.replace(path.clone());
                             doc.update_display_name();
                             doc.mark_clean();
-                            doc.disk_mtime =
-                                fs::metadata(&path).ok().and_then(|m| m.modified().ok());
+                            doc.record_disk_state(&path);
                             Some(doc.id)
                         } else {
                             None
@@ -387,6 +492,12 @@ impl FileController {
                     actions.push(FileAction::UpdateWindowTitle);
                     actions.push(FileAction::RebuildTabBar);
                     actions.push(FileAction::UpdateMenusForFileType);
+                    if let Some(old_path) = old_path.filter(|old| *old != path) {
+                        actions.push(FileAction::NotifyRenamed {
+                            old_path,
+                            new_path: path.clone(),
+                        });
+                    }
 
                     // Call lint hook after successful save
                     let lint_result = plugins.call_hook(PluginHook::OnDocumentLint {
@@ -415,8 +526,7 @@ impl FileController {
                     Some(p) => p.clone(),
                     None => return vec![], // untitled
                 };
-                let is_partial =
-                    doc.display_name.contains("(tail)") || doc.display_name.contains("(lines ");
+                let is_partial = !matches!(doc.partial_info, PartialFileInfo::Full);
                 (path, is_partial)
             } else {
                 return vec![];
@@ -434,7 +544,6 @@ impl FileController {
                 return vec![];
             }
         };
-        let new_mtime = fs::metadata(&path).ok().and_then(|m| m.modified().ok());
 
         if let Some(doc) = tab_manager.doc_by_id_mut(doc_id) {
             doc.buffer.set_text(&content);
@@ -442,7 +551,7 @@ impl FileController {
             doc.style_buffer.set_text(&default_style);
             doc.has_unsaved_changes.set(false);
             doc.checkpoints.clear();
-            doc.disk_mtime = new_mtime;
+            doc.record_disk_state(&path);
             doc.diagnostics.clear();
             doc.has_been_linted = false;
             doc.cached_tree = None;
@@ -463,6 +572,49 @@ impl FileController {
         actions
     }
 
+    /// Re-read a followed document whose file was truncated or rotated: the
+    /// whole file, or its last `TAIL_LINE_COUNT` lines for a tail view.
+    pub fn reopen_followed(
+        &self,
+        doc_id: DocumentId,
+        tab_manager: &mut TabManager,
+    ) -> Vec<FileAction> {
+        use crate::app::domain::document::PartialFileInfo;
+
+        let Some(doc) = tab_manager.doc_by_id(doc_id) else {
+            return vec![];
+        };
+        let Some(path) = doc.file_path.clone() else {
+            return vec![];
+        };
+        if !matches!(doc.partial_info, PartialFileInfo::Tail { .. }) {
+            return self.reload_file(doc_id, tab_manager);
+        }
+
+        let (content, start_byte) = match read_tail(std::path::Path::new(&path), TAIL_LINE_COUNT) {
+            Ok(tail) => tail,
+            Err(e) => {
+                eprintln!("[follow] Failed to reopen '{}': {}", path, e);
+                return vec![];
+            }
+        };
+        if let Some(doc) = tab_manager.doc_by_id_mut(doc_id) {
+            doc.buffer.set_text(&content);
+            let default_style = "A".repeat(content.len());
+            doc.style_buffer.set_text(&default_style);
+            doc.has_unsaved_changes.set(false);
+            doc.checkpoints.clear();
+            doc.partial_info = PartialFileInfo::Tail { start_byte };
+            doc.record_disk_state(&path);
+            doc.cached_tree = None;
+            doc.cached_line_count = content.lines().count();
+        }
+        vec![
+            FileAction::DetectAndHighlight(doc_id, path),
+            FileAction::UpdateWindowTitle,
+        ]
+    }
+
     /// Reload all file-backed documents from disk.
     pub fn reload_all_files(&self, tab_manager: &mut TabManager) -> Vec<FileAction> {
         let ids: Vec<DocumentId> = tab_manager
@@ -559,6 +711,7 @@ impl FileController {
                 return vec![
                     FileAction::SwitchToDocument(existing_id),
                     FileAction::RebuildTabBar,
+                    FileAction::RecordRecent(path),
                 ];
             }
             // Close empty Untitled tab if it's the only one
@@ -575,7 +728,7 @@ impl FileController {
             };
             let id = tab_manager.add_from_file(path.clone(), &content);
             if let Some(doc) = tab_manager.doc_by_id_mut(id) {
-                doc.disk_mtime = fs::metadata(&path).ok().and_then(|m| m.modified().ok());
+                doc.record_disk_state(&path);
             }
             if let Some(untitled_id) = empty_untitled {
                 tab_manager.remove(untitled_id);
@@ -585,6 +738,7 @@ impl FileController {
                 FileAction::DetectAndHighlight(id, path.clone()),
                 FileAction::SwitchToDocument(id),
                 FileAction::RebuildTabBar,
+                FileAction::RecordRecent(path.clone()),
             ];
 
             if content.len() > DEFERRED_THRESHOLD {
@@ -599,7 +753,7 @@ impl FileController {
                 doc.has_unsaved_changes.set(false);
                 doc.file_path = Some(path.clone());
                 doc.update_display_name();
-                doc.disk_mtime = fs::metadata(&path).ok().and_then(|m| m.modified().ok());
+                doc.record_disk_state(&path);
             }
             let mut actions = Vec::new();
             if let Some(id) = tab_manager.active_id() {
@@ -607,6 +761,7 @@ impl FileController {
             }
             actions.push(FileAction::UpdateWindowTitle);
             actions.push(FileAction::UpdateMenusForFileType);
+            actions.push(FileAction::RecordRecent(path.clone()));
 
             if content.len() > DEFERRED_THRESHOLD {
                 actions.push(FileAction::DeferOpenHooks { path, content });
@@ -631,6 +786,7 @@ impl FileController {
                 return vec![
                     FileAction::SwitchToDocument(existing_id),
                     FileAction::RebuildTabBar,
+                    FileAction::RecordRecent(path),
                 ];
             }
             // Close empty Untitled tab if it's the only one
@@ -647,22 +803,29 @@ impl FileController {
             };
             let id = tab_manager.add_from_buffer(path.clone(), buffer, true);
             if let Some(doc) = tab_manager.doc_by_id_mut(id) {
-                doc.disk_mtime = fs::metadata(&path).ok().and_then(|m| m.modified().ok());
+                doc.record_disk_state(&path);
             }
             if let Some(untitled_id) = empty_untitled {
                 tab_manager.remove(untitled_id);
             }
-            vec![FileAction::SwitchToDocument(id), FileAction::RebuildTabBar]
+            vec![
+                FileAction::SwitchToDocument(id),
+                FileAction::RebuildTabBar,
+                FileAction::RecordRecent(path),
+            ]
         } else {
             if let Some(doc) = tab_manager.active_doc_mut() {
                 let content = crate::app::infrastructure::buffer::buffer_text_no_leak(&buffer);
                 doc.buffer.set_text(&content);
                 doc.has_unsaved_changes.set(false);
-                doc.disk_mtime = fs::metadata(&path).ok().and_then(|m| m.modified().ok());
-                doc.file_path = Some(path);
+                doc.record_disk_state(&path);
+                doc.file_path = Some(path.clone());
                 doc.update_display_name();
             }
-            vec![FileAction::UpdateWindowTitle]
+            vec![
+                FileAction::UpdateWindowTitle,
+                FileAction::RecordRecent(path),
+            ]
         }
     }
 
@@ -700,6 +863,7 @@ impl FileController {
                 doc.display_name = format!("{} (tail)", filename);
                 doc.partial_info = PartialFileInfo::Tail { start_byte };
                 doc.has_unsaved_changes.set(false);
+                doc.record_disk_state(&path);
             }
 
             vec![
@@ -714,6 +878,7 @@ impl FileController {
                 doc.file_path = Some(path.clone());
                 doc.display_name = format!("{} (tail)", filename);
                 doc.partial_info = PartialFileInfo::Tail { start_byte };
+                doc.record_disk_state(&path);
             }
             vec![
                 FileAction::UpdateWindowTitle,
@@ -722,24 +887,165 @@ impl FileController {
         }
     }
 
-    /// Open content from a specific line range (chunk).
+    /// Open a line or byte range of a file as an editable chunk in its own
+    /// tab. Several chunks of one file can be open at once as long as they
+    /// don't overlap; asking for a chunk that is already open switches to it.
+    pub fn open_range(
+        &mut self,
+        path: String,
+        range: OpenRange,
+        tab_manager: &mut TabManager,
+        tabs_enabled: bool,
+    ) -> Vec<FileAction> {
+        let path_ref = std::path::Path::new(&path);
+        let filename = path_ref
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("file")
+            .to_string();
+
+        let stamp = match FileStamp::of(path_ref) {
+            Ok(stamp) => stamp,
+            Err(e) => {
+                dialog::alert_default(&format!("Error opening file: {}", e));
+                return vec![];
+            }
+        };
+        let (bytes, label) = match range {
+            OpenRange::Lines(start_line, end_line) => {
+                if start_line == 0 || end_line < start_line {
+                    return vec![];
+                }
+                match line_range_bytes(path_ref, start_line, end_line) {
+                    Ok(bytes) => (
+                        bytes,
+                        format!("{} (lines {}-{})", filename, start_line, end_line),
+                    ),
+                    Err(e) => {
+                        dialog::alert_default(&format!("Failed to read file chunk: {}", e));
+                        return vec![];
+                    }
+                }
+            }
+            OpenRange::Bytes(start_byte, end_byte) => {
+                let end_byte = end_byte.min(stamp.len);
+                (
+                    (start_byte.min(end_byte), end_byte),
+                    format!("{} (bytes {}-{})", filename, start_byte, end_byte),
+                )
+            }
+        };
+
+        if tabs_enabled && let Some(actions) = Self::range_conflict(&path, bytes, tab_manager) {
+            return actions;
+        }
+
+        match read_byte_range(path_ref, bytes.0, bytes.1) {
+            Ok(content) => self.open_chunk_content(
+                path,
+                content,
+                label,
+                bytes,
+                Some(stamp),
+                tab_manager,
+                tabs_enabled,
+            ),
+            Err(e) => {
+                dialog::alert_default(&format!("Failed to read file chunk: {}", e));
+                vec![]
+            }
+        }
+    }
+
+    /// Check a byte range of `path` against the documents already showing
+    /// parts of that file. Returns the actions to run instead of opening it:
+    /// a switch to the tab that shows exactly this range, or nothing after
+    /// telling the user which tab it overlaps.
+    fn range_conflict(
+        path: &str,
+        range: (u64, u64),
+        tab_manager: &TabManager,
+    ) -> Option<Vec<FileAction>> {
+        let doc = tab_manager.documents().iter().find(|doc| {
+            doc.file_path.as_deref() == Some(path)
+                && ranges_overlap(doc.partial_info.byte_range(), range)
+        })?;
+        if doc.partial_info.byte_range() == range {
+            return Some(vec![FileAction::SwitchToDocument(doc.id)]);
+        }
+        dialog::alert_default(&format!(
+            "This range overlaps '{}', which is already open.\n\n\
+             Close that tab first, or open a range that doesn't overlap it.",
+            doc.tab_label()
+        ));
+        Some(vec![])
+    }
+
+    /// After saving `new_len` bytes over `splice` of `path`, move the other
+    /// open ranges of that file that lie after the splice and mark them as
+    /// read from the new file. A range that was already stale (its stamp is
+    /// not the file before this save) is left alone, so its own save still
+    /// reports the conflict.
+    fn update_ranges_after_save(
+        tab_manager: &mut TabManager,
+        saved: DocumentId,
+        path: &str,
+        splice: (u64, u64),
+        new_len: u64,
+        stamp_before: Option<FileStamp>,
+    ) {
+        let stamp_after = FileStamp::of(std::path::Path::new(path)).ok();
+        let ids: Vec<DocumentId> = tab_manager
+            .documents()
+            .iter()
+            .filter(|doc| doc.file_path.as_deref() == Some(path))
+            .map(|doc| doc.id)
+            .collect();
+        for id in ids {
+            let Some(doc) = tab_manager.doc_by_id_mut(id) else {
+                continue;
+            };
+            match &mut doc.partial_info {
+                PartialFileInfo::Full => {}
+                PartialFileInfo::Tail { start_byte } => {
+                    if id != saved {
+                        *start_byte =
+                            shift_range((*start_byte, *start_byte), splice.0, splice.1, new_len).0;
+                    }
+                }
+                PartialFileInfo::Chunk {
+                    start_byte,
+                    end_byte,
+                    stamp,
+                } => {
+                    if id == saved {
+                        *end_byte = *start_byte + new_len;
+                    } else if *stamp == stamp_before {
+                        (*start_byte, *end_byte) =
+                            shift_range((*start_byte, *end_byte), splice.0, splice.1, new_len);
+                    } else {
+                        continue;
+                    }
+                    *stamp = stamp_after;
+                }
+            }
+        }
+    }
+
+    /// Open content from a specific byte range (chunk) under `chunk_label`.
     #[allow(clippy::too_many_arguments)]
     fn open_chunk_content(
         &self,
         path: String,
         content: String,
-        filename: &str,
-        start_line: usize,
-        end_line: usize,
-        start_byte: u64,
-        end_byte: u64,
+        chunk_label: String,
+        (start_byte, end_byte): (u64, u64),
+        stamp: Option<FileStamp>,
         tab_manager: &mut TabManager,
         tabs_enabled: bool,
     ) -> Vec<FileAction> {
         use crate::app::domain::document::PartialFileInfo;
 
-        let chunk_label = format!("{} (lines {}-{})", filename, start_line, end_line);
-
         if tabs_enabled {
             let empty_untitled = if tab_manager.count() == 1 {
                 tab_manager.active_doc().and_then(|doc| {
@@ -763,6 +1069,7 @@ impl FileController {
                 doc.partial_info = PartialFileInfo::Chunk {
                     start_byte,
                     end_byte,
+                    stamp,
                 };
                 doc.has_unsaved_changes.set(false);
             }
@@ -781,6 +1088,7 @@ impl FileController {
                 doc.partial_info = PartialFileInfo::Chunk {
                     start_byte,
                     end_byte,
+                    stamp,
                 };
             }
             vec![
@@ -17,6 +17,8 @@ use crate::app::domain::settings::SyntaxTheme;
 use crate::app::infrastructure::buffer::buffer_text_no_leak;
 use crate::app::infrastructure::defer::defer_send;
 use crate::app::services::syntax::SyntaxHighlighter;
+use crate::app::services::syntax::extra_assets::ExtraAssets;
+use crate::app::services::syntax::symbols::Symbol;
 
 const LARGE_FILE_THRESHOLD: usize = 5000;
 
@@ -54,12 +56,34 @@ impl HighlightController {
         &self.highlighter
     }
 
+    /// Symbols of a document, for Quick Open's `@` filter.
+    pub fn symbols(&mut self, text: &str, syntax_name: &str) -> Vec<Symbol> {
+        self.highlighter.symbols(text, syntax_name)
+    }
+
     pub fn style_table(&self) -> Vec<StyleTableEntryExt> {
         self.highlighter.style_table()
     }
 
-    pub fn set_theme(&mut self, theme: SyntaxTheme) {
+    /// Switch theme. A user/plugin theme name takes precedence over the
+    /// built-in `theme`, which is used if that theme isn't loaded.
+    pub fn set_theme(&mut self, theme: SyntaxTheme, custom: Option<&str>) {
         self.highlighter.set_theme(theme);
+        if let Some(name) = custom
+            && !self.highlighter.set_custom_theme(name)
+        {
+            eprintln!(
+                "[syntax] Theme '{}' not found, using {}",
+                name,
+                theme.display_name()
+            );
+        }
+    }
+
+    /// Replace user/plugin syntaxes and themes.
+    pub fn set_extra_assets(&mut self, assets: ExtraAssets) {
+        self.highlighter
+            .set_extra_assets(assets.syntax_set, assets.themes);
     }
 
     pub fn font(&self) -> Font {
@@ -79,6 +103,11 @@ impl HighlightController {
         self.highlighter.get_or_insert_marker_rgb(r, g, b)
     }
 
+    /// Get or insert the full-line variant of a marker style.
+    pub fn get_or_insert_marker_line(&mut self, marker: char) -> char {
+        self.highlighter.get_or_insert_marker_line(marker)
+    }
+
     /// Perform a full syntax highlight on text.
     pub fn highlight_full(
         &mut self,
@@ -542,13 +571,17 @@ impl HighlightController {
             entry.1.extend(ann.inline);
         }
 
+        // Lines come in ascending order, so the scan for each line start
+        // continues from the previous one
+        let mut line_start = 0;
+        let mut current_line = 0;
         for (line_num, (gutter, inlines)) in merged {
             let target_line = line_num.saturating_sub(1) as i32;
 
-            let mut line_start = 0;
-            for _ in 0..target_line {
+            while current_line < target_line {
                 if let Some(next_pos) = buf.find_char_forward(line_start, '\n') {
                     line_start = next_pos + 1;
+                    current_line += 1;
                 } else {
                     break;
                 }
@@ -564,10 +597,13 @@ impl HighlightController {
 
             let line_len = line_end - line_start;
 
-            if let Some(ref gutter_mark) = gutter
-                && line_len > 0
-            {
-                let marker_char = get_marker_char(self, &gutter_mark.color);
+            if let Some(ref gutter_mark) = gutter {
+                let mut marker_char = get_marker_char(self, &gutter_mark.color);
+                // An empty line has only its newline to style; extend the
+                // background across the line so the mark is visible
+                if line_len == 0 {
+                    marker_char = self.get_or_insert_marker_line(marker_char);
+                }
                 let marker_str: String =
                     std::iter::repeat_n(marker_char, (line_end_with_newline - line_start) as usize)
                         .collect();
@@ -19,7 +19,7 @@ pub struct HookContext<'a> {
     pub approved_commands: Vec<String>,
 }
 
-/// Process the result from a plugin hook (diagnostics, annotations, status message, open_file, clipboard, goto_line).
+/// Process the result from a plugin hook (diagnostics, annotations, status message, status items, open_file, clipboard, goto_line).
 pub fn dispatch_hook_result(result: HookResult, plugin_name: &str, ctx: &mut HookContext<'_>) {
     // Handle modified content (for format actions)
     if let Some(modified_content) = result.modified_content
@@ -48,6 +48,12 @@ pub fn dispatch_hook_result(result: HookResult, plugin_name: &str, ctx: &mut Hoo
             .send(Message::ToastShow(status.level, status.text));
     }
 
+    // Update plugin status bar items
+    for (plugin_name, items) in result.status_items {
+        ctx.sender
+            .send(Message::StatusItemsUpdate { plugin_name, items });
+    }
+
     // Handle open_file request with security validation
     if let Some(ref file_path) = result.open_file {
         use crate::app::plugins::security::{PathValidation, find_project_root, validate_path};
@@ -98,7 +104,7 @@ pub fn dispatch_hook_result(result: HookResult, plugin_name: &str, ctx: &mut Hoo
     }
 }
 
-/// Process lint result from plugin hook: send diagnostics, annotations, and toast.
+/// Process lint result from plugin hook: send diagnostics, annotations, toast and status items.
 pub fn dispatch_lint_result(result: HookResult, ctx: &mut HookContext<'_>) {
     // Process any widget requests (e.g., tree view updates from on_document_lint)
     process_widget_requests(
@@ -128,9 +134,15 @@ pub fn dispatch_lint_result(result: HookResult, ctx: &mut HookContext<'_>) {
         ctx.sender
             .send(Message::ToastShow(status.level, status.text));
     }
+
+    // Update plugin status bar items
+    for (plugin_name, items) in result.status_items {
+        ctx.sender
+            .send(Message::StatusItemsUpdate { plugin_name, items });
+    }
 }
 
-/// Process widget requests (split view, tree view, terminal view) from a hook result.
+/// Process widget requests (split view, tree view, table view, terminal view) from a hook result.
 ///
 /// `approved_commands` is the plugin's list of user-approved commands.
 /// Terminal view requests are only allowed if the command is in this list.
@@ -173,6 +185,28 @@ pub fn process_widget_requests(
         });
     }
 
+    // Check for table view request — a refresh from the same plugin reuses its session
+    if let Some(ref table_request) = result.table_view
+        && table_request.is_valid()
+    {
+        let existing = widget_manager.any_table_view_session().filter(|id| {
+            widget_manager
+                .get_session(*id)
+                .is_some_and(|s| s.plugin_name == effective_name)
+        });
+        let session_id = match existing {
+            Some(id) => id,
+            None => {
+                widget_manager.create_table_view_session(effective_name, table_request.persistent)
+            }
+        };
+        sender.send(Message::TableViewShow {
+            session_id,
+            plugin_name: effective_name.to_string(),
+            request: table_request.clone(),
+        });
+    }
+
     // Check for terminal view request — with security checks
     if let Some(ref terminal_request) = result.terminal_view
         && terminal_request.is_valid()
@@ -218,11 +252,11 @@ pub fn process_widget_requests(
             return;
         }
 
-        // Reuse existing terminal view session if one exists
-        let session_id = if let Some(existing_id) = widget_manager.any_terminal_view_session() {
-            existing_id
-        } else {
-            widget_manager.create_terminal_view_session(effective_name, terminal_request.persistent)
+        // Each plugin gets its own terminal tab; repeated requests reuse it
+        let session_id = match widget_manager.terminal_view_session_for(effective_name) {
+            Some(existing_id) => existing_id,
+            None => widget_manager
+                .create_terminal_view_session(effective_name, terminal_request.persistent),
         };
         sender.send(Message::TerminalViewShow {
             session_id,
@@ -3,21 +3,35 @@
 //! This module contains controllers that coordinate between
 //! domain models, services, and the UI:
 //! - File operations (open, save, new)
+//! - Debounced editor events for plugin hooks
 //! - Tab management
+//! - Back/forward navigation history
+//! - Editor panes (split layout and focus)
 //! - Syntax highlighting orchestration
 //! - Markdown preview
 //! - Update management
 //! - View state (line numbers, word wrap, fonts)
 //! - Session persistence
+//! - Recently used files and closed tabs
+//! - Quick Open (project file index and ranking)
+//! - Command palette (ranking and history)
 //! - Plugin management coordination
+//! - Task runs and their problems
 
+pub mod command_palette;
+pub mod editor_events;
 pub mod file;
 pub mod highlight;
 pub mod hook_dispatch;
+pub mod navigation;
+pub mod panes;
 pub mod plugin;
 pub mod preview;
+pub mod quick_open;
+pub mod recent;
 pub mod session;
 pub mod tabs;
+pub mod task;
 pub mod update;
 pub mod view;
 pub mod widget;
@@ -0,0 +1,219 @@
+//! Back/forward navigation through cursor locations.
+//!
+//! The cursor is observed once per event loop iteration (like
+//! `EditorEventTracker`). A switch to another document or a move of at least
+//! `BIG_MOVE_LINES` lines records where the cursor came from; goto commands
+//! record explicitly, so short jumps count too. Go Back and Go Forward walk
+//! the recorded locations across documents. Offsets follow edits through
+//! `edited`, the same way bookmarks do.
+
+use crate::app::domain::document::DocumentId;
+
+/// How many locations Go Back can return to
+pub const MAX_NAV_HISTORY: usize = 50;
+
+/// A cursor move of at least this many lines counts as a jump
+pub const BIG_MOVE_LINES: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NavLocation {
+    pub doc: DocumentId,
+    /// Cursor byte position
+    pub pos: i32,
+}
+
+#[derive(Debug, Default)]
+pub struct NavigationHistory {
+    back: Vec<NavLocation>,
+    forward: Vec<NavLocation>,
+    /// Last observed cursor location and its line (0-based)
+    last: Option<(NavLocation, u32)>,
+}
+
+impl NavigationHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `location` is where the cursor was last observed; the caller
+    /// can then skip counting lines for `observe`
+    pub fn is_at(&self, location: NavLocation) -> bool {
+        self.last.is_some_and(|(last, _)| last == location)
+    }
+
+    /// Observe the cursor. Records the previous location when the cursor
+    /// moved to another document or jumped far within the same one.
+    pub fn observe(&mut self, location: NavLocation, line: u32) {
+        if let Some((last, last_line)) = self.last.replace((location, line))
+            && last != location
+            && (last.doc != location.doc || last_line.abs_diff(line) >= BIG_MOVE_LINES)
+        {
+            self.record(last);
+        }
+    }
+
+    /// Record `from` as the place a jump left. Clears the forward stack.
+    pub fn record(&mut self, from: NavLocation) {
+        self.forward.clear();
+        if self.back.last() == Some(&from) {
+            return;
+        }
+        self.back.push(from);
+        if self.back.len() > MAX_NAV_HISTORY {
+            self.back.remove(0);
+        }
+    }
+
+    /// Step back from `current`. Returns the location to move to.
+    pub fn go_back(&mut self, current: NavLocation) -> Option<NavLocation> {
+        // A goto that went nowhere leaves the current location on top
+        while self.back.last() == Some(&current) {
+            self.back.pop();
+        }
+        let target = self.back.pop()?;
+        self.forward.push(current);
+        // The move to `target` is not a new jump
+        self.last = None;
+        Some(target)
+    }
+
+    /// Step forward from `current`. Returns the location to move to.
+    pub fn go_forward(&mut self, current: NavLocation) -> Option<NavLocation> {
+        while self.forward.last() == Some(&current) {
+            self.forward.pop();
+        }
+        let target = self.forward.pop()?;
+        self.back.push(current);
+        self.last = None;
+        Some(target)
+    }
+
+    /// Move the locations in `doc` past an edit reported by the modify
+    /// callback. Locations inside deleted text move to the edit position.
+    /// Edits must be replayed in order, and no location of `doc` recorded
+    /// while some are still queued (see `Document::has_queued_edits`).
+    pub fn edited(&mut self, doc: DocumentId, pos: i32, inserted: i32, deleted: i32) {
+        let shift = |location: &mut NavLocation| {
+            if location.doc != doc || location.pos < pos {
+                return;
+            }
+            if location.pos >= pos + deleted {
+                location.pos += inserted - deleted;
+            } else {
+                location.pos = pos;
+            }
+        };
+        self.back.iter_mut().for_each(shift);
+        self.forward.iter_mut().for_each(shift);
+        // Pasting many lines moves the cursor far; that is not a jump
+        if self.last.is_some_and(|(last, _)| last.doc == doc) {
+            self.last = None;
+        }
+    }
+
+    /// Forget the locations in a closed document
+    pub fn remove_document(&mut self, doc: DocumentId) {
+        self.back.retain(|l| l.doc != doc);
+        self.forward.retain(|l| l.doc != doc);
+        // Two entries may have become adjacent duplicates
+        self.back.dedup();
+        self.forward.dedup();
+        if self.last.is_some_and(|(last, _)| last.doc == doc) {
+            self.last = None;
+        }
+    }
+
+    /// Forget everything (e.g. when switching sessions)
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(doc: u64, pos: i32) -> NavLocation {
+        NavLocation {
+            doc: DocumentId(doc),
+            pos,
+        }
+    }
+
+    #[test]
+    fn test_small_moves_are_not_recorded() {
+        let mut history = NavigationHistory::new();
+        history.observe(at(1, 0), 0);
+        history.observe(at(1, 40), 3);
+        assert_eq!(history.go_back(at(1, 40)), None);
+    }
+
+    #[test]
+    fn test_big_moves_and_document_switches_are_recorded() {
+        let mut history = NavigationHistory::new();
+        history.observe(at(1, 0), 0);
+        history.observe(at(1, 500), 30);
+        history.observe(at(2, 7), 1);
+
+        assert_eq!(history.go_back(at(2, 7)), Some(at(1, 500)));
+        assert_eq!(history.go_back(at(1, 500)), Some(at(1, 0)));
+        assert_eq!(history.go_back(at(1, 0)), None);
+
+        assert_eq!(history.go_forward(at(1, 0)), Some(at(1, 500)));
+        assert_eq!(history.go_forward(at(1, 500)), Some(at(2, 7)));
+        assert_eq!(history.go_forward(at(2, 7)), None);
+    }
+
+    #[test]
+    fn test_going_back_is_not_recorded_as_a_jump() {
+        let mut history = NavigationHistory::new();
+        history.observe(at(1, 0), 0);
+        history.observe(at(1, 500), 30);
+        let target = history.go_back(at(1, 500)).unwrap();
+        history.observe(target, 0);
+        assert_eq!(history.go_forward(at(1, 0)), Some(at(1, 500)));
+    }
+
+    #[test]
+    fn test_new_jump_clears_forward() {
+        let mut history = NavigationHistory::new();
+        history.record(at(1, 0));
+        history.go_back(at(1, 90));
+        history.record(at(1, 0));
+        assert_eq!(history.go_forward(at(1, 0)), None);
+    }
+
+    #[test]
+    fn test_record_skips_duplicates_and_caps_length() {
+        let mut history = NavigationHistory::new();
+        history.record(at(1, 5));
+        history.record(at(1, 5));
+        assert_eq!(history.back.len(), 1);
+        for pos in 0..(MAX_NAV_HISTORY as i32 + 10) {
+            history.record(at(1, pos * 10));
+        }
+        assert_eq!(history.back.len(), MAX_NAV_HISTORY);
+    }
+
+    #[test]
+    fn test_edits_shift_locations_in_that_document() {
+        let mut history = NavigationHistory::new();
+        history.record(at(1, 10));
+        history.record(at(1, 50));
+        history.record(at(2, 50));
+        // Insert 5 bytes at 20 in document 1, then delete 30..60
+        history.edited(DocumentId(1), 20, 5, 0);
+        history.edited(DocumentId(1), 30, 0, 30);
+        assert_eq!(history.back, vec![at(1, 10), at(1, 30), at(2, 50)]);
+    }
+
+    #[test]
+    fn test_remove_document_drops_its_locations() {
+        let mut history = NavigationHistory::new();
+        history.record(at(1, 10));
+        history.record(at(2, 0));
+        history.record(at(1, 10));
+        history.remove_document(DocumentId(2));
+        assert_eq!(history.back, vec![at(1, 10)]);
+    }
+}
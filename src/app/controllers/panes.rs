@@ -0,0 +1,260 @@
+//! Editor panes — which document each split of the editor area shows.
+//!
+//! The editor area holds one or more panes laid out in a single row or
+//! column. Tabs are shared: every pane shows one of the open documents, and
+//! the same document may be shown in several panes at once (sharing its
+//! buffer). The focused pane's document is always the tab manager's active
+//! document. This module only tracks the layout; the widgets live in
+//! `EditorContainer`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::domain::document::DocumentId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PaneId(pub u32);
+
+/// Direction of the divider between panes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitOrientation {
+    /// Panes side by side
+    #[default]
+    Vertical,
+    /// Panes stacked top to bottom
+    Horizontal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pane {
+    pub id: PaneId,
+    /// Document shown in the pane (None only while nothing is open)
+    pub doc: Option<DocumentId>,
+}
+
+/// Snapshot of a split layout for session persistence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorLayout {
+    pub orientation: SplitOrientation,
+    /// Document and cursor position of each pane, in layout order
+    pub panes: Vec<(Option<DocumentId>, i32)>,
+    pub focused: usize,
+}
+
+pub struct PaneController {
+    panes: Vec<Pane>,
+    focused: usize,
+    orientation: SplitOrientation,
+    next_id: u32,
+}
+
+impl Default for PaneController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PaneController {
+    /// A single pane (`PaneId(0)`) showing nothing yet
+    pub fn new() -> Self {
+        Self {
+            panes: vec![Pane {
+                id: PaneId(0),
+                doc: None,
+            }],
+            focused: 0,
+            orientation: SplitOrientation::default(),
+            next_id: 1,
+        }
+    }
+
+    pub fn panes(&self) -> &[Pane] {
+        &self.panes
+    }
+
+    pub fn count(&self) -> usize {
+        self.panes.len()
+    }
+
+    pub fn is_split(&self) -> bool {
+        self.panes.len() > 1
+    }
+
+    pub fn orientation(&self) -> SplitOrientation {
+        self.orientation
+    }
+
+    pub fn focused(&self) -> &Pane {
+        &self.panes[self.focused]
+    }
+
+    pub fn focused_index(&self) -> usize {
+        self.focused
+    }
+
+    pub fn index_of(&self, id: PaneId) -> Option<usize> {
+        self.panes.iter().position(|p| p.id == id)
+    }
+
+    /// Add a pane after the focused one, showing the same document, and
+    /// focus it. All panes share one orientation, so splitting the other
+    /// way turns the whole layout. Returns the new pane's index.
+    pub fn split(&mut self, orientation: SplitOrientation) -> usize {
+        let pane = Pane {
+            id: PaneId(self.next_id),
+            doc: self.focused().doc,
+        };
+        self.next_id += 1;
+        self.orientation = orientation;
+        self.focused += 1;
+        self.panes.insert(self.focused, pane);
+        self.focused
+    }
+
+    /// Remove the pane at `index`; the last pane cannot be removed.
+    /// Focus stays on the same pane, or moves to the previous one when the
+    /// focused pane goes away.
+    pub fn close(&mut self, index: usize) -> bool {
+        if !self.is_split() || index >= self.panes.len() {
+            return false;
+        }
+        self.panes.remove(index);
+        if self.focused > index || self.focused >= self.panes.len() {
+            self.focused = self.focused.saturating_sub(1);
+        }
+        true
+    }
+
+    /// Focus the pane at `index`. Returns true if the focus changed.
+    pub fn focus(&mut self, index: usize) -> bool {
+        if index >= self.panes.len() || index == self.focused {
+            return false;
+        }
+        self.focused = index;
+        true
+    }
+
+    /// The pane after the focused one, wrapping around (None if not split)
+    pub fn neighbour(&self) -> Option<usize> {
+        self.is_split()
+            .then(|| (self.focused + 1) % self.panes.len())
+    }
+
+    /// Set the document shown in the pane at `index`
+    pub fn set_doc(&mut self, index: usize, doc: Option<DocumentId>) {
+        if let Some(pane) = self.panes.get_mut(index) {
+            pane.doc = doc;
+        }
+    }
+
+    /// A document was closed: remove the panes that showed it. The last
+    /// pane is kept (emptied) when every pane showed it. Returns the indices
+    /// of the removed panes, highest first, so widgets can be removed in
+    /// the same order.
+    pub fn remove_doc(&mut self, doc: DocumentId) -> Vec<usize> {
+        let mut removed = Vec::new();
+        for index in (0..self.panes.len()).rev() {
+            if self.panes[index].doc != Some(doc) {
+                continue;
+            }
+            if self.close(index) {
+                removed.push(index);
+            } else {
+                self.panes[index].doc = None;
+            }
+        }
+        removed
+    }
+
+    /// Drop every pane but the focused one
+    pub fn collapse(&mut self) -> Vec<usize> {
+        let keep = self.focused;
+        let removed: Vec<usize> = (0..self.panes.len()).rev().filter(|&i| i != keep).collect();
+        let pane = self.panes.swap_remove(keep);
+        self.panes = vec![pane];
+        self.focused = 0;
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn showing(ctrl: &PaneController) -> Vec<Option<u64>> {
+        ctrl.panes().iter().map(|p| p.doc.map(|d| d.0)).collect()
+    }
+
+    #[test]
+    fn test_split_shows_same_document() {
+        let mut ctrl = PaneController::new();
+        assert!(!ctrl.is_split());
+        ctrl.set_doc(0, Some(DocumentId(1)));
+
+        let idx = ctrl.split(SplitOrientation::Horizontal);
+        assert_eq!(idx, 1);
+        assert_eq!(ctrl.focused_index(), 1);
+        assert_eq!(ctrl.orientation(), SplitOrientation::Horizontal);
+        assert_eq!(showing(&ctrl), vec![Some(1), Some(1)]);
+        assert_ne!(ctrl.panes()[0].id, ctrl.panes()[1].id);
+
+        // Splitting from the first pane inserts right after it
+        ctrl.focus(0);
+        ctrl.set_doc(0, Some(DocumentId(2)));
+        assert_eq!(ctrl.split(SplitOrientation::Vertical), 1);
+        assert_eq!(showing(&ctrl), vec![Some(2), Some(2), Some(1)]);
+    }
+
+    #[test]
+    fn test_close_and_focus() {
+        let mut ctrl = PaneController::new();
+        assert!(!ctrl.close(0));
+        ctrl.split(SplitOrientation::Vertical);
+        ctrl.split(SplitOrientation::Vertical);
+        assert_eq!(ctrl.neighbour(), Some(0));
+        let last = ctrl.panes()[2].id;
+
+        // Closing an earlier pane keeps focus on the same pane
+        assert!(ctrl.close(0));
+        assert_eq!(ctrl.focused().id, last);
+
+        // Closing the focused pane focuses the previous one
+        assert!(ctrl.close(1));
+        assert_eq!(ctrl.focused_index(), 0);
+        assert!(!ctrl.is_split());
+        assert_eq!(ctrl.neighbour(), None);
+        assert!(!ctrl.focus(0));
+    }
+
+    #[test]
+    fn test_remove_doc_closes_its_panes() {
+        let mut ctrl = PaneController::new();
+        ctrl.set_doc(0, Some(DocumentId(1)));
+        ctrl.split(SplitOrientation::Vertical);
+        ctrl.set_doc(1, Some(DocumentId(2)));
+        ctrl.split(SplitOrientation::Vertical);
+
+        assert_eq!(ctrl.remove_doc(DocumentId(2)), vec![2, 1]);
+        assert_eq!(showing(&ctrl), vec![Some(1)]);
+        assert_eq!(ctrl.focused_index(), 0);
+
+        // The last pane stays, emptied
+        assert!(ctrl.remove_doc(DocumentId(1)).is_empty());
+        assert_eq!(showing(&ctrl), vec![None]);
+    }
+
+    #[test]
+    fn test_collapse_keeps_focused() {
+        let mut ctrl = PaneController::new();
+        ctrl.set_doc(0, Some(DocumentId(1)));
+        ctrl.split(SplitOrientation::Vertical);
+        ctrl.set_doc(1, Some(DocumentId(2)));
+        ctrl.split(SplitOrientation::Vertical);
+        ctrl.set_doc(2, Some(DocumentId(3)));
+        ctrl.focus(1);
+
+        assert_eq!(ctrl.collapse(), vec![2, 0]);
+        assert_eq!(showing(&ctrl), vec![Some(2)]);
+        assert_eq!(ctrl.focused_index(), 0);
+    }
+}
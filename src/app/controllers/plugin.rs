@@ -7,6 +7,7 @@ use fltk::{app::Sender, menu::MenuBar};
 
 use crate::app::domain::messages::Message;
 use crate::app::domain::settings::AppSettings;
+use crate::app::plugins::storage::{data_dir_for, remove_plugin_storage};
 use crate::app::plugins::{PluginManager, WidgetManager, get_plugin_dir};
 use crate::app::services::shortcut_registry::ShortcutRegistry;
 use crate::ui::dialogs::plugin_manager::{PluginManagerResult, show_plugin_manager_dialog};
@@ -19,7 +20,8 @@ fn normalize_plugin_name(name: &str) -> String {
     name.to_lowercase().replace(' ', "-")
 }
 
-/// Process plugin uninstalls: delete plugin directories, skipping any that were reinstalled.
+/// Process plugin uninstalls: delete plugin directories (and their persistent
+/// storage files), skipping any that were reinstalled.
 ///
 /// Returns `(actually_deleted, errors)` — names that were deleted and error messages.
 pub fn apply_plugin_uninstalls(
@@ -30,6 +32,7 @@ pub fn apply_plugin_uninstalls(
     let installed_normalized: HashSet<String> =
         installed.iter().map(|n| normalize_plugin_name(n)).collect();
 
+    let data_dir = data_dir_for(plugins_dir);
     let mut deleted = Vec::new();
     let mut errors = Vec::new();
 
@@ -45,12 +48,17 @@ pub fn apply_plugin_uninstalls(
         let plugin_path = plugins_dir.join(&dir_name);
         if plugin_path.exists() {
             if let Err(e) = std::fs::remove_dir_all(&plugin_path) {
+                // Still installed, so keep its data
                 errors.push(format!("{}: {}", name, e));
+                continue;
             } else {
                 eprintln!("[plugins] Uninstalled: {}", name);
                 deleted.push(name.clone());
             }
         }
+        if let Err(e) = remove_plugin_storage(&data_dir, &dir_name) {
+            errors.push(format!("{} (storage): {}", name, e));
+        }
     }
 
     (deleted, errors)
@@ -0,0 +1,379 @@
+//! Quick Open (Ctrl+P) — jump to a file, an open tab, a line or a symbol.
+//!
+//! The palette lists open tabs, recently used files and every file of the
+//! current project. Project files are listed on a worker thread each time
+//! the palette opens (see `FileIndex`); until that finishes the previous
+//! listing is shown, so nothing runs while the palette is closed.
+//!
+//! Query syntax:
+//! - `name` fuzzy-matches paths, ranked by match score and recency
+//! - `name:42` also jumps to line 42, and `:42` alone goes to line 42 of
+//!   the current file
+//! - `@name` fuzzy-matches symbols of the current file
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::app::domain::document::DocumentId;
+use crate::app::services::file_index::list_project_files;
+use crate::app::services::fuzzy::{fuzzy_score, fuzzy_score_path};
+use crate::app::services::syntax::symbols::Symbol;
+
+/// At most this many rows are shown
+pub const MAX_RESULTS: usize = 200;
+
+/// Score bonus for files open in a tab
+const BONUS_OPEN_TAB: i32 = 20;
+/// Score bonus for the most recently used file; it drops with each older one
+const BONUS_RECENT: i32 = 30;
+
+#[derive(Default)]
+struct IndexState {
+    root: Option<PathBuf>,
+    files: Arc<Vec<String>>,
+    indexing: bool,
+    /// Bumped whenever a listing finishes
+    generation: u64,
+}
+
+/// Project files for Quick Open, listed in the background.
+#[derive(Clone, Default)]
+pub struct FileIndex {
+    state: Arc<Mutex<IndexState>>,
+}
+
+/// The index as of now
+pub struct IndexSnapshot {
+    pub root: Option<PathBuf>,
+    /// Paths relative to `root`
+    pub files: Arc<Vec<String>>,
+    pub indexing: bool,
+    pub generation: u64,
+}
+
+impl FileIndex {
+    /// List `root` again on a worker thread. The previous listing stays
+    /// available meanwhile if it was for the same root. Wakes the FLTK
+    /// event loop when done.
+    pub fn refresh(&self, root: &Path) {
+        {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            if state.root.as_deref() == Some(root) {
+                if state.indexing {
+                    return;
+                }
+            } else {
+                state.root = Some(root.to_path_buf());
+                state.files = Arc::default();
+                state.generation += 1;
+            }
+            state.indexing = true;
+        }
+
+        let shared = self.state.clone();
+        let root = root.to_path_buf();
+        std::thread::spawn(move || {
+            let files = list_project_files(&root);
+            if let Ok(mut state) = shared.lock()
+                && state.root.as_deref() == Some(root.as_path())
+            {
+                state.files = Arc::new(files);
+                state.indexing = false;
+                state.generation += 1;
+            }
+            fltk::app::awake();
+        });
+    }
+
+    pub fn snapshot(&self) -> IndexSnapshot {
+        match self.state.lock() {
+            Ok(state) => IndexSnapshot {
+                root: state.root.clone(),
+                files: state.files.clone(),
+                indexing: state.indexing,
+                generation: state.generation,
+            },
+            Err(_) => IndexSnapshot {
+                root: None,
+                files: Arc::default(),
+                indexing: false,
+                generation: 0,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickOpenQuery<'a> {
+    /// Fuzzy file search, optionally jumping to a 1-based line
+    Files { text: &'a str, line: Option<u32> },
+    /// Symbols of the current file
+    Symbols(&'a str),
+}
+
+pub fn parse_query(input: &str) -> QuickOpenQuery<'_> {
+    let input = input.trim();
+    if let Some(rest) = input.strip_prefix('@') {
+        return QuickOpenQuery::Symbols(rest.trim());
+    }
+    if let Some((text, line)) = input.rsplit_once(':')
+        && let Ok(line) = line.parse::<u32>()
+    {
+        return QuickOpenQuery::Files {
+            text,
+            line: Some(line),
+        };
+    }
+    QuickOpenQuery::Files {
+        text: input,
+        line: None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuickOpenTarget {
+    Tab(DocumentId),
+    File(String),
+}
+
+/// One row of the file list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickOpenEntry {
+    /// Path relative to the project root (or the full path / tab name)
+    pub label: String,
+    /// "open" or "recent", shown dimmed next to the label
+    pub detail: &'static str,
+    pub target: QuickOpenTarget,
+    /// Position in the recently used history (0 = most recent)
+    pub recency: Option<usize>,
+}
+
+/// An open tab as seen by Quick Open
+pub struct OpenTab {
+    pub id: DocumentId,
+    pub name: String,
+    pub path: Option<String>,
+}
+
+/// Open tabs and the recently used history, gathered when the palette opens
+pub struct QuickOpenSources {
+    pub tabs: Vec<OpenTab>,
+    /// Most recent first
+    pub recent: Vec<String>,
+}
+
+fn label_for(path: &str, root: Option<&Path>) -> String {
+    root.and_then(|root| Path::new(path).strip_prefix(root).ok())
+        .map_or_else(|| path.to_string(), |rel| rel.to_string_lossy().to_string())
+}
+
+/// All entries, each file once: open tabs first, then recent files, then
+/// the rest of the project in path order.
+pub fn collect_entries(
+    sources: &QuickOpenSources,
+    root: Option<&Path>,
+    files: &[String],
+) -> Vec<QuickOpenEntry> {
+    let recency = |path: &str| sources.recent.iter().position(|p| p == path);
+    let mut seen = std::collections::HashSet::new();
+    let mut entries = Vec::new();
+
+    for tab in &sources.tabs {
+        let label = match tab.path {
+            Some(ref path) => {
+                seen.insert(path.clone());
+                label_for(path, root)
+            }
+            None => tab.name.clone(),
+        };
+        entries.push(QuickOpenEntry {
+            label,
+            detail: "open",
+            target: QuickOpenTarget::Tab(tab.id),
+            recency: tab.path.as_deref().and_then(recency),
+        });
+    }
+    for (i, path) in sources.recent.iter().enumerate() {
+        if seen.insert(path.clone()) {
+            entries.push(QuickOpenEntry {
+                label: label_for(path, root),
+                detail: "recent",
+                target: QuickOpenTarget::File(path.clone()),
+                recency: Some(i),
+            });
+        }
+    }
+    if let Some(root) = root {
+        for rel in files {
+            let path = root.join(rel).to_string_lossy().to_string();
+            if !seen.contains(&path) {
+                entries.push(QuickOpenEntry {
+                    label: rel.clone(),
+                    detail: "",
+                    target: QuickOpenTarget::File(path),
+                    recency: None,
+                });
+            }
+        }
+    }
+    entries
+}
+
+/// Indices of the entries matching `query`, best first
+pub fn rank_entries(entries: &[QuickOpenEntry], query: &str) -> Vec<usize> {
+    if query.trim().is_empty() {
+        return (0..entries.len().min(MAX_RESULTS)).collect();
+    }
+    let mut scored: Vec<(i32, usize)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let mut score = fuzzy_score_path(query, &entry.label)?;
+            if matches!(entry.target, QuickOpenTarget::Tab(_)) {
+                score += BONUS_OPEN_TAB;
+            }
+            if let Some(r) = entry.recency {
+                score += (BONUS_RECENT - r as i32).max(0);
+            }
+            Some((score, i))
+        })
+        .collect();
+    // Stable sort keeps the tab/recent/path order among equal scores
+    scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+    scored.truncate(MAX_RESULTS);
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+/// Indices of the symbols matching `query`, best first (file order when
+/// the query is empty)
+pub fn rank_symbols(symbols: &[Symbol], query: &str) -> Vec<usize> {
+    let mut scored: Vec<(i32, usize)> = symbols
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| fuzzy_score(query, &s.name).map(|score| (score, i)))
+        .collect();
+    scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+    scored.truncate(MAX_RESULTS);
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            parse_query("main.rs"),
+            QuickOpenQuery::Files {
+                text: "main.rs",
+                line: None
+            }
+        );
+        assert_eq!(
+            parse_query(" main.rs:42 "),
+            QuickOpenQuery::Files {
+                text: "main.rs",
+                line: Some(42)
+            }
+        );
+        assert_eq!(
+            parse_query(":7"),
+            QuickOpenQuery::Files {
+                text: "",
+                line: Some(7)
+            }
+        );
+        assert_eq!(
+            parse_query("c:notes"),
+            QuickOpenQuery::Files {
+                text: "c:notes",
+                line: None
+            }
+        );
+        assert_eq!(parse_query("@ parse"), QuickOpenQuery::Symbols("parse"));
+    }
+
+    fn sources() -> QuickOpenSources {
+        QuickOpenSources {
+            tabs: vec![
+                OpenTab {
+                    id: DocumentId(1),
+                    name: "main.rs".to_string(),
+                    path: Some("/proj/src/main.rs".to_string()),
+                },
+                OpenTab {
+                    id: DocumentId(2),
+                    name: "Untitled".to_string(),
+                    path: None,
+                },
+            ],
+            recent: vec![
+                "/proj/src/lib.rs".to_string(),
+                "/proj/src/main.rs".to_string(),
+                "/elsewhere/notes.md".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_collect_entries_dedups_and_labels() {
+        let files = vec![
+            "README.md".to_string(),
+            "src/lib.rs".to_string(),
+            "src/main.rs".to_string(),
+        ];
+        let entries = collect_entries(&sources(), Some(Path::new("/proj")), &files);
+        let labels: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.label.as_str(), e.detail))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("src/main.rs", "open"),
+                ("Untitled", "open"),
+                ("src/lib.rs", "recent"),
+                ("/elsewhere/notes.md", "recent"),
+                ("README.md", ""),
+            ]
+        );
+        assert_eq!(entries[0].recency, Some(1));
+        assert_eq!(
+            entries[4].target,
+            QuickOpenTarget::File("/proj/README.md".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rank_prefers_recent_among_equal_matches() {
+        let files = vec!["a/util.rs".to_string(), "b/util.rs".to_string()];
+        let mut sources = sources();
+        sources.recent = vec!["/proj/b/util.rs".to_string()];
+        let entries = collect_entries(&sources, Some(Path::new("/proj")), &files);
+        let ranked = rank_entries(&entries, "util.rs");
+        let labels: Vec<&str> = ranked.iter().map(|&i| entries[i].label.as_str()).collect();
+        assert_eq!(labels, vec!["b/util.rs", "a/util.rs"]);
+
+        assert!(rank_entries(&entries, "zzz").is_empty());
+        assert_eq!(rank_entries(&entries, "").len(), entries.len());
+    }
+
+    #[test]
+    fn test_rank_symbols() {
+        let symbol = |name: &str, line| Symbol {
+            name: name.to_string(),
+            kind: "function".to_string(),
+            line,
+        };
+        let symbols = vec![
+            symbol("parse_query", 1),
+            symbol("rank", 2),
+            symbol("render", 3),
+        ];
+        assert_eq!(rank_symbols(&symbols, ""), vec![0, 1, 2]);
+        assert_eq!(rank_symbols(&symbols, "rnk"), vec![1]);
+    }
+}
@@ -0,0 +1,131 @@
+//! Recently used files and recently closed tabs.
+//!
+//! The file history itself lives on disk (see `services::recent_files`) and
+//! is shared between windows; this controller keeps the last loaded copy for
+//! the menu and start page, plus this window's stack of closed tabs for
+//! Reopen Closed Tab.
+
+use crate::app::services::recent_files::{self, RecentFiles};
+
+/// How many closed tabs Reopen Closed Tab can bring back
+const MAX_CLOSED_TABS: usize = 20;
+
+/// A tab that was closed, enough to open it again
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosedTab {
+    /// File behind the tab (None for untitled tabs)
+    pub file_path: Option<String>,
+    pub cursor_position: i32,
+    /// Text of an untitled tab, so it survives being closed
+    pub content: Option<String>,
+}
+
+pub struct RecentController {
+    files: RecentFiles,
+    closed: Vec<ClosedTab>,
+}
+
+impl Default for RecentController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecentController {
+    pub fn new() -> Self {
+        Self {
+            files: recent_files::load(),
+            closed: Vec::new(),
+        }
+    }
+
+    pub fn files(&self) -> &RecentFiles {
+        &self.files
+    }
+
+    pub fn record_open(&mut self, path: &str) {
+        self.files = recent_files::record_open(path);
+    }
+
+    /// Remember a closed tab: file tabs update the history, untitled tabs
+    /// with text keep their content. Empty untitled tabs are forgotten.
+    pub fn record_close(&mut self, tab: ClosedTab) {
+        match tab.file_path {
+            Some(ref path) => self.files = recent_files::record_close(path, tab.cursor_position),
+            None if tab.content.as_deref().is_none_or(str::is_empty) => return,
+            None => {}
+        }
+        self.push_closed(tab);
+    }
+
+    pub fn forget(&mut self, path: &str) {
+        self.files = recent_files::update(|r| r.remove(path));
+    }
+
+    pub fn clear(&mut self) {
+        self.files = recent_files::update(|r| r.files.clear());
+    }
+
+    fn push_closed(&mut self, tab: ClosedTab) {
+        self.closed.push(tab);
+        if self.closed.len() > MAX_CLOSED_TABS {
+            self.closed.remove(0);
+        }
+    }
+
+    /// The most recently closed tab
+    pub fn pop_closed(&mut self) -> Option<ClosedTab> {
+        self.closed.pop()
+    }
+
+    pub fn has_closed(&self) -> bool {
+        !self.closed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn untitled(text: &str) -> ClosedTab {
+        ClosedTab {
+            file_path: None,
+            cursor_position: 0,
+            content: Some(text.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_closed_tabs_are_a_capped_stack() {
+        let mut ctrl = RecentController {
+            files: RecentFiles::default(),
+            closed: Vec::new(),
+        };
+        for i in 0..MAX_CLOSED_TABS + 2 {
+            ctrl.record_close(untitled(&i.to_string()));
+        }
+        let last = (MAX_CLOSED_TABS + 1).to_string();
+        assert_eq!(ctrl.pop_closed(), Some(untitled(&last)));
+        let mut remaining = 1;
+        while ctrl.pop_closed().is_some() {
+            remaining += 1;
+        }
+        assert_eq!(remaining, MAX_CLOSED_TABS);
+        assert!(!ctrl.has_closed());
+    }
+
+    #[test]
+    fn test_empty_untitled_tab_is_not_remembered() {
+        let mut ctrl = RecentController {
+            files: RecentFiles::default(),
+            closed: Vec::new(),
+        };
+        ctrl.record_close(untitled(""));
+        ctrl.record_close(ClosedTab {
+            file_path: None,
+            cursor_position: 0,
+            content: None,
+        });
+        assert!(!ctrl.has_closed());
+    }
+}
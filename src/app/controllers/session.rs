@@ -1,13 +1,15 @@
 use std::cell::RefCell;
 use std::fs;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::time::Instant;
 
+use super::panes::EditorLayout;
 use super::tabs::{GroupColor, GroupId, TabGroup, TabManager};
-use crate::app::domain::document::DocumentId;
+use crate::app::domain::document::{DocumentId, ViewState};
 use crate::app::domain::settings::AppSettings;
 use crate::app::services::file_size::{FileSizeCheck, check_file_size, format_size};
-use crate::app::services::session::{self, DEFAULT_SESSION_NAME, SessionRestore};
+use crate::app::services::session::{self, DEFAULT_SESSION_NAME, SessionData, SessionRestore};
 
 /// Result of restoring a session. AppState uses this to perform
 /// cross-cutting operations (highlighting, buffer binding, etc.).
@@ -15,6 +17,8 @@ pub struct RestoreResult {
     pub last_open_directory: Option<String>,
     /// Documents that need syntax highlighting: (id, path)
     pub highlight_docs: Vec<(DocumentId, String)>,
+    /// Split editor panes to recreate
+    pub editor_layout: Option<EditorLayout>,
 }
 
 /// Manages session persistence (auto-save, restore, dirty tracking).
@@ -23,6 +27,9 @@ pub struct SessionController {
     session_dirty: bool,
     /// Name of the currently active session (e.g. "default", "my-project").
     current_session_name: String,
+    /// Project session file (`.ferrispad/session.json`) to seed the session
+    /// from on startup, when it has no saved state of its own yet
+    project_file: Option<PathBuf>,
 }
 
 impl Default for SessionController {
@@ -31,6 +38,7 @@ impl Default for SessionController {
             last_auto_save: Instant::now(),
             session_dirty: false,
             current_session_name: DEFAULT_SESSION_NAME.to_string(),
+            project_file: None,
         }
     }
 }
@@ -54,6 +62,16 @@ impl SessionController {
         self.current_session_name = name;
     }
 
+    /// Set the project session file to seed the session from on startup.
+    pub fn set_project_file(&mut self, path: PathBuf) {
+        self.project_file = Some(path);
+    }
+
+    /// Take the project session file (it is only used once).
+    pub fn take_project_file(&mut self) -> Option<PathBuf> {
+        self.project_file.take()
+    }
+
     /// Mark that the session state has changed and should be auto-saved.
     pub fn mark_dirty(&mut self) {
         self.session_dirty = true;
@@ -63,6 +81,7 @@ impl SessionController {
     pub fn force_save(
         &mut self,
         tab_manager: &TabManager,
+        editor_layout: Option<&EditorLayout>,
         settings: &Rc<RefCell<AppSettings>>,
         last_open_directory: Option<&str>,
     ) {
@@ -72,6 +91,7 @@ impl SessionController {
         }
         if let Err(e) = session::save_session(
             tab_manager,
+            editor_layout,
             session_mode,
             last_open_directory,
             &self.current_session_name,
@@ -82,19 +102,22 @@ impl SessionController {
         self.last_auto_save = Instant::now();
     }
 
+    /// Whether something changed and the last auto-save is 30 seconds old.
+    pub fn auto_save_due(&self) -> bool {
+        const AUTO_SAVE_INTERVAL_SECS: u64 = 30;
+
+        self.session_dirty && self.last_auto_save.elapsed().as_secs() >= AUTO_SAVE_INTERVAL_SECS
+    }
+
     /// Auto-save the session every 30 seconds if something changed.
     pub fn auto_save_if_needed(
         &mut self,
         tab_manager: &TabManager,
+        editor_layout: Option<&EditorLayout>,
         settings: &Rc<RefCell<AppSettings>>,
         last_open_directory: Option<&str>,
     ) {
-        const AUTO_SAVE_INTERVAL_SECS: u64 = 30;
-
-        if !self.session_dirty {
-            return;
-        }
-        if self.last_auto_save.elapsed().as_secs() < AUTO_SAVE_INTERVAL_SECS {
+        if !self.auto_save_due() {
             return;
         }
 
@@ -105,6 +128,7 @@ impl SessionController {
 
         if let Err(e) = session::save_session(
             tab_manager,
+            editor_layout,
             session_mode,
             last_open_directory,
             &self.current_session_name,
@@ -131,7 +155,25 @@ impl SessionController {
         }
 
         let session_data = session::load_session(mode, session_name)?;
+        Some(Self::restore_data(
+            tab_manager,
+            settings,
+            session_data,
+            mode,
+            session_name,
+        ))
+    }
 
+    /// Restore already loaded session data into the tab manager, replacing
+    /// its initial untitled document. Temp files (unsaved changes) are only
+    /// read in `Full` mode, from the `session_name` directory.
+    pub fn restore_data(
+        tab_manager: &mut TabManager,
+        settings: &Rc<RefCell<AppSettings>>,
+        session_data: SessionData,
+        mode: SessionRestore,
+        session_name: &str,
+    ) -> RestoreResult {
         let last_open_directory = session_data.last_open_directory.clone();
 
         // Remove the initial untitled document
@@ -154,11 +196,18 @@ impl SessionController {
 
         let mut highlight_docs = Vec::new();
         let target_index = session_data.active_index;
+        // Restored document for each session entry (None if skipped)
+        let mut restored_ids = vec![None; session_data.documents.len()];
 
         for (i, doc_session) in session_data.documents.iter().enumerate() {
             let group_id = doc_session
                 .group_index
                 .and_then(|idx| group_ids.get(idx).copied());
+            let view = ViewState {
+                top_line: doc_session.top_line,
+                horiz_offset: doc_session.horiz_offset,
+                selection: doc_session.selection,
+            };
 
             if let Some(ref path) = doc_session.file_path {
                 // Skip files that are too large (don't show dialogs at startup)
@@ -178,6 +227,7 @@ impl SessionController {
 
                 if let Ok(content) = fs::read_to_string(path) {
                     let id = tab_manager.add_from_file(path.clone(), &content);
+                    restored_ids[i] = Some(id);
 
                     highlight_docs.push((id, path.clone()));
 
@@ -197,8 +247,13 @@ impl SessionController {
 
                     if let Some(doc) = tab_manager.doc_by_id_mut(id) {
                         doc.cursor_position = doc_session.cursor_position;
+                        doc.view = view;
+                        doc.set_bookmark_lines(&doc_session.bookmarks);
                         doc.group_id = group_id;
-                        doc.disk_mtime = fs::metadata(path).ok().and_then(|m| m.modified().ok());
+                        doc.record_disk_state(path);
+                    }
+                    if doc_session.pinned {
+                        tab_manager.set_pinned(id, true);
                     }
 
                     if i == target_index {
@@ -210,11 +265,17 @@ impl SessionController {
                 && let Some(temp_content) = session::read_temp_file(temp_file, session_name)
             {
                 let id = tab_manager.add_untitled();
+                restored_ids[i] = Some(id);
                 if let Some(doc) = tab_manager.doc_by_id_mut(id) {
                     doc.buffer.set_text(&temp_content);
                     doc.cursor_position = doc_session.cursor_position;
+                    doc.view = view;
+                    doc.set_bookmark_lines(&doc_session.bookmarks);
                     doc.group_id = group_id;
                 }
+                if doc_session.pinned {
+                    tab_manager.set_pinned(id, true);
+                }
                 if i == target_index {
                     tab_manager.set_active(id);
                 }
@@ -225,9 +286,25 @@ impl SessionController {
             tab_manager.add_untitled();
         }
 
-        Some(RestoreResult {
+        let editor_layout = session_data.editor_layout.map(|layout| EditorLayout {
+            orientation: layout.orientation,
+            panes: layout
+                .panes
+                .iter()
+                .map(|p| {
+                    let doc = p
+                        .document_index
+                        .and_then(|i| restored_ids.get(i).copied().flatten());
+                    (doc, p.cursor_position)
+                })
+                .collect(),
+            focused: layout.focused,
+        });
+
+        RestoreResult {
             last_open_directory,
             highlight_docs,
-        })
+            editor_layout,
+        }
     }
 }
@@ -175,6 +175,36 @@ impl TabManager {
         }
     }
 
+    /// Number of pinned tabs. Pinned tabs always come first in the tab order.
+    pub fn pinned_count(&self) -> usize {
+        self.documents.iter().take_while(|d| d.pinned).count()
+    }
+
+    /// Pin or unpin a tab. A pinned tab leaves its group and moves to the end
+    /// of the pinned run at the front; an unpinned tab moves just after it.
+    pub fn set_pinned(&mut self, id: DocumentId, pinned: bool) {
+        let Some(from) = self.documents.iter().position(|d| d.id == id) else {
+            return;
+        };
+        let mut doc = self.documents.remove(from);
+        doc.pinned = pinned;
+        if pinned {
+            doc.group_id = None;
+        }
+        let insert_at = self.pinned_count();
+        self.documents.insert(insert_at, doc);
+    }
+
+    /// Keep an insertion index on the source's side of the pinned boundary.
+    fn clamp_to_pin_region(&self, pos: usize, pinned: bool) -> usize {
+        let boundary = self.pinned_count();
+        if pinned {
+            pos.min(boundary)
+        } else {
+            pos.max(boundary)
+        }
+    }
+
     /// Remove a document by id. Activates the nearest neighbor.
     /// Cleans up the buffer to free memory immediately.
     pub fn remove(&mut self, id: DocumentId) {
@@ -211,6 +241,7 @@ impl TabManager {
         // Clamp `to` so it doesn't land inside a group the source doesn't belong to.
         // `to` is an insertion index (0..=len), not a tab index.
         let to = to.min(self.documents.len());
+        let to = self.clamp_to_pin_region(to, self.documents[from].pinned);
         let adjusted = self.clamp_insert_outside_foreign_group(to, source_group);
 
         let doc = self.documents.remove(from);
@@ -243,6 +274,11 @@ impl TabManager {
             None => return,
         };
 
+        // Pinned tabs only reorder among themselves and never join a group
+        let pinned = self.documents[from].pinned;
+        let target_group = if pinned { None } else { target_group };
+        let to = self.clamp_to_pin_region(to, pinned);
+
         // Remove the document
         let mut doc = self.documents.remove(from);
 
@@ -321,7 +357,7 @@ impl TabManager {
 
         // Clamp so we don't split a foreign group
         let insert_at = self.clamp_insert_outside_foreign_group(insert_at, Some(group_id));
-        let insert_at = insert_at.min(self.documents.len());
+        let insert_at = insert_at.max(self.pinned_count()).min(self.documents.len());
 
         // Re-insert all group docs at the target position
         for (i, doc) in group_docs.into_iter().enumerate() {
@@ -499,7 +535,11 @@ impl TabManager {
             collapsed: false,
         });
         for &doc_id in tab_ids {
-            if let Some(doc) = self.documents.iter_mut().find(|d| d.id == doc_id) {
+            if let Some(doc) = self
+                .documents
+                .iter_mut()
+                .find(|d| d.id == doc_id && !d.pinned)
+            {
                 doc.group_id = Some(id);
             }
         }
@@ -538,6 +578,10 @@ impl TabManager {
 
     pub fn set_tab_group(&mut self, doc_id: DocumentId, group_id: Option<GroupId>) {
         if let Some(doc) = self.documents.iter_mut().find(|d| d.id == doc_id) {
+            // Pinned tabs stay out of groups
+            if doc.pinned {
+                return;
+            }
             doc.group_id = group_id;
         }
         // Move the tab to sit next to its group members
@@ -0,0 +1,234 @@
+//! Task controller — runs user-defined tasks and collects their problems.
+//!
+//! Tasks run either in a terminal tab (driven by the terminal panel) or in a
+//! background PTY owned by a worker thread. Either way the raw output comes
+//! back as `Message::TaskFinished` and is parsed with the task's problem
+//! matchers. The problems of the last finished task are shown alongside the
+//! active document's diagnostics and walked with "Next Error".
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use fltk::app::Sender;
+
+use crate::app::domain::messages::Message;
+use crate::app::plugins::{Diagnostic, DiagnosticLevel};
+use crate::app::services::tasks::{self, CompiledMatcher, TaskDefinition};
+use crate::app::services::terminal::pty::{ArgvMode, PtySession};
+
+/// PTY size for background tasks (wide so tools don't wrap their messages)
+const BACKGROUND_COLS: u16 = 200;
+const BACKGROUND_ROWS: u16 = 50;
+
+/// A task that has been started and not yet finished
+struct TaskRun {
+    name: String,
+    dir: PathBuf,
+    matchers: Vec<CompiledMatcher>,
+}
+
+/// Result of a finished task, for the status toast
+pub struct TaskOutcome {
+    pub name: String,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+#[derive(Default)]
+pub struct TaskController {
+    /// Last started task and the project root it ran for
+    last: Option<(TaskDefinition, PathBuf)>,
+    running: HashMap<u32, TaskRun>,
+    next_run_id: u32,
+    /// Problems found in the output of the last finished task
+    diagnostics: Vec<Diagnostic>,
+    /// Index of the problem "Next Error" jumps to
+    next_error: usize,
+}
+
+impl TaskController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tasks available for a project: those in its `.ferrispad/tasks.json`
+    /// first, then the global ones from settings. A broken project file is
+    /// reported as the error, with the global tasks still returned.
+    pub fn available_tasks(
+        root: &Path,
+        global: &[TaskDefinition],
+    ) -> (Vec<TaskDefinition>, Option<String>) {
+        let (mut list, error) = match tasks::load_project_tasks(root) {
+            Ok(list) => (list, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        // Project tasks shadow global ones with the same name
+        let extra: Vec<TaskDefinition> = global
+            .iter()
+            .filter(|g| !list.iter().any(|t| t.name == g.name))
+            .cloned()
+            .collect();
+        list.extend(extra);
+        (list, error)
+    }
+
+    /// Register a new run of `task` for the project at `root`.
+    /// Returns the run ID and the directory to run in.
+    pub fn start(&mut self, task: &TaskDefinition, root: &Path) -> Result<(u32, PathBuf), String> {
+        if self.running.values().any(|r| r.name == task.name) {
+            return Err(format!("Task '{}' is already running", task.name));
+        }
+        let matchers = tasks::compile_matchers(&task.problem_matchers)
+            .map_err(|e| format!("Task '{}': {}", task.name, e))?;
+        let dir = task.working_dir(root);
+
+        self.next_run_id += 1;
+        let run_id = self.next_run_id;
+        self.running.insert(
+            run_id,
+            TaskRun {
+                name: task.name.clone(),
+                dir: dir.clone(),
+                matchers,
+            },
+        );
+        self.last = Some((task.clone(), root.to_path_buf()));
+        Ok((run_id, dir))
+    }
+
+    /// The task to re-run and its project root
+    pub fn last_task(&self) -> Option<(TaskDefinition, PathBuf)> {
+        self.last.clone()
+    }
+
+    /// Forget a run that could not be started
+    pub fn cancel(&mut self, run_id: u32) {
+        self.running.remove(&run_id);
+    }
+
+    /// Parse the output of a finished run and make its problems current.
+    /// Returns None for unknown runs.
+    pub fn finish(&mut self, run_id: u32, output: &[u8]) -> Option<TaskOutcome> {
+        let run = self.running.remove(&run_id)?;
+        let lines = tasks::output_lines(output);
+        self.diagnostics = tasks::parse_problems(&lines, &run.matchers, &run.dir, &run.name);
+        self.next_error = 0;
+
+        let count = |level| self.diagnostics.iter().filter(|d| d.level == level).count();
+        Some(TaskOutcome {
+            errors: count(DiagnosticLevel::Error),
+            warnings: count(DiagnosticLevel::Warning),
+            name: run.name,
+        })
+    }
+
+    /// Problems from the last finished task
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// The next problem to visit, wrapping around at the end.
+    /// Returns the problem and its 1-based position.
+    pub fn next_error(&mut self) -> Option<(&Diagnostic, usize)> {
+        if self.diagnostics.is_empty() {
+            return None;
+        }
+        let idx = self.next_error % self.diagnostics.len();
+        self.next_error = idx + 1;
+        Some((&self.diagnostics[idx], idx + 1))
+    }
+}
+
+/// Run a task's command in a hidden PTY on a worker thread and report its
+/// output with `Message::TaskFinished` when it exits.
+pub fn spawn_background(run_id: u32, command: String, dir: PathBuf, sender: Sender<Message>) {
+    std::thread::spawn(move || {
+        let dir = dir.to_string_lossy().into_owned();
+        let (output, exit_code) = match PtySession::spawn(
+            Some(&command),
+            &[],
+            ArgvMode::Shell,
+            Some(&dir),
+            &BTreeMap::new(),
+            BACKGROUND_COLS,
+            BACKGROUND_ROWS,
+        ) {
+            Ok((pty, mut reader)) => {
+                let mut output = Vec::new();
+                // The PTY reports EOF as an error once the child is gone;
+                // everything read up to then is kept.
+                let _ = reader.read_to_end(&mut output);
+                let code = pty.wait_exit(Duration::from_secs(2));
+                (output, code)
+            }
+            Err(e) => (e.into_bytes(), None),
+        };
+        sender.send(Message::TaskFinished {
+            run_id,
+            output,
+            exit_code,
+        });
+        fltk::app::awake();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::services::tasks::{MatcherSpec, TaskRunMode};
+
+    fn task(name: &str) -> TaskDefinition {
+        TaskDefinition {
+            name: name.to_string(),
+            command: "make".to_string(),
+            cwd: None,
+            problem_matchers: vec![MatcherSpec::Builtin("gcc".to_string())],
+            run_in: TaskRunMode::Background,
+        }
+    }
+
+    #[test]
+    fn test_start_and_finish() {
+        let mut ctrl = TaskController::new();
+        let (run, dir) = ctrl.start(&task("build"), Path::new("/proj")).unwrap();
+        assert_eq!(dir, PathBuf::from("/proj"));
+        assert!(ctrl.start(&task("build"), Path::new("/proj")).is_err());
+        assert_eq!(ctrl.last_task().unwrap().0.name, "build");
+
+        let outcome = ctrl
+            .finish(run, b"a.c:1:2: error: x\r\nb.c:3:4: warning: y\r\n")
+            .unwrap();
+        assert_eq!((outcome.errors, outcome.warnings), (1, 1));
+        assert!(ctrl.finish(run, b"").is_none());
+        // Finished runs can be started again
+        assert!(ctrl.start(&task("build"), Path::new("/proj")).is_ok());
+    }
+
+    #[test]
+    fn test_next_error_wraps() {
+        let mut ctrl = TaskController::new();
+        assert!(ctrl.next_error().is_none());
+        let (run, _) = ctrl.start(&task("build"), Path::new("/proj")).unwrap();
+        ctrl.finish(run, b"a.c:1:2: error: x\nb.c:3:4: error: y\n");
+
+        let visited: Vec<(u32, usize)> = (0..3)
+            .map(|_| {
+                let (d, pos) = ctrl.next_error().unwrap();
+                (d.line, pos)
+            })
+            .collect();
+        assert_eq!(visited, vec![(1, 1), (3, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn test_bad_matcher_is_rejected() {
+        let mut ctrl = TaskController::new();
+        let mut bad = task("lint");
+        bad.problem_matchers = vec![MatcherSpec::Builtin("unknown".to_string())];
+        assert!(ctrl.start(&bad, Path::new("/proj")).is_err());
+        // Nothing was registered, so the name is not blocked
+        assert!(ctrl.start(&task("lint"), Path::new("/proj")).is_ok());
+    }
+}
@@ -31,6 +31,11 @@ impl ViewController {
         }
     }
 
+    /// Point the view at the focused pane's editor
+    pub fn set_editor(&mut self, editor: TextEditor) {
+        self.editor = editor;
+    }
+
     /// Update line number gutter width based on document line count.
     pub fn update_linenumber_width(&mut self, tab_manager: &TabManager) {
         if !self.show_linenumbers {
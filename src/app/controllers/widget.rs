@@ -1,4 +1,4 @@
-//! Widget controller — manages tree view and split view lifecycle.
+//! Widget controller — manages tree view, table view and split view lifecycle.
 //!
 //! Extracted from AppState to isolate widget management from core coordination.
 
@@ -13,6 +13,7 @@ use crate::app::infrastructure::buffer::buffer_text_no_leak;
 use crate::app::infrastructure::defer::defer_send;
 use crate::app::plugins::{HookResult, PluginHook, PluginManager, WidgetActionData, WidgetManager};
 use crate::ui::split_panel::SplitPanel;
+use crate::ui::table_panel::TablePanel;
 use crate::ui::tree_panel::TreePanel;
 
 /// Look up a plugin's approved commands by name.
@@ -124,6 +125,7 @@ impl WidgetController {
                     input_text: None,
                     content: None,
                     target_path: None,
+                    row_id: None,
                 },
                 path: tab_manager.active_doc().and_then(|d| d.file_path.clone()),
             },
@@ -235,6 +237,7 @@ impl WidgetController {
                     input_text: None,
                     content: Some(buffer_content),
                     target_path: None,
+                    row_id: None,
                 },
                 path: current_path,
             },
@@ -293,6 +296,7 @@ impl WidgetController {
                     input_text: None,
                     content: Some(buffer_content),
                     target_path: None,
+                    row_id: None,
                 },
                 path: current_path,
             },
@@ -351,6 +355,90 @@ impl WidgetController {
                     input_text,
                     content: None,
                     target_path,
+                    row_id: None,
+                },
+                path: current_path,
+            },
+        );
+
+        if let Some(result) = result {
+            let approved = approved_commands_for(plugins, &plugin_name);
+            process_widget_requests(
+                &result,
+                &plugin_name,
+                &approved,
+                &mut self.widget_manager,
+                self.sender,
+            );
+            let mut ctx = HookContext {
+                tab_manager,
+                view,
+                widget_manager: &mut self.widget_manager,
+                sender: self.sender,
+                approved_commands: approved,
+            };
+            hook_dispatch::dispatch_hook_result(result, &plugin_name, &mut ctx);
+        }
+    }
+
+    /// Show a table view panel from a plugin request.
+    pub fn show_table_view(
+        &mut self,
+        session_id: u32,
+        plugin_name: &str,
+        request: &crate::app::plugins::TableViewRequest,
+        table_panel: &mut TablePanel,
+        highlight: &HighlightController,
+        view: &ViewController,
+    ) {
+        // Only one table is shown at a time; drop the session it replaces
+        if let Some(old_id) = table_panel.session_id()
+            && old_id != session_id
+        {
+            self.widget_manager.remove_session(old_id);
+        }
+        let theme_bg = highlight.highlighter().theme_background();
+        table_panel.apply_theme(view.dark_mode, theme_bg);
+        table_panel.show_request(session_id, plugin_name, request);
+    }
+
+    /// Hide the table view panel.
+    pub fn hide_table_view(&mut self, session_id: u32, table_panel: &mut TablePanel) {
+        if table_panel.session_id() == Some(session_id) {
+            table_panel.hide();
+        }
+        self.widget_manager.remove_session(session_id);
+    }
+
+    /// Handle a table view row click or context menu action.
+    pub fn handle_table_view_action(
+        &mut self,
+        session_id: u32,
+        action: String,
+        row_id: Option<String>,
+        input_text: Option<String>,
+        plugins: &mut PluginManager,
+        tab_manager: &mut TabManager,
+        view: &mut ViewController,
+    ) {
+        let session = match self.widget_manager.get_session(session_id) {
+            Some(s) => s.clone(),
+            None => return,
+        };
+
+        let plugin_name = session.plugin_name.clone();
+        let current_path = tab_manager.active_doc().and_then(|d| d.file_path.clone());
+
+        let result = plugins.call_hook_on_plugin(
+            &plugin_name,
+            PluginHook::OnWidgetAction {
+                widget_type: "table_view".to_string(),
+                action,
+                session_id,
+                data: WidgetActionData {
+                    input_text,
+                    row_id,
+                    ..Default::default()
                 },
                 path: current_path,
             },
@@ -446,6 +534,14 @@ impl WidgetController {
         }
     }
 
+    /// Close a non-persistent table view; it belongs to the document that
+    /// was active when the plugin showed it. Called on tab switch.
+    pub fn close_non_persistent_table_view(&self) {
+        if let Some(id) = self.widget_manager.non_persistent_table_session() {
+            self.sender.send(Message::TableViewHide(id));
+        }
+    }
+
     /// Refresh the tree view panel for the current active document.
     pub fn refresh_tree_view_for_active_doc(&mut self, tab_manager: &TabManager) {
         // Handle persistent + non-persistent coexistence
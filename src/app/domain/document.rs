@@ -8,6 +8,9 @@ use fltk::text::TextBuffer;
 use super::messages::Message;
 use crate::app::controllers::tabs::GroupId;
 use crate::app::plugins::{Diagnostic, TreeViewRequest};
+use crate::app::services::bookmarks;
+use crate::app::services::file_size::FileStamp;
+use crate::app::services::follow::FollowState;
 use crate::app::services::syntax::checkpoint::SparseCheckpoints;
 use crate::app::services::text_ops::extract_filename;
 
@@ -89,10 +92,12 @@ fn register_modify_callback(
     buffer: &TextBuffer,
     style_buffer: &TextBuffer,
     has_unsaved_changes: &Rc<Cell<bool>>,
+    edits_sent: &Rc<Cell<u64>>,
     doc_id: DocumentId,
     sender: Sender<Message>,
 ) -> *mut c_void {
     let changes = has_unsaved_changes.clone();
+    let edits_sent = edits_sent.clone();
     let mut style_buf = style_buffer.clone();
 
     let cb: Box<ModifyCb> = Box::new(
@@ -110,6 +115,7 @@ fn register_modify_callback(
                 if deleted > 0 {
                     style_buf.remove(pos, pos + deleted);
                 }
+                edits_sent.set(edits_sent.get() + 1);
                 sender.send(Message::BufferModified {
                     id: doc_id,
                     pos,
@@ -143,7 +149,41 @@ pub enum PartialFileInfo {
     /// Tail view — buffer contains content starting at `start_byte` to the end of the file.
     Tail { start_byte: u64 },
     /// Chunk view — buffer contains content from `start_byte` to `end_byte` (exclusive).
-    Chunk { start_byte: u64, end_byte: u64 },
+    /// `stamp` is the file as it was when the chunk was read; a file that no
+    /// longer matches it has moved the bytes under the chunk's offsets.
+    Chunk {
+        start_byte: u64,
+        end_byte: u64,
+        stamp: Option<FileStamp>,
+    },
+}
+
+impl PartialFileInfo {
+    /// The bytes of the file the document covers, end exclusive
+    pub fn byte_range(&self) -> (u64, u64) {
+        match *self {
+            PartialFileInfo::Full => (0, u64::MAX),
+            PartialFileInfo::Tail { start_byte } => (start_byte, u64::MAX),
+            PartialFileInfo::Chunk {
+                start_byte,
+                end_byte,
+                ..
+            } => (start_byte, end_byte),
+        }
+    }
+}
+
+/// Where a document was scrolled to and what was selected when it was last
+/// shown, so switching back to it (or restoring a session) looks the same.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ViewState {
+    /// First visible line (1-based); 0 when unknown, e.g. for a file that has
+    /// not been shown yet. The cursor is then scrolled into view instead.
+    pub top_line: i32,
+    /// Horizontal scroll offset in pixels
+    pub horiz_offset: i32,
+    /// Selected byte range (start, end)
+    pub selection: Option<(i32, i32)>,
 }
 
 pub struct Document {
@@ -154,9 +194,17 @@ pub struct Document {
     pub has_unsaved_changes: Rc<Cell<bool>>,
     pub display_name: String,
     pub cursor_position: i32,
+    /// Scroll position and selection saved when the document is deactivated
+    pub view: ViewState,
+    /// Bookmarked line starts (byte positions), sorted. Kept on their lines
+    /// through edits by `replay_edit`.
+    pub bookmarks: Vec<i32>,
     pub checkpoints: SparseCheckpoints,
     pub syntax_name: Option<String>,
     pub group_id: Option<GroupId>,
+    /// Pinned tabs sit compact at the left of the tab bar and are skipped
+    /// by Close Tab and the bulk closes. Kept in sync by TabManager.
+    pub pinned: bool,
     /// Diagnostics from the last lint run (persisted across tab switches)
     pub diagnostics: Vec<Diagnostic>,
     /// Whether this document has been linted at least once
@@ -167,12 +215,24 @@ pub struct Document {
     pub cached_line_count: usize,
     /// Last-known modification time of the on-disk file (None for untitled docs)
     pub disk_mtime: Option<std::time::SystemTime>,
+    /// Length of the on-disk file when the buffer was last read from or
+    /// saved to it. Following starts here, so appends made since are shown.
+    pub disk_len: Option<u64>,
     /// Disambiguated tab label when multiple files share the same filename.
     /// Set by TabManager; None means display_name is already unique.
     pub disambiguated_name: Option<String>,
     /// For tail/chunk documents: byte offsets into the original file so edits
     /// can be saved back to the correct position.
     pub partial_info: PartialFileInfo,
+    /// Set while the document follows appends to its file (`tail -f`)
+    pub follow: Option<FollowState>,
+    /// Number of `BufferModified` messages sent by the modify callback
+    edits_sent: Rc<Cell<u64>>,
+    /// Number of those messages handled by `replay_edit`
+    edits_replayed: u64,
+    /// `edits_sent` when the bookmarks were last set from the buffer as it
+    /// was; older edits are already accounted for in their positions
+    bookmarks_edit: u64,
     /// Pointer to the heap-allocated closure passed to FLTK's modify callback.
     /// Must be freed in cleanup() after removing the callback.
     modify_cb_data: *mut c_void,
@@ -189,9 +249,16 @@ impl Document {
         let buffer = TextBuffer::default();
         let style_buffer = TextBuffer::default();
         let has_unsaved_changes = Rc::new(Cell::new(false));
+        let edits_sent = Rc::new(Cell::new(0));
 
-        let modify_cb_data =
-            register_modify_callback(&buffer, &style_buffer, &has_unsaved_changes, id, sender);
+        let modify_cb_data = register_modify_callback(
+            &buffer,
+            &style_buffer,
+            &has_unsaved_changes,
+            &edits_sent,
+            id,
+            sender,
+        );
 
         Self {
             id,
@@ -201,16 +268,24 @@ impl Document {
             has_unsaved_changes,
             display_name,
             cursor_position: 0,
+            view: ViewState::default(),
+            bookmarks: Vec::new(),
             checkpoints: SparseCheckpoints::new(),
             syntax_name: None,
             group_id: None,
+            pinned: false,
             diagnostics: Vec::new(),
             has_been_linted: false,
             cached_tree: None,
             cached_line_count: 0,
             disk_mtime: None,
+            disk_len: None,
             disambiguated_name: None,
             partial_info: PartialFileInfo::Full,
+            follow: None,
+            edits_sent,
+            edits_replayed: 0,
+            bookmarks_edit: 0,
             modify_cb_data,
         }
     }
@@ -226,9 +301,16 @@ impl Document {
         let buffer = TextBuffer::default();
         let mut style_buffer = TextBuffer::default();
         let has_unsaved_changes = Rc::new(Cell::new(false));
+        let edits_sent = Rc::new(Cell::new(0));
 
-        let modify_cb_data =
-            register_modify_callback(&buffer, &style_buffer, &has_unsaved_changes, id, sender);
+        let modify_cb_data = register_modify_callback(
+            &buffer,
+            &style_buffer,
+            &has_unsaved_changes,
+            &edits_sent,
+            id,
+            sender,
+        );
 
         // These trigger the modify callback, which keeps style_buffer in sync
         buffer.clone().set_text(content);
@@ -244,16 +326,24 @@ impl Document {
             has_unsaved_changes,
             display_name,
             cursor_position: 0,
+            view: ViewState::default(),
+            bookmarks: Vec::new(),
             checkpoints: SparseCheckpoints::new(),
             syntax_name: None,
             group_id: None,
+            pinned: false,
             diagnostics: Vec::new(),
             has_been_linted: false,
             cached_tree: None,
             cached_line_count: content.lines().count(),
             disk_mtime: None,
+            disk_len: None,
             disambiguated_name: None,
             partial_info: PartialFileInfo::Full,
+            follow: None,
+            edits_sent,
+            edits_replayed: 0,
+            bookmarks_edit: 0,
             modify_cb_data,
         }
     }
@@ -276,9 +366,16 @@ impl Document {
 
         let mut style_buffer = TextBuffer::default();
         let has_unsaved_changes = Rc::new(Cell::new(false));
+        let edits_sent = Rc::new(Cell::new(0));
 
-        let modify_cb_data =
-            register_modify_callback(&buffer, &style_buffer, &has_unsaved_changes, id, sender);
+        let modify_cb_data = register_modify_callback(
+            &buffer,
+            &style_buffer,
+            &has_unsaved_changes,
+            &edits_sent,
+            id,
+            sender,
+        );
 
         // Initialize style buffer for syntax highlighting
         // For large files, skip this to save memory (no highlighting anyway)
@@ -299,20 +396,36 @@ impl Document {
             has_unsaved_changes,
             display_name,
             cursor_position: 0,
+            view: ViewState::default(),
+            bookmarks: Vec::new(),
             checkpoints: SparseCheckpoints::new(),
             syntax_name: None,
             group_id: None,
+            pinned: false,
             diagnostics: Vec::new(),
             has_been_linted: false,
             cached_tree: None,
             cached_line_count,
             disk_mtime: None,
+            disk_len: None,
             disambiguated_name: None,
             partial_info: PartialFileInfo::Full,
+            follow: None,
+            edits_sent,
+            edits_replayed: 0,
+            bookmarks_edit: 0,
             modify_cb_data,
         }
     }
 
+    /// Remember the modification time and length of the file the buffer
+    /// was just read from or saved to
+    pub fn record_disk_state(&mut self, path: impl AsRef<std::path::Path>) {
+        let metadata = std::fs::metadata(path).ok();
+        self.disk_mtime = metadata.as_ref().and_then(|m| m.modified().ok());
+        self.disk_len = metadata.map(|m| m.len());
+    }
+
     pub fn is_dirty(&self) -> bool {
         self.has_unsaved_changes.get()
     }
@@ -336,6 +449,68 @@ impl Document {
             .unwrap_or(&self.display_name)
     }
 
+    /// Replay an edit from a `BufferModified` message, which is handled
+    /// after the fact and possibly after later edits have been made.
+    /// Bookmarks are shifted arithmetically and only snapped back onto line
+    /// starts once the last queued edit has been replayed, when the buffer
+    /// is in the state their positions describe. Returns true then.
+    pub fn replay_edit(&mut self, pos: i32, inserted: i32, deleted: i32) -> bool {
+        self.edits_replayed += 1;
+        if self.edits_replayed > self.bookmarks_edit {
+            bookmarks::shift_positions(&mut self.bookmarks, pos, inserted, deleted);
+        }
+        if self.has_queued_edits() {
+            return false;
+        }
+        let buffer = &self.buffer;
+        let len = buffer.length();
+        bookmarks::snap(&mut self.bookmarks, |p| buffer.line_start(p.clamp(0, len)));
+        true
+    }
+
+    /// Whether the buffer has edits whose `BufferModified` message hasn't
+    /// been replayed yet. Positions taken from the buffer now are ahead of
+    /// positions kept through `replay_edit` until it returns true.
+    pub fn has_queued_edits(&self) -> bool {
+        self.edits_replayed < self.edits_sent.get()
+    }
+
+    /// Bookmark the line containing `pos`, or remove its bookmark.
+    /// Returns true when the bookmark was added.
+    pub fn toggle_bookmark(&mut self, pos: i32) -> bool {
+        let line_start = self.buffer.line_start(pos.clamp(0, self.buffer.length()));
+        bookmarks::toggle(&mut self.bookmarks, line_start)
+    }
+
+    /// Bookmarked lines, 1-based
+    pub fn bookmark_lines(&self) -> Vec<u32> {
+        self.bookmarks
+            .iter()
+            .map(|&p| self.buffer.count_lines(0, p) as u32 + 1)
+            .collect()
+    }
+
+    /// Replace the bookmarks with the given 1-based lines. Lines past the
+    /// end of the buffer are dropped.
+    pub fn set_bookmark_lines(&mut self, lines: &[u32]) {
+        let mut lines = lines.to_vec();
+        lines.sort_unstable();
+        lines.dedup();
+        self.bookmarks.clear();
+        self.bookmarks_edit = self.edits_sent.get();
+        let (mut line, mut line_start) = (1, 0);
+        for target in lines.into_iter().filter(|&l| l >= 1) {
+            while line < target {
+                match self.buffer.find_char_forward(line_start, '\n') {
+                    Some(newline) => line_start = newline + 1,
+                    None => return,
+                }
+                line += 1;
+            }
+            self.bookmarks.push(line_start);
+        }
+    }
+
     /// Clean up FFI resources. Called automatically by Drop.
     ///
     /// This method is idempotent - safe to call multiple times.
@@ -1,8 +1,10 @@
 use super::document::DocumentId;
 use super::settings::SyntaxTheme;
+use crate::app::controllers::panes::{PaneId, SplitOrientation};
 use crate::app::controllers::tabs::{GroupColor, GroupId};
 use crate::app::plugins::{
-    Diagnostic, LineAnnotation, SplitViewRequest, TerminalViewRequest, TreeViewRequest,
+    Diagnostic, LineAnnotation, SplitViewRequest, StatusItem, TableViewRequest,
+    TerminalViewRequest, TreeViewRequest,
 };
 use crate::app::services::plugin_update_checker::PluginUpdateInfo;
 use crate::app::services::updater::ReleaseInfo;
@@ -15,10 +17,17 @@ pub enum Message {
     // File
     FileNew,
     FileOpen,
+    /// Open a line or byte range of a file as an editable chunk
+    FileOpenRange,
+    /// Choose a file and show it in the hex viewer
+    FileOpenHex,
     FileSave,
     FileSaveAs,
     FileReload,
     FileReloadAll,
+    /// Open a file from the recently used history
+    FileOpenRecent(String),
+    FileClearRecent,
     FileQuit,
     WindowClose,
     WindowFocusGained,
@@ -30,6 +39,20 @@ pub enum Message {
     TabMove(usize, usize),
     TabNext,
     TabPrevious,
+    /// Close every unpinned tab except this one
+    TabCloseOthers(DocumentId),
+    /// Close the unpinned tabs after this one
+    TabCloseToRight(DocumentId),
+    /// Close the unpinned tabs without unsaved changes
+    TabCloseSaved,
+    /// Close every unpinned tab
+    TabCloseAll,
+    TabCopyPath(DocumentId),
+    TabCopyRelativePath(DocumentId),
+    TabRevealInTree(DocumentId),
+    TabTogglePin(DocumentId),
+    /// Reopen the most recently closed tab
+    TabReopenClosed,
 
     // Tab Groups
     TabGroupCreate(DocumentId),
@@ -55,15 +78,37 @@ pub enum Message {
     ShowFind,
     ShowReplace,
     ShowGoToLine,
+    /// Bookmark the cursor line, or remove its bookmark
+    BookmarkToggle,
+    BookmarkNext,
+    BookmarkPrevious,
+    /// List the bookmarks of all open documents
+    ShowBookmarks,
+    /// Back/forward through the navigation history
+    NavigateBack,
+    NavigateForward,
 
     // View
     ToggleLineNumbers,
     ToggleWordWrap,
     ToggleDarkMode,
     ToggleHighlighting,
+    /// Follow appends to the active document's file (`tail -f`)
+    ToggleFollow,
+    /// Poll followed files for appended content
+    FollowPoll,
     TogglePreview,
     ToggleDiagnosticsPanel,
 
+    // Editor panes
+    SplitEditor(SplitOrientation),
+    CloseEditorPane,
+    FocusNextPane,
+    /// Show a tab in the other pane (splitting first if needed)
+    MoveTabToOtherPane(DocumentId),
+    /// A pane's editor got keyboard focus
+    EditorPaneFocused(PaneId),
+
     // Format
     SetFont(String),
     SetFontSize(i32),
@@ -86,14 +131,16 @@ pub enum Message {
     ContinueHighlight,
     /// Debounced text change hook (fires 300ms after last edit)
     DoTextChangeHook,
+    /// Debounced editor event hooks (cursor, selection, tab, focus, session, rename)
+    DoEditorEventHooks,
 
     // Background updates
     BackgroundUpdateResult(Option<ReleaseInfo>),
     ShowBannerUpdate,
     DismissBanner,
 
-    // Live preview from settings dialog
-    PreviewSyntaxTheme(SyntaxTheme),
+    // Live preview from settings dialog (built-in theme, optional user/plugin theme)
+    PreviewSyntaxTheme(SyntaxTheme, Option<String>),
 
     // Plugin system
     PluginsToggleGlobal,
@@ -122,6 +169,11 @@ pub enum Message {
     CheckPluginUpdates,
     /// Plugin update check completed with results
     PluginUpdatesChecked(Vec<PluginUpdateInfo>),
+    /// A plugin replaced its status bar items
+    StatusItemsUpdate {
+        plugin_name: String,
+        items: Vec<StatusItem>,
+    },
 
     // Diagnostics
     DiagnosticsUpdate(Vec<Diagnostic>),
@@ -217,15 +269,76 @@ pub enum Message {
         plugin_name: String,
         request: TerminalViewRequest,
     },
-    /// Hide the current terminal view
+    /// Hide the terminal panel (tabs keep running)
     TerminalViewHide(u32),
-    /// Terminal produced output (signal to drain shared buffer)
+    /// Terminal produced output (signal to drain shared buffers)
     TerminalOutput(Vec<u8>),
-    /// Terminal child process exited
-    TerminalExited,
+    /// Child process of a terminal tab exited (tab ID)
+    TerminalExited(u32),
+    /// View > Terminal: show/hide the terminal panel, opening a shell if none exists
+    TerminalToggle,
+    /// Open a new shell tab in the terminal panel
+    TerminalNewTab,
+    /// Switch to a terminal tab (tab ID)
+    TerminalSelectTab(u32),
+    /// Close a terminal tab and kill its process (tab ID)
+    TerminalCloseTab(u32),
+    /// Rename a terminal tab
+    TerminalRenameTab {
+        tab_id: u32,
+        title: String,
+    },
     /// User dragged the terminal panel divider to resize
     TerminalViewResize(i32),
 
+    // Palettes
+    /// File > Quick Open...: fuzzy-find a file, line or symbol
+    ShowQuickOpen,
+    /// View > Command Palette...: run any menu action by name
+    ShowCommandPalette,
+
+    // Tasks
+    /// Tasks > Run Task...: pick a task of the current project and run it
+    RunTask,
+    /// Tasks > Re-run Last Task
+    RerunLastTask,
+    /// Tasks > Next Error: open the next problem reported by the last task
+    NextError,
+    /// A task run exited; its raw output is parsed for problems
+    TaskFinished {
+        run_id: u32,
+        output: Vec<u8>,
+        exit_code: Option<u32>,
+    },
+
+    // Widget API - Table View
+    /// Show a table view requested by a plugin
+    TableViewShow {
+        session_id: u32,
+        plugin_name: String,
+        request: TableViewRequest,
+    },
+    /// Hide the current table view
+    TableViewHide(u32),
+    /// User clicked (or pressed Enter on) a row in the table view
+    TableViewRowClicked {
+        session_id: u32,
+        row_id: String,
+    },
+    /// User triggered a context menu action in the table view
+    TableViewContextAction {
+        session_id: u32,
+        action: String,
+        row_id: Option<String>,
+        input_text: Option<String>,
+    },
+    /// User typed in the table view filter bar
+    TableViewFilter {
+        query: String,
+    },
+    /// User clicked a column title to sort by it
+    TableViewSort(usize),
+
     /// Deferred malloc_trim to return freed C++ pages to the OS without blocking UI
     MallocTrim,
 
@@ -255,4 +368,8 @@ pub enum Message {
     SessionDelete(String),
     /// Prompt for a name and open a new empty session in a new window
     SessionNewWindow,
+    /// Export the open files as a portable session file
+    SessionExport,
+    /// Import a portable session file
+    SessionImport,
 }
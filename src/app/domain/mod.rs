@@ -9,6 +9,6 @@ pub mod document;
 pub mod messages;
 pub mod settings;
 
-pub use document::{Document, DocumentId};
+pub use document::{Document, DocumentId, ViewState};
 pub use messages::Message;
 pub use settings::{AppSettings, SyntaxTheme, ThemeMode};
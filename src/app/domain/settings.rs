@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use crate::app::infrastructure::error::AppError;
 use crate::app::services::session::SessionRestore;
+use crate::app::services::tasks::TaskDefinition;
 use crate::app::services::updater::UpdateChannel;
 
 /// Per-plugin permission approvals stored in settings.
@@ -221,6 +222,14 @@ pub struct AppSettings {
     #[serde(default = "default_syntax_theme_dark")]
     pub syntax_theme_dark: SyntaxTheme,
 
+    /// User/plugin theme (`.tmTheme` file stem) used instead of `syntax_theme_light`
+    #[serde(default)]
+    pub custom_syntax_theme_light: Option<String>,
+
+    /// User/plugin theme (`.tmTheme` file stem) used instead of `syntax_theme_dark`
+    #[serde(default)]
+    pub custom_syntax_theme_dark: Option<String>,
+
     /// Tab size in spaces (default 4)
     #[serde(default = "default_tab_size")]
     pub tab_size: u32,
@@ -273,6 +282,23 @@ pub struct AppSettings {
     /// File size (MB) above which editing is blocked — read-only/tail only (default 150)
     #[serde(default = "default_max_editable_size_mb")]
     pub max_editable_size_mb: u32,
+
+    /// Let programs in the terminal set the clipboard via OSC 52 (default false)
+    #[serde(default)]
+    pub terminal_clipboard_write: bool,
+
+    /// Tasks available in every project, in addition to `.ferrispad/tasks.json`
+    #[serde(default)]
+    pub tasks: Vec<TaskDefinition>,
+
+    /// Command palette commands, most recently used first
+    #[serde(default)]
+    pub recent_commands: Vec<String>,
+
+    /// Open a project's `.ferrispad/session.json` when started inside it
+    /// without `--session` (default true)
+    #[serde(default = "default_project_sessions")]
+    pub project_sessions: bool,
 }
 
 fn default_line_numbers() -> bool {
@@ -331,6 +357,10 @@ fn default_run_all_checks_shortcut() -> String {
     "Ctrl+Shift+L".to_string()
 }
 
+fn default_project_sessions() -> bool {
+    true
+}
+
 fn default_large_file_warning_mb() -> u32 {
     50
 }
@@ -357,6 +387,8 @@ impl Default for AppSettings {
             preview_enabled: false,
             syntax_theme_light: default_syntax_theme_light(),
             syntax_theme_dark: default_syntax_theme_dark(),
+            custom_syntax_theme_light: None,
+            custom_syntax_theme_dark: None,
             tab_size: default_tab_size(),
             use_spaces: false,
             plugins_enabled: default_plugins_enabled(),
@@ -370,6 +402,10 @@ impl Default for AppSettings {
             shortcut_overrides: HashMap::new(),
             large_file_warning_mb: default_large_file_warning_mb(),
             max_editable_size_mb: default_max_editable_size_mb(),
+            terminal_clipboard_write: false,
+            tasks: Vec::new(),
+            recent_commands: Vec::new(),
+            project_sessions: true,
         }
     }
 }
@@ -399,6 +435,15 @@ impl AppSettings {
         }
     }
 
+    /// Get the user/plugin theme selected for the current mode, if any
+    pub fn current_custom_syntax_theme(&self, is_dark: bool) -> Option<&str> {
+        if is_dark {
+            self.custom_syntax_theme_dark.as_deref()
+        } else {
+            self.custom_syntax_theme_light.as_deref()
+        }
+    }
+
     /// Load settings from disk, or create default if not exists
     pub fn load() -> Self {
         let config_path = Self::get_config_path();
@@ -462,6 +507,8 @@ mod tests {
         assert_eq!(settings.last_update_check, 0);
         assert!(settings.skipped_versions.is_empty());
         assert_eq!(settings.tab_size, 4);
+        assert!(!settings.terminal_clipboard_write);
+        assert!(settings.tasks.is_empty());
     }
 
     #[test]
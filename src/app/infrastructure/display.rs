@@ -0,0 +1,66 @@
+use fltk::enums::{Color, FrameType};
+use fltk::prelude::{ValuatorExt, WidgetBase, WidgetExt};
+use fltk::valuator::Scrollbar;
+
+/// Read the scroll position of a TextDisplay/TextEditor as
+/// `(top line, horizontal offset in pixels)`.
+///
+/// # Why This Exists
+///
+/// fltk-rs can set the scroll position (`scroll(top_line, h_offset)`) but has
+/// no getter for it. `Fl_Text_Display` mirrors both values in its scrollbars,
+/// which are its first two children: the horizontal one at index 0 and the
+/// vertical one at index 1. Their values are updated when the display is
+/// laid out, so a `scroll()` that has not been drawn yet is not reflected.
+pub fn scroll_position(widget_ptr: fltk::app::WidgetPtr) -> (i32, i32) {
+    let value = |index| scrollbar(widget_ptr, index).map(|s| s.value() as i32);
+    (value(1).unwrap_or(1).max(1), value(0).unwrap_or(0).max(0))
+}
+
+/// Whether a TextDisplay/TextEditor is scrolled to its last line (or all of
+/// its text fits). Read from the vertical scrollbar, see `scroll_position`.
+pub fn scrolled_to_bottom(widget_ptr: fltk::app::WidgetPtr) -> bool {
+    // The display allows scrolling two lines past the end; showing the last
+    // line at the bottom edge counts as the bottom too
+    scrollbar(widget_ptr, 1).is_none_or(|s| s.value() + 2.0 >= s.maximum())
+}
+
+/// Give both scrollbars of a TextDisplay/TextEditor flat themed colors
+pub fn style_scrollbars(widget_ptr: fltk::app::WidgetPtr, track: Color, thumb: Color) {
+    for index in 0..2 {
+        if let Some(mut sb) = scrollbar(widget_ptr, index) {
+            sb.set_frame(FrameType::FlatBox);
+            sb.set_color(track);
+            sb.set_slider_frame(FrameType::FlatBox);
+            sb.set_selection_color(thumb);
+        }
+    }
+}
+
+/// Child `index` of a text display's group: its horizontal (0) or vertical
+/// (1) scrollbar.
+fn scrollbar(widget_ptr: fltk::app::WidgetPtr, index: i32) -> Option<Scrollbar> {
+    unsafe extern "C" {
+        fn Fl_Group_children(grp: *mut std::ffi::c_void) -> std::ffi::c_int;
+        fn Fl_Group_child(
+            grp: *mut std::ffi::c_void,
+            index: std::ffi::c_int,
+        ) -> *mut std::ffi::c_void;
+    }
+
+    // SAFETY: widget_ptr is a valid Fl_Group subclass (TextDisplay/TextEditor).
+    // Fl_Group_children/Fl_Group_child are stable FLTK C API. We null-check
+    // the child pointer before reconstructing. The widget outlives this call.
+    unsafe {
+        let group_ptr = widget_ptr as *mut std::ffi::c_void;
+        if Fl_Group_children(group_ptr) < 2 {
+            return None;
+        }
+        let ptr = Fl_Group_child(group_ptr, index);
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Scrollbar::from_widget_ptr(ptr as fltk::app::WidgetPtr))
+        }
+    }
+}
@@ -1,11 +1,12 @@
 //! Infrastructure layer - external integrations and utilities.
 //!
 //! This module contains code that interfaces with external systems:
-//! - FLTK buffer utilities
+//! - FLTK buffer and text display utilities
 //! - Platform-specific detection
 //! - Error types
 
 pub mod buffer;
 pub mod defer;
+pub mod display;
 pub mod error;
 pub mod platform;
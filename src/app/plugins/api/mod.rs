@@ -14,6 +14,7 @@ mod commands;
 mod editor;
 mod filesystem;
 mod sandbox;
+mod storage;
 
 use mlua::{UserData, UserDataMethods};
 use std::collections::HashMap;
@@ -57,6 +58,10 @@ pub struct EditorApi {
     /// Plugin-specific configuration from user settings.
     /// Key-value pairs configured via Plugins > {Plugin} > Settings.
     pub config: HashMap<String, String>,
+
+    /// Path to this plugin's persistent storage file.
+    /// None disables `storage_*` methods (e.g. in tests).
+    pub storage_path: Option<PathBuf>,
 }
 
 impl EditorApi {
@@ -161,6 +166,11 @@ impl UserData for EditorApi {
         // ── Git & diff ───────────────────────────────────────────────
         methods.add_method("git_status", filesystem::git_status);
         methods.add_method("diff_text", filesystem::diff_text);
+
+        // ── Persistent storage ───────────────────────────────────────
+        methods.add_method("storage_get", storage::storage_get);
+        methods.add_method("storage_set", storage::storage_set);
+        methods.add_method("storage_keys", storage::storage_keys);
     }
 }
 
@@ -673,4 +683,58 @@ mod tests {
         })
         .unwrap();
     }
+
+    // ── Persistent storage tests ────────────────────────────────────
+
+    #[test]
+    fn test_lua_storage_roundtrip_and_scopes() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("project");
+        std::fs::create_dir(&root).unwrap();
+
+        let lua = mlua::Lua::new();
+        let api = EditorApi {
+            project_root: Some(root),
+            storage_path: Some(dir.path().join("data").join("test.json")),
+            ..Default::default()
+        };
+
+        lua.scope(|scope| {
+            let ud = scope.create_userdata(api).unwrap();
+            let set: mlua::Function = ud.get("storage_set").unwrap();
+            let get: mlua::Function = ud.get("storage_get").unwrap();
+            let keys: mlua::Function = ud.get("storage_keys").unwrap();
+
+            let tbl = lua.create_table().unwrap();
+            tbl.set("count", 3).unwrap();
+            let (ok, err): (bool, String) = set.call((&ud, "state", tbl)).unwrap();
+            assert!(ok, "storage_set should succeed: {}", err);
+            let (ok, _): (bool, String) = set.call((&ud, "venv", ".venv", "project")).unwrap();
+            assert!(ok);
+
+            let state: mlua::Table = get.call((&ud, "state")).unwrap();
+            assert_eq!(state.get::<i64>("count").unwrap(), 3);
+
+            // Project value is not visible in the global namespace
+            let global_venv: mlua::Value = get.call((&ud, "venv")).unwrap();
+            assert!(matches!(global_venv, mlua::Value::Nil));
+            let project_venv: String = get.call((&ud, "venv", "project")).unwrap();
+            assert_eq!(project_venv, ".venv");
+
+            let names: Vec<String> = keys
+                .call::<mlua::Table>(&ud)
+                .unwrap()
+                .sequence_values::<String>()
+                .map(|k| k.unwrap())
+                .collect();
+            assert_eq!(names, vec!["state"]);
+
+            // Functions can't be serialized
+            let f = lua.create_function(|_, ()| Ok(())).unwrap();
+            let (ok, _): (bool, String) = set.call((&ud, "bad", f)).unwrap();
+            assert!(!ok);
+            Ok(())
+        })
+        .unwrap();
+    }
 }
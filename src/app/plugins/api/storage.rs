@@ -0,0 +1,172 @@
+//! Persistent key-value storage methods exposed to Lua plugins.
+//!
+//! Backed by `plugins::storage` (one JSON file per plugin in the config dir).
+//! The optional `scope` argument selects the namespace: "global" (default)
+//! or "project" (keyed by the current project root).
+
+use serde_json::Value;
+
+use super::super::storage::{self, PluginStore, StorageScope};
+use super::EditorApi;
+
+/// Maximum nesting depth for stored Lua tables.
+const MAX_VALUE_DEPTH: usize = 16;
+
+/// Resolve the scope argument into a `StorageScope`.
+fn resolve_scope(this: &EditorApi, scope: Option<&str>) -> Result<StorageScope, String> {
+    match scope.unwrap_or("global") {
+        "global" => Ok(StorageScope::Global),
+        "project" => match this.project_root {
+            Some(ref root) => Ok(StorageScope::Project(root.to_string_lossy().into_owned())),
+            None => Err("No project root".to_string()),
+        },
+        other => Err(format!(
+            "Unknown storage scope '{}' (expected \"global\" or \"project\")",
+            other
+        )),
+    }
+}
+
+/// Convert a Lua value to JSON. Arrays are tables with keys 1..n; other
+/// tables must have string keys. Functions, userdata and threads are rejected.
+fn lua_to_json(value: &mlua::Value, depth: usize) -> Result<Value, String> {
+    if depth > MAX_VALUE_DEPTH {
+        return Err("Value nested too deeply".to_string());
+    }
+    match value {
+        mlua::Value::Nil => Ok(Value::Null),
+        mlua::Value::Boolean(b) => Ok(Value::Bool(*b)),
+        mlua::Value::Integer(i) => Ok(Value::from(*i)),
+        mlua::Value::Number(n) => serde_json::Number::from_f64(*n)
+            .map(Value::Number)
+            .ok_or_else(|| "Cannot store NaN or infinity".to_string()),
+        mlua::Value::String(s) => s
+            .to_str()
+            .map(|s| Value::String(s.to_string()))
+            .map_err(|_| "Strings must be valid UTF-8".to_string()),
+        mlua::Value::Table(t) => {
+            let len = t.raw_len();
+            let pair_count = t.clone().pairs::<mlua::Value, mlua::Value>().count();
+            if len > 0 && len == pair_count {
+                let mut arr = Vec::with_capacity(len);
+                for v in t.clone().sequence_values::<mlua::Value>() {
+                    let v = v.map_err(|e| e.to_string())?;
+                    arr.push(lua_to_json(&v, depth + 1)?);
+                }
+                Ok(Value::Array(arr))
+            } else {
+                let mut map = serde_json::Map::new();
+                for pair in t.clone().pairs::<mlua::Value, mlua::Value>() {
+                    let (k, v) = pair.map_err(|e| e.to_string())?;
+                    let key = match k {
+                        mlua::Value::String(s) => s
+                            .to_str()
+                            .map(|s| s.to_string())
+                            .map_err(|_| "Keys must be valid UTF-8".to_string())?,
+                        mlua::Value::Integer(i) => i.to_string(),
+                        _ => return Err("Table keys must be strings".to_string()),
+                    };
+                    map.insert(key, lua_to_json(&v, depth + 1)?);
+                }
+                Ok(Value::Object(map))
+            }
+        }
+        _ => Err(format!("Cannot store a {}", value.type_name())),
+    }
+}
+
+/// Convert stored JSON back into a Lua value.
+fn json_to_lua(lua: &mlua::Lua, value: &Value) -> mlua::Result<mlua::Value> {
+    Ok(match value {
+        Value::Null => mlua::Value::Nil,
+        Value::Bool(b) => mlua::Value::Boolean(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => mlua::Value::Integer(i),
+            None => mlua::Value::Number(n.as_f64().unwrap_or(0.0)),
+        },
+        Value::String(s) => mlua::Value::String(lua.create_string(s)?),
+        Value::Array(arr) => {
+            let t = lua.create_table()?;
+            for (i, v) in arr.iter().enumerate() {
+                t.set(i + 1, json_to_lua(lua, v)?)?;
+            }
+            mlua::Value::Table(t)
+        }
+        Value::Object(map) => {
+            let t = lua.create_table()?;
+            for (k, v) in map {
+                t.set(k.as_str(), json_to_lua(lua, v)?)?;
+            }
+            mlua::Value::Table(t)
+        }
+    })
+}
+
+/// Read a stored value. Returns nil if the key is not set or storage is unavailable.
+pub fn storage_get(
+    lua: &mlua::Lua,
+    this: &EditorApi,
+    (key, scope): (String, Option<String>),
+) -> mlua::Result<mlua::Value> {
+    let Some(ref path) = this.storage_path else {
+        return Ok(mlua::Value::Nil);
+    };
+    let Ok(scope) = resolve_scope(this, scope.as_deref()) else {
+        return Ok(mlua::Value::Nil);
+    };
+    match PluginStore::load(path).get(&scope, &key) {
+        Some(v) => json_to_lua(lua, v),
+        None => Ok(mlua::Value::Nil),
+    }
+}
+
+/// Store a value (string, number, boolean or table). Passing nil deletes the key.
+/// Returns (true, "") on success, (false, error_msg) on failure.
+pub fn storage_set(
+    _: &mlua::Lua,
+    this: &EditorApi,
+    (key, value, scope): (String, mlua::Value, Option<String>),
+) -> mlua::Result<(bool, String)> {
+    let Some(ref path) = this.storage_path else {
+        return Ok((false, "Storage unavailable".to_string()));
+    };
+    let scope = match resolve_scope(this, scope.as_deref()) {
+        Ok(s) => s,
+        Err(e) => return Ok((false, e)),
+    };
+    let json = match lua_to_json(&value, 0) {
+        Ok(Value::Null) => None,
+        Ok(v) => Some(v),
+        Err(e) => return Ok((false, e)),
+    };
+    match storage::set_value(path, &scope, &key, json) {
+        Ok(()) => Ok((true, String::new())),
+        Err(e) => {
+            let plugin_name = this.plugin_name.as_deref().unwrap_or("unknown");
+            eprintln!(
+                "[plugins] {} storage_set '{}' failed: {}",
+                plugin_name, key, e
+            );
+            Ok((false, e))
+        }
+    }
+}
+
+/// List stored keys (sorted). Returns an empty table if nothing is stored.
+pub fn storage_keys(
+    lua: &mlua::Lua,
+    this: &EditorApi,
+    scope: Option<String>,
+) -> mlua::Result<mlua::Table> {
+    let result = lua.create_table()?;
+    let Some(ref path) = this.storage_path else {
+        return Ok(result);
+    };
+    let Ok(scope) = resolve_scope(this, scope.as_deref()) else {
+        return Ok(result);
+    };
+    for (i, key) in PluginStore::load(path).keys(&scope).iter().enumerate() {
+        result.set(i + 1, key.as_str())?;
+    }
+    Ok(result)
+}
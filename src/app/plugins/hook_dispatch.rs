@@ -19,7 +19,7 @@ pub(super) fn call_hook_on_plugin(
 ) -> Option<HookResult> {
     let plugin = plugins.iter().find(|p| p.name == plugin_name)?;
 
-    if !plugin.enabled {
+    if !plugin.enabled || !plugin.subscribed_hooks.contains(hook.lua_name()) {
         return None;
     }
 
@@ -51,6 +51,7 @@ pub(super) fn call_hook_on_plugin(
                 source: plugin.name.clone(),
                 fix_message: None,
                 url: None,
+                file: None,
             });
         }
     }
@@ -67,7 +68,7 @@ pub(super) fn call_hook(
     let mut result = HookResult::default();
 
     for plugin in plugins {
-        if !plugin.enabled {
+        if !plugin.enabled || !plugin.subscribed_hooks.contains(hook.lua_name()) {
             continue;
         }
 
@@ -96,6 +97,10 @@ pub(super) fn call_hook(
                     result.tree_view = hook_output.tree_view;
                     result.source_plugin = Some(plugin.name.clone());
                 }
+                if hook_output.table_view.is_some() {
+                    result.table_view = hook_output.table_view;
+                    result.source_plugin = Some(plugin.name.clone());
+                }
                 if hook_output.open_file.is_some() {
                     result.open_file = hook_output.open_file;
                 }
@@ -105,6 +110,8 @@ pub(super) fn call_hook(
                 if hook_output.goto_line.is_some() {
                     result.goto_line = hook_output.goto_line;
                 }
+                // Status items are keyed by plugin, so all updates are kept
+                result.status_items.extend(hook_output.status_items);
             }
             Err(e) => {
                 eprintln!("[plugins] {} hook error: {}", plugin.name, e);
@@ -153,6 +160,7 @@ pub(super) fn call_hook(
                     source: plugin.name.clone(),
                     fix_message,
                     url,
+                    file: None,
                 });
             }
         }
@@ -294,6 +302,38 @@ fn call_plugin_hook(
             return Ok(result);
         }
 
+        PluginHook::OnCursorMoved { line, column, .. } => {
+            let value = runtime.call_hook(&plugin.table, hook_name, (api, *line, *column))?;
+            return parse_event_result(value, &plugin.name, result);
+        }
+
+        PluginHook::OnSelectionChanged {
+            start, end, text, ..
+        } => {
+            let value =
+                runtime.call_hook(&plugin.table, hook_name, (api, *start, *end, text.clone()))?;
+            return parse_event_result(value, &plugin.name, result);
+        }
+
+        PluginHook::OnTabActivated { path } | PluginHook::OnWindowFocus { path } => {
+            let value = runtime.call_hook(&plugin.table, hook_name, (api, path.clone()))?;
+            return parse_event_result(value, &plugin.name, result);
+        }
+
+        PluginHook::OnSessionSwitched { name } => {
+            let value = runtime.call_hook(&plugin.table, hook_name, (api, name.clone()))?;
+            return parse_event_result(value, &plugin.name, result);
+        }
+
+        PluginHook::OnFileRenamed { old_path, new_path } => {
+            let value = runtime.call_hook(
+                &plugin.table,
+                hook_name,
+                (api, old_path.clone(), new_path.clone()),
+            )?;
+            return parse_event_result(value, &plugin.name, result);
+        }
+
         PluginHook::OnWidgetAction {
             widget_type,
             action,
@@ -324,6 +364,9 @@ fn call_plugin_hook(
                 }
                 data_table.set("target_path", target_table)?;
             }
+            if let Some(ref row_id) = data.row_id {
+                data_table.set("row_id", row_id.as_str())?;
+            }
 
             let value = runtime.call_hook(
                 &plugin.table,
@@ -355,6 +398,19 @@ fn call_plugin_hook(
     Ok(result)
 }
 
+/// Parse the optional table returned by editor event hooks (cursor, tab, focus...).
+/// These hooks may return the same fields as lint hooks (diagnostics, status, widgets).
+fn parse_event_result(
+    value: mlua::Value,
+    plugin_name: &str,
+    mut result: HookResult,
+) -> Result<HookResult, mlua::Error> {
+    if let mlua::Value::Table(return_table) = value {
+        hook_result_parser::parse_lint_result(&return_table, plugin_name, &mut result);
+    }
+    Ok(result)
+}
+
 /// Create an EditorApi instance for a specific hook with plugin context
 fn create_api_for_hook(hook: &PluginHook, plugin: &LoadedPlugin) -> EditorApi {
     let mut api = match hook {
@@ -401,6 +457,31 @@ fn create_api_for_hook(hook: &PluginHook, plugin: &LoadedPlugin) -> EditorApi {
             EditorApi::with_path_and_content(path.clone(), content.clone())
         }
 
+        PluginHook::OnCursorMoved { path, position, .. } => {
+            let mut api = EditorApi::with_path(path.clone());
+            api.cursor_position = *position;
+            api
+        }
+
+        PluginHook::OnSelectionChanged {
+            path, start, text, ..
+        } => {
+            let mut api = EditorApi::with_path(path.clone());
+            api.cursor_position = *start;
+            api.selection = (!text.is_empty()).then(|| text.clone());
+            api
+        }
+
+        PluginHook::OnTabActivated { path } | PluginHook::OnWindowFocus { path } => {
+            EditorApi::with_path(path.clone())
+        }
+
+        PluginHook::OnSessionSwitched { .. } => {
+            EditorApi::with_project_root(crate::app::mcp::cwd_as_string())
+        }
+
+        PluginHook::OnFileRenamed { new_path, .. } => EditorApi::with_path(Some(new_path.clone())),
+
         PluginHook::OnWidgetAction { path, data, .. } => {
             // Prefer buffer content (avoids stale reads for unsaved files),
             // fall back to reading from disk
@@ -422,5 +503,11 @@ fn create_api_for_hook(hook: &PluginHook, plugin: &LoadedPlugin) -> EditorApi {
     // Add plugin-specific configuration
     api.config = plugin.config_params.clone();
 
+    // Persistent storage lives outside the project, keyed by plugin directory
+    api.storage_path = Some(super::storage::storage_file(
+        &super::storage::get_plugin_data_dir(),
+        &super::dir_name(&plugin.path),
+    ));
+
     api
 }
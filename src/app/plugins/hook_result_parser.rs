@@ -3,15 +3,23 @@
 //! All functions are free functions — no dependency on `PluginManager` fields.
 
 use super::annotations::{AnnotationColor, GutterMark, InlineHighlight, LineAnnotation};
-use super::hooks::{Diagnostic, DiagnosticLevel, HookResult, StatusMessage};
-use super::widgets::{SplitViewRequest, TerminalViewRequest, TreeViewRequest};
+use super::hooks::{
+    Diagnostic, DiagnosticLevel, HookResult, StatusAlignment, StatusItem, StatusMessage,
+};
+use super::widgets::{SplitViewRequest, TableViewRequest, TerminalViewRequest, TreeViewRequest};
 
 use mlua::Table;
 
+/// Maximum number of status bar items a single plugin can show.
+const MAX_STATUS_ITEMS_PER_PLUGIN: usize = 8;
+
+/// Maximum length (in characters) of a status bar item's text.
+const MAX_STATUS_ITEM_CHARS: usize = 60;
+
 /// Parse lint/highlight result from Lua table.
 /// Supports both old format (array of diagnostics) and new extended format:
 /// - Old: { {line=1, message="..."}, ... }
-/// - New: { diagnostics = {...}, highlights = {...}, status_message = {...}, split_view = {...}, tree_view = {...} }
+/// - New: { diagnostics = {...}, highlights = {...}, status_message = {...}, status_items = {...}, split_view = {...}, tree_view = {...}, table_view = {...} }
 pub(super) fn parse_lint_result(table: &Table, plugin_name: &str, result: &mut HookResult) {
     let has_diagnostics_key: bool = table.contains_key("diagnostics").unwrap_or(false);
     let has_highlights_key: bool = table.contains_key("highlights").unwrap_or(false);
@@ -19,9 +27,11 @@ pub(super) fn parse_lint_result(table: &Table, plugin_name: &str, result: &mut H
     let has_split_view_key: bool = table.contains_key("split_view").unwrap_or(false);
     let has_tree_view_key: bool = table.contains_key("tree_view").unwrap_or(false);
     let has_terminal_view_key: bool = table.contains_key("terminal_view").unwrap_or(false);
+    let has_table_view_key: bool = table.contains_key("table_view").unwrap_or(false);
     let has_open_file_key: bool = table.contains_key("open_file").unwrap_or(false);
     let has_clipboard_text_key: bool = table.contains_key("clipboard_text").unwrap_or(false);
     let has_goto_line_key: bool = table.contains_key("goto_line").unwrap_or(false);
+    let has_status_items_key: bool = table.contains_key("status_items").unwrap_or(false);
 
     if has_diagnostics_key
         || has_highlights_key
@@ -29,9 +39,11 @@ pub(super) fn parse_lint_result(table: &Table, plugin_name: &str, result: &mut H
         || has_split_view_key
         || has_tree_view_key
         || has_terminal_view_key
+        || has_table_view_key
         || has_open_file_key
         || has_clipboard_text_key
         || has_goto_line_key
+        || has_status_items_key
     {
         // New extended format
         if let Ok(mlua::Value::Table(diags_table)) = table.get::<mlua::Value>("diagnostics") {
@@ -62,6 +74,10 @@ pub(super) fn parse_lint_result(table: &Table, plugin_name: &str, result: &mut H
         {
             result.terminal_view = TerminalViewRequest::from_lua_table(&terminal_view_table);
         }
+        // Parse optional table view request
+        if let Ok(mlua::Value::Table(table_view_table)) = table.get::<mlua::Value>("table_view") {
+            result.table_view = TableViewRequest::from_lua_table(&table_view_table);
+        }
         // Parse optional open_file request
         if let Ok(mlua::Value::String(s)) = table.get::<mlua::Value>("open_file")
             && let Ok(path) = s.to_str()
@@ -78,6 +94,13 @@ pub(super) fn parse_lint_result(table: &Table, plugin_name: &str, result: &mut H
         if let Ok(line) = table.get::<u32>("goto_line") {
             result.goto_line = Some(line);
         }
+        // Parse optional status bar items (replaces this plugin's previous set)
+        if let Ok(mlua::Value::Table(items_table)) = table.get::<mlua::Value>("status_items") {
+            result.status_items.push((
+                plugin_name.to_string(),
+                parse_status_items(&items_table, plugin_name),
+            ));
+        }
     } else {
         // Old format: array of diagnostics directly
         result
@@ -125,6 +148,7 @@ fn parse_single_diagnostic(table: &Table, plugin_name: &str) -> Option<Diagnosti
         source: plugin_name.to_string(),
         fix_message,
         url,
+        file: None,
     })
 }
 
@@ -148,6 +172,61 @@ fn parse_status_message(table: &Table) -> Option<StatusMessage> {
     Some(StatusMessage { level, text })
 }
 
+/// Parse a Lua array of status bar items. Items need `id` and `text`;
+/// a repeated id replaces the earlier item.
+fn parse_status_items(table: &Table, plugin_name: &str) -> Vec<StatusItem> {
+    let mut items: Vec<StatusItem> = Vec::new();
+    for item_table in table.clone().sequence_values::<Table>().flatten() {
+        let Some(item) = parse_single_status_item(&item_table) else {
+            continue;
+        };
+        if let Some(existing) = items.iter_mut().find(|i| i.id == item.id) {
+            *existing = item;
+        } else if items.len() < MAX_STATUS_ITEMS_PER_PLUGIN {
+            items.push(item);
+        } else {
+            eprintln!(
+                "[plugins] {} returned more than {} status items, ignoring '{}'",
+                plugin_name, MAX_STATUS_ITEMS_PER_PLUGIN, item.id
+            );
+        }
+    }
+    items
+}
+
+/// Parse a single status bar item from a Lua table
+fn parse_single_status_item(table: &Table) -> Option<StatusItem> {
+    // Required: id and text
+    let id: String = table.get("id").ok()?;
+    let text: String = table.get("text").ok()?;
+    // Status bar is a single line
+    let text: String = text
+        .replace(['\n', '\r', '\t'], " ")
+        .chars()
+        .take(MAX_STATUS_ITEM_CHARS)
+        .collect();
+
+    // Optional: alignment (defaults to "left")
+    let alignment = match table
+        .get::<String>("alignment")
+        .unwrap_or_default()
+        .to_lowercase()
+        .as_str()
+    {
+        "right" => StatusAlignment::Right,
+        _ => StatusAlignment::Left,
+    };
+
+    Some(StatusItem {
+        id,
+        text,
+        tooltip: table.get("tooltip").ok(),
+        alignment,
+        priority: table.get("priority").unwrap_or(0),
+        action: table.get("action").ok(),
+    })
+}
+
 /// Parse a Lua table of line annotations
 fn parse_line_annotations(table: &Table, plugin_name: &str) -> Vec<LineAnnotation> {
     table
@@ -4,7 +4,7 @@
 //! No async, no background threads. This ensures 0% CPU when idle.
 
 use super::annotations::LineAnnotation;
-use super::widgets::{SplitViewRequest, TerminalViewRequest, TreeViewRequest};
+use super::widgets::{SplitViewRequest, TableViewRequest, TerminalViewRequest, TreeViewRequest};
 use crate::ui::toast::ToastLevel;
 
 /// Diagnostic severity level
@@ -51,6 +51,9 @@ pub struct Diagnostic {
     pub fix_message: Option<String>,
     /// Optional documentation URL
     pub url: Option<String>,
+    /// File the diagnostic belongs to (None = the active document).
+    /// Set for task output, which can report problems across the project.
+    pub file: Option<String>,
 }
 
 /// Plugin hooks that can be registered and called.
@@ -109,7 +112,7 @@ pub enum PluginHook {
     /// Called when user interacts with a plugin-created widget.
     /// Sent when user clicks Accept/Reject in split view, or clicks a tree node.
     OnWidgetAction {
-        /// Type of widget: "split_view" or "tree_view"
+        /// Type of widget: "split_view", "tree_view" or "table_view"
         widget_type: String,
         /// Action name: "accept", "reject", "node_clicked", etc.
         action: String,
@@ -120,6 +123,36 @@ pub enum PluginHook {
         /// Current document path (for project root detection)
         path: Option<String>,
     },
+
+    /// Called (debounced) after the cursor moves within the active document.
+    /// Line and column are 1-indexed.
+    OnCursorMoved {
+        path: Option<String>,
+        position: i32,
+        line: u32,
+        column: u32,
+    },
+
+    /// Called (debounced) after the selection changes. An empty `text`
+    /// with `start == end` means the selection was cleared.
+    OnSelectionChanged {
+        path: Option<String>,
+        start: i32,
+        end: i32,
+        text: String,
+    },
+
+    /// Called (debounced) after a different tab becomes active.
+    OnTabActivated { path: Option<String> },
+
+    /// Called (debounced) when the editor window regains focus.
+    OnWindowFocus { path: Option<String> },
+
+    /// Called after switching to a different named session.
+    OnSessionSwitched { name: String },
+
+    /// Called after an open document's path changes (e.g. Save As).
+    OnFileRenamed { old_path: String, new_path: String },
 }
 
 impl PluginHook {
@@ -137,10 +170,49 @@ impl PluginHook {
             Self::OnHighlightRequest { .. } => "on_highlight_request",
             Self::OnMenuAction { .. } => "on_menu_action",
             Self::OnWidgetAction { .. } => "on_widget_action",
+            Self::OnCursorMoved { .. } => "on_cursor_moved",
+            Self::OnSelectionChanged { .. } => "on_selection_changed",
+            Self::OnTabActivated { .. } => "on_tab_activated",
+            Self::OnWindowFocus { .. } => "on_window_focus",
+            Self::OnSessionSwitched { .. } => "on_session_switched",
+            Self::OnFileRenamed { .. } => "on_file_renamed",
         }
     }
 }
 
+/// Lua function names of every hook, in declaration order.
+/// Used to validate `hooks = [...]` in plugin.toml and to detect
+/// which hooks a plugin implements when the manifest doesn't say.
+pub const ALL_HOOK_NAMES: &[&str] = &[
+    "init",
+    "shutdown",
+    "on_document_open",
+    "on_document_save",
+    "on_document_close",
+    "on_text_changed",
+    "on_theme_changed",
+    "on_document_lint",
+    "on_highlight_request",
+    "on_menu_action",
+    "on_widget_action",
+    "on_cursor_moved",
+    "on_selection_changed",
+    "on_tab_activated",
+    "on_window_focus",
+    "on_session_switched",
+    "on_file_renamed",
+];
+
+/// Lifecycle and UI callback hooks. Delivered whenever init.lua defines
+/// them, even if a `hooks = [...]` list in plugin.toml leaves them out.
+pub const ALWAYS_DELIVERED_HOOKS: &[&str] =
+    &["init", "shutdown", "on_menu_action", "on_widget_action"];
+
+/// Hooks that can fire many times per second while the user types or
+/// moves around. Flagged in the install review dialog.
+pub const HIGH_FREQUENCY_HOOKS: &[&str] =
+    &["on_text_changed", "on_cursor_moved", "on_selection_changed"];
+
 /// Data passed to OnWidgetAction hook
 #[derive(Debug, Clone, Default)]
 pub struct WidgetActionData {
@@ -154,6 +226,8 @@ pub struct WidgetActionData {
     pub content: Option<String>,
     /// For tree view drag-and-drop: path to the drop target node
     pub target_path: Option<Vec<String>>,
+    /// For table view: id of the clicked row
+    pub row_id: Option<String>,
 }
 
 /// A status message to display to the user
@@ -165,6 +239,31 @@ pub struct StatusMessage {
     pub text: String,
 }
 
+/// Which side of the status bar a plugin item is shown on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusAlignment {
+    #[default]
+    Left,
+    Right,
+}
+
+/// A plugin-contributed status bar segment (e.g. git branch, error count)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusItem {
+    /// Identifier, unique within the plugin
+    pub id: String,
+    /// Text shown in the status bar
+    pub text: String,
+    /// Tooltip shown on hover
+    pub tooltip: Option<String>,
+    /// Side of the status bar
+    pub alignment: StatusAlignment,
+    /// Higher priority items are placed first and hidden last when space runs out
+    pub priority: i32,
+    /// Menu action sent to the plugin's `on_menu_action` when clicked
+    pub action: Option<String>,
+}
+
 /// Result from calling plugin hooks
 #[derive(Debug, Default)]
 pub struct HookResult {
@@ -182,16 +281,20 @@ pub struct HookResult {
     pub tree_view: Option<TreeViewRequest>,
     /// Request to show a terminal view widget
     pub terminal_view: Option<TerminalViewRequest>,
+    /// Request to show a table view widget
+    pub table_view: Option<TableViewRequest>,
     /// Request to open a file (from tree view clicks, etc.)
     pub open_file: Option<String>,
     /// Text to copy to the system clipboard (e.g., from tree node context actions)
     pub clipboard_text: Option<String>,
     /// Navigate editor to this line number (1-indexed)
     pub goto_line: Option<u32>,
+    /// Status bar items per plugin: (plugin name, full item set replacing the previous one)
+    pub status_items: Vec<(String, Vec<StatusItem>)>,
     /// Whether at least one plugin actually produced lint results (returned a table).
     /// When false, no plugin linted this file (all returned nil/skipped).
     pub had_lint_results: bool,
-    /// Name of the plugin that produced widget requests (tree_view / split_view).
+    /// Name of the plugin that produced widget requests (tree_view / split_view / table_view).
     /// Set by `call_hook` broadcast so `process_widget_requests` can create sessions
     /// with the correct plugin name even when the caller passes "".
     pub source_plugin: Option<String>,
@@ -248,6 +351,7 @@ mod tests {
                 source: "linter-a".to_string(),
                 fix_message: None,
                 url: None,
+                file: None,
             }],
             ..Default::default()
         };
@@ -295,6 +399,7 @@ mod tests {
                 source: "linter".to_string(),
                 fix_message: None,
                 url: None,
+                file: None,
             }],
             ..Default::default()
         };
@@ -327,4 +432,44 @@ mod tests {
             "on_document_lint"
         );
     }
+
+    #[test]
+    fn test_all_hook_names_cover_every_variant() {
+        let hooks = [
+            PluginHook::OnCursorMoved {
+                path: None,
+                position: 0,
+                line: 1,
+                column: 1,
+            },
+            PluginHook::OnSelectionChanged {
+                path: None,
+                start: 0,
+                end: 0,
+                text: String::new(),
+            },
+            PluginHook::OnTabActivated { path: None },
+            PluginHook::OnWindowFocus { path: None },
+            PluginHook::OnSessionSwitched {
+                name: "default".to_string(),
+            },
+            PluginHook::OnFileRenamed {
+                old_path: "a".to_string(),
+                new_path: "b".to_string(),
+            },
+        ];
+        for hook in &hooks {
+            assert!(
+                ALL_HOOK_NAMES.contains(&hook.lua_name()),
+                "{} missing from ALL_HOOK_NAMES",
+                hook.lua_name()
+            );
+        }
+        for name in ALWAYS_DELIVERED_HOOKS {
+            assert!(ALL_HOOK_NAMES.contains(name));
+        }
+        for name in HIGH_FREQUENCY_HOOKS {
+            assert!(ALL_HOOK_NAMES.contains(name));
+        }
+    }
 }
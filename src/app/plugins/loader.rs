@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::hooks::ALL_HOOK_NAMES;
+
 /// Permissions requested by a plugin in its manifest.
 /// These must be approved by the user before the plugin can use them.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -25,7 +27,7 @@ pub struct PluginMenuItem {
     /// Action name passed to `on_menu_action` hook
     pub action: String,
 
-    /// Optional keyboard shortcut (e.g., "Ctrl+Shift+P")
+    /// Optional keyboard shortcut (e.g., "Ctrl+Alt+L")
     /// Plugin authors can use either "shortcut" or "default_shortcut" in plugin.toml.
     #[serde(default, alias = "default_shortcut")]
     pub shortcut: Option<String>,
@@ -90,6 +92,9 @@ pub struct PluginMetadata {
     pub menu_items: Vec<PluginMenuItem>,
     /// Configuration schema for this plugin
     pub config: PluginConfigDef,
+    /// Hooks the plugin subscribes to (`hooks = [...]`).
+    /// None means the manifest doesn't say; hooks are then detected from init.lua.
+    pub hooks: Option<Vec<String>>,
 }
 
 /// Get the plugin directory path.
@@ -268,6 +273,21 @@ pub fn parse_plugin_toml_content(content: &str) -> Option<PluginMetadata> {
         })
         .unwrap_or_default();
 
+    // Parse top-level hooks = ["on_document_open", ...]
+    let hooks = parsed.get("hooks").and_then(|v| v.as_array()).map(|arr| {
+        arr.iter()
+            .filter_map(|v| v.as_str())
+            .filter(|name| {
+                let known = ALL_HOOK_NAMES.contains(name);
+                if !known {
+                    eprintln!("[plugins] Warning: unknown hook '{}' in plugin.toml", name);
+                }
+                known
+            })
+            .map(String::from)
+            .collect()
+    });
+
     Some(PluginMetadata {
         name,
         version,
@@ -275,6 +295,7 @@ pub fn parse_plugin_toml_content(content: &str) -> Option<PluginMetadata> {
         permissions,
         menu_items,
         config,
+        hooks,
     })
 }
 
@@ -310,7 +331,7 @@ execute = ["ruff"]
 [[menu_items]]
 label = "Run Lint"
 action = "lint"
-shortcut = "Ctrl+Shift+P"
+shortcut = "Ctrl+Alt+L"
 
 [[menu_items]]
 label = "Format Code"
@@ -330,7 +351,7 @@ action = "format"
         assert_eq!(metadata.menu_items[0].action, "lint");
         assert_eq!(
             metadata.menu_items[0].shortcut,
-            Some("Ctrl+Shift+P".to_string())
+            Some("Ctrl+Alt+L".to_string())
         );
 
         assert_eq!(metadata.menu_items[1].label, "Format Code");
@@ -400,6 +421,25 @@ action = "thing"
         assert_eq!(metadata.menu_items[0].action, "thing");
     }
 
+    #[test]
+    fn test_parse_plugin_toml_hooks() {
+        let content = r#"
+name = "Status"
+hooks = ["on_cursor_moved", "on_tab_activated", "on_bogus"]
+"#;
+        let metadata = parse_plugin_toml_content(content).unwrap();
+        assert_eq!(
+            metadata.hooks,
+            Some(vec![
+                "on_cursor_moved".to_string(),
+                "on_tab_activated".to_string()
+            ])
+        );
+
+        let metadata = parse_plugin_toml_content("name = \"Legacy\"").unwrap();
+        assert!(metadata.hooks.is_none());
+    }
+
     #[test]
     fn test_parse_plugin_toml_content_invalid() {
         assert!(parse_plugin_toml_content("not valid toml {{{").is_none());
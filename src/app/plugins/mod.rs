@@ -19,17 +19,23 @@ pub mod hooks;
 pub mod loader;
 pub mod runtime;
 pub mod security;
+pub mod storage;
 pub mod widgets;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use mlua::Table;
 
 pub use annotations::{AnnotationColor, GutterMark, InlineHighlight, LineAnnotation};
-pub use hooks::{Diagnostic, DiagnosticLevel, HookResult, PluginHook, WidgetActionData};
+pub use hooks::{
+    Diagnostic, DiagnosticLevel, HookResult, PluginHook, StatusAlignment, StatusItem,
+    WidgetActionData,
+};
 pub use loader::{ConfigParamDef, PluginConfigDef, PluginMenuItem, get_plugin_dir};
-pub use widgets::{SplitViewRequest, TerminalViewRequest, TreeViewRequest, WidgetManager};
+pub use widgets::{
+    SplitViewRequest, TableViewRequest, TerminalViewRequest, TreeViewRequest, WidgetManager,
+};
 // Re-export widget types for public API (may not be used internally yet)
 #[allow(unused_imports)]
 pub use widgets::{
@@ -86,6 +92,12 @@ pub struct LoadedPlugin {
     /// User configuration values (from AppSettings.plugin_configs)
     pub config_params: HashMap<String, String>,
 
+    /// Lua hook names this plugin receives. Taken from `hooks = [...]` in
+    /// plugin.toml, or detected from the functions init.lua defines.
+    /// Lifecycle and menu/widget hooks defined in init.lua are always included.
+    /// Hooks outside this set are never dispatched to the plugin.
+    pub subscribed_hooks: HashSet<String>,
+
     /// The Lua table returned by init.lua
     pub(crate) table: Table,
 }
@@ -238,6 +250,21 @@ impl PluginManager {
             .map(|m| m.config.clone())
             .unwrap_or_default();
 
+        // Declared hooks win; otherwise subscribe to every hook function defined.
+        // Lifecycle and menu/widget callbacks are kept either way.
+        let detected = detect_table_hooks(&table);
+        let subscribed_hooks = match toml_meta.as_ref().and_then(|m| m.hooks.clone()) {
+            Some(declared) => declared
+                .into_iter()
+                .chain(
+                    detected
+                        .into_iter()
+                        .filter(|name| hooks::ALWAYS_DELIVERED_HOOKS.contains(&name.as_str())),
+                )
+                .collect(),
+            None => detected,
+        };
+
         Ok(LoadedPlugin {
             name,
             version,
@@ -249,6 +276,7 @@ impl PluginManager {
             menu_items,
             config_schema,
             config_params: HashMap::new(), // Will be populated from settings
+            subscribed_hooks,
             table,
         })
     }
@@ -265,6 +293,17 @@ impl PluginManager {
         hook_dispatch::call_hook_on_plugin(runtime, &self.plugins, plugin_name, hook)
     }
 
+    /// Whether any enabled plugin subscribes to the given hook.
+    /// Callers check this before building expensive hook payloads.
+    pub fn has_subscribers(&self, hook_name: &str) -> bool {
+        self.enabled
+            && self.runtime.is_some()
+            && self
+                .plugins
+                .iter()
+                .any(|p| p.enabled && p.subscribed_hooks.contains(hook_name))
+    }
+
     /// Call a hook on all enabled plugins
     pub fn call_hook(&self, hook: PluginHook) -> HookResult {
         if !self.enabled {
@@ -423,6 +462,20 @@ fn get_lua_string(table: &Table, key: &str) -> Option<String> {
     })
 }
 
+/// Detect which hooks a plugin implements by checking its table for functions
+fn detect_table_hooks(table: &Table) -> HashSet<String> {
+    hooks::ALL_HOOK_NAMES
+        .iter()
+        .filter(|name| {
+            matches!(
+                table.get::<mlua::Value>(**name),
+                Ok(mlua::Value::Function(_))
+            )
+        })
+        .map(|name| name.to_string())
+        .collect()
+}
+
 /// Helper to get directory name as string
 fn dir_name(path: &std::path::Path) -> String {
     path.file_name()
@@ -512,6 +565,114 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_subscribed_hooks_declared_and_detected() {
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let detected = dir.path().join("detected");
+        std::fs::create_dir(&detected).unwrap();
+        std::fs::write(
+            detected.join("init.lua"),
+            "return { name = 'detected', on_cursor_moved = function() end }",
+        )
+        .unwrap();
+
+        // Manifest narrows the subscription even though init.lua defines more
+        let declared = dir.path().join("declared");
+        std::fs::create_dir(&declared).unwrap();
+        std::fs::write(
+            declared.join("init.lua"),
+            "return { init = function() end, on_menu_action = function() end, \
+             on_tab_activated = function() end, on_cursor_moved = function() end }",
+        )
+        .unwrap();
+        std::fs::write(
+            declared.join("plugin.toml"),
+            "name = \"declared\"\nhooks = [\"on_tab_activated\"]\n",
+        )
+        .unwrap();
+
+        let mut pm = PluginManager::new(true);
+        pm.load_plugins(dir.path());
+
+        let find = |name: &str| pm.list_plugins().iter().find(|p| p.name == name).unwrap();
+        assert!(
+            find("detected")
+                .subscribed_hooks
+                .contains("on_cursor_moved")
+        );
+        assert!(
+            !find("detected")
+                .subscribed_hooks
+                .contains("on_text_changed")
+        );
+        assert!(
+            find("declared")
+                .subscribed_hooks
+                .contains("on_tab_activated")
+        );
+        assert!(
+            !find("declared")
+                .subscribed_hooks
+                .contains("on_cursor_moved")
+        );
+
+        // Lifecycle and menu hooks survive a manifest that leaves them out
+        assert!(find("declared").subscribed_hooks.contains("init"));
+        assert!(find("declared").subscribed_hooks.contains("on_menu_action"));
+        assert!(!find("declared").subscribed_hooks.contains("shutdown"));
+
+        assert!(pm.has_subscribers("on_cursor_moved"));
+        assert!(!pm.has_subscribers("on_selection_changed"));
+        pm.toggle_plugin("detected", false);
+        assert!(!pm.has_subscribers("on_cursor_moved"));
+    }
+
+    #[test]
+    fn test_status_items_parsed_per_plugin() {
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let plugin = dir.path().join("git-status");
+        std::fs::create_dir(&plugin).unwrap();
+        std::fs::write(
+            plugin.join("init.lua"),
+            r#"return {
+                name = 'git-status',
+                on_document_open = function(api, path)
+                    return { status_items = {
+                        { id = 'branch', text = 'main', action = 'switch_branch' },
+                        { id = 'errors', text = '2 errors', alignment = 'right',
+                          priority = 5, tooltip = 'Open diagnostics' },
+                        { id = 'branch', text = 'dev\nbranch' },
+                        { text = 'missing id' },
+                    } }
+                end,
+            }"#,
+        )
+        .unwrap();
+
+        let mut pm = PluginManager::new(true);
+        pm.load_plugins(dir.path());
+        let result = pm.call_hook(PluginHook::OnDocumentOpen {
+            path: None,
+            content: None,
+        });
+
+        assert_eq!(result.status_items.len(), 1);
+        let (plugin_name, items) = &result.status_items[0];
+        assert_eq!(plugin_name, "git-status");
+        assert_eq!(items.len(), 2);
+        // Repeated id replaces the earlier item; newlines are flattened
+        assert_eq!(items[0].id, "branch");
+        assert_eq!(items[0].text, "dev branch");
+        assert_eq!(items[0].action, None);
+        assert_eq!(items[1].alignment, StatusAlignment::Right);
+        assert_eq!(items[1].priority, 5);
+        assert_eq!(items[1].tooltip.as_deref(), Some("Open diagnostics"));
+    }
+
     #[test]
     fn test_clear_plugins_triggers_gc() {
         let mut pm = PluginManager::new(true);
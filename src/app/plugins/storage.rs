@@ -0,0 +1,320 @@
+//! Persistent per-plugin key-value storage.
+//!
+//! Each plugin gets one JSON file under ~/.config/ferrispad/plugin-data/
+//! (a sibling of the plugins directory), so plugins can remember state
+//! between runs without writing into the user's project.
+//!
+//! The file holds a global namespace plus one namespace per project root:
+//!
+//! ```json
+//! { "global": { "key": 1 }, "projects": { "/home/me/repo": { "key": "x" } } }
+//! ```
+//!
+//! Writes are bounded by `MAX_STORAGE_BYTES` (serialized size of the whole file).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Maximum serialized size of a plugin's storage file (256 KB).
+pub const MAX_STORAGE_BYTES: usize = 256 * 1024;
+
+/// Maximum length of a storage key in bytes.
+pub const MAX_KEY_LEN: usize = 256;
+
+/// Which namespace a storage operation targets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageScope {
+    /// Shared across all projects
+    Global,
+    /// Private to one project root (absolute path)
+    Project(String),
+}
+
+/// On-disk contents of a plugin's storage file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginStore {
+    #[serde(default)]
+    pub global: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub projects: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+impl PluginStore {
+    /// Load a store from disk. Missing, unreadable or corrupt files yield an
+    /// empty store; the file itself is left untouched.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read(path)
+            .ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Load a store that is about to be modified and saved. A corrupt file
+    /// is moved aside to `<name>.json.corrupt` first so the save can't
+    /// destroy it; a file that can't be read or moved aside refuses the write.
+    fn load_for_write(path: &Path) -> Result<Self, String> {
+        let content = match std::fs::read(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        match serde_json::from_slice(&content) {
+            Ok(store) => Ok(store),
+            Err(e) => {
+                let aside = corrupt_file(path);
+                std::fs::rename(path, &aside).map_err(|rename_err| {
+                    format!(
+                        "Storage file {} is corrupt ({}) and could not be moved aside: {}",
+                        path.display(),
+                        e,
+                        rename_err
+                    )
+                })?;
+                eprintln!(
+                    "[plugins] Moved corrupt storage file {} to {}: {}",
+                    path.display(),
+                    aside.display(),
+                    e
+                );
+                Ok(Self::default())
+            }
+        }
+    }
+
+    /// Write the store to disk atomically (temp file + rename).
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Get a value from a namespace.
+    pub fn get(&self, scope: &StorageScope, key: &str) -> Option<&Value> {
+        match scope {
+            StorageScope::Global => self.global.get(key),
+            StorageScope::Project(root) => self.projects.get(root)?.get(key),
+        }
+    }
+
+    /// Set a value in a namespace. `None` removes the key; empty project
+    /// namespaces are dropped so the file doesn't accumulate dead roots.
+    pub fn set(&mut self, scope: &StorageScope, key: &str, value: Option<Value>) {
+        match scope {
+            StorageScope::Global => match value {
+                Some(v) => {
+                    self.global.insert(key.to_string(), v);
+                }
+                None => {
+                    self.global.remove(key);
+                }
+            },
+            StorageScope::Project(root) => match value {
+                Some(v) => {
+                    self.projects
+                        .entry(root.clone())
+                        .or_default()
+                        .insert(key.to_string(), v);
+                }
+                None => {
+                    if let Some(ns) = self.projects.get_mut(root) {
+                        ns.remove(key);
+                        if ns.is_empty() {
+                            self.projects.remove(root);
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    /// List keys in a namespace (sorted).
+    pub fn keys(&self, scope: &StorageScope) -> Vec<String> {
+        match scope {
+            StorageScope::Global => self.global.keys().cloned().collect(),
+            StorageScope::Project(root) => self
+                .projects
+                .get(root)
+                .map(|ns| ns.keys().cloned().collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Serialized size in bytes (what counts against the quota).
+    pub fn size_bytes(&self) -> usize {
+        serde_json::to_string_pretty(self)
+            .map(|s| s.len())
+            .unwrap_or(0)
+    }
+}
+
+/// Load, modify and save a plugin's store in one step.
+/// Rejects the write (leaving the file untouched) if it would exceed the quota.
+pub fn set_value(
+    path: &Path,
+    scope: &StorageScope,
+    key: &str,
+    value: Option<Value>,
+) -> Result<(), String> {
+    if key.is_empty() || key.len() > MAX_KEY_LEN {
+        return Err(format!("Key must be 1-{} bytes", MAX_KEY_LEN));
+    }
+    let mut store = PluginStore::load_for_write(path)?;
+    store.set(scope, key, value);
+    let size = store.size_bytes();
+    if size > MAX_STORAGE_BYTES {
+        return Err(format!(
+            "Storage quota exceeded ({} KB max)",
+            MAX_STORAGE_BYTES / 1024
+        ));
+    }
+    store.save(path).map_err(|e| e.to_string())
+}
+
+/// Get the plugin data directory path.
+/// Returns ~/.config/ferrispad/plugin-data/ (or platform equivalent)
+pub fn get_plugin_data_dir() -> PathBuf {
+    data_dir_for(&super::get_plugin_dir())
+}
+
+/// Data directory that sits next to a given plugins directory.
+pub fn data_dir_for(plugins_dir: &Path) -> PathBuf {
+    plugins_dir
+        .parent()
+        .map(|p| p.join("plugin-data"))
+        .unwrap_or_else(|| PathBuf::from("plugin-data"))
+}
+
+/// Storage file for a plugin, keyed by its directory name.
+pub fn storage_file(data_dir: &Path, plugin_dir_name: &str) -> PathBuf {
+    data_dir.join(format!("{}.json", plugin_dir_name))
+}
+
+/// Where a corrupt storage file is kept once a fresh store replaces it.
+fn corrupt_file(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".corrupt");
+    path.with_file_name(name)
+}
+
+/// Delete a plugin's storage file and any corrupt copy of it (called on uninstall).
+/// Returns Ok(true) if a file was removed, Ok(false) if there was nothing to remove.
+pub fn remove_plugin_storage(data_dir: &Path, plugin_dir_name: &str) -> std::io::Result<bool> {
+    let path = storage_file(data_dir, plugin_dir_name);
+    let mut removed = false;
+    for file in [corrupt_file(&path), path] {
+        match std::fs::remove_file(&file) {
+            Ok(()) => removed = true,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_set_get_keys_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = storage_file(dir.path(), "my-plugin");
+
+        set_value(&path, &StorageScope::Global, "b", Some(Value::from(2))).unwrap();
+        set_value(&path, &StorageScope::Global, "a", Some(Value::from("x"))).unwrap();
+
+        let store = PluginStore::load(&path);
+        assert_eq!(
+            store.get(&StorageScope::Global, "a"),
+            Some(&Value::from("x"))
+        );
+        assert_eq!(store.keys(&StorageScope::Global), vec!["a", "b"]);
+
+        set_value(&path, &StorageScope::Global, "a", None).unwrap();
+        assert_eq!(
+            PluginStore::load(&path).keys(&StorageScope::Global),
+            vec!["b"]
+        );
+    }
+
+    #[test]
+    fn test_project_namespaces_are_isolated() {
+        let mut store = PluginStore::default();
+        let p1 = StorageScope::Project("/repo/one".to_string());
+        let p2 = StorageScope::Project("/repo/two".to_string());
+
+        store.set(&p1, "venv", Some(Value::from(".venv")));
+        assert_eq!(store.get(&p1, "venv"), Some(&Value::from(".venv")));
+        assert!(store.get(&p2, "venv").is_none());
+        assert!(store.get(&StorageScope::Global, "venv").is_none());
+
+        // Removing the last key drops the project namespace entirely
+        store.set(&p1, "venv", None);
+        assert!(store.projects.is_empty());
+    }
+
+    #[test]
+    fn test_quota_rejects_oversized_write() {
+        let dir = tempdir().unwrap();
+        let path = storage_file(dir.path(), "big");
+        set_value(&path, &StorageScope::Global, "keep", Some(Value::from(1))).unwrap();
+
+        let huge = "x".repeat(MAX_STORAGE_BYTES + 1);
+        let err = set_value(
+            &path,
+            &StorageScope::Global,
+            "huge",
+            Some(Value::from(huge)),
+        );
+        assert!(err.is_err());
+
+        // Previous contents are preserved
+        let store = PluginStore::load(&path);
+        assert_eq!(store.keys(&StorageScope::Global), vec!["keep"]);
+    }
+
+    #[test]
+    fn test_corrupt_file_loads_empty_and_remove() {
+        let dir = tempdir().unwrap();
+        let path = storage_file(dir.path(), "broken");
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(PluginStore::load(&path).global.is_empty());
+        // Reading never touches the file
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{ not json");
+        assert!(!dir.path().join("broken.json.corrupt").exists());
+
+        assert!(remove_plugin_storage(dir.path(), "broken").unwrap());
+        assert!(!path.exists());
+        assert!(!remove_plugin_storage(dir.path(), "broken").unwrap());
+    }
+
+    #[test]
+    fn test_write_over_corrupt_file_keeps_it() {
+        let dir = tempdir().unwrap();
+        let path = storage_file(dir.path(), "broken");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        set_value(&path, &StorageScope::Global, "k", Some(Value::from(1))).unwrap();
+        assert_eq!(
+            PluginStore::load(&path).get(&StorageScope::Global, "k"),
+            Some(&Value::from(1))
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("broken.json.corrupt")).unwrap(),
+            "{ not json"
+        );
+    }
+
+    #[test]
+    fn test_data_dir_is_sibling_of_plugins_dir() {
+        let data = data_dir_for(Path::new("/cfg/ferrispad/plugins"));
+        assert_eq!(data, PathBuf::from("/cfg/ferrispad/plugin-data"));
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides a way for plugins to create and manage UI widgets such as:
 //! - Split views for diffs and suggestions
 //! - Tree views for file browsers and YAML viewers
+//! - Table views for test results, TODO lists and query results
 //!
 //! ## Architecture
 //!
@@ -11,6 +12,7 @@
 //! are sent back to plugins via the OnWidgetAction hook.
 
 pub mod split_view;
+pub mod table_view;
 pub mod terminal_view;
 pub mod tree_view;
 
@@ -18,6 +20,10 @@ pub use split_view::{
     HighlightColor, IntralineSpan, LineHighlight, SplitDisplayMode, SplitPane, SplitViewAction,
     SplitViewRequest,
 };
+pub use table_view::{
+    CellKind, CellValue, TableColumn, TableRow, TableSort, TableViewRequest, column_widths,
+    visible_rows,
+};
 pub use terminal_view::TerminalViewRequest;
 pub use tree_view::{ContextMenuItem, ContextMenuTarget, TreeClickMode, TreeNode, TreeViewRequest};
 
@@ -51,6 +57,7 @@ pub enum WidgetType {
     SplitView,
     TreeView,
     TerminalView,
+    TableView,
 }
 
 /// Manages widget sessions created by plugins
@@ -113,6 +120,21 @@ impl WidgetManager {
         id
     }
 
+    /// Create a new table view session
+    pub fn create_table_view_session(&mut self, plugin_name: &str, persistent: bool) -> u32 {
+        let id = next_session_id();
+        self.sessions.insert(
+            id,
+            WidgetSession {
+                id,
+                plugin_name: plugin_name.to_string(),
+                widget_type: WidgetType::TableView,
+                persistent,
+            },
+        );
+        id
+    }
+
     /// Get a session by ID
     pub fn get_session(&self, id: u32) -> Option<&WidgetSession> {
         self.sessions.get(&id)
@@ -160,11 +182,19 @@ impl WidgetManager {
             .map(|s| s.id)
     }
 
-    /// Find any active terminal view session and return its ID.
-    pub fn any_terminal_view_session(&self) -> Option<u32> {
+    /// Find the terminal view session owned by a plugin (one terminal tab per plugin).
+    pub fn terminal_view_session_for(&self, plugin_name: &str) -> Option<u32> {
         self.sessions
             .values()
-            .find(|s| s.widget_type == WidgetType::TerminalView)
+            .find(|s| s.widget_type == WidgetType::TerminalView && s.plugin_name == plugin_name)
+            .map(|s| s.id)
+    }
+
+    /// Find any active table view session and return its ID.
+    pub fn any_table_view_session(&self) -> Option<u32> {
+        self.sessions
+            .values()
+            .find(|s| s.widget_type == WidgetType::TableView)
             .map(|s| s.id)
     }
 
@@ -175,6 +205,14 @@ impl WidgetManager {
             .find(|s| s.widget_type == WidgetType::TreeView && !s.persistent)
             .map(|s| s.id)
     }
+
+    /// Find a non-persistent table view session (closed on tab switch).
+    pub fn non_persistent_table_session(&self) -> Option<u32> {
+        self.sessions
+            .values()
+            .find(|s| s.widget_type == WidgetType::TableView && !s.persistent)
+            .map(|s| s.id)
+    }
 }
 
 #[cfg(test)]
@@ -210,6 +248,46 @@ mod tests {
         assert!(manager.has_session(tree_id));
     }
 
+    #[test]
+    fn test_table_view_session() {
+        let mut manager = WidgetManager::new();
+        assert_eq!(manager.any_table_view_session(), None);
+
+        let tree_id = manager.create_tree_view_session("todo", false);
+        let table_id = manager.create_table_view_session("todo", true);
+        assert_eq!(manager.any_table_view_session(), Some(table_id));
+        assert_eq!(manager.any_tree_view_session(), Some(tree_id));
+        assert!(manager.get_session(table_id).unwrap().persistent);
+
+        manager.remove_session(table_id);
+        assert_eq!(manager.any_table_view_session(), None);
+    }
+
+    #[test]
+    fn test_non_persistent_table_session() {
+        let mut manager = WidgetManager::new();
+        manager.create_table_view_session("todo", true);
+        manager.create_tree_view_session("yaml", false);
+        assert_eq!(manager.non_persistent_table_session(), None);
+
+        let table_id = manager.create_table_view_session("csv", false);
+        assert_eq!(manager.non_persistent_table_session(), Some(table_id));
+    }
+
+    #[test]
+    fn test_terminal_view_session_per_plugin() {
+        let mut manager = WidgetManager::new();
+        let a = manager.create_terminal_view_session("runner", false);
+        let b = manager.create_terminal_view_session("watcher", false);
+        assert_ne!(a, b);
+        assert_eq!(manager.terminal_view_session_for("runner"), Some(a));
+        assert_eq!(manager.terminal_view_session_for("watcher"), Some(b));
+        assert_eq!(manager.terminal_view_session_for("other"), None);
+
+        manager.remove_session(a);
+        assert_eq!(manager.terminal_view_session_for("runner"), None);
+    }
+
     #[test]
     fn test_clear_plugin_sessions() {
         let mut manager = WidgetManager::new();
@@ -0,0 +1,516 @@
+//! Table view widget types for plugin API.
+//!
+//! Allows plugins to display sortable, filterable multi-column data, useful for:
+//! - Test results
+//! - TODO lists
+//! - SQL query results and dependency audits
+
+use std::cmp::Ordering;
+
+use super::tree_view::{ContextMenuItem, TreeClickMode};
+
+/// Maximum number of rows accepted from a single request.
+/// Extra rows are dropped so a runaway plugin can't stall the UI.
+pub const MAX_TABLE_ROWS: usize = 10_000;
+
+/// Maximum number of columns accepted from a single request.
+pub const MAX_TABLE_COLUMNS: usize = 16;
+
+/// How the cells of a column are interpreted for display and sorting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellKind {
+    /// Plain text, sorted case-insensitively (default)
+    #[default]
+    Text,
+    /// Numeric value, sorted numerically
+    Number,
+    /// Boolean, shown as a check mark
+    Bool,
+    /// File path, copied by clipboard context menu items
+    Path,
+}
+
+impl CellKind {
+    fn from_str(s: &str) -> Self {
+        match s {
+            "number" => CellKind::Number,
+            "bool" | "boolean" => CellKind::Bool,
+            "path" => CellKind::Path,
+            _ => CellKind::Text,
+        }
+    }
+}
+
+/// A typed table cell
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CellValue {
+    /// Missing value (sorted last)
+    #[default]
+    Empty,
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    Path(String),
+}
+
+impl CellValue {
+    /// Convert a Lua value to a cell, coerced to the column's kind where possible
+    fn from_lua_value(value: mlua::Value, kind: CellKind) -> Self {
+        let cell = match value {
+            mlua::Value::Nil => return CellValue::Empty,
+            mlua::Value::Boolean(b) => CellValue::Bool(b),
+            mlua::Value::Integer(i) => CellValue::Number(i as f64),
+            mlua::Value::Number(n) => CellValue::Number(n),
+            mlua::Value::String(s) => match s.to_str() {
+                Ok(s) => CellValue::Text(s.to_string()),
+                Err(_) => return CellValue::Empty,
+            },
+            _ => return CellValue::Empty,
+        };
+        match (kind, cell) {
+            (CellKind::Number, CellValue::Text(s)) => match s.trim().parse::<f64>() {
+                Ok(n) => CellValue::Number(n),
+                Err(_) => CellValue::Text(s),
+            },
+            (CellKind::Path, CellValue::Text(s)) => CellValue::Path(s),
+            (_, cell) => cell,
+        }
+    }
+
+    /// Text shown in the table (tabs and newlines flattened to spaces)
+    pub fn display(&self) -> String {
+        let text = match self {
+            CellValue::Empty => String::new(),
+            CellValue::Text(s) | CellValue::Path(s) => s.clone(),
+            CellValue::Number(n) => {
+                if n.fract() == 0.0 && n.abs() < 1e15 {
+                    format!("{}", *n as i64)
+                } else {
+                    format!("{}", n)
+                }
+            }
+            CellValue::Bool(true) => "\u{2714}".to_string(),
+            CellValue::Bool(false) => "\u{2718}".to_string(),
+        };
+        text.replace(['\t', '\n', '\r'], " ")
+    }
+
+    /// Sort rank of the cell variant, so mixed columns still order deterministically
+    fn rank(&self) -> u8 {
+        match self {
+            CellValue::Number(_) => 0,
+            CellValue::Bool(_) => 1,
+            CellValue::Text(_) | CellValue::Path(_) => 2,
+            CellValue::Empty => 3,
+        }
+    }
+
+    /// Compare two cells for sorting
+    pub fn compare(&self, other: &CellValue) -> Ordering {
+        match (self, other) {
+            (CellValue::Number(a), CellValue::Number(b)) => a.total_cmp(b),
+            (CellValue::Bool(a), CellValue::Bool(b)) => a.cmp(b),
+            (CellValue::Text(a) | CellValue::Path(a), CellValue::Text(b) | CellValue::Path(b)) => {
+                a.to_lowercase().cmp(&b.to_lowercase())
+            }
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+/// A column definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableColumn {
+    /// Key used to look up cells in keyed rows
+    pub key: String,
+    /// Header text
+    pub title: String,
+    /// Fixed width in pixels (None = share the remaining width)
+    pub width: Option<i32>,
+    /// How cells in this column are interpreted
+    pub kind: CellKind,
+}
+
+impl TableColumn {
+    /// Parse a column from a Lua table
+    pub fn from_lua_table(table: &mlua::Table) -> Option<Self> {
+        let key: String = table.get("key").ok()?;
+        let title: String = table.get("title").unwrap_or_else(|_| key.clone());
+        let width: Option<i32> = table.get::<i32>("width").ok().filter(|w| *w > 0);
+        let kind = table
+            .get::<String>("kind")
+            .map(|s| CellKind::from_str(&s))
+            .unwrap_or_default();
+        Some(Self {
+            key,
+            title,
+            width,
+            kind,
+        })
+    }
+}
+
+/// A row of cells, in column order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TableRow {
+    /// Identifier sent back to the plugin on click (defaults to the row's 1-based index)
+    pub id: String,
+    /// Cells in column order (missing cells are `Empty`)
+    pub cells: Vec<CellValue>,
+    /// Optional semantic color name for the row (e.g., "error", "warning", "success", "muted")
+    pub color: Option<String>,
+}
+
+impl TableRow {
+    /// Parse a row from a Lua table. Cells can be keyed by column key
+    /// (`cells = { name = "x", time = 1.2 }`) or positional (`cells = { "x", 1.2 }`).
+    pub fn from_lua_table(table: &mlua::Table, columns: &[TableColumn], index: usize) -> Self {
+        let id: String = match table.get::<mlua::Value>("id") {
+            Ok(mlua::Value::String(s)) => s.to_str().map(|s| s.to_string()).unwrap_or_default(),
+            Ok(mlua::Value::Integer(i)) => i.to_string(),
+            _ => index.to_string(),
+        };
+        let color: Option<String> = table.get("color").ok();
+
+        let cells = match table.get::<mlua::Value>("cells") {
+            Ok(mlua::Value::Table(cells_table)) => columns
+                .iter()
+                .enumerate()
+                .map(|(i, col)| {
+                    let value = match cells_table.get::<mlua::Value>(col.key.as_str()) {
+                        Ok(mlua::Value::Nil) | Err(_) => cells_table
+                            .get::<mlua::Value>(i + 1)
+                            .unwrap_or(mlua::Value::Nil),
+                        Ok(v) => v,
+                    };
+                    CellValue::from_lua_value(value, col.kind)
+                })
+                .collect(),
+            _ => vec![CellValue::Empty; columns.len()],
+        };
+
+        Self { id, cells, color }
+    }
+
+    /// Cell at a column index (Empty when out of range)
+    pub fn cell(&self, column: usize) -> &CellValue {
+        const EMPTY: &CellValue = &CellValue::Empty;
+        self.cells.get(column).unwrap_or(EMPTY)
+    }
+
+    /// Whether any cell contains `query` (already lowercased)
+    pub fn matches(&self, query: &str) -> bool {
+        query.is_empty()
+            || self
+                .cells
+                .iter()
+                .any(|c| c.display().to_lowercase().contains(query))
+    }
+
+    /// First path cell, used by clipboard context menu items
+    pub fn path(&self) -> Option<&str> {
+        self.cells.iter().find_map(|c| match c {
+            CellValue::Path(p) => Some(p.as_str()),
+            _ => None,
+        })
+    }
+
+    /// All cells joined by tabs (clipboard fallback when the row has no path)
+    pub fn to_tsv(&self) -> String {
+        self.cells
+            .iter()
+            .map(|c| c.display())
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+}
+
+/// Current sort column and direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSort {
+    pub column: usize,
+    pub ascending: bool,
+}
+
+/// A request to show a table view, returned from plugin hooks
+#[derive(Debug, Clone, Default)]
+pub struct TableViewRequest {
+    /// Title shown in the table view header
+    pub title: String,
+    /// Column definitions
+    pub columns: Vec<TableColumn>,
+    /// Rows (at most `MAX_TABLE_ROWS`)
+    pub rows: Vec<TableRow>,
+    /// Action name sent back to plugin on row click
+    pub on_click_action: Option<String>,
+    /// Click mode: "single" or "double" (default)
+    pub click_mode: TreeClickMode,
+    /// Plugin-defined context menu items (targets: "row", "empty", "all")
+    pub context_menu: Vec<ContextMenuItem>,
+    /// Initial filter text
+    pub filter: Option<String>,
+    /// Initial sort (None = plugin order)
+    pub sort: Option<TableSort>,
+    /// If true, this table view persists across tab switches
+    pub persistent: bool,
+}
+
+impl TableViewRequest {
+    /// Parse a table view request from a Lua table
+    pub fn from_lua_table(table: &mlua::Table) -> Option<Self> {
+        let title: String = table.get("title").unwrap_or_default();
+
+        let columns: Vec<TableColumn> =
+            if let Ok(mlua::Value::Table(cols_table)) = table.get::<mlua::Value>("columns") {
+                cols_table
+                    .sequence_values::<mlua::Table>()
+                    .flatten()
+                    .filter_map(|t| TableColumn::from_lua_table(&t))
+                    .take(MAX_TABLE_COLUMNS)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+        let rows = if let Ok(mlua::Value::Table(rows_table)) = table.get::<mlua::Value>("rows") {
+            rows_table
+                .sequence_values::<mlua::Table>()
+                .flatten()
+                .take(MAX_TABLE_ROWS)
+                .enumerate()
+                .map(|(i, t)| TableRow::from_lua_table(&t, &columns, i + 1))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let on_click_action: Option<String> = table.get("on_click").ok();
+        let click_mode = match table.get::<String>("click_mode") {
+            Ok(ref s) if s == "single" => TreeClickMode::SingleClick,
+            _ => TreeClickMode::DoubleClick,
+        };
+
+        let context_menu =
+            if let Ok(mlua::Value::Table(menu_table)) = table.get::<mlua::Value>("context_menu") {
+                menu_table
+                    .sequence_values::<mlua::Table>()
+                    .flatten()
+                    .filter_map(|t| ContextMenuItem::from_lua_table(&t))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+        let filter: Option<String> = table.get("filter").ok();
+
+        // sort_by = column key, sort_desc = true for descending
+        let sort = table
+            .get::<String>("sort_by")
+            .ok()
+            .and_then(|key| columns.iter().position(|c| c.key == key))
+            .map(|column| TableSort {
+                column,
+                ascending: !table.get::<bool>("sort_desc").unwrap_or(false),
+            });
+
+        let persistent: bool = table.get("persistent").unwrap_or(false);
+
+        Some(Self {
+            title,
+            columns,
+            rows,
+            on_click_action,
+            click_mode,
+            context_menu,
+            filter,
+            sort,
+            persistent,
+        })
+    }
+
+    /// Check if this is a valid request (has at least one column)
+    pub fn is_valid(&self) -> bool {
+        !self.columns.is_empty()
+    }
+}
+
+/// Indices of the rows to display, filtered by `filter` and ordered by `sort`.
+/// Sorting is stable, so equal cells keep the plugin's order.
+pub fn visible_rows(rows: &[TableRow], filter: &str, sort: Option<TableSort>) -> Vec<usize> {
+    let query = filter.trim().to_lowercase();
+    let mut indices: Vec<usize> = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.matches(&query))
+        .map(|(i, _)| i)
+        .collect();
+
+    if let Some(sort) = sort {
+        indices.sort_by(|&a, &b| {
+            let (ca, cb) = (rows[a].cell(sort.column), rows[b].cell(sort.column));
+            // Empty cells stay last in both directions
+            match (ca, cb) {
+                (CellValue::Empty, CellValue::Empty) => Ordering::Equal,
+                (CellValue::Empty, _) => Ordering::Greater,
+                (_, CellValue::Empty) => Ordering::Less,
+                _ if sort.ascending => ca.compare(cb),
+                _ => cb.compare(ca),
+            }
+        });
+    }
+    indices
+}
+
+/// Pixel widths for each column given the available width.
+/// Fixed-width columns keep their width; the rest share what's left
+/// (never narrower than `min_width`).
+pub fn column_widths(columns: &[TableColumn], total_width: i32, min_width: i32) -> Vec<i32> {
+    let fixed: i32 = columns.iter().filter_map(|c| c.width).sum();
+    let flexible = columns.iter().filter(|c| c.width.is_none()).count() as i32;
+    let share = if flexible > 0 {
+        ((total_width - fixed) / flexible).max(min_width)
+    } else {
+        0
+    };
+    columns
+        .iter()
+        .map(|c| c.width.unwrap_or(share).max(min_width))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(key: &str, kind: CellKind, width: Option<i32>) -> TableColumn {
+        TableColumn {
+            key: key.to_string(),
+            title: key.to_string(),
+            width,
+            kind,
+        }
+    }
+
+    fn row(id: &str, cells: Vec<CellValue>) -> TableRow {
+        TableRow {
+            id: id.to_string(),
+            cells,
+            color: None,
+        }
+    }
+
+    #[test]
+    fn test_table_view_request_from_lua() {
+        let lua = mlua::Lua::new();
+        let table: mlua::Table = lua
+            .load(
+                r#"{
+                    title = "Tests",
+                    columns = {
+                        { key = "name", title = "Test" },
+                        { key = "ms", title = "Time", kind = "number", width = 60 },
+                        { key = "ok", kind = "bool" },
+                        { key = "file", kind = "path" },
+                    },
+                    rows = {
+                        { id = "t1", cells = { name = "parses", ms = 12, ok = true, file = "src/a.rs" } },
+                        { cells = { "renders", "7.5", false } },
+                    },
+                    on_click = "open",
+                    click_mode = "single",
+                    context_menu = { { label = "Rerun", action = "rerun", target = "row" } },
+                    sort_by = "ms",
+                    sort_desc = true,
+                }"#,
+            )
+            .eval()
+            .unwrap();
+
+        let req = TableViewRequest::from_lua_table(&table).unwrap();
+        assert!(req.is_valid());
+        assert_eq!(req.title, "Tests");
+        assert_eq!(req.columns.len(), 4);
+        assert_eq!(req.columns[2].title, "ok");
+        assert_eq!(req.columns[1].width, Some(60));
+        assert_eq!(req.click_mode, TreeClickMode::SingleClick);
+        assert_eq!(req.context_menu.len(), 1);
+        assert_eq!(
+            req.sort,
+            Some(TableSort {
+                column: 1,
+                ascending: false
+            })
+        );
+
+        assert_eq!(req.rows[0].id, "t1");
+        assert_eq!(req.rows[0].cells[1], CellValue::Number(12.0));
+        assert_eq!(req.rows[0].path(), Some("src/a.rs"));
+
+        // Positional cells, string coerced to number, default id
+        assert_eq!(req.rows[1].id, "2");
+        assert_eq!(req.rows[1].cells[1], CellValue::Number(7.5));
+        assert_eq!(req.rows[1].cells[2], CellValue::Bool(false));
+        assert_eq!(req.rows[1].cells[3], CellValue::Empty);
+    }
+
+    #[test]
+    fn test_request_without_columns_is_invalid() {
+        assert!(!TableViewRequest::default().is_valid());
+    }
+
+    #[test]
+    fn test_cell_display() {
+        assert_eq!(CellValue::Number(3.0).display(), "3");
+        assert_eq!(CellValue::Number(2.5).display(), "2.5");
+        assert_eq!(CellValue::Text("a\tb\nc".into()).display(), "a b c");
+        assert_eq!(CellValue::Empty.display(), "");
+    }
+
+    #[test]
+    fn test_visible_rows_filter_and_sort() {
+        let rows = vec![
+            row(
+                "a",
+                vec![CellValue::Text("beta".into()), CellValue::Number(10.0)],
+            ),
+            row(
+                "b",
+                vec![CellValue::Text("Alpha".into()), CellValue::Number(2.0)],
+            ),
+            row("c", vec![CellValue::Text("gamma".into()), CellValue::Empty]),
+        ];
+
+        assert_eq!(visible_rows(&rows, "", None), vec![0, 1, 2]);
+        assert_eq!(visible_rows(&rows, "  ALP ", None), vec![1]);
+
+        let by_name = TableSort {
+            column: 0,
+            ascending: true,
+        };
+        assert_eq!(visible_rows(&rows, "", Some(by_name)), vec![1, 0, 2]);
+
+        // Numeric sort, not lexicographic; empty cells last either way
+        let by_num_desc = TableSort {
+            column: 1,
+            ascending: false,
+        };
+        assert_eq!(visible_rows(&rows, "", Some(by_num_desc)), vec![0, 1, 2]);
+        let by_num_asc = TableSort {
+            column: 1,
+            ascending: true,
+        };
+        assert_eq!(visible_rows(&rows, "", Some(by_num_asc)), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let cols = vec![
+            column("a", CellKind::Text, Some(100)),
+            column("b", CellKind::Text, None),
+            column("c", CellKind::Text, None),
+        ];
+        assert_eq!(column_widths(&cols, 500, 40), vec![100, 200, 200]);
+        // Too narrow: flexible columns clamp to the minimum
+        assert_eq!(column_widths(&cols, 120, 40), vec![100, 40, 40]);
+    }
+}
@@ -3,16 +3,23 @@
 //! Allows plugins to display an embedded terminal emulator.
 //! Used for CLI tools like Claude Code.
 
+use std::collections::BTreeMap;
+
 /// A request to show a terminal view, returned from plugin hooks
 #[derive(Debug, Clone, Default)]
 pub struct TerminalViewRequest {
     /// Title shown in the terminal view header
     pub title: String,
-    /// Command to run (None = default shell from $SHELL)
+    /// Command to run (None = default shell from $SHELL). Parsed by the
+    /// login shell, so it may contain its own arguments, unless `exec` is set
     pub command: Option<String>,
-    /// CLI arguments for the command
+    /// CLI arguments for the command, passed through unchanged (quoted for the shell)
     pub args: Vec<String>,
-    /// Working directory (None = project root or home)
+    /// Run `command` as a program with `args` as its argv, without shell parsing
+    pub exec: bool,
+    /// Extra environment variables for the process
+    pub env: BTreeMap<String, String>,
+    /// Working directory (None = project root or home; a leading `~` is expanded)
     pub working_dir: Option<String>,
     /// If true, this terminal persists across tab switches
     pub persistent: bool,
@@ -24,6 +31,7 @@ impl TerminalViewRequest {
         let title: String = table.get("title").unwrap_or_default();
         let command: Option<String> = table.get("command").ok();
         let persistent: bool = table.get("persistent").unwrap_or(false);
+        let exec: bool = table.get("exec").unwrap_or(false);
         let working_dir: Option<String> = table.get("working_dir").ok();
 
         // Parse args array
@@ -37,10 +45,19 @@ impl TerminalViewRequest {
             Vec::new()
         };
 
+        // Parse env table (name = value)
+        let env = if let Ok(mlua::Value::Table(env_table)) = table.get::<mlua::Value>("env") {
+            env_table.pairs::<String, String>().flatten().collect()
+        } else {
+            BTreeMap::new()
+        };
+
         Some(Self {
             title,
             command,
             args,
+            exec,
+            env,
             working_dir,
             persistent,
         })
@@ -76,4 +93,28 @@ mod tests {
         assert!(request.is_valid());
         assert_eq!(request.command.as_deref(), Some("claude"));
     }
+
+    #[test]
+    fn test_terminal_view_request_from_lua() {
+        let lua = mlua::Lua::new();
+        let table: mlua::Table = lua
+            .load(
+                r#"{
+                    title = "Agent",
+                    command = "claude",
+                    args = { "--add-dir", "~/My Projects", "it's $HOME" },
+                    exec = true,
+                    env = { NO_COLOR = "1", DEPTH = 3 },
+                }"#,
+            )
+            .eval()
+            .unwrap();
+
+        let req = TerminalViewRequest::from_lua_table(&table).unwrap();
+        assert_eq!(req.args, vec!["--add-dir", "~/My Projects", "it's $HOME"]);
+        assert!(req.exec);
+        assert_eq!(req.env.get("NO_COLOR").map(String::as_str), Some("1"));
+        assert_eq!(req.env.get("DEPTH").map(String::as_str), Some("3"));
+        assert!(!req.persistent);
+    }
 }
@@ -24,6 +24,8 @@ pub enum ContextMenuTarget {
     File,
     /// Show only when right-clicking empty area (no node)
     Empty,
+    /// Show only for table view rows
+    Row,
     /// Show for all node types and empty area
     All,
 }
@@ -57,6 +59,7 @@ impl ContextMenuItem {
             "folder" => ContextMenuTarget::Folder,
             "file" => ContextMenuTarget::File,
             "empty" => ContextMenuTarget::Empty,
+            "row" => ContextMenuTarget::Row,
             _ => ContextMenuTarget::All,
         };
         let input_prompt: Option<String> = table.get("input").ok();
@@ -0,0 +1,173 @@
+//! Line bookmarks.
+//!
+//! A document keeps its bookmarks as the byte positions of the bookmarked
+//! line starts, sorted and without duplicates. Positions follow edits the
+//! same way the style buffer does (see `shift_positions`), so a bookmark
+//! stays on its line when text is inserted or removed above it. Sessions
+//! store them as 1-based line numbers instead, which survive the file being
+//! changed on disk a little better than byte offsets.
+
+/// Move bookmark positions past an edit at `pos` that replaced `deleted`
+/// bytes with `inserted` bytes (the arguments of a buffer modify callback).
+///
+/// An insertion at the very start of a bookmarked line pushes the line, and
+/// the bookmark with it, down. Positions inside a replaced range keep their
+/// offset as far as the new text reaches, so reloading a file from disk
+/// leaves bookmarks roughly where they were. The caller snaps the results
+/// back onto line starts with `snap` once the buffer matches them.
+pub fn shift_positions(positions: &mut [i32], pos: i32, inserted: i32, deleted: i32) {
+    for p in positions.iter_mut() {
+        if *p < pos {
+            continue;
+        }
+        if deleted == 0 || *p >= pos + deleted {
+            *p += inserted - deleted;
+        } else {
+            *p = pos + (*p - pos).min(inserted);
+        }
+    }
+}
+
+/// Move each position to the start of its line (as told by `line_start`)
+/// and drop the duplicates.
+///
+/// Edits are reported after the fact, so positions must only be snapped
+/// when every edit up to the buffer's current text has been shifted in;
+/// snapping earlier looks up lines in text the positions don't belong to.
+pub fn snap(positions: &mut Vec<i32>, line_start: impl Fn(i32) -> i32) {
+    for p in positions.iter_mut() {
+        *p = line_start(*p);
+    }
+    positions.sort_unstable();
+    positions.dedup();
+}
+
+/// Add the bookmark at `line_start` or remove it if it is already set.
+/// Returns true when the bookmark was added.
+pub fn toggle(positions: &mut Vec<i32>, line_start: i32) -> bool {
+    match positions.binary_search(&line_start) {
+        Ok(index) => {
+            positions.remove(index);
+            false
+        }
+        Err(index) => {
+            positions.insert(index, line_start);
+            true
+        }
+    }
+}
+
+/// The first bookmark after `line_start`, wrapping around to the first one
+pub fn next_after(positions: &[i32], line_start: i32) -> Option<i32> {
+    positions
+        .iter()
+        .copied()
+        .find(|&p| p > line_start)
+        .or_else(|| positions.first().copied())
+}
+
+/// The last bookmark before `line_start`, wrapping around to the last one
+pub fn previous_before(positions: &[i32], line_start: i32) -> Option<i32> {
+    positions
+        .iter()
+        .rev()
+        .copied()
+        .find(|&p| p < line_start)
+        .or_else(|| positions.last().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shift_follows_edits_above_and_ignores_edits_below() {
+        // Bookmarks on the line starts at 10 and 30
+        let mut positions = vec![10, 30];
+        // Typing five bytes on the first line (before 10)
+        shift_positions(&mut positions, 3, 5, 0);
+        assert_eq!(positions, vec![15, 35]);
+        // Deleting three bytes between the bookmarks
+        shift_positions(&mut positions, 20, 0, 3);
+        assert_eq!(positions, vec![15, 32]);
+        // Editing after the last bookmark changes nothing
+        shift_positions(&mut positions, 40, 7, 2);
+        assert_eq!(positions, vec![15, 32]);
+    }
+
+    #[test]
+    fn test_shift_insert_at_line_start_moves_bookmark_down() {
+        let mut positions = vec![10];
+        shift_positions(&mut positions, 10, 1, 0);
+        assert_eq!(positions, vec![11]);
+    }
+
+    #[test]
+    fn test_shift_deleted_range_collapses_onto_edit() {
+        // Joining the bookmarked line with the previous one
+        let mut positions = vec![10, 30];
+        shift_positions(&mut positions, 9, 0, 1);
+        assert_eq!(positions, vec![9, 29]);
+        // Deleting the text of the bookmarked line keeps it in place
+        let mut positions = vec![10];
+        shift_positions(&mut positions, 10, 0, 5);
+        assert_eq!(positions, vec![10]);
+    }
+
+    #[test]
+    fn test_shift_replaced_range_keeps_offsets() {
+        // Reloading a 100 byte file as 50 bytes
+        let mut positions = vec![0, 20, 80];
+        shift_positions(&mut positions, 0, 50, 100);
+        assert_eq!(positions, vec![0, 20, 50]);
+    }
+
+    #[test]
+    fn test_queued_edits_shift_before_snapping() {
+        let text = "a\nb\nc\n";
+        let line_start = |p: i32| {
+            let p = p.clamp(0, text.len() as i32) as usize;
+            text[..p].rfind('\n').map_or(0, |i| i as i32 + 1)
+        };
+        // Bookmarks on "b" and "c". A line is inserted above them and
+        // removed again before either edit is replayed, so the buffer is
+        // already back to `text`.
+        let mut positions = vec![2, 4];
+        shift_positions(&mut positions, 0, 5, 0);
+        assert_eq!(positions, vec![7, 9]);
+        shift_positions(&mut positions, 0, 0, 5);
+        snap(&mut positions, line_start);
+        assert_eq!(positions, vec![2, 4]);
+    }
+
+    #[test]
+    fn test_snap_moves_to_line_start_and_dedups() {
+        let text = "one\ntwo\n";
+        let line_start = |p: i32| text[..p as usize].rfind('\n').map_or(0, |i| i as i32 + 1);
+        let mut positions = vec![6, 2, 4];
+        snap(&mut positions, line_start);
+        assert_eq!(positions, vec![0, 4]);
+    }
+
+    #[test]
+    fn test_toggle_keeps_positions_sorted() {
+        let mut positions = Vec::new();
+        assert!(toggle(&mut positions, 20));
+        assert!(toggle(&mut positions, 0));
+        assert!(toggle(&mut positions, 10));
+        assert_eq!(positions, vec![0, 10, 20]);
+        assert!(!toggle(&mut positions, 10));
+        assert_eq!(positions, vec![0, 20]);
+    }
+
+    #[test]
+    fn test_next_and_previous_wrap_around() {
+        let positions = [0, 10, 20];
+        assert_eq!(next_after(&positions, 10), Some(20));
+        assert_eq!(next_after(&positions, 20), Some(0));
+        assert_eq!(previous_before(&positions, 10), Some(0));
+        assert_eq!(previous_before(&positions, 0), Some(20));
+        assert_eq!(next_after(&[], 0), None);
+        assert_eq!(previous_before(&[], 0), None);
+    }
+}
@@ -0,0 +1,297 @@
+//! Project file listing for Quick Open.
+//!
+//! Walks a project directory and returns every file's path relative to the
+//! root, skipping `.git` and anything excluded by `.gitignore` files (at any
+//! level) or `.git/info/exclude`. Symlinked directories are not followed.
+//!
+//! The `.gitignore` support covers what projects use in practice: `#`
+//! comments, `!` negation, trailing `/` for directories, patterns anchored by
+//! a `/`, and the `*`, `?`, `**` and `[...]` wildcards.
+
+use std::fs;
+use std::path::Path;
+
+/// Stop walking after this many files
+pub const MAX_INDEXED_FILES: usize = 50_000;
+
+#[derive(Debug)]
+struct IgnoreRule {
+    pattern: Vec<char>,
+    negate: bool,
+    dir_only: bool,
+    /// Pattern contains a `/`, so it matches from the ignore file's
+    /// directory instead of against any path component
+    anchored: bool,
+}
+
+/// Rules of one ignore file, applying to the directory it sits in.
+#[derive(Debug, Default)]
+pub struct IgnoreFile {
+    /// Directory of the ignore file, relative to the project root
+    /// ("" for the root, otherwise ending in `/`)
+    base: String,
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreFile {
+    pub fn parse(base: &str, text: &str) -> Self {
+        let rules = text.lines().filter_map(parse_rule).collect();
+        Self {
+            base: base.to_string(),
+            rules,
+        }
+    }
+
+    /// Whether the rules decide about `rel_path` (relative to the project
+    /// root): Some(true) if ignored, Some(false) if re-included with `!`,
+    /// None if no rule matches. The last matching rule wins.
+    pub fn matches(&self, rel_path: &str, is_dir: bool) -> Option<bool> {
+        let path = rel_path.strip_prefix(&self.base)?;
+        let path: Vec<char> = path.chars().collect();
+        let name_start = path.iter().rposition(|&c| c == '/').map_or(0, |i| i + 1);
+        self.rules.iter().rev().find_map(|rule| {
+            if rule.dir_only && !is_dir {
+                return None;
+            }
+            let subject = if rule.anchored {
+                &path[..]
+            } else {
+                &path[name_start..]
+            };
+            glob_match(&rule.pattern, subject).then_some(!rule.negate)
+        })
+    }
+}
+
+fn parse_rule(line: &str) -> Option<IgnoreRule> {
+    let mut line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let negate = line.starts_with('!');
+    if negate {
+        line = &line[1..];
+    }
+    // `\#` and `\!` stand for a literal leading character
+    if line.starts_with("\\#") || line.starts_with("\\!") {
+        line = &line[1..];
+    }
+    let dir_only = line.ends_with('/');
+    let line = line.trim_end_matches('/');
+    let anchored = line.contains('/');
+    let line = line.trim_start_matches('/');
+    if line.is_empty() {
+        return None;
+    }
+    Some(IgnoreRule {
+        pattern: line.chars().collect(),
+        negate,
+        dir_only,
+        anchored,
+    })
+}
+
+/// Match a gitignore glob against a `/`-separated path.
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            if rest.first() == Some(&'/') {
+                // `**/x` matches x at any depth, including none
+                let rest = &rest[1..];
+                glob_match(rest, text)
+                    || text
+                        .iter()
+                        .enumerate()
+                        .any(|(i, &c)| c == '/' && glob_match(rest, &text[i + 1..]))
+            } else {
+                (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+            }
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for i in 0..=text.len() {
+                if glob_match(rest, &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => {
+            text.first().is_some_and(|&c| c != '/') && glob_match(&pattern[1..], &text[1..])
+        }
+        Some('[') => match (
+            text.first(),
+            class_match(&pattern[1..], text.first().copied()),
+        ) {
+            (Some(&c), Some((matched, len))) if c != '/' => {
+                matched && glob_match(&pattern[1 + len..], &text[1..])
+            }
+            // Unterminated class: treat `[` literally
+            (Some(&'['), None) => glob_match(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+        Some('\\') if pattern.len() > 1 => {
+            text.first() == Some(&pattern[1]) && glob_match(&pattern[2..], &text[1..])
+        }
+        Some(&p) => text.first() == Some(&p) && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
+/// Match `c` against a `[...]` class whose body starts at `class`.
+/// Returns whether it matched and the class length including the closing `]`.
+fn class_match(class: &[char], c: Option<char>) -> Option<(bool, usize)> {
+    let c = c?;
+    let negate = matches!(class.first(), Some('!') | Some('^'));
+    let mut i = usize::from(negate);
+    let mut matched = false;
+    let mut first = true;
+    while i < class.len() {
+        let start = class[i];
+        if start == ']' && !first {
+            return Some((matched != negate, i + 1));
+        }
+        first = false;
+        if class.get(i + 1) == Some(&'-') && class.get(i + 2).is_some_and(|&e| e != ']') {
+            matched |= (start..=class[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= start == c;
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Whether the ignore files (outermost first) exclude `rel_path`.
+fn is_ignored(ignores: &[IgnoreFile], rel_path: &str, is_dir: bool) -> bool {
+    ignores
+        .iter()
+        .rev()
+        .find_map(|f| f.matches(rel_path, is_dir))
+        .unwrap_or(false)
+}
+
+/// List the files under `root`, relative to it with `/` separators, sorted.
+pub fn list_project_files(root: &Path) -> Vec<String> {
+    let mut ignores = Vec::new();
+    if let Ok(text) = fs::read_to_string(root.join(".git").join("info").join("exclude")) {
+        ignores.push(IgnoreFile::parse("", &text));
+    }
+    let mut files = Vec::new();
+    walk(root, "", &mut ignores, &mut files);
+    files.sort();
+    files
+}
+
+fn walk(dir: &Path, rel_dir: &str, ignores: &mut Vec<IgnoreFile>, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let pushed = match fs::read_to_string(dir.join(".gitignore")) {
+        Ok(text) => {
+            ignores.push(IgnoreFile::parse(rel_dir, &text));
+            true
+        }
+        Err(_) => false,
+    };
+    let mut subdirs = Vec::new();
+    for entry in entries.flatten() {
+        if files.len() >= MAX_INDEXED_FILES {
+            break;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let rel_path = format!("{}{}", rel_dir, name);
+        if file_type.is_dir() {
+            if name != ".git" && !is_ignored(ignores, &rel_path, true) {
+                subdirs.push((entry.path(), rel_path + "/"));
+            }
+        } else if (file_type.is_file() || entry.path().is_file())
+            && !is_ignored(ignores, &rel_path, false)
+        {
+            files.push(rel_path);
+        }
+    }
+    for (path, rel) in subdirs {
+        if files.len() >= MAX_INDEXED_FILES {
+            break;
+        }
+        walk(&path, &rel, ignores, files);
+    }
+
+    if pushed {
+        ignores.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str, text: &str) -> bool {
+        let p: Vec<char> = pattern.chars().collect();
+        let t: Vec<char> = text.chars().collect();
+        glob_match(&p, &t)
+    }
+
+    #[test]
+    fn test_glob_wildcards() {
+        assert!(glob("*.rs", "main.rs"));
+        assert!(!glob("*.rs", "src/main.rs"));
+        assert!(glob("src/*.rs", "src/main.rs"));
+        assert!(glob("**/build", "build"));
+        assert!(glob("**/build", "a/b/build"));
+        assert!(glob("docs/**", "docs/a/b.md"));
+        assert!(glob("a/**/z", "a/z"));
+        assert!(glob("a/**/z", "a/b/c/z"));
+        assert!(glob("file?.txt", "file1.txt"));
+        assert!(!glob("file?.txt", "file/.txt"));
+        assert!(glob("[abc].log", "b.log"));
+        assert!(glob("[a-c].log", "c.log"));
+        assert!(!glob("[!a-c].log", "c.log"));
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let root = IgnoreFile::parse("", "# comment\ntarget/\n*.log\n!keep.log\n/only_root.txt\n");
+        assert_eq!(root.matches("target", true), Some(true));
+        assert_eq!(root.matches("target", false), None);
+        assert_eq!(root.matches("a/debug.log", false), Some(true));
+        assert_eq!(root.matches("a/keep.log", false), Some(false));
+        assert_eq!(root.matches("only_root.txt", false), Some(true));
+        assert_eq!(root.matches("sub/only_root.txt", false), None);
+
+        let nested = IgnoreFile::parse("sub/", "gen.rs\n");
+        assert_eq!(nested.matches("sub/gen.rs", false), Some(true));
+        assert_eq!(nested.matches("gen.rs", false), None);
+    }
+
+    #[test]
+    fn test_list_project_files_respects_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".git").join("HEAD"), "").unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("target").join("debug")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n*.tmp\n").unwrap();
+        fs::write(root.join("src").join(".gitignore"), "generated.rs\n").unwrap();
+        fs::write(root.join("src").join("main.rs"), "").unwrap();
+        fs::write(root.join("src").join("generated.rs"), "").unwrap();
+        fs::write(root.join("notes.tmp"), "").unwrap();
+        fs::write(root.join("target").join("debug").join("app"), "").unwrap();
+        fs::write(root.join("README.md"), "").unwrap();
+
+        assert_eq!(
+            list_project_files(root),
+            vec![".gitignore", "README.md", "src/.gitignore", "src/main.rs"]
+        );
+    }
+}
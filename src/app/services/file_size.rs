@@ -4,10 +4,12 @@
 //! from very large files that would consume too much memory.
 //!
 //! Also provides tail reading and chunk reading for viewing portions
-//! of very large files.
+//! of very large files, and the atomic splice that saves an edited
+//! portion back.
 
-use std::io::{self, Read, Seek, SeekFrom};
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::time::SystemTime;
 
 /// Default number of lines to read in tail mode
 pub const TAIL_LINE_COUNT: usize = 10_000;
@@ -123,149 +125,204 @@ pub fn read_tail(path: &Path, lines: usize) -> io::Result<(String, u64)> {
     Ok((tail, start_byte))
 }
 
-/// Read a specific line range from a file.
-///
-/// Reads lines from `start_line` to `end_line` (1-indexed, inclusive).
-///
-/// Returns `(content, start_byte, end_byte)` where `start_byte` and
-/// `end_byte` are the byte offsets of the chunk in the original file.
-/// Used by `save_partial` to splice edits back into the correct position.
-pub fn read_chunk(
-    path: &Path,
-    start_line: usize,
-    end_line: usize,
-) -> io::Result<(String, u64, u64)> {
-    if start_line == 0 || end_line < start_line {
-        return Ok((String::new(), 0, 0));
+/// A range of a file to open for editing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenRange {
+    /// 1-based lines, inclusive
+    Lines(usize, usize),
+    /// Byte offsets, end exclusive
+    Bytes(u64, u64),
+}
+
+/// Parse a range typed by the user: `100-200`, `100:200` or a single `100`.
+/// Returns None for anything else or an end before the start.
+pub fn parse_range(text: &str) -> Option<(u64, u64)> {
+    let text = text.trim();
+    let (start, end) = text
+        .split_once('-')
+        .or_else(|| text.split_once(':'))
+        .unwrap_or((text, text));
+    let start = start.trim().replace([',', '_'], "").parse::<u64>().ok()?;
+    let end = end.trim().replace([',', '_'], "").parse::<u64>().ok()?;
+    (end >= start).then_some((start, end))
+}
+
+/// Size and modification time of a file, taken when a range of it is read.
+/// A save compares it with the file's current stamp to detect that the file
+/// changed on disk in the meantime, which would make the range's byte
+/// offsets stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> io::Result<Self> {
+        Ok(Self::from_metadata(&std::fs::metadata(path)?))
     }
 
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        }
+    }
+}
+
+/// Whether the byte ranges `a` and `b` (end exclusive) share any byte.
+pub fn ranges_overlap(a: (u64, u64), b: (u64, u64)) -> bool {
+    // An empty range still sits at a position, which the other may cover
+    let end = |r: (u64, u64)| r.1.max(r.0 + 1);
+    a.0 < end(b) && b.0 < end(a)
+}
+
+/// Where `range` ends up after `splice_start..splice_end` was replaced by
+/// `new_len` bytes. Ranges after the splice shift, the others stay; a range
+/// overlapping the splice has no meaningful new position.
+pub fn shift_range(
+    range: (u64, u64),
+    splice_start: u64,
+    splice_end: u64,
+    new_len: u64,
+) -> (u64, u64) {
+    if range.0 < splice_end {
+        return range;
+    }
+    let shift = |offset: u64| offset - (splice_end - splice_start) + new_len;
+    (shift(range.0), shift(range.1))
+}
+
+/// Byte offsets of a line range (1-indexed, inclusive).
+///
+/// `start_byte` is where `start_line` begins and `end_byte` where the line
+/// after `end_line` begins, so the range includes `end_line`'s newline.
+/// Lines past the end of the file resolve to the file length.
+pub fn line_range_bytes(path: &Path, start_line: usize, end_line: usize) -> io::Result<(u64, u64)> {
     let file = std::fs::File::open(path)?;
     let mut reader = io::BufReader::new(file);
-
-    use std::io::BufRead;
-    let mut result = String::new();
-    let mut current_line = 0usize;
-    let mut byte_pos: u64 = 0;
-    let mut start_byte: u64 = 0;
-    let mut line_buf = String::new();
+    let mut line_buf = Vec::new();
+    let mut line = 1usize;
+    let mut pos: u64 = 0;
+    let mut start_byte = None;
 
     loop {
+        if line == start_line {
+            start_byte = Some(pos);
+        }
+        if line > end_line {
+            break;
+        }
         line_buf.clear();
-        let n = reader.read_line(&mut line_buf)?;
+        let n = reader.read_until(b'\n', &mut line_buf)?;
         if n == 0 {
             break; // EOF
         }
+        pos += n as u64;
+        line += 1;
+    }
 
-        current_line += 1;
-
-        if current_line < start_line {
-            byte_pos += n as u64;
-            continue;
-        }
-
-        if current_line == start_line {
-            start_byte = byte_pos;
-        }
+    Ok((start_byte.unwrap_or(pos), pos))
+}
 
-        byte_pos += n as u64;
+/// Read `start_byte..end_byte` of a file as text.
+///
+/// Fails with `InvalidData` when the bytes are not valid UTF-8 (including a
+/// range that cuts a character in two): such text could not be saved back
+/// unchanged.
+pub fn read_byte_range(path: &Path, start_byte: u64, end_byte: u64) -> io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let end_byte = end_byte.min(file_size);
+    if start_byte >= end_byte {
+        return Ok(String::new());
+    }
 
-        if current_line > end_line {
-            break;
-        }
+    file.seek(SeekFrom::Start(start_byte))?;
+    let mut bytes = Vec::with_capacity((end_byte - start_byte) as usize);
+    file.take(end_byte - start_byte).read_to_end(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the range is not valid UTF-8 text or splits a character",
+        )
+    })
+}
 
-        if !result.is_empty() {
-            result.push('\n');
-        }
-        // Strip the trailing newline from read_line
-        let trimmed = line_buf.trim_end_matches('\n').trim_end_matches('\r');
-        result.push_str(trimmed);
+/// Read a specific line range from a file.
+///
+/// Reads lines from `start_line` to `end_line` (1-indexed, inclusive).
+///
+/// Returns `(content, start_byte, end_byte)` where `start_byte` and
+/// `end_byte` are the byte offsets of the chunk in the original file.
+/// The content is exactly those bytes (line endings included), so
+/// `save_partial` can splice edits back without changing anything else.
+pub fn read_chunk(
+    path: &Path,
+    start_line: usize,
+    end_line: usize,
+) -> io::Result<(String, u64, u64)> {
+    if start_line == 0 || end_line < start_line {
+        return Ok((String::new(), 0, 0));
     }
 
-    let end_byte = if current_line <= end_line {
-        byte_pos // hit EOF before end_line
-    } else {
-        // byte_pos is past the line after end_line; end_byte is where end_line ends
-        byte_pos - line_buf.len() as u64
-    };
-
-    Ok((result, start_byte, end_byte))
+    let (start_byte, end_byte) = line_range_bytes(path, start_line, end_line)?;
+    let content = read_byte_range(path, start_byte, end_byte)?;
+    Ok((content, start_byte, end_byte))
 }
 
 /// Save edited content back to the correct position in the original file.
 ///
-/// - **Tail** (`end_byte == file_size`): seeks to `start_byte`, writes content,
-///   and truncates.  Instant regardless of file size.
-/// - **Chunk, same byte length**: overwrites in place.  Instant.
-/// - **Chunk, different byte length**: writes prefix + content + suffix to a
-///   temp file in the same directory, then atomically renames over the original.
+/// Writes prefix `[0..start_byte)` + content + suffix `[end_byte..)` to a
+/// temp file in the same directory, then atomically renames it over the
+/// original, so a failed or interrupted save never leaves a half-written
+/// file behind. The temp file takes over the original's permissions.
+/// A tail saves with `end_byte` set to the file size.
+///
+/// This copies the whole file, even for a small tail of a huge one.
 pub fn save_partial(path: &Path, content: &str, start_byte: u64, end_byte: u64) -> io::Result<()> {
-    use std::fs::{File, OpenOptions};
-    use std::io::Write;
-
-    let file_size = std::fs::metadata(path)?.len();
-    let original_len = end_byte - start_byte;
-    let new_len = content.len() as u64;
-    let is_tail = end_byte >= file_size;
-
-    if is_tail || original_len == new_len {
-        // Fast path: seek + overwrite (+ truncate for tail / size change)
-        let mut file = OpenOptions::new().write(true).open(path)?;
-        file.seek(SeekFrom::Start(start_byte))?;
-        file.write_all(content.as_bytes())?;
-
-        let new_file_size = if is_tail {
-            start_byte + new_len
-        } else {
-            file_size // same-size chunk: file size unchanged
-        };
-        file.set_len(new_file_size)?;
-        file.flush()?;
-        return Ok(());
+    use std::fs::File;
+
+    let metadata = std::fs::metadata(path)?;
+    let file_size = metadata.len();
+    if start_byte > end_byte || end_byte > file_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the edited range lies outside the file (was it truncated?)",
+        ));
     }
 
-    // Slow path: content length changed for a mid-file chunk.
-    // Write prefix + new content + suffix to a temp file, then rename.
     let dir = path.parent().unwrap_or(Path::new("."));
     let temp_path = dir.join(format!(
-        ".ferrispad_save_{}",
+        ".ferrispad_save_{}_{}",
+        std::process::id(),
         path.file_name().and_then(|n| n.to_str()).unwrap_or("tmp")
     ));
 
-    {
+    let write_temp = || -> io::Result<()> {
         let mut src = File::open(path)?;
         let mut dst = File::create(&temp_path)?;
-        const BUF: usize = 8 * 1024 * 1024; // 8 MB copy buffer
 
         // Copy prefix [0..start_byte)
-        let mut remaining = start_byte;
-        let mut buf = vec![0u8; BUF];
-        while remaining > 0 {
-            let to_read = (remaining as usize).min(BUF);
-            src.read_exact(&mut buf[..to_read])?;
-            dst.write_all(&buf[..to_read])?;
-            remaining -= to_read as u64;
-        }
+        io::copy(&mut (&mut src).take(start_byte), &mut dst)?;
 
         // Write edited content
         dst.write_all(content.as_bytes())?;
 
         // Copy suffix [end_byte..file_size)
         src.seek(SeekFrom::Start(end_byte))?;
-        remaining = file_size - end_byte;
-        while remaining > 0 {
-            let to_read = (remaining as usize).min(BUF);
-            src.read_exact(&mut buf[..to_read])?;
-            dst.write_all(&buf[..to_read])?;
-            remaining -= to_read as u64;
-        }
+        io::copy(&mut src, &mut dst)?;
 
-        dst.flush()?;
-    }
+        dst.set_permissions(metadata.permissions())?;
+        dst.sync_all()
+    };
 
     // Atomic rename (same filesystem)
-    std::fs::rename(&temp_path, path)?;
-
-    Ok(())
+    let result = write_temp().and_then(|()| std::fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
 }
 
 /// Format file size for display (e.g., "1.5 GB")
@@ -481,6 +538,133 @@ mod tests {
         assert!(chunk.contains("line 2"));
     }
 
+    #[test]
+    fn test_read_chunk_keeps_line_endings() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "one\r\ntwo\r\nthree").unwrap();
+        file.flush().unwrap();
+
+        let (chunk, start_byte, end_byte) = read_chunk(file.path(), 2, 3).unwrap();
+        assert_eq!(chunk, "two\r\nthree");
+        assert_eq!((start_byte, end_byte), (5, 15));
+
+        // Saving the unchanged chunk leaves the file as it was
+        save_partial(file.path(), &chunk, start_byte, end_byte).unwrap();
+        let text = std::fs::read_to_string(file.path()).unwrap();
+        assert_eq!(text, "one\r\ntwo\r\nthree");
+    }
+
+    #[test]
+    fn test_line_range_bytes() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "a\nbb\nccc\n").unwrap();
+        file.flush().unwrap();
+
+        assert_eq!(line_range_bytes(file.path(), 1, 1).unwrap(), (0, 2));
+        assert_eq!(line_range_bytes(file.path(), 2, 3).unwrap(), (2, 9));
+        assert_eq!(line_range_bytes(file.path(), 3, 100).unwrap(), (5, 9));
+        assert_eq!(line_range_bytes(file.path(), 7, 9).unwrap(), (9, 9));
+    }
+
+    #[test]
+    fn test_read_byte_range_rejects_split_character() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "ab🦀cd").unwrap();
+        file.flush().unwrap();
+
+        assert_eq!(read_byte_range(file.path(), 1, 6).unwrap(), "b🦀");
+        assert_eq!(read_byte_range(file.path(), 6, 100).unwrap(), "cd");
+        let err = read_byte_range(file.path(), 0, 3).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_save_partial_splices_through_temp_file() {
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("big.log");
+        std::fs::write(&path, "head\nmiddle\ntail\n").unwrap();
+
+        // Longer, shorter and same-length replacements of "middle\n"
+        save_partial(&path, "the new middle\n", 5, 12).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "head\nthe new middle\ntail\n"
+        );
+        save_partial(&path, "m\n", 5, 20).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "head\nm\ntail\n");
+        save_partial(&path, "TAIL\n", 7, 12).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "head\nm\nTAIL\n");
+
+        // No temp file is left behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_save_partial_rejects_range_past_end() {
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("short.log");
+        std::fs::write(&path, "short\n").unwrap();
+
+        let err = save_partial(&path, "x", 2, 50).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "short\n");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_partial_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("script.sh");
+        std::fs::write(&path, "echo one\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o750)).unwrap();
+
+        save_partial(&path, "echo two\n", 0, 9).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o750);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("100-200"), Some((100, 200)));
+        assert_eq!(parse_range(" 5 : 9 "), Some((5, 9)));
+        assert_eq!(parse_range("42"), Some((42, 42)));
+        assert_eq!(parse_range("1,000-2,000"), Some((1000, 2000)));
+        assert_eq!(parse_range("9-5"), None);
+        assert_eq!(parse_range("abc"), None);
+        assert_eq!(parse_range(""), None);
+    }
+
+    #[test]
+    fn test_ranges_overlap_and_shift() {
+        assert!(ranges_overlap((0, 10), (5, 15)));
+        assert!(!ranges_overlap((0, 10), (10, 20)));
+        assert!(ranges_overlap((5, 5), (0, 10)));
+        assert!(!ranges_overlap((10, 10), (0, 10)));
+
+        // "middle" at 5..12 grew by 8 bytes: later ranges move, earlier stay
+        assert_eq!(shift_range((12, 17), 5, 12, 15), (20, 25));
+        assert_eq!(shift_range((0, 5), 5, 12, 15), (0, 5));
+        // Shrunk by 5 bytes
+        assert_eq!(shift_range((20, 30), 5, 12, 2), (15, 25));
+    }
+
     #[test]
     fn test_read_chunk_invalid_range() {
         use std::io::Write;
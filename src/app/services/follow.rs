@@ -0,0 +1,232 @@
+//! Follow mode (`tail -f`) for open files.
+//!
+//! A followed document remembers how many bytes of its file it already
+//! shows. Each poll reads whatever was appended past that offset. A file
+//! that shrank (truncation) or was replaced by a new file (log rotation)
+//! cannot be continued and is reported as `Replaced` so the caller can
+//! reopen it.
+
+use std::fs::{File, Metadata};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Seconds between polls of followed files
+pub const FOLLOW_POLL_INTERVAL: f64 = 0.5;
+
+/// Most bytes appended to a document per poll; the rest comes with the
+/// next polls so a burst of output doesn't stall the UI
+const MAX_FOLLOW_READ: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FollowUpdate {
+    Unchanged,
+    /// Text appended to the file since the last poll
+    Appended(String),
+    /// The file was truncated or replaced and has to be reopened
+    Replaced,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowState {
+    /// Bytes of the file the document already shows
+    pub offset: u64,
+    /// (device, inode) of the followed file where the platform has them
+    identity: Option<(u64, u64)>,
+}
+
+impl FollowState {
+    /// Start following `path` from `loaded_len`, the file length when the
+    /// document was read (or its current end if unknown), so the first poll
+    /// brings in whatever was appended since. A file now shorter than that
+    /// is reported as `Replaced` by the first poll.
+    ///
+    /// Tail views drop the file's final newline (`read_tail` joins lines);
+    /// `text_ends_with_newline` false then re-reads that newline on the first
+    /// poll, so appended lines don't run into the last one shown.
+    pub fn start(
+        path: &Path,
+        loaded_len: Option<u64>,
+        text_ends_with_newline: bool,
+    ) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let metadata = file.metadata()?;
+        let mut offset = loaded_len.unwrap_or(metadata.len());
+        if !text_ends_with_newline && offset > 0 && offset <= metadata.len() {
+            let mut last = [0u8; 1];
+            file.seek(SeekFrom::Start(offset - 1))?;
+            file.read_exact(&mut last)?;
+            if last[0] == b'\n' {
+                offset -= 1;
+            }
+        }
+        Ok(Self {
+            offset,
+            identity: file_identity(&metadata),
+        })
+    }
+
+    /// Read what was appended to `path` since the last poll. A missing file
+    /// (rotated away, not recreated yet) counts as unchanged.
+    pub fn poll(&mut self, path: &Path) -> io::Result<FollowUpdate> {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(FollowUpdate::Unchanged),
+            Err(e) => return Err(e),
+        };
+        let metadata = file.metadata()?;
+        let len = metadata.len();
+        if file_identity(&metadata) != self.identity || len < self.offset {
+            return Ok(FollowUpdate::Replaced);
+        }
+        if len == self.offset {
+            return Ok(FollowUpdate::Unchanged);
+        }
+
+        let mut bytes = Vec::new();
+        file.seek(SeekFrom::Start(self.offset))?;
+        file.take((len - self.offset).min(MAX_FOLLOW_READ))
+            .read_to_end(&mut bytes)?;
+
+        let (text, consumed) = match std::str::from_utf8(&bytes) {
+            Ok(text) => (text.to_string(), bytes.len()),
+            // A character cut off by the writer (or by MAX_FOLLOW_READ)
+            // is completed by the next poll
+            Err(e) if e.error_len().is_none() => {
+                let valid = e.valid_up_to();
+                (String::from_utf8_lossy(&bytes[..valid]).to_string(), valid)
+            }
+            Err(_) => (String::from_utf8_lossy(&bytes).to_string(), bytes.len()),
+        };
+        if consumed == 0 {
+            return Ok(FollowUpdate::Unchanged);
+        }
+        self.offset += consumed as u64;
+        Ok(FollowUpdate::Appended(text))
+    }
+}
+
+#[cfg(unix)]
+fn file_identity(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// Without inodes a rotation is only noticed when the new file is smaller
+#[cfg(not(unix))]
+fn file_identity(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn append(path: &Path, bytes: &[u8]) {
+        let mut file = OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(bytes).unwrap();
+    }
+
+    #[test]
+    fn test_poll_returns_appended_text() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, "one\n").unwrap();
+
+        let mut follow = FollowState::start(&path, None, true).unwrap();
+        assert_eq!(follow.poll(&path).unwrap(), FollowUpdate::Unchanged);
+
+        append(&path, b"two\nthree");
+        assert_eq!(
+            follow.poll(&path).unwrap(),
+            FollowUpdate::Appended("two\nthree".to_string())
+        );
+        assert_eq!(follow.offset, 13);
+        assert_eq!(follow.poll(&path).unwrap(), FollowUpdate::Unchanged);
+    }
+
+    #[test]
+    fn test_start_from_loaded_length_returns_lines_appended_since() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, "one\n").unwrap();
+        // Appended after the document was read, before following started
+        append(&path, b"two\n");
+
+        let mut follow = FollowState::start(&path, Some(4), true).unwrap();
+        assert_eq!(
+            follow.poll(&path).unwrap(),
+            FollowUpdate::Appended("two\n".to_string())
+        );
+
+        // A file shorter than what was read has been replaced
+        let mut follow = FollowState::start(&path, Some(100), true).unwrap();
+        assert_eq!(follow.poll(&path).unwrap(), FollowUpdate::Replaced);
+    }
+
+    #[test]
+    fn test_start_rereads_newline_missing_from_tail_view() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, "one\n").unwrap();
+
+        let mut follow = FollowState::start(&path, None, false).unwrap();
+        assert_eq!(follow.offset, 3);
+        // The first poll brings the newline back
+        assert_eq!(
+            follow.poll(&path).unwrap(),
+            FollowUpdate::Appended("\n".to_string())
+        );
+
+        let mut follow = FollowState::start(&path, None, false).unwrap();
+        append(&path, b"two\n");
+        assert_eq!(
+            follow.poll(&path).unwrap(),
+            FollowUpdate::Appended("\ntwo\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_split_character_waits_for_next_poll() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, "").unwrap();
+        let mut follow = FollowState::start(&path, None, true).unwrap();
+
+        let crab = "🦀".as_bytes();
+        append(&path, &crab[..2]);
+        assert_eq!(follow.poll(&path).unwrap(), FollowUpdate::Unchanged);
+        append(&path, &crab[2..]);
+        assert_eq!(
+            follow.poll(&path).unwrap(),
+            FollowUpdate::Appended("🦀".to_string())
+        );
+    }
+
+    #[test]
+    fn test_truncation_is_replaced() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, "a long first line\n").unwrap();
+        let mut follow = FollowState::start(&path, None, true).unwrap();
+
+        fs::write(&path, "new\n").unwrap();
+        assert_eq!(follow.poll(&path).unwrap(), FollowUpdate::Replaced);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rotation_is_replaced() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, "old\n").unwrap();
+        let mut follow = FollowState::start(&path, None, true).unwrap();
+
+        fs::rename(&path, dir.path().join("app.log.1")).unwrap();
+        assert_eq!(follow.poll(&path).unwrap(), FollowUpdate::Unchanged);
+        fs::write(&path, "rotated log\n").unwrap();
+        assert_eq!(follow.poll(&path).unwrap(), FollowUpdate::Replaced);
+    }
+}
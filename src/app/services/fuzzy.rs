@@ -0,0 +1,115 @@
+//! Fuzzy matching for the pickers (Quick Open and friends).
+//!
+//! A query matches a candidate when its characters appear in order,
+//! ignoring case. The score rewards runs of consecutive characters and
+//! characters at the start of a word or path component, and penalises gaps,
+//! so `tabbar` ranks `src/ui/tab_bar.rs` above `src/app/table_bar_layout.rs`.
+//! For paths, a match inside the file name beats one spread over directories.
+
+const SCORE_MATCH: i32 = 16;
+const BONUS_CONSECUTIVE: i32 = 28;
+const BONUS_BOUNDARY: i32 = 20;
+const BONUS_CASE: i32 = 1;
+const PENALTY_GAP: i32 = 1;
+const MAX_GAP_PENALTY: i32 = 12;
+const BONUS_FILE_NAME: i32 = 60;
+
+/// Score `candidate` against `query`; None when it does not match.
+/// An empty query matches everything with score 0.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let chars: Vec<char> = candidate.chars().collect();
+    subsequence_score(&query, &chars)
+}
+
+/// Like `fuzzy_score`, but prefers matches within the last path component.
+pub fn fuzzy_score_path(query: &str, path: &str) -> Option<i32> {
+    let full = fuzzy_score(query, path)?;
+    let name_start = path.rfind(['/', '\\']).map_or(0, |i| i + 1);
+    let in_name = fuzzy_score(query, &path[name_start..])
+        .map(|s| s + BONUS_FILE_NAME)
+        .unwrap_or(i32::MIN);
+    Some(full.max(in_name))
+}
+
+fn is_boundary(chars: &[char], i: usize) -> bool {
+    if i == 0 {
+        return true;
+    }
+    let (prev, cur) = (chars[i - 1], chars[i]);
+    matches!(prev, '/' | '\\' | '_' | '-' | '.' | ' ' | ':')
+        || (prev.is_lowercase() && cur.is_uppercase())
+}
+
+fn subsequence_score(query: &[char], chars: &[char]) -> Option<i32> {
+    let mut score = 0;
+    let mut qi = 0;
+    let mut last_match: Option<usize> = None;
+    for (i, &c) in chars.iter().enumerate() {
+        if qi == query.len() {
+            break;
+        }
+        let q = query[qi];
+        if !c.to_lowercase().eq(q.to_lowercase()) {
+            continue;
+        }
+        score += SCORE_MATCH;
+        if c == q {
+            score += BONUS_CASE;
+        }
+        if is_boundary(chars, i) {
+            score += BONUS_BOUNDARY;
+        }
+        match last_match {
+            Some(last) if last + 1 == i => score += BONUS_CONSECUTIVE,
+            Some(last) => score -= ((i - last - 1) as i32 * PENALTY_GAP).min(MAX_GAP_PENALTY),
+            None => score -= (i as i32 * PENALTY_GAP).min(MAX_GAP_PENALTY),
+        }
+        last_match = Some(i);
+        qi += 1;
+    }
+    if qi < query.len() {
+        return None;
+    }
+    // Among equal matches, shorter candidates win
+    Some(score - chars.len() as i32 / 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsequence_required() {
+        assert!(fuzzy_score("abc", "a_b_c").is_some());
+        assert!(fuzzy_score("ABC", "abc").is_some());
+        assert!(fuzzy_score("acb", "abc").is_none());
+        assert!(fuzzy_score("abcd", "abc").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_consecutive_and_boundaries_rank_higher() {
+        let tight = fuzzy_score("tab", "tab_bar").unwrap();
+        let spread = fuzzy_score("tab", "the_alpha_bar").unwrap();
+        assert!(tight > spread);
+
+        let boundary = fuzzy_score("tb", "tab_bar").unwrap();
+        let inner = fuzzy_score("tb", "attribute").unwrap();
+        assert!(boundary > inner);
+    }
+
+    #[test]
+    fn test_path_prefers_file_name() {
+        let in_name = fuzzy_score_path("menu", "src/ui/menu.rs").unwrap();
+        let in_dirs = fuzzy_score_path("menu", "src/me/nu/other.rs").unwrap();
+        assert!(in_name > in_dirs);
+
+        let tab_bar = fuzzy_score_path("tabbar", "src/ui/tab_bar.rs").unwrap();
+        let other = fuzzy_score_path("tabbar", "src/app/table_bar_layout.rs").unwrap();
+        assert!(tab_bar > other);
+    }
+}
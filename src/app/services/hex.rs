@@ -0,0 +1,690 @@
+//! Hex view of binary files.
+//!
+//! Binary detection for files being opened, the row layout of the hex view
+//! (offset column, hex bytes, ASCII gutter), hex pattern and offset parsing,
+//! byte search, the value inspector, and byte overwrites kept as an overlay
+//! over the memory-mapped file until they are saved in place.
+//!
+//! Byte search sees the file with the overlay applied. It goes through the
+//! file in chunks so the viewer can run it in a background thread, report
+//! its progress and cancel it.
+
+use memchr::memmem;
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Bytes read from the start of a file to decide whether it is binary
+pub const BINARY_SAMPLE_SIZE: usize = 8192;
+
+/// A sample with more than this share of bytes in invalid UTF-8 sequences
+/// is binary (text in a legacy encoding stays well below it)
+const INVALID_UTF8_RATIO: f64 = 0.10;
+
+/// Bytes shown per row
+pub const BYTES_PER_ROW: usize = 16;
+
+/// Bytes searched between progress updates and cancellation checks
+const SEARCH_CHUNK: usize = 4 * 1024 * 1024;
+
+/// Whether a sample from the start of a file looks binary: it contains a
+/// NUL byte, or too much of it is not valid UTF-8.
+pub fn looks_binary(sample: &[u8]) -> bool {
+    if sample.is_empty() {
+        return false;
+    }
+    if sample.contains(&0) {
+        return true;
+    }
+    let mut invalid = 0usize;
+    let mut rest = sample;
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(_) => break,
+            Err(e) => match e.error_len() {
+                Some(len) => {
+                    invalid += len;
+                    rest = &rest[e.valid_up_to() + len..];
+                }
+                // A character cut off by the end of the sample
+                None => break,
+            },
+        }
+    }
+    invalid as f64 / sample.len() as f64 > INVALID_UTF8_RATIO
+}
+
+/// Read the start of `path` and check it with `looks_binary`
+pub fn detect_binary(path: &Path) -> io::Result<bool> {
+    let file = std::fs::File::open(path)?;
+    let mut sample = Vec::with_capacity(BINARY_SAMPLE_SIZE);
+    file.take(BINARY_SAMPLE_SIZE as u64)
+        .read_to_end(&mut sample)?;
+    Ok(looks_binary(&sample))
+}
+
+/// Column layout of a hex view row:
+/// `0000001F  48 65 6C 6C 6F 20 77 6F  72 6C 64 0A 00 00 00 00  |Hello world.....|`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexLayout {
+    /// Hex digits of the offset column
+    pub offset_width: usize,
+}
+
+impl HexLayout {
+    /// Layout wide enough for the offsets of a file of `file_size` bytes
+    pub fn for_size(file_size: u64) -> Self {
+        let digits = format!("{:X}", file_size.saturating_sub(1)).len();
+        Self {
+            offset_width: digits.max(8),
+        }
+    }
+
+    fn hex_start(&self) -> usize {
+        self.offset_width + 2
+    }
+
+    /// Column of the first hex digit of byte `index` in a row
+    pub fn hex_column(&self, index: usize) -> usize {
+        // An extra space splits the row into two groups of eight
+        self.hex_start() + index * 3 + usize::from(index >= BYTES_PER_ROW / 2)
+    }
+
+    /// Column of byte `index` in the ASCII gutter
+    pub fn ascii_column(&self, index: usize) -> usize {
+        self.hex_column(BYTES_PER_ROW) + 2 + index
+    }
+
+    /// Characters in a full row, without the newline
+    pub fn row_len(&self) -> usize {
+        self.ascii_column(BYTES_PER_ROW) + 1
+    }
+
+    /// The byte of the row under `column`, and whether the column is its
+    /// low (second) hex digit. ASCII gutter columns count as the high digit.
+    pub fn byte_at_column(&self, column: usize) -> Option<(usize, bool)> {
+        if column >= self.ascii_column(0) {
+            let index = column - self.ascii_column(0);
+            return (index < BYTES_PER_ROW).then_some((index, false));
+        }
+        (0..BYTES_PER_ROW).find_map(|index| {
+            let start = self.hex_column(index);
+            // The space after a byte belongs to it
+            (start..start + 3)
+                .contains(&column)
+                .then_some((index, column == start + 1))
+        })
+    }
+
+    /// Format one row of up to `BYTES_PER_ROW` bytes starting at `offset`.
+    /// A short last row is padded so its ASCII gutter lines up.
+    pub fn format_row(&self, offset: u64, bytes: &[u8]) -> String {
+        let mut row = String::with_capacity(self.row_len());
+        row.push_str(&format!("{:0width$X}  ", offset, width = self.offset_width));
+        for index in 0..BYTES_PER_ROW {
+            if index == BYTES_PER_ROW / 2 {
+                row.push(' ');
+            }
+            match bytes.get(index) {
+                Some(byte) => row.push_str(&format!("{:02X} ", byte)),
+                None => row.push_str("   "),
+            }
+        }
+        row.push_str(" |");
+        row.extend(bytes.iter().map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        }));
+        row.push('|');
+        row
+    }
+}
+
+/// Parse a hex byte pattern such as `DE AD BE EF`, `deadbeef` or
+/// `0xde,0xad`. Returns None when it is empty or not whole bytes.
+pub fn parse_hex_pattern(text: &str) -> Option<Vec<u8>> {
+    let digits: String = text
+        .split([' ', ',', '\t'])
+        .map(|part| {
+            part.strip_prefix("0x")
+                .or_else(|| part.strip_prefix("0X"))
+                .unwrap_or(part)
+        })
+        .collect();
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Parse an offset typed by the user: decimal (`4096`), `0x1000` or `1000h`
+pub fn parse_offset(text: &str) -> Option<u64> {
+    let text = text.trim();
+    if let Some(hex) = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .or_else(|| text.strip_suffix('h'))
+        .or_else(|| text.strip_suffix('H'))
+    {
+        return u64::from_str_radix(hex, 16).ok();
+    }
+    text.parse().ok()
+}
+
+/// Outcome of `find_bytes` and `rfind_bytes`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteSearch {
+    Found(usize),
+    NotFound,
+    Cancelled,
+}
+
+/// Offset of the first occurrence of `needle` at or after `from` in
+/// `haystack` with `edits` applied. Stores the number of bytes scanned in
+/// `progress` as it goes, and stops when `cancelled` is set.
+pub fn find_bytes(
+    haystack: &[u8],
+    edits: &HexEdits,
+    needle: &[u8],
+    from: usize,
+    progress: &AtomicUsize,
+    cancelled: &AtomicBool,
+) -> ByteSearch {
+    let len = needle.len();
+    if len == 0 || from >= haystack.len() {
+        return ByteSearch::NotFound;
+    }
+    // Matches made by the edits don't show up in the file's bytes
+    let edited = edits
+        .bytes
+        .range(from as u64..)
+        .flat_map(|(&at, _)| (at as usize + 1).saturating_sub(len).max(from)..=at as usize)
+        .find(|&at| edits.matches_at(haystack, needle, at));
+    let end = edited.map_or(haystack.len(), |at| at + len);
+    let finder = memmem::Finder::new(needle);
+    let chunk = SEARCH_CHUNK.max(len);
+    let mut start = from;
+    while start < end {
+        if cancelled.load(Ordering::Relaxed) {
+            return ByteSearch::Cancelled;
+        }
+        let chunk_end = (start + chunk + len - 1).min(end);
+        if let Some(at) = finder
+            .find_iter(&haystack[start..chunk_end])
+            .map(|i| start + i)
+            .find(|&at| edits.matches_at(haystack, needle, at))
+        {
+            return ByteSearch::Found(at);
+        }
+        start += chunk;
+        progress.store(start.min(end) - from, Ordering::Relaxed);
+    }
+    edited.map_or(ByteSearch::NotFound, ByteSearch::Found)
+}
+
+/// Offset of the last occurrence of `needle` starting before `before`, like
+/// `find_bytes`. `progress` counts the bytes scanned back from `before`.
+pub fn rfind_bytes(
+    haystack: &[u8],
+    edits: &HexEdits,
+    needle: &[u8],
+    before: usize,
+    progress: &AtomicUsize,
+    cancelled: &AtomicBool,
+) -> ByteSearch {
+    let len = needle.len();
+    let before = before.min(haystack.len());
+    if len == 0 || before == 0 {
+        return ByteSearch::NotFound;
+    }
+    let edited = edits
+        .bytes
+        .range(..(before + len - 1) as u64)
+        .rev()
+        .flat_map(|(&at, _)| {
+            let at = at as usize;
+            ((at + 1).saturating_sub(len)..=at.min(before - 1)).rev()
+        })
+        .find(|&at| edits.matches_at(haystack, needle, at));
+    let low = edited.map_or(0, |at| at + 1);
+    let finder = memmem::FinderRev::new(needle);
+    let chunk = SEARCH_CHUNK.max(len);
+    let top = (before + len - 1).min(haystack.len());
+    let mut end = top;
+    while end > low {
+        if cancelled.load(Ordering::Relaxed) {
+            return ByteSearch::Cancelled;
+        }
+        let start = end.saturating_sub(chunk).max(low);
+        if let Some(at) = finder
+            .rfind_iter(&haystack[start..end])
+            .map(|i| start + i)
+            .find(|&at| at < before && edits.matches_at(haystack, needle, at))
+        {
+            return ByteSearch::Found(at);
+        }
+        progress.store(top - start, Ordering::Relaxed);
+        if start == low {
+            break;
+        }
+        end = start + len - 1;
+    }
+    edited.map_or(ByteSearch::NotFound, ByteSearch::Found)
+}
+
+/// One row of the value inspector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    pub name: &'static str,
+    pub little_endian: String,
+    pub big_endian: String,
+}
+
+/// Interpret the bytes at the start of `bytes` as the integer and float
+/// types that fit in them
+pub fn interpret(bytes: &[u8]) -> Vec<Interpretation> {
+    fn row<const N: usize>(
+        bytes: &[u8],
+        name: &'static str,
+        format: impl Fn([u8; N], bool) -> String,
+    ) -> Option<Interpretation> {
+        let array: [u8; N] = bytes.get(..N)?.try_into().ok()?;
+        Some(Interpretation {
+            name,
+            little_endian: format(array, true),
+            big_endian: format(array, false),
+        })
+    }
+    macro_rules! number {
+        ($ty:ty) => {
+            |b, le| {
+                if le {
+                    <$ty>::from_le_bytes(b).to_string()
+                } else {
+                    <$ty>::from_be_bytes(b).to_string()
+                }
+            }
+        };
+    }
+    macro_rules! float {
+        ($ty:ty) => {
+            |b, le| {
+                float_text(if le {
+                    <$ty>::from_le_bytes(b)
+                } else {
+                    <$ty>::from_be_bytes(b)
+                })
+            }
+        };
+    }
+
+    [
+        row::<1>(bytes, "u8", number!(u8)),
+        row::<1>(bytes, "i8", number!(i8)),
+        row::<2>(bytes, "u16", number!(u16)),
+        row::<2>(bytes, "i16", number!(i16)),
+        row::<4>(bytes, "u32", number!(u32)),
+        row::<4>(bytes, "i32", number!(i32)),
+        row::<8>(bytes, "u64", number!(u64)),
+        row::<8>(bytes, "i64", number!(i64)),
+        row::<4>(bytes, "f32", float!(f32)),
+        row::<8>(bytes, "f64", float!(f64)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// A float in plain notation, or scientific notation when plain would be
+/// very long (random bytes often decode to huge or tiny floats)
+fn float_text<T>(value: T) -> String
+where
+    T: Copy + Into<f64> + std::fmt::Display + std::fmt::LowerExp,
+{
+    let magnitude = value.into().abs();
+    if magnitude == 0.0 || !magnitude.is_finite() || (1e-6..1e15).contains(&magnitude) {
+        value.to_string()
+    } else {
+        format!("{:e}", value)
+    }
+}
+
+/// Unsaved byte overwrites, by file offset
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HexEdits {
+    bytes: BTreeMap<u64, u8>,
+}
+
+impl HexEdits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Bytes changed since the last save
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The new value of the byte at `offset`, if it was overwritten
+    pub fn get(&self, offset: u64) -> Option<u8> {
+        self.bytes.get(&offset).copied()
+    }
+
+    /// Overwrite the byte at `offset`. Writing back the original value
+    /// drops the edit.
+    pub fn set(&mut self, offset: u64, value: u8, original: u8) {
+        if value == original {
+            self.bytes.remove(&offset);
+        } else {
+            self.bytes.insert(offset, value);
+        }
+    }
+
+    /// Overwrite one hex digit of the byte at `offset` (whose current value
+    /// is `current`) with `digit` (0-15)
+    pub fn set_nibble(&mut self, offset: u64, current: u8, original: u8, low: bool, digit: u8) {
+        let value = if low {
+            (current & 0xF0) | digit
+        } else {
+            (current & 0x0F) | (digit << 4)
+        };
+        self.set(offset, value, original);
+    }
+
+    /// Whether `needle` occurs at `at` in `haystack` with the edits applied
+    fn matches_at(&self, haystack: &[u8], needle: &[u8], at: usize) -> bool {
+        at + needle.len() <= haystack.len()
+            && needle
+                .iter()
+                .zip(at..)
+                .all(|(&b, i)| self.get(i as u64).unwrap_or(haystack[i]) == b)
+    }
+
+    /// Copy the edits falling in `bytes`, which start at file `offset`
+    pub fn apply(&self, offset: u64, bytes: &mut [u8]) {
+        for (&at, &value) in self.bytes.range(offset..offset + bytes.len() as u64) {
+            bytes[(at - offset) as usize] = value;
+        }
+    }
+
+    /// Write the edits into `path` in place, one run of adjacent bytes at a
+    /// time, and forget them. Overwriting never changes the file length, so
+    /// the view's memory map stays valid and shows the new bytes.
+    pub fn save(&mut self, path: &Path) -> io::Result<()> {
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        let len = file.metadata()?.len();
+        if self
+            .bytes
+            .keys()
+            .next_back()
+            .is_some_and(|&last| last >= len)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the file became shorter than the edited bytes",
+            ));
+        }
+
+        let mut runs: Vec<(u64, Vec<u8>)> = Vec::new();
+        for (&offset, &value) in &self.bytes {
+            match runs.last_mut() {
+                Some((start, run)) if *start + run.len() as u64 == offset => run.push(value),
+                _ => runs.push((offset, vec![value])),
+            }
+        }
+        for (offset, run) in runs {
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(&run)?;
+        }
+        file.sync_all()?;
+        self.bytes.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_binary() {
+        assert!(!looks_binary(b"plain text\nwith lines\n"));
+        assert!(!looks_binary("unicode: caf\u{e9} \u{1F980}".as_bytes()));
+        assert!(looks_binary(b"ELF\x00\x01\x02"));
+        // A Latin-1 letter here and there is still text
+        assert!(!looks_binary(
+            b"caf\xe9 au lait, cr\xe8me br\xfbl\xe9e and more text"
+        ));
+        assert!(looks_binary(&[0xff, 0xfe, 0x80, 0x81, b'a', 0x90, 0xc0]));
+        // A character cut off by the end of the sample is not invalid
+        assert!(!looks_binary(&"ab\u{1F980}".as_bytes()[..4]));
+        assert!(!looks_binary(b""));
+    }
+
+    #[test]
+    fn test_format_row_and_columns() {
+        let layout = HexLayout::for_size(0x100);
+        let row = layout.format_row(0x10, b"Hello\x00world\n\xff!!!");
+        assert_eq!(
+            row,
+            "00000010  48 65 6C 6C 6F 00 77 6F  72 6C 64 0A FF 21 21 21  |Hello.world..!!!|"
+        );
+        assert_eq!(row.len(), layout.row_len());
+        assert_eq!(&row[layout.hex_column(8)..layout.hex_column(8) + 2], "72");
+        assert_eq!(
+            &row[layout.ascii_column(4)..layout.ascii_column(4) + 1],
+            "o"
+        );
+
+        // A short last row keeps the gutter aligned
+        let short = layout.format_row(0x20, b"AB");
+        assert_eq!(&short[layout.ascii_column(0)..], "AB|");
+    }
+
+    #[test]
+    fn test_offset_column_grows_for_big_files() {
+        assert_eq!(HexLayout::for_size(0).offset_width, 8);
+        assert_eq!(HexLayout::for_size(0x1_0000_0000).offset_width, 8);
+        assert_eq!(HexLayout::for_size(0x1_0000_0001).offset_width, 9);
+    }
+
+    #[test]
+    fn test_byte_at_column() {
+        let layout = HexLayout::for_size(0x100);
+        assert_eq!(layout.byte_at_column(0), None);
+        assert_eq!(
+            layout.byte_at_column(layout.hex_column(0)),
+            Some((0, false))
+        );
+        assert_eq!(
+            layout.byte_at_column(layout.hex_column(0) + 1),
+            Some((0, true))
+        );
+        assert_eq!(
+            layout.byte_at_column(layout.hex_column(9) + 1),
+            Some((9, true))
+        );
+        assert_eq!(
+            layout.byte_at_column(layout.ascii_column(15)),
+            Some((15, false))
+        );
+        assert_eq!(layout.byte_at_column(layout.ascii_column(16)), None);
+    }
+
+    #[test]
+    fn test_parse_hex_pattern_and_offset() {
+        assert_eq!(
+            parse_hex_pattern("DE AD be ef"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            parse_hex_pattern("deadbeef"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(parse_hex_pattern("0x0a,0x0D"), Some(vec![0x0a, 0x0d]));
+        assert_eq!(parse_hex_pattern("abc"), None);
+        assert_eq!(parse_hex_pattern("zz"), None);
+        assert_eq!(parse_hex_pattern(" "), None);
+
+        assert_eq!(parse_offset("4096"), Some(4096));
+        assert_eq!(parse_offset("0x1000"), Some(4096));
+        assert_eq!(parse_offset("1000h"), Some(4096));
+        assert_eq!(parse_offset("x"), None);
+    }
+
+    fn find(data: &[u8], edits: &HexEdits, needle: &[u8], from: usize) -> ByteSearch {
+        find_bytes(
+            data,
+            edits,
+            needle,
+            from,
+            &AtomicUsize::new(0),
+            &AtomicBool::new(false),
+        )
+    }
+
+    fn rfind(data: &[u8], edits: &HexEdits, needle: &[u8], before: usize) -> ByteSearch {
+        rfind_bytes(
+            data,
+            edits,
+            needle,
+            before,
+            &AtomicUsize::new(0),
+            &AtomicBool::new(false),
+        )
+    }
+
+    #[test]
+    fn test_find_and_rfind_bytes() {
+        let data = b"\x00\x01\x02\x00\x01\x02";
+        let none = HexEdits::new();
+        assert_eq!(find(data, &none, &[1, 2], 0), ByteSearch::Found(1));
+        assert_eq!(find(data, &none, &[1, 2], 2), ByteSearch::Found(4));
+        assert_eq!(find(data, &none, &[1, 2], 5), ByteSearch::NotFound);
+        assert_eq!(rfind(data, &none, &[1, 2], 4), ByteSearch::Found(1));
+        assert_eq!(rfind(data, &none, &[1, 2], 6), ByteSearch::Found(4));
+        assert_eq!(rfind(data, &none, &[1, 2], 1), ByteSearch::NotFound);
+    }
+
+    #[test]
+    fn test_search_sees_unsaved_edits() {
+        let data = b"\x00\x01\x02\x00\x01\x02\x00";
+        let mut edits = HexEdits::new();
+        // 00 01 02 00 01 02 00 -> 00 01 FF 00 01 02 AA
+        edits.set(2, 0xFF, 0x02);
+        edits.set(6, 0xAA, 0x00);
+        // The edit hides the first on-disk match
+        assert_eq!(find(data, &edits, &[1, 2], 0), ByteSearch::Found(4));
+        assert_eq!(rfind(data, &edits, &[1, 2], 4), ByteSearch::NotFound);
+        // Typed bytes are found, alone or next to on-disk ones
+        assert_eq!(find(data, &edits, &[0x01, 0xFF], 0), ByteSearch::Found(1));
+        assert_eq!(find(data, &edits, &[0x02, 0xAA], 0), ByteSearch::Found(5));
+        assert_eq!(rfind(data, &edits, &[0xFF], 7), ByteSearch::Found(2));
+        assert_eq!(rfind(data, &edits, &[0x02, 0xAA], 7), ByteSearch::Found(5));
+        assert_eq!(rfind(data, &edits, &[0x02, 0xAA], 5), ByteSearch::NotFound);
+        // The earliest match wins over a later edited one, and vice versa
+        assert_eq!(find(data, &edits, &[0x00], 4), ByteSearch::NotFound);
+        assert_eq!(find(data, &edits, &[0x00], 1), ByteSearch::Found(3));
+        assert_eq!(rfind(data, &edits, &[0x01], 7), ByteSearch::Found(4));
+    }
+
+    #[test]
+    fn test_search_across_chunks_reports_progress_and_cancels() {
+        let mut data = vec![0u8; SEARCH_CHUNK * 2 + 10];
+        // A match straddling the first chunk boundary
+        data[SEARCH_CHUNK - 1] = 0xDE;
+        data[SEARCH_CHUNK] = 0xAD;
+        let none = HexEdits::new();
+        let progress = AtomicUsize::new(0);
+        let go = AtomicBool::new(false);
+        let found = find_bytes(&data, &none, &[0xDE, 0xAD], 0, &progress, &go);
+        assert_eq!(found, ByteSearch::Found(SEARCH_CHUNK - 1));
+        let found = rfind_bytes(&data, &none, &[0xDE, 0xAD], data.len(), &progress, &go);
+        assert_eq!(found, ByteSearch::Found(SEARCH_CHUNK - 1));
+        // The chunk at the end was scanned before the one with the match
+        assert_eq!(progress.load(Ordering::Relaxed), SEARCH_CHUNK);
+
+        let missing = find_bytes(&data, &none, &[0xBE], 0, &progress, &go);
+        assert_eq!(missing, ByteSearch::NotFound);
+        assert_eq!(progress.load(Ordering::Relaxed), data.len());
+
+        let stop = AtomicBool::new(true);
+        let cancelled = find_bytes(&data, &none, &[0xBE], 0, &progress, &stop);
+        assert_eq!(cancelled, ByteSearch::Cancelled);
+    }
+
+    #[test]
+    fn test_interpret() {
+        let values = interpret(&[0x01, 0x00, 0x00, 0x80]);
+        let find = |name| values.iter().find(|v| v.name == name).unwrap();
+        assert_eq!(find("u8").little_endian, "1");
+        assert_eq!(find("u16").little_endian, "1");
+        assert_eq!(find("u16").big_endian, "256");
+        assert_eq!(find("i32").little_endian, "-2147483647");
+        assert_eq!(
+            find("f32").big_endian,
+            format!("{:e}", f32::from_be_bytes([1, 0, 0, 0x80]))
+        );
+        let floats = interpret(&1.5f32.to_le_bytes());
+        assert_eq!(floats.last().unwrap().name, "f32");
+        assert_eq!(floats.last().unwrap().little_endian, "1.5");
+        // Eight-byte types need eight bytes
+        assert!(values.iter().all(|v| v.name != "u64" && v.name != "f64"));
+        assert!(interpret(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_edits_overlay_and_save() {
+        use tempfile::NamedTempFile;
+
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), [0u8; 8]).unwrap();
+
+        let mut edits = HexEdits::new();
+        edits.set_nibble(2, 0x00, 0x00, false, 0xA);
+        edits.set_nibble(2, 0xA0, 0x00, true, 0x5);
+        edits.set(3, 0xFF, 0x00);
+        edits.set(6, 0x01, 0x00);
+        // Writing back the original value is no edit
+        edits.set(7, 0x01, 0x00);
+        edits.set(7, 0x00, 0x00);
+        assert_eq!(edits.len(), 3);
+        assert_eq!(edits.get(2), Some(0xA5));
+        assert_eq!(edits.get(7), None);
+
+        let mut view = [0u8; 4];
+        edits.apply(2, &mut view);
+        assert_eq!(view, [0xA5, 0xFF, 0, 0]);
+
+        edits.save(file.path()).unwrap();
+        assert!(edits.is_empty());
+        assert_eq!(
+            std::fs::read(file.path()).unwrap(),
+            [0, 0, 0xA5, 0xFF, 0, 0, 1, 0]
+        );
+    }
+
+    #[test]
+    fn test_save_rejects_edits_past_end() {
+        use tempfile::NamedTempFile;
+
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), [0u8; 2]).unwrap();
+        let mut edits = HexEdits::new();
+        edits.set(5, 1, 0);
+        assert!(edits.save(file.path()).is_err());
+        assert_eq!(std::fs::read(file.path()).unwrap(), [0, 0]);
+    }
+}
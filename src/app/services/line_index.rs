@@ -0,0 +1,493 @@
+//! Line index and line search for the read-only large-file viewer.
+//!
+//! The viewer pages through a memory-mapped file that can be many gigabytes
+//! long, so it never splits the whole file into lines. A `LineIndex` keeps
+//! the byte offset of every `LINE_INDEX_STRIDE`-th line start. It is built
+//! once in a background thread, after which finding a line (or the line of
+//! an offset) is a binary search plus a scan of at most one stride of lines.
+//!
+//! Searching works line by line with a `LinePattern` (a plain or regex query,
+//! optionally case-insensitive). `scan_lines`, `find_next` and
+//! `find_previous` walk a bounded slice of the file per call, so the viewer
+//! can stream results and filtered pages and look for the next match from
+//! its event loop without blocking.
+
+use memchr::{memchr, memmem, memrchr};
+use regex_lite::{Regex, RegexBuilder};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Every this many lines the index stores a line start
+pub const LINE_INDEX_STRIDE: usize = 1024;
+
+/// Bytes scanned between progress updates and cancellation checks
+const INDEX_CHUNK: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Byte offsets of lines 1, 1 + STRIDE, 1 + 2 * STRIDE, ...
+    checkpoints: Vec<usize>,
+    /// Newlines in the file
+    newlines: usize,
+    len: usize,
+    ends_with_newline: bool,
+}
+
+impl LineIndex {
+    /// Index `bytes`, storing the scanned byte count in `progress` as it
+    /// goes. Returns None when `cancelled` is set before the end.
+    pub fn build(bytes: &[u8], progress: &AtomicUsize, cancelled: &AtomicBool) -> Option<Self> {
+        let mut checkpoints = vec![0];
+        let mut newlines = 0usize;
+        for (chunk_index, chunk) in bytes.chunks(INDEX_CHUNK).enumerate() {
+            if cancelled.load(Ordering::Relaxed) {
+                return None;
+            }
+            let base = chunk_index * INDEX_CHUNK;
+            for (i, &byte) in chunk.iter().enumerate() {
+                if byte == b'\n' {
+                    newlines += 1;
+                    if newlines.is_multiple_of(LINE_INDEX_STRIDE) {
+                        checkpoints.push(base + i + 1);
+                    }
+                }
+            }
+            progress.store(base + chunk.len(), Ordering::Relaxed);
+        }
+        Some(Self {
+            checkpoints,
+            newlines,
+            len: bytes.len(),
+            ends_with_newline: bytes.last() == Some(&b'\n'),
+        })
+    }
+
+    /// Lines in the file. A final newline ends the last line rather than
+    /// starting an empty one.
+    pub fn total_lines(&self) -> usize {
+        if self.len == 0 {
+            0
+        } else if self.ends_with_newline {
+            self.newlines
+        } else {
+            self.newlines + 1
+        }
+    }
+
+    /// Byte offset where 1-based `line` starts, or the file length past
+    /// the last line
+    pub fn line_offset(&self, bytes: &[u8], line: usize) -> usize {
+        if line <= 1 {
+            return 0;
+        }
+        let checkpoint = ((line - 1) / LINE_INDEX_STRIDE).min(self.checkpoints.len() - 1);
+        let from_line = checkpoint * LINE_INDEX_STRIDE + 1;
+        scan_line_offset(bytes, self.checkpoints[checkpoint], from_line, line)
+    }
+
+    /// The 1-based line containing byte `offset`
+    pub fn line_at(&self, bytes: &[u8], offset: usize) -> usize {
+        let offset = offset.min(bytes.len());
+        let checkpoint = match self.checkpoints.binary_search(&offset) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        };
+        let start = self.checkpoints[checkpoint];
+        checkpoint * LINE_INDEX_STRIDE + 1 + count_newlines(&bytes[start..offset])
+    }
+}
+
+/// Newlines in `bytes`
+pub fn count_newlines(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&b| b == b'\n').count()
+}
+
+/// Byte offset of 1-based `target_line`, scanning forward from `offset`
+/// where line `from_line` starts. Returns the file length past the end.
+pub fn scan_line_offset(
+    bytes: &[u8],
+    offset: usize,
+    from_line: usize,
+    target_line: usize,
+) -> usize {
+    let mut line = from_line;
+    let mut pos = offset;
+    while line < target_line {
+        match bytes[pos..].iter().position(|&b| b == b'\n') {
+            Some(i) => pos += i + 1,
+            None => return bytes.len(),
+        }
+        line += 1;
+    }
+    pos
+}
+
+/// A search query matched against one line at a time
+#[derive(Debug, Clone)]
+pub struct LinePattern {
+    matcher: Matcher,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    /// A plain query, compared byte for byte
+    Literal(Box<memmem::Finder<'static>>),
+    /// A plain query, compared ignoring ASCII case like the regex engine
+    Caseless(Vec<u8>),
+    /// Lines are matched as UTF-8, lossily for invalid lines
+    Regex(Regex),
+}
+
+impl LinePattern {
+    /// Build a pattern from a query typed by the user. Plain queries are
+    /// matched literally on the raw bytes. Returns the regex error for an
+    /// invalid pattern.
+    pub fn new(query: &str, use_regex: bool, case_sensitive: bool) -> Result<Self, String> {
+        if query.is_empty() {
+            return Err("Empty search".to_string());
+        }
+        let matcher = if !use_regex && case_sensitive {
+            Matcher::Literal(Box::new(memmem::Finder::new(query.as_bytes()).into_owned()))
+        } else if !use_regex {
+            Matcher::Caseless(query.as_bytes().to_vec())
+        } else {
+            let regex = RegexBuilder::new(query)
+                .case_insensitive(!case_sensitive)
+                .build()
+                .map_err(|e| e.to_string())?;
+            Matcher::Regex(regex)
+        };
+        Ok(Self { matcher })
+    }
+
+    /// First match in `line` starting at or after byte `from`, as a byte
+    /// range within the line. Regexes match invalid UTF-8 lossily, which can
+    /// shift the reported range on such lines.
+    pub fn find_in_line(&self, line: &[u8], from: usize) -> Option<(usize, usize)> {
+        let rest = line.get(from..)?;
+        match &self.matcher {
+            Matcher::Literal(finder) => finder
+                .find(rest)
+                .map(|i| (from + i, from + i + finder.needle().len())),
+            Matcher::Caseless(query) => rest
+                .windows(query.len())
+                .position(|w| w.eq_ignore_ascii_case(query))
+                .map(|i| (from + i, from + i + query.len())),
+            Matcher::Regex(regex) => regex
+                .find_iter(&String::from_utf8_lossy(line))
+                .find(|m| m.start() >= from)
+                .map(|m| (m.start(), m.end().min(line.len()))),
+        }
+    }
+
+    /// Last match in `line` starting before byte `before`
+    pub fn rfind_in_line(&self, line: &[u8], before: usize) -> Option<(usize, usize)> {
+        match &self.matcher {
+            Matcher::Literal(finder) => {
+                let needle = finder.needle();
+                let end = (before + needle.len()).saturating_sub(1).min(line.len());
+                memmem::rfind(&line[..end], needle).map(|i| (i, i + needle.len()))
+            }
+            Matcher::Caseless(query) => {
+                let end = (before + query.len()).saturating_sub(1).min(line.len());
+                line[..end]
+                    .windows(query.len())
+                    .rposition(|w| w.eq_ignore_ascii_case(query))
+                    .map(|i| (i, i + query.len()))
+            }
+            Matcher::Regex(regex) => regex
+                .find_iter(&String::from_utf8_lossy(line))
+                .take_while(|m| m.start() < before)
+                .last()
+                .map(|m| (m.start(), m.end().min(line.len()))),
+        }
+    }
+
+    pub fn is_match(&self, line: &[u8]) -> bool {
+        match &self.matcher {
+            Matcher::Regex(regex) => regex.is_match(&String::from_utf8_lossy(line)),
+            _ => self.find_in_line(line, 0).is_some(),
+        }
+    }
+}
+
+/// A line that matched a `LinePattern`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineMatch {
+    /// 1-based line number in the file
+    pub line: usize,
+    /// Byte offset of the line start
+    pub offset: usize,
+    /// The line without its newline
+    pub text: String,
+}
+
+/// Result of one bounded `scan_lines` step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineScan {
+    pub matches: Vec<LineMatch>,
+    /// Where the next step continues: a line start and its line number
+    pub next_offset: usize,
+    pub next_line: usize,
+    /// The end of the file was reached
+    pub done: bool,
+}
+
+/// Collect the lines matching `pattern`, starting at `offset` where line
+/// `line` starts. Stops at a line boundary once `max_bytes` have been
+/// scanned or `max_matches` lines matched.
+pub fn scan_lines(
+    bytes: &[u8],
+    pattern: &LinePattern,
+    offset: usize,
+    line: usize,
+    max_bytes: usize,
+    max_matches: usize,
+) -> LineScan {
+    let mut matches = Vec::new();
+    let mut pos = offset;
+    let mut line = line;
+    while pos < bytes.len() && pos - offset < max_bytes && matches.len() < max_matches {
+        let end = bytes[pos..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |i| pos + i);
+        let text = strip_cr(&bytes[pos..end]);
+        if pattern.is_match(text) {
+            matches.push(LineMatch {
+                line,
+                offset: pos,
+                text: String::from_utf8_lossy(text).into_owned(),
+            });
+        }
+        pos = (end + 1).min(bytes.len());
+        line += 1;
+    }
+    LineScan {
+        matches,
+        next_offset: pos,
+        next_line: line,
+        done: pos >= bytes.len(),
+    }
+}
+
+/// Result of one bounded `find_next` or `find_previous` step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindStep {
+    /// A match, as a byte range in the file
+    Found(usize, usize),
+    /// No match in the bytes scanned so far; the next step continues from
+    /// this position
+    Continue(usize),
+    /// No match up to the end (or the start) of the file
+    NotFound,
+}
+
+/// Look for the next match at or after byte `from`. Stops at a line
+/// boundary once `max_bytes` have been scanned.
+pub fn find_next(bytes: &[u8], pattern: &LinePattern, from: usize, max_bytes: usize) -> FindStep {
+    let mut start = line_start_of(bytes, from);
+    let mut skip = from - start;
+    let stop = start.saturating_add(max_bytes);
+    while start < bytes.len() {
+        if start >= stop {
+            return FindStep::Continue(start);
+        }
+        let end = memchr(b'\n', &bytes[start..]).map_or(bytes.len(), |i| start + i);
+        if let Some((s, e)) = pattern.find_in_line(&bytes[start..end], skip) {
+            return FindStep::Found(start + s, start + e);
+        }
+        start = end + 1;
+        skip = 0;
+    }
+    FindStep::NotFound
+}
+
+/// Look for the last match starting before byte `before`. Stops at a line
+/// boundary once `max_bytes` have been scanned.
+pub fn find_previous(
+    bytes: &[u8],
+    pattern: &LinePattern,
+    before: usize,
+    max_bytes: usize,
+) -> FindStep {
+    let before = before.min(bytes.len());
+    let mut end = memchr(b'\n', &bytes[before..]).map_or(bytes.len(), |i| before + i);
+    let stop = end.saturating_sub(max_bytes);
+    let mut limit = before;
+    loop {
+        let start = line_start_of(bytes, end);
+        if let Some((s, e)) = pattern.rfind_in_line(&bytes[start..end], limit - start) {
+            return FindStep::Found(start + s, start + e);
+        }
+        if start == 0 {
+            return FindStep::NotFound;
+        }
+        end = start - 1;
+        limit = end;
+        if start <= stop {
+            return FindStep::Continue(end);
+        }
+    }
+}
+
+/// Offset of the start of the line containing byte `pos`
+fn line_start_of(bytes: &[u8], pos: usize) -> usize {
+    let pos = pos.min(bytes.len());
+    memrchr(b'\n', &bytes[..pos]).map_or(0, |i| i + 1)
+}
+
+fn strip_cr(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(bytes: &[u8]) -> LineIndex {
+        LineIndex::build(bytes, &AtomicUsize::new(0), &AtomicBool::new(false)).unwrap()
+    }
+
+    /// "line 1\nline 2\n..." with `n` lines
+    fn numbered_lines(n: usize) -> Vec<u8> {
+        (1..=n)
+            .map(|i| format!("line {}\n", i))
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    #[test]
+    fn test_index_finds_lines_across_checkpoints() {
+        let bytes = numbered_lines(LINE_INDEX_STRIDE * 3 + 7);
+        let index = index(&bytes);
+        assert_eq!(index.total_lines(), LINE_INDEX_STRIDE * 3 + 7);
+        for line in [1, 2, LINE_INDEX_STRIDE, LINE_INDEX_STRIDE + 1, 3000, 3078] {
+            let offset = index.line_offset(&bytes, line);
+            assert_eq!(offset, scan_line_offset(&bytes, 0, 1, line));
+            assert!(bytes[offset..].starts_with(format!("line {}\n", line).as_bytes()));
+            assert_eq!(index.line_at(&bytes, offset), line);
+            assert_eq!(index.line_at(&bytes, offset + 3), line);
+        }
+        assert_eq!(index.line_offset(&bytes, 100_000), bytes.len());
+    }
+
+    #[test]
+    fn test_total_lines_with_and_without_final_newline() {
+        assert_eq!(index(b"a\nb\n").total_lines(), 2);
+        assert_eq!(index(b"a\nb").total_lines(), 2);
+        assert_eq!(index(b"a").total_lines(), 1);
+        assert_eq!(index(b"").total_lines(), 0);
+        // A final newline that lands exactly on a checkpoint
+        let bytes = numbered_lines(LINE_INDEX_STRIDE);
+        assert_eq!(index(&bytes).total_lines(), LINE_INDEX_STRIDE);
+    }
+
+    #[test]
+    fn test_build_reports_progress_and_can_be_cancelled() {
+        let bytes = numbered_lines(10);
+        let progress = AtomicUsize::new(0);
+        assert!(LineIndex::build(&bytes, &progress, &AtomicBool::new(false)).is_some());
+        assert_eq!(progress.load(Ordering::Relaxed), bytes.len());
+        assert!(LineIndex::build(&bytes, &progress, &AtomicBool::new(true)).is_none());
+    }
+
+    #[test]
+    fn test_pattern_plain_regex_and_case() {
+        let plain = LinePattern::new("a.b", false, true).unwrap();
+        assert!(plain.is_match(b"xa.by"));
+        assert!(!plain.is_match(b"axb"));
+
+        let regex = LinePattern::new(r"^err(or)?\b", true, true).unwrap();
+        assert!(regex.is_match(b"error: disk full"));
+        assert!(!regex.is_match(b"an error"));
+
+        let any_case = LinePattern::new("WARN", false, false).unwrap();
+        assert!(any_case.is_match(b"warn: low memory"));
+        assert!(LinePattern::new("(", true, true).is_err());
+        assert!(LinePattern::new("", false, true).is_err());
+    }
+
+    #[test]
+    fn test_scan_lines_streams_matches_with_line_numbers() {
+        let bytes = b"info a\nERROR b\ninfo c\nerror d\r\nerror e";
+        let pattern = LinePattern::new("error", false, false).unwrap();
+        let all = scan_lines(bytes, &pattern, 0, 1, usize::MAX, usize::MAX);
+        let lines: Vec<_> = all
+            .matches
+            .iter()
+            .map(|m| (m.line, m.text.as_str()))
+            .collect();
+        assert_eq!(lines, vec![(2, "ERROR b"), (4, "error d"), (5, "error e")]);
+        assert!(all.done);
+
+        // Bounded steps pick up where the previous one stopped
+        let first = scan_lines(bytes, &pattern, 0, 1, 1, usize::MAX);
+        assert_eq!(
+            (first.next_offset, first.next_line, first.done),
+            (7, 2, false)
+        );
+        let second = scan_lines(bytes, &pattern, first.next_offset, first.next_line, 100, 1);
+        assert_eq!(second.matches[0].offset, 7);
+        assert_eq!(second.next_line, 3);
+    }
+
+    #[test]
+    fn test_pattern_finds_ranges_in_line() {
+        let line = b"Foo bar foo FOO";
+        for (query, case_sensitive, first, last) in [
+            ("foo", true, (8, 11), (8, 11)),
+            ("foo", false, (0, 3), (12, 15)),
+            ("f.o", false, (0, 3), (12, 15)),
+        ] {
+            let pattern = LinePattern::new(query, query.contains('.'), case_sensitive).unwrap();
+            assert_eq!(pattern.find_in_line(line, 0), Some(first), "{}", query);
+            assert_eq!(pattern.rfind_in_line(line, line.len()), Some(last));
+        }
+        let pattern = LinePattern::new("foo", false, false).unwrap();
+        assert_eq!(pattern.find_in_line(line, 1), Some((8, 11)));
+        assert_eq!(pattern.rfind_in_line(line, 8), Some((0, 3)));
+        assert_eq!(pattern.rfind_in_line(line, 0), None);
+        assert_eq!(pattern.find_in_line(line, 100), None);
+
+        // Plain queries match raw bytes, including in invalid UTF-8
+        let pattern = LinePattern::new("ab", false, true).unwrap();
+        assert_eq!(pattern.find_in_line(b"\xff\xfeab", 0), Some((2, 4)));
+        let pattern = LinePattern::new("ÄB", false, false).unwrap();
+        assert!(pattern.is_match("xÄb".as_bytes()));
+    }
+
+    #[test]
+    fn test_find_next_and_previous() {
+        let bytes = b"foo bar foo\nbar\nfoo";
+        let pattern = LinePattern::new("foo", false, true).unwrap();
+        let next = |from| find_next(bytes, &pattern, from, usize::MAX);
+        assert_eq!(next(0), FindStep::Found(0, 3));
+        assert_eq!(next(1), FindStep::Found(8, 11));
+        assert_eq!(next(9), FindStep::Found(16, 19));
+        assert_eq!(next(17), FindStep::NotFound);
+
+        let previous = |before| find_previous(bytes, &pattern, before, usize::MAX);
+        assert_eq!(previous(16), FindStep::Found(8, 11));
+        assert_eq!(previous(8), FindStep::Found(0, 3));
+        assert_eq!(previous(0), FindStep::NotFound);
+        assert_eq!(previous(bytes.len()), FindStep::Found(16, 19));
+    }
+
+    #[test]
+    fn test_find_steps_continue_where_they_stopped() {
+        let bytes = b"foo\nbar\nbar\nfoo";
+        let pattern = LinePattern::new("foo", false, true).unwrap();
+        assert_eq!(find_next(bytes, &pattern, 1, 1), FindStep::Continue(4));
+        assert_eq!(find_next(bytes, &pattern, 4, 1), FindStep::Continue(8));
+        assert_eq!(find_next(bytes, &pattern, 8, 1), FindStep::Continue(12));
+        assert_eq!(find_next(bytes, &pattern, 12, 1), FindStep::Found(12, 15));
+
+        assert_eq!(
+            find_previous(bytes, &pattern, 12, 1),
+            FindStep::Continue(11)
+        );
+        assert_eq!(find_previous(bytes, &pattern, 11, 1), FindStep::Continue(7));
+        assert_eq!(find_previous(bytes, &pattern, 7, 1), FindStep::Continue(3));
+        assert_eq!(find_previous(bytes, &pattern, 3, 1), FindStep::Found(0, 3));
+    }
+}
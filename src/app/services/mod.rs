@@ -1,21 +1,36 @@
 //! Services layer - business operations and utilities.
 //!
 //! This module contains business logic and operations:
-//! - Session persistence
+//! - Session persistence and portable session files
+//! - Line bookmarks
+//! - Following appended file content
+//! - Line index and search for the large-file viewer
+//! - Hex view of binary files
+//! - Recently used files
+//! - Project file listing and fuzzy matching
 //! - Update checking
 //! - Text operations
 //! - Syntax highlighting
 //! - Plugin registry
 
+pub mod bookmarks;
 pub mod editor_context;
+pub mod file_index;
 pub mod file_size;
+pub mod follow;
 pub mod font_catalog;
+pub mod fuzzy;
+pub mod hex;
+pub mod line_index;
 pub mod plugin_registry;
 pub mod plugin_update_checker;
 pub mod plugin_verify;
+pub mod portable_session;
+pub mod recent_files;
 pub mod session;
 pub mod shortcut_registry;
 pub mod syntax;
+pub mod tasks;
 pub mod terminal;
 pub mod text_ops;
 pub mod updater;
@@ -9,6 +9,7 @@ use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use sha2::{Digest, Sha256};
 
 use crate::app::infrastructure::error::AppError;
+use crate::app::plugins::hooks::HIGH_FREQUENCY_HOOKS;
 
 /// FerrisPad's official plugin signing public key (embedded at compile time).
 /// This key is used to verify signatures on plugins from the official registry.
@@ -336,18 +337,23 @@ fn contains_load_call(line: &str) -> bool {
     false
 }
 
-/// Check whether a plugin's init.lua registers an `on_text_changed` hook.
+/// Find the high-frequency hooks (`on_text_changed`, `on_cursor_moved`,
+/// `on_selection_changed`) a plugin mentions.
 ///
 /// This is a simple string-based heuristic used to flag plugins that respond
-/// to every keystroke, which may have performance implications.
+/// to every keystroke or cursor move, which may have performance implications.
 ///
 /// # Arguments
-/// * `init_lua` - The content of the plugin's init.lua file
+/// * `source` - The content of the plugin's init.lua or plugin.toml
 ///
 /// # Returns
-/// `true` if the source contains `on_text_changed`
-pub fn detects_text_change_hook(init_lua: &str) -> bool {
-    init_lua.contains("on_text_changed")
+/// The matching hook names, in `HIGH_FREQUENCY_HOOKS` order
+pub fn detect_high_frequency_hooks(source: &str) -> Vec<String> {
+    HIGH_FREQUENCY_HOOKS
+        .iter()
+        .filter(|name| source.contains(*name))
+        .map(|name| name.to_string())
+        .collect()
 }
 
 #[cfg(test)]
@@ -585,14 +591,20 @@ return M
     }
 
     #[test]
-    fn test_detects_text_change_hook_true() {
+    fn test_detect_high_frequency_hooks_found() {
         let source = r#"function M.on_text_changed(doc) end"#;
-        assert!(detects_text_change_hook(source));
+        assert_eq!(detect_high_frequency_hooks(source), vec!["on_text_changed"]);
+
+        let manifest = r#"hooks = ["on_selection_changed", "on_cursor_moved"]"#;
+        assert_eq!(
+            detect_high_frequency_hooks(manifest),
+            vec!["on_cursor_moved", "on_selection_changed"]
+        );
     }
 
     #[test]
-    fn test_detects_text_change_hook_false() {
+    fn test_detect_high_frequency_hooks_none() {
         let source = r#"function M.on_document_open(doc) end"#;
-        assert!(!detects_text_change_hook(source));
+        assert!(detect_high_frequency_hooks(source).is_empty());
     }
 }
@@ -0,0 +1,399 @@
+//! Portable session files, for sharing a session or checking it into a
+//! repository.
+//!
+//! A portable session lists saved files by their path relative to a project
+//! root (always with `/` separators), along with their cursor, scroll
+//! position, groups and split layout. Unsaved changes, temp files and
+//! absolute paths are never written. A session stored at
+//! `<root>/.ferrispad/session.json` is picked up when FerrisPad starts
+//! inside that project.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use super::session::{
+    self, CURRENT_SESSION_VERSION, DocumentSession, EditorLayoutSession, GroupSession, PaneSession,
+    SessionData,
+};
+use crate::app::infrastructure::error::AppError;
+
+/// Directory holding the project session, relative to the project root
+pub const PROJECT_SESSION_DIR: &str = ".ferrispad";
+/// File name of the project session inside `PROJECT_SESSION_DIR`
+pub const PROJECT_SESSION_FILE: &str = "session.json";
+
+const PORTABLE_SESSION_VERSION: u32 = 1;
+
+fn default_version() -> u32 {
+    PORTABLE_SESSION_VERSION
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PortableSession {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub active_index: usize,
+    pub documents: Vec<PortableDocument>,
+    #[serde(default)]
+    pub groups: Vec<GroupSession>,
+    #[serde(default)]
+    pub editor_layout: Option<EditorLayoutSession>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PortableDocument {
+    /// Path relative to the project root, `/`-separated
+    pub path: String,
+    #[serde(default)]
+    pub cursor_position: i32,
+    #[serde(default)]
+    pub top_line: i32,
+    #[serde(default)]
+    pub horiz_offset: i32,
+    #[serde(default)]
+    pub selection: Option<(i32, i32)>,
+    #[serde(default)]
+    pub bookmarks: Vec<u32>,
+    #[serde(default)]
+    pub group_index: Option<usize>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Make `data` portable with paths relative to `root`. Files outside the
+/// root cannot be expressed and are left out; returns how many were.
+pub fn to_portable(data: &SessionData, root: &Path) -> (PortableSession, usize) {
+    let mut documents = Vec::new();
+    // New index of each document of `data` (None if left out)
+    let mut new_index = Vec::with_capacity(data.documents.len());
+    for doc in &data.documents {
+        let relative = doc
+            .file_path
+            .as_deref()
+            .and_then(|p| relative_path(Path::new(p), root));
+        match relative {
+            Some(path) => {
+                new_index.push(Some(documents.len()));
+                documents.push(PortableDocument {
+                    path,
+                    cursor_position: doc.cursor_position,
+                    top_line: doc.top_line,
+                    horiz_offset: doc.horiz_offset,
+                    selection: doc.selection,
+                    bookmarks: doc.bookmarks.clone(),
+                    group_index: doc.group_index,
+                    pinned: doc.pinned,
+                });
+            }
+            None => new_index.push(None),
+        }
+    }
+    let left_out = data.documents.len() - documents.len();
+
+    let portable = PortableSession {
+        version: PORTABLE_SESSION_VERSION,
+        active_index: remap(&new_index, data.active_index).unwrap_or(0),
+        documents,
+        groups: clone_groups(&data.groups),
+        editor_layout: data
+            .editor_layout
+            .as_ref()
+            .map(|layout| remap_layout(layout, &new_index)),
+    };
+    (portable, left_out)
+}
+
+/// Turn a portable session back into session data for the project at
+/// `root`. Files that no longer exist (or whose path leaves the root) are
+/// dropped and returned by their relative path.
+pub fn from_portable(portable: &PortableSession, root: &Path) -> (SessionData, Vec<String>) {
+    let mut documents = Vec::new();
+    let mut new_index = Vec::with_capacity(portable.documents.len());
+    let mut missing = Vec::new();
+    for doc in &portable.documents {
+        let full = resolve_path(&doc.path, root).filter(|p| p.is_file());
+        let Some(full) = full else {
+            missing.push(doc.path.clone());
+            new_index.push(None);
+            continue;
+        };
+        new_index.push(Some(documents.len()));
+        documents.push(DocumentSession {
+            display_name: full
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| doc.path.clone()),
+            file_path: Some(full.to_string_lossy().to_string()),
+            cursor_position: doc.cursor_position,
+            temp_file: None,
+            was_dirty: false,
+            group_index: doc.group_index.filter(|&i| i < portable.groups.len()),
+            pinned: doc.pinned,
+            top_line: doc.top_line,
+            horiz_offset: doc.horiz_offset,
+            selection: doc.selection,
+            bookmarks: doc.bookmarks.clone(),
+        });
+    }
+
+    let data = SessionData {
+        version: CURRENT_SESSION_VERSION,
+        active_index: remap(&new_index, portable.active_index).unwrap_or(0),
+        documents,
+        last_open_directory: Some(root.to_string_lossy().to_string()),
+        groups: clone_groups(&portable.groups),
+        instance_id: None,
+        editor_layout: portable
+            .editor_layout
+            .as_ref()
+            .map(|layout| remap_layout(layout, &new_index)),
+    };
+    (data, missing)
+}
+
+/// Read a portable session file
+pub fn read_portable(path: &Path) -> Result<PortableSession, AppError> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Write a portable session file, creating its directory if needed
+pub fn write_portable(path: &Path, session: &PortableSession) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(session)?)?;
+    Ok(())
+}
+
+/// The project session file of `root`
+pub fn project_session_path(root: &Path) -> PathBuf {
+    root.join(PROJECT_SESSION_DIR).join(PROJECT_SESSION_FILE)
+}
+
+/// The nearest project session at or above `dir`
+pub fn find_project_session(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(project_session_path)
+        .find(|path| path.is_file())
+}
+
+/// The root a session file's paths are relative to: the project holding
+/// its `.ferrispad` directory, or else the directory of the file
+pub fn session_file_root(path: &Path) -> PathBuf {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    match parent.file_name() {
+        Some(name) if name == PROJECT_SESSION_DIR => {
+            parent.parent().unwrap_or(parent).to_path_buf()
+        }
+        _ => parent.to_path_buf(),
+    }
+}
+
+/// Name of the named session a project's session is kept in, e.g.
+/// "ferrispad-3fa2c1". The hash keeps projects with the same folder name apart;
+/// it is SHA-256 of the path, so the name stays the same across builds.
+pub fn project_session_name(root: &Path) -> String {
+    let base = root
+        .file_name()
+        .and_then(|n| session::sanitize_session_name(&n.to_string_lossy()))
+        .unwrap_or_else(|| "project".to_string());
+    let hash = Sha256::digest(root.to_string_lossy().as_bytes());
+    format!("{}-{:02x}{:02x}{:02x}", base, hash[0], hash[1], hash[2])
+}
+
+/// `path` relative to `root` with `/` separators, if it lies inside it
+fn relative_path(path: &Path, root: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Resolve a relative session path against `root`. Absolute paths and
+/// paths leaving the root (`..`) are rejected, so a shared session cannot
+/// open files outside the project.
+fn resolve_path(relative: &str, root: &Path) -> Option<PathBuf> {
+    let mut full = root.to_path_buf();
+    for part in relative.split('/').filter(|p| !p.is_empty() && *p != ".") {
+        let mut components = Path::new(part).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(name)), None) => full.push(name),
+            _ => return None,
+        }
+    }
+    (full != root).then_some(full)
+}
+
+fn remap(new_index: &[Option<usize>], index: usize) -> Option<usize> {
+    new_index.get(index).copied().flatten()
+}
+
+fn remap_layout(layout: &EditorLayoutSession, new_index: &[Option<usize>]) -> EditorLayoutSession {
+    EditorLayoutSession {
+        orientation: layout.orientation,
+        panes: layout
+            .panes
+            .iter()
+            .map(|pane| PaneSession {
+                document_index: pane.document_index.and_then(|i| remap(new_index, i)),
+                cursor_position: pane.cursor_position,
+            })
+            .collect(),
+        focused: layout.focused,
+    }
+}
+
+fn clone_groups(groups: &[GroupSession]) -> Vec<GroupSession> {
+    groups
+        .iter()
+        .map(|g| GroupSession {
+            name: g.name.clone(),
+            color: g.color.clone(),
+            collapsed: g.collapsed,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::controllers::panes::SplitOrientation;
+    use tempfile::tempdir;
+
+    fn document(path: &str) -> DocumentSession {
+        DocumentSession {
+            file_path: Some(path.to_string()),
+            display_name: path.rsplit('/').next().unwrap_or(path).to_string(),
+            cursor_position: 7,
+            temp_file: None,
+            was_dirty: false,
+            group_index: None,
+            pinned: false,
+            top_line: 12,
+            horiz_offset: 0,
+            selection: Some((7, 9)),
+            bookmarks: vec![3, 20],
+        }
+    }
+
+    fn session(documents: Vec<DocumentSession>) -> SessionData {
+        SessionData {
+            version: CURRENT_SESSION_VERSION,
+            active_index: 2,
+            documents,
+            last_open_directory: None,
+            groups: vec![],
+            instance_id: None,
+            editor_layout: Some(EditorLayoutSession {
+                orientation: SplitOrientation::Horizontal,
+                panes: vec![
+                    PaneSession {
+                        document_index: Some(1),
+                        cursor_position: 0,
+                    },
+                    PaneSession {
+                        document_index: Some(2),
+                        cursor_position: 3,
+                    },
+                ],
+                focused: 1,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_to_portable_relative_paths() {
+        let root = Path::new("/home/me/project");
+        let data = session(vec![
+            document("/home/me/project/src/main.rs"),
+            document("/etc/hosts"),
+            document("/home/me/project/README.md"),
+        ]);
+        let (portable, left_out) = to_portable(&data, root);
+        assert_eq!(left_out, 1);
+        let paths: Vec<&str> = portable.documents.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/main.rs", "README.md"]);
+        assert_eq!(portable.active_index, 1);
+        assert_eq!(portable.documents[0].top_line, 12);
+        assert_eq!(portable.documents[0].bookmarks, vec![3, 20]);
+        let layout = portable.editor_layout.unwrap();
+        assert_eq!(layout.panes[0].document_index, None);
+        assert_eq!(layout.panes[1].document_index, Some(1));
+    }
+
+    #[test]
+    fn test_from_portable_drops_missing_files() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+        let portable = PortableSession {
+            version: PORTABLE_SESSION_VERSION,
+            active_index: 1,
+            documents: ["gone.txt", "src/lib.rs", "../outside.txt"]
+                .iter()
+                .map(|p| PortableDocument {
+                    path: p.to_string(),
+                    cursor_position: 4,
+                    top_line: 2,
+                    horiz_offset: 0,
+                    selection: None,
+                    bookmarks: vec![1],
+                    group_index: Some(5),
+                    pinned: true,
+                })
+                .collect(),
+            groups: vec![],
+            editor_layout: None,
+        };
+
+        let (data, missing) = from_portable(&portable, dir.path());
+        assert_eq!(missing, vec!["gone.txt", "../outside.txt"]);
+        assert_eq!(data.documents.len(), 1);
+        let doc = &data.documents[0];
+        assert_eq!(
+            doc.file_path.as_deref(),
+            Some(
+                dir.path()
+                    .join("src")
+                    .join("lib.rs")
+                    .to_string_lossy()
+                    .as_ref()
+            )
+        );
+        assert_eq!(doc.display_name, "lib.rs");
+        assert_eq!(doc.cursor_position, 4);
+        assert_eq!(doc.bookmarks, vec![1]);
+        assert!(doc.pinned);
+        // The group no longer exists
+        assert_eq!(doc.group_index, None);
+        assert_eq!(data.active_index, 0);
+    }
+
+    #[test]
+    fn test_project_session_lookup() {
+        let dir = tempdir().unwrap();
+        let nested = dir.path().join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        assert!(find_project_session(&nested).is_none_or(|p| !p.starts_with(dir.path())));
+
+        let file = project_session_path(dir.path());
+        write_portable(&file, &to_portable(&session(vec![]), dir.path()).0).unwrap();
+        assert_eq!(find_project_session(&nested), Some(file.clone()));
+        assert_eq!(session_file_root(&file), dir.path());
+        assert!(read_portable(&file).unwrap().documents.is_empty());
+
+        let name = project_session_name(Path::new("/work/My Project"));
+        // Saved sessions are found by this name: it must never change
+        assert_eq!(name, "My-Project-aeda9d");
+        assert_ne!(name, project_session_name(Path::new("/other/My Project")));
+    }
+}
@@ -0,0 +1,163 @@
+//! Most-recently-used file history.
+//!
+//! Stored in `~/.config/ferrispad/recent_files.json`, most recent first,
+//! with the time each file was last opened or closed and the cursor position
+//! it was closed at. Every change re-reads the file first, so several windows
+//! share one history.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app::infrastructure::error::AppError;
+use crate::app::services::updater::current_timestamp;
+
+/// How many files the history keeps
+pub const MAX_RECENT_FILES: usize = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentFile {
+    pub path: String,
+    /// Unix time (seconds) the file was last opened or closed
+    pub last_used: i64,
+    /// Cursor position when the file was last closed
+    #[serde(default)]
+    pub cursor_position: i32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RecentFiles {
+    #[serde(default)]
+    pub files: Vec<RecentFile>,
+}
+
+impl RecentFiles {
+    /// Move `path` to the front of the history. `cursor_position` replaces
+    /// the remembered cursor when given (on close) and keeps it otherwise.
+    pub fn touch(&mut self, path: &str, cursor_position: Option<i32>, now: i64) {
+        let previous = self
+            .files
+            .iter()
+            .position(|f| f.path == path)
+            .map(|i| self.files.remove(i));
+        let cursor_position = cursor_position
+            .or(previous.map(|f| f.cursor_position))
+            .unwrap_or(0);
+        self.files.insert(
+            0,
+            RecentFile {
+                path: path.to_string(),
+                last_used: now,
+                cursor_position,
+            },
+        );
+        self.files.truncate(MAX_RECENT_FILES);
+    }
+
+    pub fn get(&self, path: &str) -> Option<&RecentFile> {
+        self.files.iter().find(|f| f.path == path)
+    }
+
+    pub fn remove(&mut self, path: &str) {
+        self.files.retain(|f| f.path != path);
+    }
+}
+
+fn recent_files_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("ferrispad").join("recent_files.json"))
+}
+
+/// Read a history file; a missing or unreadable file is an empty history.
+pub fn load_from(path: &Path) -> RecentFiles {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_to(recent: &RecentFiles, path: &Path) -> Result<(), AppError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(recent)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Load the shared history
+pub fn load() -> RecentFiles {
+    recent_files_path()
+        .map(|p| load_from(&p))
+        .unwrap_or_default()
+}
+
+/// Apply a change to the shared history on disk and return the result
+pub fn update(change: impl FnOnce(&mut RecentFiles)) -> RecentFiles {
+    let Some(path) = recent_files_path() else {
+        return RecentFiles::default();
+    };
+    let mut recent = load_from(&path);
+    change(&mut recent);
+    let _ = save_to(&recent, &path)
+        .inspect_err(|e| eprintln!("[recent] Failed to save recent files: {}", e));
+    recent
+}
+
+/// Record that a file was opened
+pub fn record_open(path: &str) -> RecentFiles {
+    update(|r| r.touch(path, None, current_timestamp()))
+}
+
+/// Record that a file was closed with the cursor at `cursor_position`
+pub fn record_close(path: &str, cursor_position: i32) -> RecentFiles {
+    update(|r| r.touch(path, Some(cursor_position), current_timestamp()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_touch_moves_to_front_and_keeps_cursor() {
+        let mut recent = RecentFiles::default();
+        recent.touch("/a", Some(10), 1);
+        recent.touch("/b", None, 2);
+        recent.touch("/a", None, 3);
+
+        let paths: Vec<&str> = recent.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["/a", "/b"]);
+        assert_eq!(recent.get("/a").unwrap().cursor_position, 10);
+        assert_eq!(recent.get("/a").unwrap().last_used, 3);
+
+        recent.touch("/a", Some(4), 5);
+        assert_eq!(recent.get("/a").unwrap().cursor_position, 4);
+    }
+
+    #[test]
+    fn test_touch_caps_history() {
+        let mut recent = RecentFiles::default();
+        for i in 0..MAX_RECENT_FILES + 5 {
+            recent.touch(&format!("/f{}", i), None, i as i64);
+        }
+        assert_eq!(recent.files.len(), MAX_RECENT_FILES);
+        assert_eq!(recent.files[0].path, format!("/f{}", MAX_RECENT_FILES + 4));
+        assert!(recent.get("/f0").is_none());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("recent_files.json");
+        assert!(load_from(&path).files.is_empty());
+
+        let mut recent = RecentFiles::default();
+        recent.touch("/tmp/x.rs", Some(7), 42);
+        save_to(&recent, &path).unwrap();
+
+        let loaded = load_from(&path);
+        assert_eq!(loaded.files, recent.files);
+
+        fs::write(&path, "not json").unwrap();
+        assert!(load_from(&path).files.is_empty());
+    }
+}
@@ -5,7 +5,9 @@ use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
+use crate::app::controllers::panes::{EditorLayout, SplitOrientation};
 use crate::app::controllers::tabs::TabManager;
+use crate::app::domain::document::{Document, DocumentId};
 use crate::app::infrastructure::buffer::buffer_text_no_leak;
 use crate::app::infrastructure::error::AppError;
 
@@ -17,15 +19,21 @@ pub enum SessionRestore {
     Full,
 }
 
-const CURRENT_SESSION_VERSION: u32 = 1;
+/// Session file format version. Fields added without a bump are optional
+/// and read with defaults.
+/// - 1: tabs and groups (files from before the version field); pinned tabs
+///   and split panes were added to it later
+/// - 2: scroll position and selection of each document
+pub const CURRENT_SESSION_VERSION: u32 = 2;
 
-fn default_version() -> u32 {
-    CURRENT_SESSION_VERSION
+/// Session files from before the version field are version 1
+fn legacy_version() -> u32 {
+    1
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct SessionData {
-    #[serde(default = "default_version")]
+    #[serde(default = "legacy_version")]
     pub version: u32,
     pub active_index: usize,
     pub documents: Vec<DocumentSession>,
@@ -35,6 +43,9 @@ pub struct SessionData {
     pub groups: Vec<GroupSession>,
     #[serde(default)]
     pub instance_id: Option<String>,
+    /// Split editor panes (absent when the editor was not split)
+    #[serde(default)]
+    pub editor_layout: Option<EditorLayoutSession>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -46,6 +57,20 @@ pub struct DocumentSession {
     pub was_dirty: bool,
     #[serde(default)]
     pub group_index: Option<usize>,
+    #[serde(default)]
+    pub pinned: bool,
+    /// First visible line (1-based); 0 when unknown
+    #[serde(default)]
+    pub top_line: i32,
+    /// Horizontal scroll offset in pixels
+    #[serde(default)]
+    pub horiz_offset: i32,
+    /// Selected byte range (start, end)
+    #[serde(default)]
+    pub selection: Option<(i32, i32)>,
+    /// Bookmarked lines (1-based)
+    #[serde(default)]
+    pub bookmarks: Vec<u32>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -55,6 +80,20 @@ pub struct GroupSession {
     pub collapsed: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct EditorLayoutSession {
+    pub orientation: SplitOrientation,
+    pub panes: Vec<PaneSession>,
+    pub focused: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PaneSession {
+    /// Index into `SessionData::documents` (None if the document was not saved)
+    pub document_index: Option<usize>,
+    pub cursor_position: i32,
+}
+
 /// Default session name used when no --session flag is provided.
 pub const DEFAULT_SESSION_NAME: &str = "default";
 
@@ -85,6 +124,11 @@ pub fn list_sessions() -> Vec<String> {
     names
 }
 
+/// Whether a named session has been saved.
+pub fn session_exists(name: &str) -> bool {
+    session_dir(name).join("session.json").is_file()
+}
+
 /// Delete a named session (removes its directory and all temp files).
 pub fn delete_session(name: &str) -> Result<(), AppError> {
     let dir = session_dir(name);
@@ -173,6 +217,7 @@ pub fn session_is_locked(name: &str) -> bool {
 /// Save the current session to disk under the given session name.
 pub fn save_session(
     tab_manager: &TabManager,
+    editor_layout: Option<&EditorLayout>,
     mode: SessionRestore,
     last_open_directory: Option<&str>,
     session_name: &str,
@@ -201,16 +246,11 @@ pub fn save_session(
 
     // Build group sessions and a mapping from GroupId -> index
     let groups = tab_manager.groups();
-    let group_sessions: Vec<GroupSession> = groups
-        .iter()
-        .map(|g| GroupSession {
-            name: g.name.clone(),
-            color: g.color.as_str().to_string(),
-            collapsed: g.collapsed,
-        })
-        .collect();
+    let group_sessions = group_sessions(tab_manager);
 
     let mut doc_sessions = Vec::new();
+    // Document behind each saved entry, for mapping panes to indices
+    let mut saved_ids = Vec::new();
 
     for doc in docs {
         let is_dirty = doc.is_dirty();
@@ -224,14 +264,8 @@ pub fn save_session(
         match mode {
             SessionRestore::SavedFiles => {
                 if has_path {
-                    doc_sessions.push(DocumentSession {
-                        file_path: doc.file_path.clone(),
-                        display_name: doc.display_name.clone(),
-                        cursor_position: doc.cursor_position,
-                        temp_file: None,
-                        was_dirty: false,
-                        group_index,
-                    });
+                    doc_sessions.push(document_session(doc, group_index, None, false));
+                    saved_ids.push(doc.id);
                 }
             }
             SessionRestore::Full => {
@@ -255,14 +289,8 @@ pub fn save_session(
                     None
                 };
 
-                doc_sessions.push(DocumentSession {
-                    file_path: doc.file_path.clone(),
-                    display_name: doc.display_name.clone(),
-                    cursor_position: doc.cursor_position,
-                    temp_file,
-                    was_dirty: is_dirty,
-                    group_index,
-                });
+                doc_sessions.push(document_session(doc, group_index, temp_file, is_dirty));
+                saved_ids.push(doc.id);
             }
             SessionRestore::Off => unreachable!(),
         }
@@ -318,6 +346,8 @@ pub fn save_session(
         }
     }
 
+    let editor_layout = editor_layout.map(|layout| layout_session(layout, &saved_ids));
+
     let session_data = SessionData {
         version: CURRENT_SESSION_VERSION,
         active_index,
@@ -325,6 +355,7 @@ pub fn save_session(
         last_open_directory: last_open_directory.map(|s| s.to_string()),
         groups: group_sessions,
         instance_id: Some(instance_id),
+        editor_layout,
     };
 
     let json = serde_json::to_string_pretty(&session_data)?;
@@ -337,6 +368,90 @@ pub fn save_session(
     Ok(())
 }
 
+/// The open saved files as session data, without writing anything: unsaved
+/// and untitled documents are left out. Used to export a session.
+pub fn snapshot_session(
+    tab_manager: &TabManager,
+    editor_layout: Option<&EditorLayout>,
+) -> SessionData {
+    let groups = tab_manager.groups();
+    let mut documents = Vec::new();
+    let mut saved_ids = Vec::new();
+    for doc in tab_manager.documents() {
+        if doc.file_path.is_none() {
+            continue;
+        }
+        let group_index = doc
+            .group_id
+            .and_then(|gid| groups.iter().position(|g| g.id == gid));
+        documents.push(document_session(doc, group_index, None, false));
+        saved_ids.push(doc.id);
+    }
+    let active_index = tab_manager
+        .active_id()
+        .and_then(|id| saved_ids.iter().position(|&s| s == id))
+        .unwrap_or(0);
+
+    SessionData {
+        version: CURRENT_SESSION_VERSION,
+        active_index,
+        documents,
+        last_open_directory: None,
+        groups: group_sessions(tab_manager),
+        instance_id: None,
+        editor_layout: editor_layout.map(|layout| layout_session(layout, &saved_ids)),
+    }
+}
+
+fn document_session(
+    doc: &Document,
+    group_index: Option<usize>,
+    temp_file: Option<String>,
+    was_dirty: bool,
+) -> DocumentSession {
+    DocumentSession {
+        file_path: doc.file_path.clone(),
+        display_name: doc.display_name.clone(),
+        cursor_position: doc.cursor_position,
+        temp_file,
+        was_dirty,
+        group_index,
+        pinned: doc.pinned,
+        top_line: doc.view.top_line,
+        horiz_offset: doc.view.horiz_offset,
+        selection: doc.view.selection,
+        bookmarks: doc.bookmark_lines(),
+    }
+}
+
+fn group_sessions(tab_manager: &TabManager) -> Vec<GroupSession> {
+    tab_manager
+        .groups()
+        .iter()
+        .map(|g| GroupSession {
+            name: g.name.clone(),
+            color: g.color.as_str().to_string(),
+            collapsed: g.collapsed,
+        })
+        .collect()
+}
+
+/// Panes refer to documents by their index among `saved_ids`
+fn layout_session(layout: &EditorLayout, saved_ids: &[DocumentId]) -> EditorLayoutSession {
+    EditorLayoutSession {
+        orientation: layout.orientation,
+        panes: layout
+            .panes
+            .iter()
+            .map(|&(doc, cursor_position)| PaneSession {
+                document_index: doc.and_then(|id| saved_ids.iter().position(|&s| s == id)),
+                cursor_position,
+            })
+            .collect(),
+        focused: layout.focused,
+    }
+}
+
 /// Remove .tmp files that are no longer referenced by any document in the session.
 fn cleanup_orphaned_temp_files(session: &SessionData, dir: &std::path::Path) {
     // Collect all referenced temp files
@@ -413,7 +528,7 @@ mod tests {
     #[test]
     fn test_session_data_serialization() {
         let data = SessionData {
-            version: 1,
+            version: CURRENT_SESSION_VERSION,
             active_index: 0,
             documents: vec![DocumentSession {
                 file_path: Some("/tmp/test.txt".to_string()),
@@ -422,16 +537,35 @@ mod tests {
                 temp_file: None,
                 was_dirty: false,
                 group_index: None,
+                pinned: true,
+                top_line: 30,
+                horiz_offset: 12,
+                selection: Some((40, 44)),
+                bookmarks: Vec::new(),
             }],
             last_open_directory: Some("/tmp".to_string()),
             groups: vec![],
             instance_id: None,
+            editor_layout: Some(EditorLayoutSession {
+                orientation: SplitOrientation::Horizontal,
+                panes: vec![
+                    PaneSession {
+                        document_index: Some(0),
+                        cursor_position: 42,
+                    },
+                    PaneSession {
+                        document_index: Some(0),
+                        cursor_position: 7,
+                    },
+                ],
+                focused: 1,
+            }),
         };
 
         let json = serde_json::to_string(&data).unwrap();
         let loaded: SessionData = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(loaded.version, 1);
+        assert_eq!(loaded.version, CURRENT_SESSION_VERSION);
         assert_eq!(loaded.active_index, 0);
         assert_eq!(loaded.documents.len(), 1);
         assert_eq!(
@@ -439,10 +573,19 @@ mod tests {
             Some("/tmp/test.txt".to_string())
         );
         assert_eq!(loaded.documents[0].cursor_position, 42);
+        assert!(loaded.documents[0].pinned);
+        assert_eq!(loaded.documents[0].top_line, 30);
+        assert_eq!(loaded.documents[0].horiz_offset, 12);
+        assert_eq!(loaded.documents[0].selection, Some((40, 44)));
+        let layout = loaded.editor_layout.unwrap();
+        assert_eq!(layout.orientation, SplitOrientation::Horizontal);
+        assert_eq!(layout.panes[1].document_index, Some(0));
+        assert_eq!(layout.panes[1].cursor_position, 7);
+        assert_eq!(layout.focused, 1);
     }
 
     #[test]
-    fn test_session_data_missing_version_uses_default() {
+    fn test_session_data_missing_version_is_v1() {
         // Old format without version field
         let json = r#"{
             "active_index": 0,
@@ -450,7 +593,32 @@ mod tests {
         }"#;
 
         let loaded: SessionData = serde_json::from_str(json).unwrap();
-        assert_eq!(loaded.version, CURRENT_SESSION_VERSION);
+        assert_eq!(loaded.version, 1);
+        assert!(loaded.editor_layout.is_none());
+    }
+
+    #[test]
+    fn test_v1_session_has_no_view() {
+        let json = r#"{
+            "version": 1,
+            "active_index": 0,
+            "documents": [{
+                "file_path": "/tmp/a.txt",
+                "display_name": "a.txt",
+                "cursor_position": 3,
+                "temp_file": null,
+                "was_dirty": false
+            }]
+        }"#;
+
+        // Version 1 saved no view state: the scroll position stays unknown
+        // so each document scrolls its cursor into view
+        let loaded: SessionData = serde_json::from_str(json).unwrap();
+        assert_eq!(loaded.version, 1);
+        let doc = &loaded.documents[0];
+        assert_eq!(doc.cursor_position, 3);
+        assert_eq!(doc.top_line, 0);
+        assert!(doc.selection.is_none());
     }
 
     #[test]
@@ -462,6 +630,11 @@ mod tests {
             temp_file: Some("abc123.tmp".to_string()),
             was_dirty: true,
             group_index: Some(0),
+            pinned: false,
+            top_line: 0,
+            horiz_offset: 0,
+            selection: None,
+            bookmarks: Vec::new(),
         };
 
         let json = serde_json::to_string(&doc).unwrap();
@@ -474,6 +647,22 @@ mod tests {
         assert_eq!(loaded.group_index, Some(0));
     }
 
+    #[test]
+    fn test_document_session_without_pinned_is_unpinned() {
+        // Sessions saved before pinned tabs existed
+        let json = r#"{
+            "file_path": "/tmp/a.txt",
+            "display_name": "a.txt",
+            "cursor_position": 3,
+            "temp_file": null,
+            "was_dirty": false
+        }"#;
+
+        let loaded: DocumentSession = serde_json::from_str(json).unwrap();
+        assert!(!loaded.pinned);
+        assert!(loaded.group_index.is_none());
+    }
+
     #[test]
     fn test_group_session_serialization() {
         let group = GroupSession {
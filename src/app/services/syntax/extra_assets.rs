@@ -0,0 +1,246 @@
+//! User- and plugin-supplied syntax definitions and color themes.
+//!
+//! Each asset root may contain `syntaxes/*.sublime-syntax` and
+//! `themes/*.tmTheme`. Roots are the user config dir (`~/.config/ferrispad/`)
+//! and every enabled plugin directory. Syntaxes are compiled into one extra
+//! `SyntaxSet` that is searched before syntect's defaults; themes are keyed by
+//! file stem and offered next to the built-in `SyntaxTheme` choices.
+//!
+//! Invalid files are skipped and reported in `ExtraAssets::errors` — they
+//! never abort loading or reach the highlighter.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::{SyntaxDefinition, SyntaxSet, SyntaxSetBuilder};
+
+use crate::app::domain::settings::SyntaxTheme;
+
+/// Subdirectory holding `.sublime-syntax` files.
+pub const SYNTAXES_DIR: &str = "syntaxes";
+
+/// Subdirectory holding `.tmTheme` files.
+pub const THEMES_DIR: &str = "themes";
+
+/// Syntaxes and themes loaded from asset roots.
+#[derive(Default)]
+pub struct ExtraAssets {
+    /// Compiled extra syntaxes (None when no valid definitions were found)
+    pub syntax_set: Option<SyntaxSet>,
+    /// Extra themes keyed by file stem
+    pub themes: BTreeMap<String, Theme>,
+    /// One "path: reason" line per file that failed to load
+    pub errors: Vec<String>,
+}
+
+/// User asset root (~/.config/ferrispad/ or platform equivalent).
+pub fn user_asset_root() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("ferrispad"))
+}
+
+/// Files in `dir` with the given extension, sorted by name.
+fn files_with_extension(dir: &Path, ext: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == ext))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Load all syntaxes and themes under the given roots.
+/// Later roots win when two themes share a name.
+pub fn load_extra_assets(roots: &[PathBuf]) -> ExtraAssets {
+    let mut assets = ExtraAssets::default();
+    let mut builder = SyntaxSetBuilder::new();
+    let mut syntax_count = 0;
+
+    for root in roots {
+        for path in files_with_extension(&root.join(SYNTAXES_DIR), "sublime-syntax") {
+            match load_syntax(&path) {
+                Ok(def) => {
+                    builder.add(def);
+                    syntax_count += 1;
+                }
+                Err(e) => assets.errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+
+        for path in files_with_extension(&root.join(THEMES_DIR), "tmTheme") {
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if SyntaxTheme::all().iter().any(|t| t.theme_key() == name) {
+                assets.errors.push(format!(
+                    "{}: name conflicts with a built-in theme",
+                    path.display()
+                ));
+                continue;
+            }
+            match ThemeSet::get_theme(&path) {
+                Ok(theme) => {
+                    assets.themes.insert(name.to_string(), theme);
+                }
+                Err(e) => assets.errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    if syntax_count > 0 {
+        assets.syntax_set = Some(builder.build());
+    }
+    assets
+}
+
+/// Parse one `.sublime-syntax` file. Regexes are compiled during parsing,
+/// so a definition that loads here cannot fail later while highlighting.
+fn load_syntax(path: &Path) -> Result<SyntaxDefinition, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let fallback_name = path.file_stem().and_then(|s| s.to_str());
+    SyntaxDefinition::load_from_str(&content, true, fallback_name).map_err(|e| e.to_string())
+}
+
+/// Background and foreground RGB of a theme, falling back to
+/// Base16 Ocean Dark for colors the theme leaves unset.
+pub fn theme_colors(theme: &Theme) -> ((u8, u8, u8), (u8, u8, u8)) {
+    let fallback = SyntaxTheme::Base16OceanDark;
+    let bg = theme
+        .settings
+        .background
+        .map(|c| (c.r, c.g, c.b))
+        .unwrap_or_else(|| fallback.background());
+    let fg = theme
+        .settings
+        .foreground
+        .map(|c| (c.r, c.g, c.b))
+        .unwrap_or_else(|| fallback.foreground());
+    (bg, fg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const HCL_SYNTAX: &str = r#"%YAML 1.2
+---
+name: HCL
+file_extensions: [hcl, tf]
+scope: source.hcl
+contexts:
+  main:
+    - match: '#.*$'
+      scope: comment.line.hcl
+    - match: '\b(resource|variable)\b'
+      scope: keyword.other.hcl
+"#;
+
+    const THEME: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>name</key><string>Midnight</string>
+  <key>settings</key>
+  <array>
+    <dict>
+      <key>settings</key>
+      <dict>
+        <key>background</key><string>#101020</string>
+        <key>foreground</key><string>#E0E0F0</string>
+      </dict>
+    </dict>
+  </array>
+</dict>
+</plist>
+"#;
+
+    fn write(root: &Path, sub: &str, name: &str, content: &str) {
+        std::fs::create_dir_all(root.join(sub)).unwrap();
+        std::fs::write(root.join(sub).join(name), content).unwrap();
+    }
+
+    #[test]
+    fn test_loads_syntaxes_and_themes() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), SYNTAXES_DIR, "HCL.sublime-syntax", HCL_SYNTAX);
+        write(dir.path(), THEMES_DIR, "midnight.tmTheme", THEME);
+
+        let assets = load_extra_assets(&[dir.path().to_path_buf()]);
+        assert!(assets.errors.is_empty(), "{:?}", assets.errors);
+
+        let set = assets.syntax_set.expect("syntax set built");
+        assert_eq!(set.find_syntax_by_extension("tf").unwrap().name, "HCL");
+
+        let theme = assets.themes.get("midnight").expect("theme loaded");
+        assert_eq!(theme_colors(theme), ((16, 16, 32), (224, 224, 240)));
+    }
+
+    #[test]
+    fn test_highlighter_uses_extra_syntaxes_and_themes() {
+        use crate::app::services::syntax::SyntaxHighlighter;
+
+        let dir = tempdir().unwrap();
+        write(dir.path(), SYNTAXES_DIR, "HCL.sublime-syntax", HCL_SYNTAX);
+        write(dir.path(), THEMES_DIR, "midnight.tmTheme", THEME);
+        let assets = load_extra_assets(&[dir.path().to_path_buf()]);
+
+        let mut hl =
+            SyntaxHighlighter::new(SyntaxTheme::InspiredGitHub, fltk::enums::Font::Courier, 14);
+        hl.set_extra_assets(assets.syntax_set, assets.themes);
+        assert_eq!(hl.detect_syntax("/infra/main.tf").as_deref(), Some("HCL"));
+        // Built-in syntaxes still resolve
+        assert_eq!(hl.detect_syntax("/src/lib.rs").as_deref(), Some("Rust"));
+
+        assert!(!hl.set_custom_theme("missing"));
+        assert!(hl.set_custom_theme("midnight"));
+        assert_eq!(hl.theme_background(), (16, 16, 32));
+        let result = hl.highlight_full("resource x # note\n", "HCL");
+        assert_eq!(result.style_string.len(), "resource x # note\n".len());
+
+        // Dropping the theme falls back to the built-in one
+        hl.set_extra_assets(None, BTreeMap::new());
+        assert_eq!(hl.custom_theme(), None);
+        assert_eq!(
+            hl.theme_background(),
+            SyntaxTheme::InspiredGitHub.background()
+        );
+    }
+
+    #[test]
+    fn test_invalid_files_are_reported_not_loaded() {
+        let dir = tempdir().unwrap();
+        write(dir.path(), SYNTAXES_DIR, "broken.sublime-syntax", "name: [");
+        write(
+            dir.path(),
+            SYNTAXES_DIR,
+            "bad-regex.sublime-syntax",
+            "%YAML 1.2\n---\nname: Bad\nscope: source.bad\ncontexts:\n  main:\n    - match: '(unclosed'\n      scope: x\n",
+        );
+        write(dir.path(), THEMES_DIR, "garbage.tmTheme", "not a plist");
+        write(dir.path(), THEMES_DIR, "InspiredGitHub.tmTheme", THEME);
+
+        let assets = load_extra_assets(&[dir.path().to_path_buf()]);
+        assert!(assets.syntax_set.is_none());
+        assert!(assets.themes.is_empty());
+        assert_eq!(assets.errors.len(), 4);
+        assert!(
+            assets
+                .errors
+                .iter()
+                .any(|e| e.contains("bad-regex.sublime-syntax"))
+        );
+    }
+
+    #[test]
+    fn test_missing_roots_yield_nothing() {
+        let assets = load_extra_assets(&[PathBuf::from("/nonexistent/ferrispad")]);
+        assert!(assets.syntax_set.is_none());
+        assert!(assets.themes.is_empty());
+        assert!(assets.errors.is_empty());
+    }
+}
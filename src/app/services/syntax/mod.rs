@@ -1,12 +1,15 @@
 pub mod checkpoint;
+pub mod extra_assets;
 mod highlighter;
 pub mod style_map;
+pub mod symbols;
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use fltk::enums::Font;
 use fltk::text::StyleTableEntryExt;
-use syntect::highlighting::{HighlightIterator, HighlightState, Highlighter, ThemeSet};
+use syntect::highlighting::{HighlightIterator, HighlightState, Highlighter, Theme, ThemeSet};
 use syntect::parsing::{
     ParseState, ScopeStack, SyntaxDefinition, SyntaxReference, SyntaxSet, SyntaxSetBuilder,
 };
@@ -46,40 +49,44 @@ pub struct ChunkOutput {
 struct SyntaxHighlighterInner {
     syntax_set: SyntaxSet,
     toml_syntax_set: SyntaxSet,
+    /// User/plugin syntaxes (searched first so they can override defaults)
+    extra_syntax_set: Option<SyntaxSet>,
     theme_set: ThemeSet,
     chunked: Option<ChunkedState>,
 }
 
 impl SyntaxHighlighterInner {
-    /// Find a syntax by name across both sets (TOML first, then defaults).
+    /// All sets in lookup order: extra, TOML, then defaults.
+    fn syntax_sets(&self) -> impl Iterator<Item = &SyntaxSet> {
+        self.extra_syntax_set
+            .iter()
+            .chain([&self.toml_syntax_set, &self.syntax_set])
+    }
+
+    /// Find a syntax by name across all sets.
     fn find_syntax_by_name(&self, name: &str) -> Option<&SyntaxReference> {
-        self.toml_syntax_set
-            .find_syntax_by_name(name)
-            .or_else(|| self.syntax_set.find_syntax_by_name(name))
+        self.syntax_sets()
+            .find_map(|set| set.find_syntax_by_name(name))
     }
 
-    /// Find a syntax by file extension across both sets.
+    /// Find a syntax by file extension (or full file name, e.g. `Dockerfile`) across all sets.
     fn find_syntax_by_extension(&self, ext: &str) -> Option<&SyntaxReference> {
-        self.toml_syntax_set
-            .find_syntax_by_extension(ext)
-            .or_else(|| self.syntax_set.find_syntax_by_extension(ext))
+        self.syntax_sets()
+            .find_map(|set| set.find_syntax_by_extension(ext))
     }
 
     /// Get the SyntaxSet that owns a given syntax (needed for parse_line).
     fn syntax_set_for(&self, syntax_name: &str) -> &SyntaxSet {
-        if self
-            .toml_syntax_set
-            .find_syntax_by_name(syntax_name)
-            .is_some()
-        {
-            &self.toml_syntax_set
-        } else {
-            &self.syntax_set
-        }
+        self.syntax_sets()
+            .find(|set| set.find_syntax_by_name(syntax_name).is_some())
+            .unwrap_or(&self.syntax_set)
     }
 }
 
-fn init_inner() -> SyntaxHighlighterInner {
+fn init_inner(
+    extra_syntax_set: Option<SyntaxSet>,
+    extra_themes: &BTreeMap<String, Theme>,
+) -> SyntaxHighlighterInner {
     // Load pre-compiled defaults AS-IS (no builder conversion = no regex recompilation)
     let syntax_set = SyntaxSet::load_defaults_newlines();
 
@@ -90,11 +97,17 @@ fn init_inner() -> SyntaxHighlighterInner {
     }
     let toml_syntax_set = toml_builder.build();
 
-    let theme_set = ThemeSet::load_defaults();
+    let mut theme_set = ThemeSet::load_defaults();
+    theme_set.themes.extend(
+        extra_themes
+            .iter()
+            .map(|(name, theme)| (name.clone(), theme.clone())),
+    );
 
     SyntaxHighlighterInner {
         syntax_set,
         toml_syntax_set,
+        extra_syntax_set,
         theme_set,
         chunked: None,
     }
@@ -103,6 +116,12 @@ fn init_inner() -> SyntaxHighlighterInner {
 pub struct SyntaxHighlighter {
     inner: Option<SyntaxHighlighterInner>,
     theme: SyntaxTheme,
+    /// Active user/plugin theme (key into `extra_themes`); overrides `theme` when set
+    custom_theme: Option<String>,
+    /// Extra syntaxes waiting for syntect to be loaded
+    pending_extra_syntaxes: Option<SyntaxSet>,
+    /// User/plugin themes keyed by name
+    extra_themes: BTreeMap<String, Theme>,
     theme_name: String,
     style_map: StyleMap,
     font: Font,
@@ -129,6 +148,9 @@ impl SyntaxHighlighter {
         Self {
             inner: None,
             theme,
+            custom_theme: None,
+            pending_extra_syntaxes: None,
+            extra_themes: BTreeMap::new(),
             theme_name,
             style_map,
             font,
@@ -138,28 +160,98 @@ impl SyntaxHighlighter {
 
     /// Ensure syntect is loaded, returning a mutable reference.
     fn inner_mut(&mut self) -> &mut SyntaxHighlighterInner {
-        self.inner.get_or_insert_with(init_inner)
+        self.ensure_loaded();
+        self.inner.as_mut().unwrap()
     }
 
     /// Ensure syntect is loaded (called before methods that need split borrows).
     fn ensure_loaded(&mut self) {
         if self.inner.is_none() {
-            self.inner = Some(init_inner());
+            self.inner = Some(init_inner(
+                self.pending_extra_syntaxes.take(),
+                &self.extra_themes,
+            ));
         }
     }
 
-    /// Detect the syntax for a file path based on extension.
+    /// Replace the user/plugin syntaxes and themes. If the active custom theme
+    /// is no longer available, falls back to the built-in theme.
+    pub fn set_extra_assets(
+        &mut self,
+        syntax_set: Option<SyntaxSet>,
+        themes: BTreeMap<String, Theme>,
+    ) {
+        if let Some(inner) = self.inner.as_mut() {
+            // A running chunked pass may reference a syntax that is going away
+            inner.chunked = None;
+            inner.extra_syntax_set = syntax_set;
+            for name in self.extra_themes.keys() {
+                inner.theme_set.themes.remove(name);
+            }
+            inner.theme_set.themes.extend(
+                themes
+                    .iter()
+                    .map(|(name, theme)| (name.clone(), theme.clone())),
+            );
+        } else {
+            self.pending_extra_syntaxes = syntax_set;
+        }
+        self.extra_themes = themes;
+
+        match self.custom_theme.clone() {
+            Some(name) if !self.extra_themes.contains_key(&name) => self.set_theme(self.theme),
+            // The theme file may have changed on disk
+            Some(_) => self.apply_theme_colors(),
+            None => {}
+        }
+    }
+
+    /// Whether any user/plugin syntaxes or themes are loaded.
+    pub fn has_extra_assets(&self) -> bool {
+        !self.extra_themes.is_empty()
+            || self.pending_extra_syntaxes.is_some()
+            || self
+                .inner
+                .as_ref()
+                .is_some_and(|inner| inner.extra_syntax_set.is_some())
+    }
+
+    /// Names of the available user/plugin themes (sorted).
+    pub fn custom_theme_names(&self) -> Vec<String> {
+        self.extra_themes.keys().cloned().collect()
+    }
+
+    /// Name of the active user/plugin theme, if one is selected.
+    pub fn custom_theme(&self) -> Option<&str> {
+        self.custom_theme.as_deref()
+    }
+
+    /// Detect the syntax for a file path based on extension, falling back to
+    /// the full file name (for extensionless files like `Dockerfile`).
     pub fn detect_syntax(&mut self, file_path: &str) -> Option<String> {
         let path = Path::new(file_path);
-        let ext = path.extension()?.to_str()?;
+        let ext = path.extension().and_then(|e| e.to_str());
+        let file_name = path.file_name()?.to_str()?;
         let inner = self.inner_mut();
-        let syntax = inner.find_syntax_by_extension(ext)?;
+        let syntax = ext
+            .and_then(|ext| inner.find_syntax_by_extension(ext))
+            .or_else(|| inner.find_syntax_by_extension(file_name))?;
         if syntax.name == "Plain Text" {
             return None;
         }
         Some(syntax.name.clone())
     }
 
+    /// Declarations (functions, types, headings…) found by parsing `text`.
+    pub fn symbols(&mut self, text: &str, syntax_name: &str) -> Vec<symbols::Symbol> {
+        self.ensure_loaded();
+        let inner = self.inner.as_ref().unwrap();
+        let Some(syntax) = inner.find_syntax_by_name(syntax_name) else {
+            return Vec::new();
+        };
+        symbols::extract_symbols(text, syntax, inner.syntax_set_for(syntax_name))
+    }
+
     /// Perform a full highlight. Returns style string + sparse checkpoints.
     pub fn highlight_full(&mut self, text: &str, syntax_name: &str) -> FullHighlightResult {
         self.ensure_loaded();
@@ -228,8 +320,25 @@ impl SyntaxHighlighter {
     /// Switch to a specific theme. Clears the style map and updates theme colors.
     pub fn set_theme(&mut self, theme: SyntaxTheme) {
         self.theme = theme;
+        self.custom_theme = None;
         self.theme_name = theme.theme_key().to_string();
+        self.apply_theme_colors();
+    }
 
+    /// Switch to a user/plugin theme by name. Returns false (leaving the
+    /// current theme active) if no such theme is loaded.
+    pub fn set_custom_theme(&mut self, name: &str) -> bool {
+        if !self.extra_themes.contains_key(name) {
+            return false;
+        }
+        self.custom_theme = Some(name.to_string());
+        self.theme_name = name.to_string();
+        self.apply_theme_colors();
+        true
+    }
+
+    /// Push the active theme's colors into the style map.
+    fn apply_theme_colors(&mut self) {
         let bg = self.theme_background();
         let fg = self.theme_foreground();
         let is_dark = (bg.0 as u32 + bg.1 as u32 + bg.2 as u32) / 3 < 128;
@@ -243,15 +352,25 @@ impl SyntaxHighlighter {
     }
 
     /// Get the background color of the current theme as RGB tuple.
-    /// Uses hardcoded values — no syntect needed.
+    /// Built-in themes use hardcoded values — no syntect needed.
     pub fn theme_background(&self) -> (u8, u8, u8) {
-        self.theme.background()
+        match self.active_custom_theme() {
+            Some(theme) => extra_assets::theme_colors(theme).0,
+            None => self.theme.background(),
+        }
     }
 
     /// Get the foreground color of the current theme as RGB tuple.
-    /// Uses hardcoded values — no syntect needed.
+    /// Built-in themes use hardcoded values — no syntect needed.
     pub fn theme_foreground(&self) -> (u8, u8, u8) {
-        self.theme.foreground()
+        match self.active_custom_theme() {
+            Some(theme) => extra_assets::theme_colors(theme).1,
+            None => self.theme.foreground(),
+        }
+    }
+
+    fn active_custom_theme(&self) -> Option<&Theme> {
+        self.extra_themes.get(self.custom_theme.as_deref()?)
     }
 
     pub fn font(&self) -> Font {
@@ -290,6 +409,11 @@ impl SyntaxHighlighter {
         self.style_map.get_or_insert_marker_rgb(r, g, b)
     }
 
+    /// Get or insert the full-line variant of a marker style.
+    pub fn get_or_insert_marker_line(&mut self, marker: char) -> char {
+        self.style_map.get_or_insert_marker_line(marker)
+    }
+
     /// Begin chunked highlighting for a large file.
     /// Takes ownership of the text to avoid re-copying it on every chunk.
     pub fn start_chunked(&mut self, doc_id: DocumentId, text: String, syntax_name: &str) {
@@ -236,6 +236,36 @@ impl StyleMap {
     }
 
     /// Extract RGB values from an FLTK Color
+    /// Get or insert a variant of a marker style whose background extends
+    /// to the right edge of the line. Used to mark empty lines, where a
+    /// plain bgcolor marker has no text to color. Falls back to `marker`
+    /// when the style table is full.
+    pub fn get_or_insert_marker_line(&mut self, marker: char) -> char {
+        let Some(base) = self.entries.get((marker as u8).wrapping_sub(b'A') as usize) else {
+            return marker;
+        };
+        let bgcolor = base.bgcolor;
+        for (idx, entry) in self.entries.iter().enumerate().skip(7) {
+            if entry.attr == TextAttr::BgColorExt && entry.bgcolor == bgcolor {
+                return (b'A' + idx as u8) as char;
+            }
+        }
+
+        let idx = self.entries.len();
+        if idx >= 26 {
+            return marker;
+        }
+        let ch = (b'A' + idx as u8) as char;
+        self.entries.push(StyleTableEntryExt {
+            color: self.theme_fgcolor,
+            font: self.font,
+            size: self.font_size,
+            attr: TextAttr::BgColorExt,
+            bgcolor,
+        });
+        ch
+    }
+
     fn color_to_rgb(&self, color: Color) -> (u8, u8, u8) {
         let (r, g, b) = color.to_rgb();
         (r, g, b)
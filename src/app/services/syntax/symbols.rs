@@ -0,0 +1,124 @@
+//! Symbols (functions, types, headings…) of a document, for Quick Open's
+//! `@` filter.
+//!
+//! Uses the syntax definitions' own scopes: anything the grammar tags as
+//! `entity.name.*` (function, struct, class, section…) is a symbol. Tags and
+//! labels are left out since they are not declarations.
+
+use syntect::parsing::{ParseState, Scope, ScopeStack, SyntaxReference, SyntaxSet};
+
+use super::highlighter::LinesWithEndings;
+
+/// Stop collecting after this many symbols
+const MAX_SYMBOLS: usize = 5000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    /// Kind taken from the scope, e.g. "function" for `entity.name.function`
+    pub kind: String,
+    /// 1-based line number
+    pub line: u32,
+}
+
+pub(super) fn extract_symbols(
+    text: &str,
+    syntax: &SyntaxReference,
+    syntax_set: &SyntaxSet,
+) -> Vec<Symbol> {
+    let (Ok(entity_name), Ok(tag), Ok(label)) = (
+        Scope::new("entity.name"),
+        Scope::new("entity.name.tag"),
+        Scope::new("entity.name.label"),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut symbols = Vec::new();
+    let mut parse_state = ParseState::new(syntax);
+    let mut stack = ScopeStack::new();
+    // The symbol being read; grammars may split a name over several tokens
+    let mut current: Option<(String, Scope)> = None;
+
+    for (line_idx, line) in LinesWithEndings::new(text).enumerate() {
+        let ops = parse_state.parse_line(line, syntax_set).unwrap_or_default();
+        let mut pos = 0;
+        for (end, op) in ops
+            .iter()
+            .map(|(i, op)| (*i, Some(op)))
+            .chain([(line.len(), None)])
+        {
+            let piece = &line[pos..end];
+            pos = end;
+            let scope = stack.as_slice().iter().rev().copied().find(|s| {
+                entity_name.is_prefix_of(*s) && !tag.is_prefix_of(*s) && !label.is_prefix_of(*s)
+            });
+            match (scope, current.as_mut()) {
+                (Some(scope), Some((name, cur))) if *cur == scope => name.push_str(piece),
+                (Some(scope), _) => {
+                    finish_symbol(&mut current, line_idx, &mut symbols);
+                    current = Some((piece.to_string(), scope));
+                }
+                (None, _) if !piece.is_empty() => {
+                    finish_symbol(&mut current, line_idx, &mut symbols)
+                }
+                (None, _) => {}
+            }
+            if let Some(op) = op {
+                let _ = stack.apply(op);
+            }
+        }
+        finish_symbol(&mut current, line_idx, &mut symbols);
+        if symbols.len() >= MAX_SYMBOLS {
+            break;
+        }
+    }
+    symbols
+}
+
+fn finish_symbol(current: &mut Option<(String, Scope)>, line_idx: usize, out: &mut Vec<Symbol>) {
+    let Some((name, scope)) = current.take() else {
+        return;
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        return;
+    }
+    // "entity.name.function.rust" -> "function"
+    let scope = scope.build_string();
+    let kind = scope.split('.').nth(2).unwrap_or("symbol").to_string();
+    out.push(Symbol {
+        name: name.to_string(),
+        kind,
+        line: line_idx as u32 + 1,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols_of(text: &str, ext: &str) -> Vec<(String, String, u32)> {
+        let set = SyntaxSet::load_defaults_newlines();
+        let syntax = set.find_syntax_by_extension(ext).unwrap();
+        extract_symbols(text, syntax, &set)
+            .into_iter()
+            .map(|s| (s.name, s.kind, s.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_symbols() {
+        let text =
+            "struct Point {\n    x: i32,\n}\n\nfn distance(a: &Point) -> i32 {\n    a.x\n}\n";
+        let symbols = symbols_of(text, "rs");
+        assert!(symbols.contains(&("Point".to_string(), "struct".to_string(), 1)));
+        assert!(symbols.contains(&("distance".to_string(), "function".to_string(), 5)));
+        assert!(symbols.iter().all(|(name, _, _)| name != "x"));
+    }
+
+    #[test]
+    fn test_plain_text_has_no_symbols() {
+        assert!(symbols_of("fn not_code() {}\n", "txt").is_empty());
+    }
+}
@@ -0,0 +1,446 @@
+//! User-defined tasks and problem matchers.
+//!
+//! A task is a named shell command (`cargo build`, `pytest`, `make`, ...)
+//! defined in the project's `.ferrispad/tasks.json` or in `AppSettings`.
+//! Its output is scanned with problem matchers — regexes with named groups —
+//! to turn compiler and test-runner messages into diagnostics.
+
+use std::path::{Component, Path, PathBuf};
+
+use regex_lite::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::app::plugins::{Diagnostic, DiagnosticLevel};
+
+/// Task file inside a project root
+pub const PROJECT_TASKS_FILE: &str = ".ferrispad/tasks.json";
+
+/// Where a task's process runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskRunMode {
+    /// In a terminal tab, so the output can be watched live
+    #[default]
+    Terminal,
+    /// In a hidden PTY; only the parsed problems are shown
+    Background,
+}
+
+/// A user-defined task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskDefinition {
+    pub name: String,
+    /// Shell command line, run through the user's login shell
+    pub command: String,
+    /// Working directory, relative to the project root (default: the root)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Built-in matcher names ("rustc", "gcc", "tsc", "pytest") or custom matchers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_matchers: Vec<MatcherSpec>,
+    #[serde(default)]
+    pub run_in: TaskRunMode,
+}
+
+impl TaskDefinition {
+    /// Directory the task runs in
+    pub fn working_dir(&self, root: &Path) -> PathBuf {
+        match self.cwd {
+            Some(ref dir) => normalize(&root.join(dir)),
+            None => root.to_path_buf(),
+        }
+    }
+}
+
+/// A problem matcher reference in a task definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MatcherSpec {
+    Builtin(String),
+    Custom(ProblemMatcher),
+}
+
+/// Regexes matched against consecutive output lines.
+///
+/// Named groups `file`, `line`, `column`, `severity` and `message` may appear
+/// in any of the patterns; `file` and `line` are required for a match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemMatcher {
+    pub patterns: Vec<String>,
+    /// Severity used when no `severity` group matched
+    #[serde(default = "default_severity")]
+    pub severity: String,
+}
+
+fn default_severity() -> String {
+    "error".to_string()
+}
+
+/// Layout of `.ferrispad/tasks.json`
+#[derive(Debug, Default, Deserialize)]
+struct TasksFile {
+    #[serde(default)]
+    tasks: Vec<TaskDefinition>,
+}
+
+/// Built-in problem matchers by name
+fn builtin_matchers(name: &str) -> Option<Vec<ProblemMatcher>> {
+    let matcher = |patterns: &[&str]| ProblemMatcher {
+        patterns: patterns.iter().map(|p| p.to_string()).collect(),
+        severity: default_severity(),
+    };
+    let matchers = match name {
+        // error[E0308]: mismatched types
+        //   --> src/main.rs:4:5
+        "rustc" | "cargo" => vec![matcher(&[
+            r"^(?P<severity>error|warning)(?:\[\w+\])?: (?P<message>.+)$",
+            r"^\s*--> (?P<file>.+?):(?P<line>\d+):(?P<column>\d+)$",
+        ])],
+        // main.c:3:5: error: unknown type name 'foo'
+        "gcc" | "clang" | "make" => vec![matcher(&[
+            r"^(?P<file>[^:\s][^:]*):(?P<line>\d+):(?:(?P<column>\d+):)? (?P<severity>fatal error|error|warning|note): (?P<message>.+)$",
+        ])],
+        // src/a.ts(3,14): error TS2304: Cannot find name 'x'.
+        // src/a.ts:3:14 - error TS2304: Cannot find name 'x'.
+        "tsc" => vec![
+            matcher(&[
+                r"^(?P<file>[^(\s][^(]*)\((?P<line>\d+),(?P<column>\d+)\): (?P<severity>error|warning) (?P<message>.+)$",
+            ]),
+            matcher(&[
+                r"^(?P<file>[^:\s][^:]*):(?P<line>\d+):(?P<column>\d+) - (?P<severity>error|warning) (?P<message>.+)$",
+            ]),
+        ],
+        // tests/test_api.py:120: AssertionError
+        "pytest" => vec![matcher(&[
+            r"^(?P<file>[^\s:][^:]*\.py):(?P<line>\d+): (?P<message>.+)$",
+        ])],
+        _ => return None,
+    };
+    Some(matchers)
+}
+
+/// A problem matcher ready to run
+#[derive(Debug)]
+pub struct CompiledMatcher {
+    patterns: Vec<Regex>,
+    severity: DiagnosticLevel,
+}
+
+/// Compile the matchers of a task. Unknown built-ins and invalid regexes are errors.
+pub fn compile_matchers(specs: &[MatcherSpec]) -> Result<Vec<CompiledMatcher>, String> {
+    let mut matchers = Vec::new();
+    for spec in specs {
+        let defs = match spec {
+            MatcherSpec::Builtin(name) => builtin_matchers(name)
+                .ok_or_else(|| format!("Unknown problem matcher '{}'", name))?,
+            MatcherSpec::Custom(m) => vec![m.clone()],
+        };
+        for def in defs {
+            if def.patterns.is_empty() {
+                return Err("Problem matcher has no patterns".to_string());
+            }
+            let patterns = def
+                .patterns
+                .iter()
+                .map(|p| Regex::new(p).map_err(|e| format!("Invalid pattern '{}': {}", p, e)))
+                .collect::<Result<Vec<_>, _>>()?;
+            matchers.push(CompiledMatcher {
+                patterns,
+                severity: parse_severity(&def.severity),
+            });
+        }
+    }
+    Ok(matchers)
+}
+
+fn parse_severity(s: &str) -> DiagnosticLevel {
+    let s = s.to_ascii_lowercase();
+    if s.contains("error") || s == "fatal" {
+        DiagnosticLevel::Error
+    } else if s.starts_with("warn") {
+        DiagnosticLevel::Warning
+    } else if s == "hint" {
+        DiagnosticLevel::Hint
+    } else {
+        DiagnosticLevel::Info
+    }
+}
+
+/// Turn raw PTY output into plain text lines: escape sequences are dropped
+/// and carriage-return redraws (progress bars) keep only the final text.
+pub fn output_lines(raw: &[u8]) -> Vec<String> {
+    struct PlainText {
+        lines: Vec<String>,
+        current: String,
+        carriage: bool,
+    }
+
+    impl vte::Perform for PlainText {
+        fn print(&mut self, c: char) {
+            if self.carriage {
+                self.current.clear();
+                self.carriage = false;
+            }
+            self.current.push(c);
+        }
+
+        fn execute(&mut self, byte: u8) {
+            match byte {
+                b'\n' => {
+                    self.lines.push(std::mem::take(&mut self.current));
+                    self.carriage = false;
+                }
+                b'\r' => self.carriage = true,
+                b'\t' => self.print('\t'),
+                _ => {}
+            }
+        }
+    }
+
+    let mut text = PlainText {
+        lines: Vec::new(),
+        current: String::new(),
+        carriage: false,
+    };
+    vte::Parser::new().advance(&mut text, raw);
+    if !text.current.is_empty() {
+        text.lines.push(text.current);
+    }
+    text.lines
+}
+
+/// Scan task output for problems. Relative paths are resolved against `base_dir`.
+pub fn parse_problems(
+    lines: &[String],
+    matchers: &[CompiledMatcher],
+    base_dir: &Path,
+    source: &str,
+) -> Vec<Diagnostic> {
+    let mut problems: Vec<Diagnostic> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let found = matchers
+            .iter()
+            .find_map(|m| match_at(m, lines, i, base_dir, source).map(|d| (d, m.patterns.len())));
+        match found {
+            Some((diag, consumed)) => {
+                let duplicate = problems.iter().any(|p| {
+                    p.file == diag.file
+                        && p.line == diag.line
+                        && p.column == diag.column
+                        && p.message == diag.message
+                });
+                if !duplicate {
+                    problems.push(diag);
+                }
+                i += consumed;
+            }
+            None => i += 1,
+        }
+    }
+    problems
+}
+
+/// Try one matcher on the lines starting at `start`
+fn match_at(
+    matcher: &CompiledMatcher,
+    lines: &[String],
+    start: usize,
+    base_dir: &Path,
+    source: &str,
+) -> Option<Diagnostic> {
+    let (mut file, mut line, mut column, mut severity, mut message) =
+        (None, None, None, None, None);
+    for (offset, re) in matcher.patterns.iter().enumerate() {
+        let caps = re.captures(lines.get(start + offset)?.trim_end())?;
+        let group = |name| caps.name(name).map(|m| m.as_str().to_string());
+        file = group("file").or(file);
+        line = group("line").and_then(|l| l.parse().ok()).or(line);
+        column = group("column").and_then(|c| c.parse().ok()).or(column);
+        severity = group("severity").or(severity);
+        message = group("message").or(message);
+    }
+
+    let path = PathBuf::from(file?.trim());
+    let path = if path.is_relative() {
+        base_dir.join(path)
+    } else {
+        path
+    };
+    Some(Diagnostic {
+        line: line.filter(|&l| l > 0)?,
+        column,
+        message: message.unwrap_or_default().trim().to_string(),
+        level: severity
+            .as_deref()
+            .map(parse_severity)
+            .unwrap_or(matcher.severity),
+        source: source.to_string(),
+        fix_message: None,
+        url: None,
+        file: Some(normalize(&path).to_string_lossy().into_owned()),
+    })
+}
+
+/// Drop `.` components and fold `..` so paths compare equal to open documents
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if out.file_name().is_some() => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Load the tasks defined in `<root>/.ferrispad/tasks.json`.
+/// A missing file means no tasks.
+pub fn load_project_tasks(root: &Path) -> Result<Vec<TaskDefinition>, String> {
+    let path = root.join(PROJECT_TASKS_FILE);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    serde_json::from_str::<TasksFile>(&content)
+        .map(|f| f.tasks)
+        .map_err(|e| format!("Invalid {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    fn problems(builtin: &str, text: &str) -> Vec<Diagnostic> {
+        let matchers = compile_matchers(&[MatcherSpec::Builtin(builtin.into())]).unwrap();
+        parse_problems(&lines(text), &matchers, Path::new("/proj"), "build")
+    }
+
+    #[test]
+    fn test_rustc_matcher() {
+        let out = "   Compiling demo v0.1.0\n\
+                   error[E0308]: mismatched types\n  \
+                   --> src/main.rs:4:5\n   |\n\
+                   warning: unused variable: `x`\n \
+                   --> src/lib.rs:2:9\n\
+                   error: could not compile `demo`";
+        let diags = problems("rustc", out);
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].file.as_deref(), Some("/proj/src/main.rs"));
+        assert_eq!((diags[0].line, diags[0].column), (4, Some(5)));
+        assert_eq!(diags[0].message, "mismatched types");
+        assert_eq!(diags[0].level, DiagnosticLevel::Error);
+        assert_eq!(diags[1].level, DiagnosticLevel::Warning);
+        assert_eq!(diags[1].source, "build");
+    }
+
+    #[test]
+    fn test_gcc_and_tsc_matchers() {
+        let diags = problems("gcc", "./main.c:3:5: fatal error: foo.h: No such file");
+        assert_eq!(diags[0].file.as_deref(), Some("/proj/main.c"));
+        assert_eq!(diags[0].level, DiagnosticLevel::Error);
+        assert_eq!(diags[0].message, "foo.h: No such file");
+
+        let diags = problems(
+            "tsc",
+            "src/a.ts(3,14): error TS2304: Cannot find name 'x'.\n\
+             lib/b.ts:7:1 - warning TS6133: 'y' is unused.",
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!((diags[0].line, diags[0].column), (3, Some(14)));
+        assert_eq!(diags[1].file.as_deref(), Some("/proj/lib/b.ts"));
+        assert_eq!(diags[1].level, DiagnosticLevel::Warning);
+    }
+
+    #[test]
+    fn test_pytest_matcher_dedups() {
+        let out = "tests/test_api.py:120: AssertionError\n\
+                   tests/test_api.py:120: AssertionError\n\
+                   FAILED tests/test_api.py::test_get - assert 1 == 2";
+        let diags = problems("pytest", out);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].line, 120);
+        assert_eq!(diags[0].column, None);
+    }
+
+    #[test]
+    fn test_custom_matcher() {
+        let spec: Vec<MatcherSpec> = serde_json::from_str(
+            r#"[{"patterns": ["^LINT (?P<file>\\S+) (?P<line>\\d+) (?P<message>.*)$"], "severity": "warning"}]"#,
+        )
+        .unwrap();
+        let matchers = compile_matchers(&spec).unwrap();
+        let diags = parse_problems(
+            &lines("LINT /abs/x.md 3 trailing space"),
+            &matchers,
+            Path::new("/proj"),
+            "lint",
+        );
+        assert_eq!(diags[0].file.as_deref(), Some("/abs/x.md"));
+        assert_eq!(diags[0].level, DiagnosticLevel::Warning);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert!(compile_matchers(&[MatcherSpec::Builtin("nope".into())]).is_err());
+        let bad = ProblemMatcher {
+            patterns: vec!["(".into()],
+            severity: default_severity(),
+        };
+        assert!(compile_matchers(&[MatcherSpec::Custom(bad)]).is_err());
+    }
+
+    #[test]
+    fn test_output_lines_strips_escapes() {
+        let raw = b"\x1b[1m\x1b[31merror\x1b[0m: bad\r\n 10%\r 100%\r\ndone";
+        assert_eq!(output_lines(raw), vec!["error: bad", " 100%", "done"]);
+    }
+
+    #[test]
+    fn test_task_definition_json() {
+        let file: TasksFile = serde_json::from_str(
+            r#"{"tasks": [
+                {"name": "build", "command": "cargo build", "problem_matchers": ["rustc"]},
+                {"name": "test", "command": "pytest -q", "cwd": "py", "run_in": "background"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(file.tasks.len(), 2);
+        assert_eq!(file.tasks[0].run_in, TaskRunMode::Terminal);
+        assert_eq!(
+            file.tasks[0].problem_matchers,
+            vec![MatcherSpec::Builtin("rustc".into())]
+        );
+        assert_eq!(file.tasks[1].run_in, TaskRunMode::Background);
+        assert_eq!(
+            file.tasks[1].working_dir(Path::new("/proj")),
+            PathBuf::from("/proj/py")
+        );
+    }
+
+    #[test]
+    fn test_load_project_tasks() {
+        let dir = std::env::temp_dir().join(format!("ferrispad_tasks_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join(".ferrispad")).unwrap();
+        assert!(load_project_tasks(&dir).unwrap().is_empty());
+
+        std::fs::write(
+            dir.join(PROJECT_TASKS_FILE),
+            r#"{"tasks": [{"name": "make", "command": "make"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(load_project_tasks(&dir).unwrap()[0].name, "make");
+
+        std::fs::write(dir.join(PROJECT_TASKS_FILE), "{ not json").unwrap();
+        assert!(load_project_tasks(&dir).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
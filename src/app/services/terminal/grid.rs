@@ -2,30 +2,107 @@
 //!
 //! Stores the visible terminal content as a 2D grid of cells,
 //! each with a character and color attributes. Includes a scrollback
-//! ring buffer for history.
+//! ring buffer for history, an alternate screen for full-screen apps and
+//! the input modes (mouse, bracketed paste) those apps enable.
+//!
+//! Wide (East Asian / emoji) characters occupy a `Wide` cell followed by a
+//! `Spacer` cell. Zero-width characters (combining marks, ZWJ sequences,
+//! variation selectors) are appended to the preceding cell's cluster.
+//! Soft-wrapped rows are flagged on their last cell so resizes can reflow
+//! the primary screen and scrollback.
+//!
+//! State reported by the shell through OSC sequences (title, working
+//! directory, hyperlinks, prompt marks, clipboard writes) is kept here too,
+//! for the panel to pick up after each batch of output.
+
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use fltk::enums::Color;
+use unicode_width::UnicodeWidthChar;
+
+use super::input::MouseMode;
 
 /// Maximum scrollback lines
 const MAX_SCROLLBACK: usize = 10_000;
 
+/// Zero width joiner — the next character joins the current cluster
+const ZWJ: char = '\u{200d}';
+
+/// How many columns a cell's character occupies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellWidth {
+    /// Regular single-column character
+    #[default]
+    Single,
+    /// First half of a double-width character
+    Wide,
+    /// Second half of a double-width character (never drawn)
+    Spacer,
+    /// Blank left at the end of a row when a wide character wrapped early
+    Padding,
+}
+
 /// A single cell in the terminal grid
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Cell {
     pub ch: char,
+    /// Zero-width characters following `ch` in the same grapheme cluster
+    pub combining: Option<Box<str>>,
     pub fg: Color,
     pub bg: Color,
     pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    pub width: CellWidth,
+    /// Set on the last cell of a row that soft-wraps into the next row
+    pub wrapped: bool,
+    /// OSC 8 hyperlink target
+    pub link: Option<Arc<str>>,
+    /// Start of a shell prompt (OSC 133;A). Survives the prompt being
+    /// printed over it.
+    pub prompt: bool,
 }
 
 impl Default for Cell {
     fn default() -> Self {
         Self {
             ch: ' ',
+            combining: None,
             fg: Color::XtermWhite,
             bg: Color::TransparentBg,
             bold: false,
+            dim: false,
+            italic: false,
+            underline: false,
+            strikethrough: false,
+            width: CellWidth::Single,
+            wrapped: false,
+            link: None,
+            prompt: false,
+        }
+    }
+}
+
+impl Cell {
+    /// The full grapheme cluster stored in this cell
+    pub fn text(&self) -> String {
+        let mut s = String::from(self.ch);
+        if let Some(ref extra) = self.combining {
+            s.push_str(extra);
         }
+        s
+    }
+
+    /// Whether the cell is an unstyled blank (safe to trim when reflowing)
+    fn is_blank(&self) -> bool {
+        self.ch == ' '
+            && self.combining.is_none()
+            && self.bg == Color::TransparentBg
+            && !self.underline
+            && !self.strikethrough
     }
 }
 
@@ -47,6 +124,14 @@ pub struct TerminalGrid {
     pub current_bg: Color,
     /// Current bold state
     pub current_bold: bool,
+    /// Current dim/faint state (SGR 2)
+    pub current_dim: bool,
+    /// Current italic state (SGR 3)
+    pub current_italic: bool,
+    /// Current underline state (SGR 4)
+    pub current_underline: bool,
+    /// Current strikethrough state (SGR 9)
+    pub current_strikethrough: bool,
     /// Scrollback buffer (oldest first)
     scrollback: Vec<Vec<Cell>>,
     /// Scroll offset from bottom (0 = at bottom, >0 = scrolled up)
@@ -61,6 +146,23 @@ pub struct TerminalGrid {
     pub current_reverse: bool,
     /// Saved cursor position (for DECSC/DECRC)
     saved_cursor: Option<(usize, usize)>,
+    /// Primary screen contents while the alternate screen is active
+    primary_cells: Option<Vec<Vec<Cell>>>,
+    /// Mouse tracking mode (CSI ?1000h / ?1002h)
+    pub mouse_mode: MouseMode,
+    /// SGR extended mouse coordinates (CSI ?1006h)
+    pub mouse_sgr: bool,
+    /// Bracketed paste mode (CSI ?2004h)
+    pub bracketed_paste: bool,
+    /// Window title set by the application (OSC 0 / OSC 2)
+    pub title: Option<String>,
+    /// Shell's current directory (OSC 7)
+    pub cwd: Option<PathBuf>,
+    /// Hyperlink applied to newly printed characters (OSC 8)
+    pub current_link: Option<Arc<str>>,
+    /// Text the application asked to put on the clipboard (OSC 52),
+    /// waiting to be taken by the UI
+    pub clipboard_request: Option<String>,
 }
 
 impl TerminalGrid {
@@ -76,6 +178,10 @@ impl TerminalGrid {
             current_fg: Color::XtermWhite,
             current_bg: Color::TransparentBg,
             current_bold: false,
+            current_dim: false,
+            current_italic: false,
+            current_underline: false,
+            current_strikethrough: false,
             scrollback: Vec::new(),
             scroll_offset: 0,
             scroll_top: 0,
@@ -83,13 +189,87 @@ impl TerminalGrid {
             cursor_visible: true,
             current_reverse: false,
             saved_cursor: None,
+            primary_cells: None,
+            mouse_mode: MouseMode::Off,
+            mouse_sgr: false,
+            bracketed_paste: false,
+            title: None,
+            cwd: None,
+            current_link: None,
+            clipboard_request: None,
         }
     }
 
-    /// Put a character at the current cursor position and advance
+    /// Whether the alternate screen is active
+    pub fn alt_screen_active(&self) -> bool {
+        self.primary_cells.is_some()
+    }
+
+    /// Switch to a blank alternate screen (CSI ?47h / ?1047h / ?1049h).
+    /// Output on the alternate screen never reaches the scrollback.
+    pub fn enter_alt_screen(&mut self) {
+        if self.primary_cells.is_some() {
+            return;
+        }
+        let blank = vec![vec![Cell::default(); self.cols]; self.rows];
+        self.primary_cells = Some(std::mem::replace(&mut self.cells, blank));
+        self.reset_scroll_region();
+    }
+
+    /// Return to the primary screen, discarding the alternate screen contents.
+    pub fn exit_alt_screen(&mut self) {
+        if let Some(primary) = self.primary_cells.take() {
+            self.cells = primary;
+            self.reset_scroll_region();
+        }
+    }
+
+    /// Reset input modes and leave the alternate screen (RIS)
+    pub fn reset_modes(&mut self) {
+        self.exit_alt_screen();
+        self.mouse_mode = MouseMode::Off;
+        self.mouse_sgr = false;
+        self.bracketed_paste = false;
+        self.cursor_visible = true;
+        self.current_link = None;
+    }
+
+    /// Mark the cursor position as the start of a shell prompt (OSC 133;A)
+    pub fn mark_prompt(&mut self) {
+        if self.cursor_row < self.rows && self.cols > 0 {
+            let col = self.cursor_col.min(self.cols - 1);
+            self.cells[self.cursor_row][col].prompt = true;
+        }
+    }
+
+    /// Put a character at the current cursor position and advance.
+    /// Wide characters take two cells; zero-width characters join the
+    /// previous cell's cluster.
     pub fn put_char(&mut self, ch: char) {
+        let width = char_width(ch);
+        if width == 0 || self.joins_previous() {
+            self.append_to_previous(ch);
+            return;
+        }
+        if self.cols == 0 || self.rows == 0 {
+            return;
+        }
+
+        // A wide char that doesn't fit in the last column wraps early,
+        // leaving a padding blank behind
+        if width == 2 && self.cols >= 2 && self.cursor_col == self.cols - 1 {
+            self.clear_wide_partner(self.cursor_row, self.cursor_col);
+            self.cells[self.cursor_row][self.cursor_col] = Cell {
+                width: CellWidth::Padding,
+                ..Cell::default()
+            };
+            self.cursor_col = self.cols;
+        }
         if self.cursor_col >= self.cols {
             // Auto-wrap
+            if let Some(last) = self.cells[self.cursor_row].last_mut() {
+                last.wrapped = true;
+            }
             self.cursor_col = 0;
             self.newline();
         }
@@ -105,13 +285,95 @@ impl TerminalGrid {
             } else {
                 (self.current_fg, self.current_bg)
             };
-            self.cells[self.cursor_row][self.cursor_col] = Cell {
+            let row = self.cursor_row;
+            let col = self.cursor_col;
+            let wide = width == 2 && col + 1 < self.cols;
+            self.clear_wide_partner(row, col);
+            if wide {
+                self.clear_wide_partner(row, col + 1);
+            }
+            let cell = Cell {
                 ch,
+                combining: None,
                 fg,
                 bg,
                 bold: self.current_bold,
+                dim: self.current_dim,
+                italic: self.current_italic,
+                underline: self.current_underline,
+                strikethrough: self.current_strikethrough,
+                width: if wide {
+                    CellWidth::Wide
+                } else {
+                    CellWidth::Single
+                },
+                wrapped: false,
+                link: self.current_link.clone(),
+                prompt: self.cells[row][col].prompt,
             };
-            self.cursor_col += 1;
+            if wide {
+                self.cells[row][col + 1] = Cell {
+                    ch: ' ',
+                    combining: None,
+                    width: CellWidth::Spacer,
+                    prompt: false,
+                    ..cell.clone()
+                };
+            }
+            self.cells[row][col] = cell;
+            self.cursor_col += if wide { 2 } else { 1 };
+        }
+    }
+
+    /// Position of the cell holding the most recently printed character
+    fn previous_cell(&self) -> Option<(usize, usize)> {
+        let row = self.cursor_row.min(self.rows.checked_sub(1)?);
+        let (row, col) = if self.cursor_col > 0 {
+            (row, self.cursor_col.min(self.cols) - 1)
+        } else if row > 0 && self.cells[row - 1].last().is_some_and(|c| c.wrapped) {
+            (row - 1, self.cols.checked_sub(1)?)
+        } else {
+            return None;
+        };
+        let col = match self.cells[row][col].width {
+            CellWidth::Spacer | CellWidth::Padding => col.checked_sub(1)?,
+            _ => col,
+        };
+        Some((row, col))
+    }
+
+    /// Whether the previous cell's cluster ends with a zero width joiner
+    fn joins_previous(&self) -> bool {
+        self.previous_cell().is_some_and(|(r, c)| {
+            self.cells[r][c]
+                .combining
+                .as_deref()
+                .is_some_and(|s| s.ends_with(ZWJ))
+        })
+    }
+
+    /// Append a zero-width (or joined) character to the previous cell
+    fn append_to_previous(&mut self, ch: char) {
+        let Some((row, col)) = self.previous_cell() else {
+            return;
+        };
+        let cell = &mut self.cells[row][col];
+        let mut cluster = cell.combining.take().map(String::from).unwrap_or_default();
+        cluster.push(ch);
+        cell.combining = Some(cluster.into_boxed_str());
+    }
+
+    /// Before overwriting (row, col), blank the other half of any wide
+    /// character it belongs to so no orphaned half is left behind.
+    fn clear_wide_partner(&mut self, row: usize, col: usize) {
+        match self.cells[row][col].width {
+            CellWidth::Wide if col + 1 < self.cols => {
+                self.cells[row][col + 1] = Cell::default();
+            }
+            CellWidth::Spacer if col > 0 => {
+                self.cells[row][col - 1] = Cell::default();
+            }
+            _ => {}
         }
     }
 
@@ -132,8 +394,8 @@ impl TerminalGrid {
     /// Scroll the screen up by n lines within the scroll region
     pub fn scroll_up(&mut self, n: usize) {
         for _ in 0..n {
-            // Move top line to scrollback
-            if self.scroll_top == 0 {
+            // Move top line to scrollback (the alternate screen has none)
+            if self.scroll_top == 0 && self.primary_cells.is_none() {
                 let line = self.cells[0].clone();
                 self.scrollback.push(line);
                 if self.scrollback.len() > MAX_SCROLLBACK {
@@ -328,25 +590,45 @@ impl TerminalGrid {
         }
     }
 
-    /// Resize the grid to new dimensions
+    /// Resize the grid to new dimensions.
+    ///
+    /// The primary screen and scrollback are reflowed: soft-wrapped lines are
+    /// joined and re-wrapped at the new width, keeping wide characters whole,
+    /// and the cursor follows its character. The alternate screen is simply
+    /// cropped or padded since full-screen apps redraw on SIGWINCH.
     pub fn resize(&mut self, new_cols: usize, new_rows: usize) {
-        // Adjust rows
-        while self.cells.len() < new_rows {
-            self.cells.push(vec![Cell::default(); new_cols]);
+        if new_cols == 0 || new_rows == 0 {
+            return;
         }
-        self.cells.truncate(new_rows);
-
-        // Adjust cols in each row
-        for row in &mut self.cells {
-            row.resize(new_cols, Cell::default());
+        if let Some(primary) = self.primary_cells.take() {
+            let cursor = self.saved_cursor.unwrap_or((0, 0));
+            let (screen, cursor) =
+                reflow(&mut self.scrollback, primary, cursor, new_cols, new_rows);
+            self.primary_cells = Some(screen);
+            if self.saved_cursor.is_some() {
+                self.saved_cursor = Some(cursor);
+            }
+            resize_cells(&mut self.cells, new_cols, new_rows);
+            self.cursor_row = self.cursor_row.min(new_rows - 1);
+            self.cursor_col = self.cursor_col.min(new_cols - 1);
+        } else {
+            let screen = std::mem::take(&mut self.cells);
+            let (screen, (row, col)) = reflow(
+                &mut self.scrollback,
+                screen,
+                (self.cursor_row, self.cursor_col),
+                new_cols,
+                new_rows,
+            );
+            self.cells = screen;
+            self.cursor_row = row;
+            self.cursor_col = col;
         }
 
         self.cols = new_cols;
         self.rows = new_rows;
-        self.cursor_row = self.cursor_row.min(new_rows.saturating_sub(1));
-        self.cursor_col = self.cursor_col.min(new_cols.saturating_sub(1));
         self.scroll_top = 0;
-        self.scroll_bottom = new_rows.saturating_sub(1);
+        self.scroll_bottom = new_rows - 1;
     }
 
     /// Get scrollback line count
@@ -380,10 +662,172 @@ impl TerminalGrid {
         self.current_fg = Color::XtermWhite;
         self.current_bg = Color::TransparentBg;
         self.current_bold = false;
+        self.current_dim = false;
+        self.current_italic = false;
+        self.current_underline = false;
+        self.current_strikethrough = false;
         self.current_reverse = false;
     }
 }
 
+/// Display width of a character: 0 (combining), 1, or 2 (wide)
+fn char_width(ch: char) -> usize {
+    // Emoji skin tone modifiers always attach to the preceding emoji
+    if ('\u{1F3FB}'..='\u{1F3FF}').contains(&ch) {
+        return 0;
+    }
+    ch.width().unwrap_or(1).min(2)
+}
+
+/// Resize a screen buffer to `new_rows` × `new_cols`, padding with blanks.
+/// Wide characters cut in half by the new width are blanked.
+fn resize_cells(cells: &mut Vec<Vec<Cell>>, new_cols: usize, new_rows: usize) {
+    while cells.len() < new_rows {
+        cells.push(vec![Cell::default(); new_cols]);
+    }
+    cells.truncate(new_rows);
+    for row in cells.iter_mut() {
+        row.resize(new_cols, Cell::default());
+        if let Some(last) = row.last_mut()
+            && last.width == CellWidth::Wide
+        {
+            *last = Cell::default();
+        }
+    }
+}
+
+/// Reflow `scrollback` + `screen` to `new_cols` × `new_rows`.
+///
+/// Returns the new screen and the cursor position; lines pushed above the
+/// screen are appended to `scrollback`.
+fn reflow(
+    scrollback: &mut Vec<Vec<Cell>>,
+    screen: Vec<Vec<Cell>>,
+    cursor: (usize, usize),
+    new_cols: usize,
+    new_rows: usize,
+) -> (Vec<Vec<Cell>>, (usize, usize)) {
+    let cursor_line = scrollback.len() + cursor.0;
+    let mut rows: Vec<Vec<Cell>> = std::mem::take(scrollback);
+    rows.extend(screen);
+
+    // Join soft-wrapped rows into logical lines, tracking the cursor's
+    // cell index within its logical line
+    let mut lines: Vec<Vec<Cell>> = Vec::new();
+    let mut cursor_at: Option<(usize, usize)> = None;
+    let mut current: Vec<Cell> = Vec::new();
+    for (i, row) in rows.into_iter().enumerate() {
+        let wrapped = row.last().is_some_and(|c| c.wrapped);
+        if i == cursor_line {
+            let before = row
+                .iter()
+                .take(cursor.1)
+                .filter(|c| c.width != CellWidth::Padding)
+                .count();
+            cursor_at = Some((lines.len(), current.len() + before));
+        }
+        current.extend(
+            row.into_iter()
+                .filter(|c| c.width != CellWidth::Padding)
+                .map(|mut c| {
+                    c.wrapped = false;
+                    c
+                }),
+        );
+        if !wrapped {
+            lines.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    // Drop blank lines below the cursor so shrinking the window doesn't push
+    // content into the scrollback just to keep empty rows visible
+    let (cursor_line, cursor_idx) = cursor_at.unwrap_or((lines.len(), 0));
+    while lines.len() > cursor_line + 1
+        && lines.last().is_some_and(|l| l.iter().all(Cell::is_blank))
+    {
+        lines.pop();
+    }
+
+    // Re-wrap every logical line at the new width
+    let mut out: Vec<Vec<Cell>> = Vec::new();
+    let mut new_cursor = (0, 0);
+    for (li, mut line) in lines.into_iter().enumerate() {
+        let keep = if li == cursor_line { cursor_idx } else { 0 };
+        while line.len() > keep && line.last().is_some_and(Cell::is_blank) {
+            line.pop();
+        }
+
+        let mut row: Vec<Cell> = Vec::with_capacity(new_cols);
+        let mut i = 0;
+        while i < line.len() {
+            let cell_width = if line[i].width == CellWidth::Wide {
+                2
+            } else {
+                1
+            };
+            if row.len() + cell_width > new_cols {
+                // Pad and wrap; a wide char never straddles two rows
+                if cell_width == 2 && new_cols >= 2 {
+                    row.resize(
+                        new_cols,
+                        Cell {
+                            width: CellWidth::Padding,
+                            ..Cell::default()
+                        },
+                    );
+                } else {
+                    row.resize(new_cols, Cell::default());
+                }
+                if let Some(last) = row.last_mut() {
+                    last.wrapped = true;
+                }
+                out.push(std::mem::replace(&mut row, Vec::with_capacity(new_cols)));
+            }
+            if li == cursor_line && i == cursor_idx {
+                new_cursor = (out.len(), row.len());
+            }
+            if cell_width == 2 && new_cols < 2 {
+                // Too narrow for a wide char: show a blank instead
+                row.push(Cell::default());
+                i += 2;
+                continue;
+            }
+            row.push(line[i].clone());
+            if cell_width == 2 {
+                row.push(line.get(i + 1).cloned().unwrap_or_default());
+            }
+            i += cell_width;
+        }
+        if li == cursor_line && cursor_idx >= line.len() {
+            // Cursor after the last character (e.g. at a shell prompt)
+            new_cursor = (out.len(), row.len().min(new_cols.saturating_sub(1)));
+        }
+        row.resize(new_cols, Cell::default());
+        out.push(row);
+    }
+    if cursor_line >= out.len() {
+        new_cursor = (out.len(), 0);
+    }
+
+    // The last `new_rows` rows (or fewer, padded) form the screen; anything
+    // above goes to the scrollback
+    let total = out.len().max(new_cursor.0 + 1);
+    let top = total.saturating_sub(new_rows);
+    let mut screen = out.split_off(top.min(out.len()));
+    screen.resize(new_rows, vec![Cell::default(); new_cols]);
+    if out.len() > MAX_SCROLLBACK {
+        out.drain(..out.len() - MAX_SCROLLBACK);
+    }
+    *scrollback = out;
+
+    let row = new_cursor.0.saturating_sub(top).min(new_rows - 1);
+    let col = new_cursor.1.min(new_cols - 1);
+    (screen, (row, col))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -483,4 +927,93 @@ mod tests {
         assert_eq!(grid.cells[0][3].ch, 'E');
         assert_eq!(grid.cells[0][4].ch, ' ');
     }
+
+    #[test]
+    fn test_alt_screen_preserves_primary_and_scrollback() {
+        let mut grid = TerminalGrid::new(4, 2);
+        grid.put_char('P');
+        grid.enter_alt_screen();
+        assert!(grid.alt_screen_active());
+        assert_eq!(grid.cells[0][0].ch, ' ');
+
+        // Scrolling on the alternate screen must not touch the scrollback
+        grid.put_char('A');
+        grid.cursor_row = 1;
+        grid.newline();
+        grid.newline();
+        assert_eq!(grid.scrollback_len(), 0);
+
+        grid.resize(6, 3);
+        grid.exit_alt_screen();
+        assert!(!grid.alt_screen_active());
+        assert_eq!(grid.cells[0][0].ch, 'P');
+        assert_eq!(grid.cells.len(), 3);
+        assert_eq!(grid.cells[0].len(), 6);
+    }
+
+    fn row_text(grid: &TerminalGrid, row: usize) -> String {
+        grid.cells[row]
+            .iter()
+            .filter(|c| c.width != CellWidth::Spacer)
+            .map(Cell::text)
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    #[test]
+    fn test_reflow_narrow_then_wide() {
+        let mut grid = TerminalGrid::new(6, 3);
+        for ch in "abcdefgh".chars() {
+            grid.put_char(ch);
+        }
+        assert_eq!(row_text(&grid, 0), "abcdef");
+        assert_eq!(row_text(&grid, 1), "gh");
+
+        // Widening joins the soft-wrapped line back together
+        grid.resize(10, 3);
+        assert_eq!(row_text(&grid, 0), "abcdefgh");
+        assert_eq!((grid.cursor_row, grid.cursor_col), (0, 8));
+
+        // Narrowing wraps it again, pushing the overflow into scrollback
+        grid.resize(3, 2);
+        assert_eq!(grid.scrollback_len(), 1);
+        assert_eq!(row_text(&grid, 0), "def");
+        assert_eq!(row_text(&grid, 1), "gh");
+        assert_eq!((grid.cursor_row, grid.cursor_col), (1, 2));
+    }
+
+    #[test]
+    fn test_reflow_keeps_wide_chars_whole() {
+        let mut grid = TerminalGrid::new(6, 2);
+        grid.put_char('a');
+        for ch in "漢字".chars() {
+            grid.put_char(ch);
+        }
+        // Width 4 would split 字 across rows: it moves down with padding
+        grid.resize(4, 3);
+        assert_eq!(row_text(&grid, 0), "a漢");
+        assert_eq!(grid.cells[0][3].width, CellWidth::Padding);
+        assert_eq!(grid.cells[1][0].ch, '字');
+        assert_eq!(grid.cells[1][1].width, CellWidth::Spacer);
+
+        // Widening again drops the padding
+        grid.resize(8, 3);
+        assert_eq!(row_text(&grid, 0), "a漢字");
+    }
+
+    #[test]
+    fn test_reflow_hard_newlines_stay_separate() {
+        let mut grid = TerminalGrid::new(10, 3);
+        for ch in "ab".chars() {
+            grid.put_char(ch);
+        }
+        grid.carriage_return();
+        grid.newline();
+        grid.put_char('c');
+        grid.resize(20, 3);
+        assert_eq!(row_text(&grid, 0), "ab");
+        assert_eq!(row_text(&grid, 1), "c");
+        assert_eq!((grid.cursor_row, grid.cursor_col), (1, 1));
+    }
 }
@@ -0,0 +1,169 @@
+//! Input encoding for terminal mouse reports and pastes.
+//!
+//! Pure functions that turn UI events into the byte sequences a TUI app
+//! expects, based on the modes it enabled (see `TerminalGrid`).
+
+/// Mouse tracking mode requested by the application (DEC private modes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MouseMode {
+    /// No mouse reporting
+    #[default]
+    Off,
+    /// Mode 1000: report button presses and releases
+    Press,
+    /// Mode 1002: also report motion while a button is held
+    ButtonMotion,
+}
+
+/// A mouse event to report to the application
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseReport {
+    /// Button pressed (0 = left, 1 = middle, 2 = right)
+    Press(u8),
+    /// Button released
+    Release(u8),
+    /// Motion with a button held
+    Drag(u8),
+    WheelUp,
+    WheelDown,
+}
+
+/// Modifier bits added to the button code. Shift (4) is never reported:
+/// Shift+mouse is kept for the terminal's own selection.
+pub const MOUSE_MOD_ALT: u8 = 8;
+pub const MOUSE_MOD_CTRL: u8 = 16;
+
+/// Bracketed paste start/end markers (mode 2004)
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Encode a mouse event for the given mode.
+///
+/// `col` and `row` are 0-indexed cell coordinates. Returns an empty vec if
+/// the mode does not report this kind of event. With `sgr` (mode 1006) the
+/// report is `ESC [ < b ; x ; y M/m`; otherwise the legacy X10 form
+/// `ESC [ M b x y`, whose coordinates cannot exceed 223.
+pub fn encode_mouse(
+    mode: MouseMode,
+    sgr: bool,
+    report: MouseReport,
+    col: usize,
+    row: usize,
+    modifiers: u8,
+) -> Vec<u8> {
+    let code = match report {
+        MouseReport::Press(b) | MouseReport::Release(b) => b,
+        MouseReport::Drag(b) => {
+            if mode != MouseMode::ButtonMotion {
+                return Vec::new();
+            }
+            b + 32
+        }
+        MouseReport::WheelUp => 64,
+        MouseReport::WheelDown => 65,
+    };
+    if mode == MouseMode::Off {
+        return Vec::new();
+    }
+    let code = code + modifiers;
+    let released = matches!(report, MouseReport::Release(_));
+
+    if sgr {
+        let suffix = if released { 'm' } else { 'M' };
+        format!("\x1b[<{};{};{}{}", code, col + 1, row + 1, suffix).into_bytes()
+    } else {
+        // Legacy encoding has no per-button release: it reports button 3
+        let code = if released { 3 + modifiers } else { code };
+        let x = (col + 1).min(223) as u8;
+        let y = (row + 1).min(223) as u8;
+        vec![0x1b, b'[', b'M', 32 + code, 32 + x, 32 + y]
+    }
+}
+
+/// Prepare pasted text for the PTY.
+///
+/// In bracketed paste mode the text is wrapped in `ESC [200~ … ESC [201~`,
+/// and any end marker inside the text is removed so a paste cannot break
+/// out of the bracket and inject commands.
+pub fn encode_paste(text: &str, bracketed: bool) -> Vec<u8> {
+    if !bracketed {
+        return text.as_bytes().to_vec();
+    }
+    let end = std::str::from_utf8(PASTE_END).unwrap_or_default();
+    let body = text.replace(end, "");
+    let mut out = Vec::with_capacity(body.len() + PASTE_START.len() + PASTE_END.len());
+    out.extend_from_slice(PASTE_START);
+    out.extend_from_slice(body.as_bytes());
+    out.extend_from_slice(PASTE_END);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mouse_off_reports_nothing() {
+        let bytes = encode_mouse(MouseMode::Off, true, MouseReport::Press(0), 3, 4, 0);
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn test_mouse_sgr_press_release() {
+        let press = encode_mouse(MouseMode::Press, true, MouseReport::Press(0), 9, 4, 0);
+        assert_eq!(press, b"\x1b[<0;10;5M");
+        let release = encode_mouse(MouseMode::Press, true, MouseReport::Release(2), 9, 4, 0);
+        assert_eq!(release, b"\x1b[<2;10;5m");
+    }
+
+    #[test]
+    fn test_mouse_legacy_encoding() {
+        let press = encode_mouse(MouseMode::Press, false, MouseReport::Press(0), 0, 0, 0);
+        assert_eq!(press, vec![0x1b, b'[', b'M', 32, 33, 33]);
+        let release = encode_mouse(MouseMode::Press, false, MouseReport::Release(0), 0, 0, 0);
+        assert_eq!(release, vec![0x1b, b'[', b'M', 35, 33, 33]);
+        // Coordinates are clamped so they still fit in a byte
+        let far = encode_mouse(MouseMode::Press, false, MouseReport::Press(0), 500, 0, 0);
+        assert_eq!(far[4], 32 + 223);
+    }
+
+    #[test]
+    fn test_mouse_drag_needs_button_motion_mode() {
+        let drag = MouseReport::Drag(0);
+        assert!(encode_mouse(MouseMode::Press, true, drag, 1, 1, 0).is_empty());
+        assert_eq!(
+            encode_mouse(MouseMode::ButtonMotion, true, drag, 1, 1, 0),
+            b"\x1b[<32;2;2M"
+        );
+    }
+
+    #[test]
+    fn test_mouse_wheel_and_modifiers() {
+        let up = encode_mouse(MouseMode::Press, true, MouseReport::WheelUp, 0, 0, 0);
+        assert_eq!(up, b"\x1b[<64;1;1M");
+        let ctrl_click = encode_mouse(
+            MouseMode::Press,
+            true,
+            MouseReport::Press(0),
+            0,
+            0,
+            MOUSE_MOD_CTRL,
+        );
+        assert_eq!(ctrl_click, b"\x1b[<16;1;1M");
+    }
+
+    #[test]
+    fn test_paste_plain_and_bracketed() {
+        assert_eq!(encode_paste("ls\n", false), b"ls\n");
+        assert_eq!(encode_paste("ls\n", true), b"\x1b[200~ls\n\x1b[201~");
+    }
+
+    #[test]
+    fn test_bracketed_paste_strips_end_marker() {
+        let evil = "echo hi\x1b[201~rm -rf ~\n";
+        assert_eq!(
+            encode_paste(evil, true),
+            b"\x1b[200~echo hirm -rf ~\n\x1b[201~"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Clickable link detection in terminal output.
+//!
+//! Recognizes URLs and `path:line[:col]` locations as printed by compilers,
+//! linters and test runners (plus Python's `File "x.py", line N`), so they
+//! can be Ctrl+clicked. Explicit OSC 8 hyperlinks on cells take precedence.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex_lite::Regex;
+
+use super::grid::Cell;
+use super::selection::row_text;
+
+/// What a link points to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    Url(String),
+    File {
+        path: String,
+        /// Line to jump to (None for plain file links)
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+}
+
+/// A link found in a row: columns `start..end`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub start: usize,
+    pub end: usize,
+    pub target: LinkTarget,
+}
+
+fn url_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"https?://[^\s<>"'`()\[\]{}]+"#).unwrap())
+}
+
+fn location_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"((?:[A-Za-z]:)?(?:[\w.~-]*[/\\])*[\w.-]*\w\.[A-Za-z0-9]+):(\d+)(?::(\d+))?")
+            .unwrap()
+    })
+}
+
+fn python_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"File "([^"]+)", line (\d+)"#).unwrap())
+}
+
+/// Find all links in a text line. Offsets are byte offsets into `text`.
+pub fn find_links_in_text(text: &str) -> Vec<(usize, usize, LinkTarget)> {
+    let mut links: Vec<(usize, usize, LinkTarget)> = Vec::new();
+
+    for m in url_regex().find_iter(text) {
+        // Trailing punctuation usually ends the sentence, not the URL
+        let url = m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        links.push((
+            m.start(),
+            m.start() + url.len(),
+            LinkTarget::Url(url.to_string()),
+        ));
+    }
+
+    let overlaps = |links: &[(usize, usize, LinkTarget)], s: usize, e: usize| {
+        links.iter().any(|(ls, le, _)| s < *le && *ls < e)
+    };
+
+    for caps in python_regex().captures_iter(text) {
+        let (Some(all), Some(path), Some(line)) = (caps.get(0), caps.get(1), caps.get(2)) else {
+            continue;
+        };
+        let Ok(line) = line.as_str().parse() else {
+            continue;
+        };
+        if !overlaps(&links, all.start(), all.end()) {
+            links.push((
+                path.start(),
+                all.end(),
+                LinkTarget::File {
+                    path: path.as_str().to_string(),
+                    line: Some(line),
+                    column: None,
+                },
+            ));
+        }
+    }
+
+    for caps in location_regex().captures_iter(text) {
+        let (Some(all), Some(path), Some(line)) = (caps.get(0), caps.get(1), caps.get(2)) else {
+            continue;
+        };
+        let Ok(line) = line.as_str().parse() else {
+            continue;
+        };
+        if line == 0 || overlaps(&links, all.start(), all.end()) {
+            continue;
+        }
+        let column = caps.get(3).and_then(|c| c.as_str().parse().ok());
+        links.push((
+            all.start(),
+            all.end(),
+            LinkTarget::File {
+                path: path.as_str().to_string(),
+                line: Some(line),
+                column,
+            },
+        ));
+    }
+
+    links.sort_by_key(|(s, _, _)| *s);
+    links
+}
+
+/// Target of an OSC 8 hyperlink URI: local `file://` URLs open in the
+/// editor, everything else in the browser.
+pub fn uri_target(uri: &str) -> LinkTarget {
+    match parse_file_url(uri.as_bytes()) {
+        Some(path) => LinkTarget::File {
+            path: path.to_string_lossy().into_owned(),
+            line: None,
+            column: None,
+        },
+        None => LinkTarget::Url(uri.to_string()),
+    }
+}
+
+/// Find the link covering column `col` of a terminal row, if any
+pub fn link_at(cells: &[Cell], col: usize) -> Option<Link> {
+    if let Some(uri) = cells.get(col).and_then(|c| c.link.as_ref()) {
+        let same = |c: &Cell| c.link.as_ref() == Some(uri);
+        let start = cells[..col]
+            .iter()
+            .rposition(|c| !same(c))
+            .map_or(0, |i| i + 1);
+        let end = cells[col..]
+            .iter()
+            .position(|c| !same(c))
+            .map_or(cells.len(), |i| col + i);
+        return Some(Link {
+            start,
+            end,
+            target: uri_target(uri),
+        });
+    }
+
+    let (text, cols) = row_text(cells);
+    find_links_in_text(&text)
+        .into_iter()
+        .map(|(s, e, target)| Link {
+            start: cols[s],
+            end: cols[e.saturating_sub(1)] + 1,
+            target,
+        })
+        .find(|l| col >= l.start && col < l.end)
+}
+
+/// Parse a `file://host/path` URL (OSC 7 directories, OSC 8 links) into a
+/// local path. The host is ignored; the path is percent-decoded.
+pub fn parse_file_url(url: &[u8]) -> Option<PathBuf> {
+    let rest = url.strip_prefix(b"file://")?;
+    let path = &rest[rest.iter().position(|&b| b == b'/')?..];
+
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut i = 0;
+    while i < path.len() {
+        if path[i] == b'%'
+            && let Some(hex) = path.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16)
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(path[i]);
+            i += 1;
+        }
+    }
+    let mut path = String::from_utf8(decoded).ok()?;
+    // Windows drive paths arrive as /C:/Users/...
+    if path.len() >= 3 && path.as_bytes()[2] == b':' && path.as_bytes()[1].is_ascii_alphabetic() {
+        path.remove(0);
+    }
+    Some(PathBuf::from(path))
+}
+
+/// Resolve a link path against the terminal's working directory.
+/// Returns None if the file does not exist.
+pub fn resolve_link_path(path: &str, cwd: Option<&Path>) -> Option<PathBuf> {
+    let expanded = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()?.join(rest),
+        None => PathBuf::from(path),
+    };
+    let full = match cwd {
+        Some(dir) if expanded.is_relative() => dir.join(&expanded),
+        _ => expanded,
+    };
+    full.is_file().then_some(full)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(text: &str) -> Vec<LinkTarget> {
+        find_links_in_text(text)
+            .into_iter()
+            .map(|(_, _, t)| t)
+            .collect()
+    }
+
+    fn file(path: &str, line: usize, column: Option<usize>) -> LinkTarget {
+        LinkTarget::File {
+            path: path.to_string(),
+            line: Some(line),
+            column,
+        }
+    }
+
+    #[test]
+    fn test_rust_compiler_location() {
+        assert_eq!(
+            targets("   --> src/app/main.rs:42:7"),
+            vec![file("src/app/main.rs", 42, Some(7))]
+        );
+    }
+
+    #[test]
+    fn test_test_failure_and_linter_locations() {
+        assert_eq!(
+            targets("FAILED tests/test_api.py:120: AssertionError"),
+            vec![file("tests/test_api.py", 120, None)]
+        );
+        assert_eq!(
+            targets("./lib/util.ts:3:14 - error TS2304"),
+            vec![file("./lib/util.ts", 3, Some(14))]
+        );
+    }
+
+    #[test]
+    fn test_python_traceback() {
+        assert_eq!(
+            targets(r#"  File "/tmp/app.py", line 9, in main"#),
+            vec![file("/tmp/app.py", 9, None)]
+        );
+    }
+
+    #[test]
+    fn test_urls() {
+        assert_eq!(
+            targets("see https://example.com/docs?a=1. Also http://localhost:8080/x"),
+            vec![
+                LinkTarget::Url("https://example.com/docs?a=1".into()),
+                LinkTarget::Url("http://localhost:8080/x".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_links() {
+        assert!(targets("12:30 meeting, ratio 3:4").is_empty());
+        assert!(targets("version 1.2.3").is_empty());
+    }
+
+    #[test]
+    fn test_link_at_columns() {
+        let mut grid = super::super::grid::TerminalGrid::new(40, 1);
+        for ch in "err: a.rs:3 x".chars() {
+            grid.put_char(ch);
+        }
+        let link = link_at(&grid.cells[0], 6).unwrap();
+        assert_eq!((link.start, link.end), (5, 11));
+        assert_eq!(link.target, file("a.rs", 3, None));
+        assert!(link_at(&grid.cells[0], 1).is_none());
+    }
+
+    #[test]
+    fn test_hyperlinked_cells() {
+        let mut grid = super::super::grid::TerminalGrid::new(40, 1);
+        for ch in "see ".chars() {
+            grid.put_char(ch);
+        }
+        grid.current_link = Some("file:///tmp/notes.txt".into());
+        for ch in "notes".chars() {
+            grid.put_char(ch);
+        }
+        grid.current_link = None;
+        grid.put_char('!');
+
+        let link = link_at(&grid.cells[0], 6).unwrap();
+        assert_eq!((link.start, link.end), (4, 9));
+        assert_eq!(
+            link.target,
+            LinkTarget::File {
+                path: "/tmp/notes.txt".into(),
+                line: None,
+                column: None,
+            }
+        );
+        assert!(link_at(&grid.cells[0], 9).is_none());
+        assert_eq!(
+            uri_target("https://example.com"),
+            LinkTarget::Url("https://example.com".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_file_url() {
+        assert_eq!(
+            parse_file_url(b"file://host/a%20b/c"),
+            Some(PathBuf::from("/a b/c"))
+        );
+        assert_eq!(
+            parse_file_url(b"file:///C:/Users/me"),
+            Some(PathBuf::from("C:/Users/me"))
+        );
+        assert_eq!(parse_file_url(b"https://example.com/"), None);
+    }
+}
@@ -4,5 +4,8 @@
 //! Zero cost when not instantiated (lazy loading).
 
 pub mod grid;
+pub mod input;
+pub mod links;
 pub mod pty;
+pub mod selection;
 pub mod vte_handler;
@@ -1,15 +1,116 @@
 //! PTY session management.
 //!
 //! Wraps `portable_pty` to spawn child processes in a pseudo-terminal.
+//! Commands run through the user's login shell so its profile (PATH, etc.)
+//! is loaded; arguments are quoted or passed as argv so the shell never
+//! re-interprets them.
 
 use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use std::collections::BTreeMap;
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Cloneable handle for sending input to a PTY from UI callbacks.
+#[derive(Clone)]
+pub struct PtyWriter(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl PtyWriter {
+    /// Write data to the PTY (keyboard input)
+    pub fn write(&self, data: &[u8]) {
+        if let Ok(mut w) = self.0.lock() {
+            let _ = w.write_all(data);
+            let _ = w.flush();
+        }
+    }
+}
+
+/// How a command and its arguments reach the login shell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArgvMode {
+    /// The command is a shell snippet (`npm run dev`, `make && ./app`);
+    /// each argument is quoted and appended to it
+    #[default]
+    Shell,
+    /// The command is a program run with the arguments as its argv,
+    /// without any shell parsing (`exec "$0" "$@"`). This needs a POSIX
+    /// shell, see `command_shell`.
+    Exec,
+}
+
+/// Login shells that understand `-lc 'exec "$0" "$@"' program args...`
+const POSIX_SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh", "mksh", "yash", "ash"];
+
+/// The shell that runs a command: `shell` (the user's `$SHELL`), unless the
+/// mode is Exec and it isn't POSIX-compatible (fish, nu, xonsh...). Exec
+/// mode then falls back to `/bin/sh`, so the program gets the environment
+/// of `/bin/sh -l` (`~/.profile`) rather than the user's shell config.
+pub fn command_shell(shell: &str, mode: ArgvMode) -> &str {
+    let name = std::path::Path::new(shell)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(shell);
+    if mode == ArgvMode::Exec && !POSIX_SHELLS.contains(&name) {
+        "/bin/sh"
+    } else {
+        shell
+    }
+}
+
+/// Quote an argument for a POSIX shell.
+///
+/// Plain words are left as they are; anything else is single-quoted, with
+/// embedded single quotes written as `'\''`. A leading `~/` stays outside
+/// the quotes so the home directory is still expanded.
+pub fn shell_quote(arg: &str) -> String {
+    if let Some(rest) = arg.strip_prefix("~/") {
+        return format!("~/{}", shell_quote(rest));
+    }
+    let plain = |c: char| c.is_ascii_alphanumeric() || "_-+=@%:,./".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// Arguments for `$SHELL` that run `command` with `args` in a login shell
+pub fn login_shell_args(command: &str, args: &[String], mode: ArgvMode) -> Vec<String> {
+    match mode {
+        ArgvMode::Shell => {
+            let mut line = command.to_string();
+            for arg in args {
+                line.push(' ');
+                line.push_str(&shell_quote(arg));
+            }
+            vec!["-lc".to_string(), line]
+        }
+        ArgvMode::Exec => {
+            let mut argv = vec![
+                "-lc".to_string(),
+                r#"exec "$0" "$@""#.to_string(),
+                command.to_string(),
+            ];
+            argv.extend(args.iter().cloned());
+            argv
+        }
+    }
+}
+
+/// Expand a leading `~` in a working directory to `home`
+fn expand_home(dir: &str, home: Option<&Path>) -> PathBuf {
+    match (dir.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(dir),
+    }
+}
 
 /// A PTY session wrapping a child process
 pub struct PtySession {
     /// Writer to send input to the child
-    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    writer: PtyWriter,
     /// The child process handle
     child: Arc<Mutex<Box<dyn portable_pty::Child + Send + Sync>>>,
     /// Master PTY (kept alive for resize; reader/writer already cloned from it)
@@ -17,12 +118,15 @@ pub struct PtySession {
 }
 
 impl PtySession {
-    /// Spawn a new PTY session with the given command.
+    /// Spawn a new PTY session with the given command (None = interactive
+    /// shell). `env` is added to the inherited environment.
     /// Returns the session and a reader for the PTY output.
     pub fn spawn(
         command: Option<&str>,
         args: &[String],
+        mode: ArgvMode,
         cwd: Option<&str>,
+        env: &BTreeMap<String, String>,
         cols: u16,
         rows: u16,
     ) -> Result<(Self, Box<dyn Read + Send>), String> {
@@ -42,25 +146,22 @@ impl PtySession {
             // Wrap in a login shell so the user's profile (PATH, etc.) is loaded.
             // This is essential when FerrisPad is launched from a desktop icon
             // where the session environment may be minimal.
-            let mut full_cmd = String::from(program);
-            for arg in args {
-                full_cmd.push(' ');
-                full_cmd.push_str(arg);
-            }
-            let mut c = CommandBuilder::new(&shell);
-            c.arg("-lc");
-            c.arg(&full_cmd);
+            let mut c = CommandBuilder::new(command_shell(&shell, mode));
+            c.args(login_shell_args(program, args, mode));
             c
         } else {
             // Interactive shell — no wrapping needed
             CommandBuilder::new(&shell)
         };
         if let Some(dir) = cwd {
-            cmd.cwd(dir);
+            cmd.cwd(expand_home(dir, dirs::home_dir().as_deref()));
         }
 
         // Set TERM for proper terminal emulation
         cmd.env("TERM", "xterm-256color");
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
 
         let child = pair.slave.spawn_command(cmd).map_err(|e| {
             let label = command.unwrap_or(&shell);
@@ -81,7 +182,7 @@ impl PtySession {
         // pair.master stays valid for resize even after take_writer
         Ok((
             Self {
-                writer: Arc::new(Mutex::new(writer)),
+                writer: PtyWriter(Arc::new(Mutex::new(writer))),
                 child: Arc::new(Mutex::new(child)),
                 master: pair.master,
             },
@@ -91,10 +192,12 @@ impl PtySession {
 
     /// Write data to the PTY (keyboard input)
     pub fn write(&self, data: &[u8]) {
-        if let Ok(mut w) = self.writer.lock() {
-            let _ = w.write_all(data);
-            let _ = w.flush();
-        }
+        self.writer.write(data);
+    }
+
+    /// Get a cloneable writer handle for this session
+    pub fn writer(&self) -> PtyWriter {
+        self.writer.clone()
     }
 
     /// Resize the PTY
@@ -116,6 +219,21 @@ impl PtySession {
         }
     }
 
+    /// Wait up to `timeout` for the child to exit and return its exit code.
+    /// Used after the output reached EOF, when the child is about to be reaped.
+    pub fn wait_exit(&self, timeout: Duration) -> Option<u32> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(code) = self.try_wait() {
+                return Some(code);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
     /// Kill the child process
     pub fn kill(&self) {
         if let Ok(mut child) = self.child.lock() {
@@ -129,3 +247,136 @@ impl Drop for PtySession {
         self.kill();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tricky arguments: spaces, quotes, `$`, globs, backslashes, newlines
+    fn tricky_args() -> Vec<String> {
+        [
+            "My Projects",
+            "it's",
+            "say \"hi\"",
+            "$HOME",
+            "`id`",
+            "*.rs",
+            "a\\b",
+            "line1\nline2",
+            "",
+            "--flag=value",
+            "; rm -rf /",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("src/main.rs"), "src/main.rs");
+        assert_eq!(shell_quote("--flag=value"), "--flag=value");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("My Projects"), "'My Projects'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("$HOME"), "'$HOME'");
+        assert_eq!(shell_quote("~/My Projects"), "~/'My Projects'");
+        assert_eq!(shell_quote("~user"), "'~user'");
+    }
+
+    #[test]
+    fn test_login_shell_args() {
+        let args = vec!["a b".to_string(), "c".to_string()];
+        assert_eq!(
+            login_shell_args("npm run", &args, ArgvMode::Shell),
+            vec!["-lc", "npm run 'a b' c"]
+        );
+        assert_eq!(
+            login_shell_args("my tool", &args, ArgvMode::Exec),
+            vec!["-lc", r#"exec "$0" "$@""#, "my tool", "a b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_exec_mode_needs_a_posix_shell() {
+        assert_eq!(
+            command_shell("/usr/bin/zsh", ArgvMode::Exec),
+            "/usr/bin/zsh"
+        );
+        assert_eq!(command_shell("/bin/bash", ArgvMode::Exec), "/bin/bash");
+        assert_eq!(command_shell("/usr/bin/fish", ArgvMode::Exec), "/bin/sh");
+        assert_eq!(command_shell("nu", ArgvMode::Exec), "/bin/sh");
+        // Shell snippets are written for the user's own shell
+        assert_eq!(
+            command_shell("/usr/bin/fish", ArgvMode::Shell),
+            "/usr/bin/fish"
+        );
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = Some(Path::new("/home/tester"));
+        assert_eq!(expand_home("~", home), PathBuf::from("/home/tester"));
+        assert_eq!(
+            expand_home("~/src/app", home),
+            PathBuf::from("/home/tester/src/app")
+        );
+        assert_eq!(expand_home("~other/src", home), PathBuf::from("~other/src"));
+        assert_eq!(expand_home("~/src", None), PathBuf::from("~/src"));
+        assert_eq!(expand_home("/tmp", home), PathBuf::from("/tmp"));
+    }
+
+    /// Run `sh -c <script> <argv...>` with `HOME` set to `home` and return
+    /// what it printed
+    #[cfg(unix)]
+    fn run_sh(shell_args: &[String], home: &str) -> String {
+        // `-lc` would load the tester's profile; `-c` behaves the same otherwise
+        let mut args = shell_args.to_vec();
+        args[0] = "-c".to_string();
+        let out = std::process::Command::new("/bin/sh")
+            .args(&args)
+            .env("HOME", home)
+            .output()
+            .unwrap();
+        String::from_utf8(out.stdout).unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_quoted_args_survive_the_shell() {
+        let args = tricky_args();
+        let printed = run_sh(
+            &login_shell_args("printf '[%s]\\n'", &args, ArgvMode::Shell),
+            "/home/tester",
+        );
+        let expected: String = args.iter().map(|a| format!("[{}]\n", a)).collect();
+        assert_eq!(printed, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exec_args_survive_the_shell() {
+        let mut args = vec!["[%s]\n".to_string()];
+        args.extend(tricky_args());
+        let printed = run_sh(
+            &login_shell_args("printf", &args, ArgvMode::Exec),
+            "/home/tester",
+        );
+        let expected: String = tricky_args().iter().map(|a| format!("[{}]\n", a)).collect();
+        assert_eq!(printed, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_home_is_expanded() {
+        let printed = run_sh(
+            &login_shell_args(
+                "printf '%s'",
+                &["~/My Projects".to_string()],
+                ArgvMode::Shell,
+            ),
+            "/home/tester",
+        );
+        assert_eq!(printed, "/home/tester/My Projects");
+    }
+}
@@ -0,0 +1,297 @@
+//! Text selection and search over terminal content.
+//!
+//! Positions are `(line, col)` pairs in the combined buffer where lines
+//! `0..scrollback.len()` are scrollback (oldest first) and the visible
+//! screen follows.
+
+use super::grid::{Cell, CellWidth};
+
+/// A cell position: (line in scrollback + screen, column)
+pub type Point = (usize, usize);
+
+/// A mouse selection from `anchor` (where the drag started) to `head`.
+/// Both ends are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    pub anchor: Point,
+    pub head: Point,
+}
+
+impl Selection {
+    /// Start a selection at a single cell
+    pub fn new(at: Point) -> Self {
+        Self {
+            anchor: at,
+            head: at,
+        }
+    }
+
+    /// Start and end in reading order
+    pub fn bounds(&self) -> (Point, Point) {
+        if self.anchor <= self.head {
+            (self.anchor, self.head)
+        } else {
+            (self.head, self.anchor)
+        }
+    }
+
+    /// Whether the cell at (line, col) is selected
+    pub fn contains(&self, line: usize, col: usize) -> bool {
+        let (start, end) = self.bounds();
+        (line, col) >= start && (line, col) <= end
+    }
+}
+
+/// A search hit: columns `start..end` of `line`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Look up a line in the combined scrollback + screen buffer
+pub fn line_at<'a>(
+    scrollback: &'a [Vec<Cell>],
+    screen: &'a [Vec<Cell>],
+    idx: usize,
+) -> Option<&'a [Cell]> {
+    if idx < scrollback.len() {
+        Some(&scrollback[idx])
+    } else {
+        screen.get(idx - scrollback.len()).map(|l| l.as_slice())
+    }
+}
+
+/// Text of a row plus the column of every byte in it.
+///
+/// `cols[i]` is the column of the cell that byte `i` belongs to, with one
+/// extra entry for the end of the text, so byte ranges found in the text
+/// map back to cell columns. Wide-char spacers and wrap padding are skipped.
+pub fn row_text(cells: &[Cell]) -> (String, Vec<usize>) {
+    let mut text = String::with_capacity(cells.len());
+    let mut cols = Vec::with_capacity(cells.len() + 1);
+    for (col, cell) in cells.iter().enumerate() {
+        if matches!(cell.width, CellWidth::Spacer | CellWidth::Padding) {
+            continue;
+        }
+        let before = text.len();
+        text.push(cell.ch);
+        if let Some(ref extra) = cell.combining {
+            text.push_str(extra);
+        }
+        cols.extend(std::iter::repeat_n(col, text.len() - before));
+    }
+    cols.push(cells.len());
+    (text, cols)
+}
+
+/// Bounds (inclusive) of the word under `col`, for double-click selection.
+/// Words include path punctuation so file paths select in one go.
+pub fn word_bounds(cells: &[Cell], col: usize) -> (usize, usize) {
+    let is_word = |c: &Cell| {
+        c.width == CellWidth::Spacer || !(c.ch.is_whitespace() || "\"'`()[]{}<>,;|".contains(c.ch))
+    };
+    let Some(cell) = cells.get(col) else {
+        return (col, col);
+    };
+    if !is_word(cell) {
+        return (col, col);
+    }
+    let mut start = col;
+    while start > 0 && is_word(&cells[start - 1]) {
+        start -= 1;
+    }
+    let mut end = col;
+    while end + 1 < cells.len() && is_word(&cells[end + 1]) {
+        end += 1;
+    }
+    (start, end)
+}
+
+/// Extract the selected text. Rows are joined with newlines except where
+/// the terminal soft-wrapped them; trailing blanks on each row are dropped.
+pub fn selected_text(scrollback: &[Vec<Cell>], screen: &[Vec<Cell>], sel: &Selection) -> String {
+    let ((start_line, start_col), (end_line, end_col)) = sel.bounds();
+    let mut out = String::new();
+    for line in start_line..=end_line {
+        let Some(cells) = line_at(scrollback, screen, line) else {
+            break;
+        };
+        let from = if line == start_line { start_col } else { 0 };
+        let to = if line == end_line {
+            (end_col + 1).min(cells.len())
+        } else {
+            cells.len()
+        };
+        let (text, _) = row_text(cells.get(from..to).unwrap_or_default());
+        let wrapped = to == cells.len() && cells.last().is_some_and(|c| c.wrapped);
+        if wrapped {
+            out.push_str(&text);
+        } else {
+            out.push_str(text.trim_end());
+            if line != end_line {
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// Find all occurrences of `query`, oldest first.
+///
+/// Smart case: the search is case-insensitive (ASCII) unless the query
+/// contains an uppercase letter.
+pub fn find_matches(
+    scrollback: &[Vec<Cell>],
+    screen: &[Vec<Cell>],
+    query: &str,
+) -> Vec<SearchMatch> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let case_sensitive = query.chars().any(|c| c.is_uppercase());
+    let needle = if case_sensitive {
+        query.to_string()
+    } else {
+        query.to_ascii_lowercase()
+    };
+
+    let mut matches = Vec::new();
+    for line in 0..scrollback.len() + screen.len() {
+        let Some(cells) = line_at(scrollback, screen, line) else {
+            break;
+        };
+        let (mut text, cols) = row_text(cells);
+        if !case_sensitive {
+            text.make_ascii_lowercase();
+        }
+        for (byte, _) in text.match_indices(&needle) {
+            matches.push(SearchMatch {
+                line,
+                start: cols[byte],
+                end: cols[byte + needle.len() - 1] + 1,
+            });
+        }
+    }
+    matches
+}
+
+/// Lines holding a shell prompt mark (OSC 133;A), oldest first
+pub fn prompt_lines(scrollback: &[Vec<Cell>], screen: &[Vec<Cell>]) -> Vec<usize> {
+    scrollback
+        .iter()
+        .chain(screen)
+        .enumerate()
+        .filter(|(_, row)| row.iter().any(|c| c.prompt))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::grid::TerminalGrid;
+    use super::*;
+
+    fn feed(grid: &mut TerminalGrid, text: &str) {
+        for ch in text.chars() {
+            if ch == '\n' {
+                grid.carriage_return();
+                grid.newline();
+            } else {
+                grid.put_char(ch);
+            }
+        }
+    }
+
+    fn grid_with(cols: usize, rows: usize, text: &str) -> TerminalGrid {
+        let mut grid = TerminalGrid::new(cols, rows);
+        feed(&mut grid, text);
+        grid
+    }
+
+    #[test]
+    fn test_selection_bounds_and_contains() {
+        let sel = Selection {
+            anchor: (2, 5),
+            head: (1, 3),
+        };
+        assert_eq!(sel.bounds(), ((1, 3), (2, 5)));
+        assert!(sel.contains(1, 3));
+        assert!(sel.contains(1, 70));
+        assert!(sel.contains(2, 5));
+        assert!(!sel.contains(2, 6));
+        assert!(!sel.contains(0, 10));
+    }
+
+    #[test]
+    fn test_selected_text_multiline() {
+        let grid = grid_with(10, 3, "hello\nworld");
+        let sel = Selection {
+            anchor: (0, 1),
+            head: (1, 2),
+        };
+        assert_eq!(selected_text(&[], &grid.cells, &sel), "ello\nwor");
+    }
+
+    #[test]
+    fn test_selected_text_joins_soft_wraps() {
+        let grid = grid_with(4, 3, "abcdefg");
+        let sel = Selection {
+            anchor: (0, 0),
+            head: (1, 3),
+        };
+        assert_eq!(selected_text(&[], &grid.cells, &sel), "abcdefg");
+    }
+
+    #[test]
+    fn test_word_bounds() {
+        let grid = grid_with(40, 1, "error at src/main.rs:10 (x)");
+        let row = &grid.cells[0];
+        assert_eq!(word_bounds(row, 12), (9, 22));
+        assert_eq!(word_bounds(row, 0), (0, 4));
+        // On a delimiter: just that cell
+        assert_eq!(word_bounds(row, 24), (24, 24));
+    }
+
+    #[test]
+    fn test_find_matches_smart_case() {
+        let grid = grid_with(20, 3, "Error: one\nerror: two");
+        let scrollback = vec![grid.cells[0].clone()];
+        let screen = &grid.cells[1..];
+
+        let all = find_matches(&scrollback, screen, "error");
+        assert_eq!(all.len(), 2);
+        assert_eq!(
+            all[0],
+            SearchMatch {
+                line: 0,
+                start: 0,
+                end: 5
+            }
+        );
+        assert_eq!(all[1].line, 1);
+
+        let exact = find_matches(&scrollback, screen, "Error");
+        assert_eq!(exact.len(), 1);
+        assert!(find_matches(&scrollback, screen, "").is_empty());
+    }
+
+    #[test]
+    fn test_find_matches_wide_columns() {
+        let grid = grid_with(20, 1, "漢字 ok");
+        let m = find_matches(&[], &grid.cells, "ok");
+        assert_eq!(m[0].start, 5);
+        assert_eq!(m[0].end, 7);
+    }
+
+    #[test]
+    fn test_prompt_lines() {
+        let mut grid = TerminalGrid::new(20, 4);
+        grid.mark_prompt();
+        feed(&mut grid, "$ make\nbuilt\n");
+        grid.mark_prompt();
+        let scrollback = vec![Vec::new()];
+        assert_eq!(prompt_lines(&scrollback, &grid.cells), vec![1, 3]);
+    }
+}
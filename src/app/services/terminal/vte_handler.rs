@@ -3,9 +3,13 @@
 //! Implements the `vte::Perform` trait to translate terminal escape
 //! sequences into grid operations.
 
+use std::sync::Arc;
+
 use fltk::enums::Color;
 
 use super::grid::TerminalGrid;
+use super::input::MouseMode;
+use super::links::parse_file_url;
 
 /// Maps ANSI color index (0-7 normal, 8-15 bright) to FLTK Color
 fn ansi_to_color(idx: u8) -> Color {
@@ -83,8 +87,59 @@ impl vte::Perform for VteHandler<'_> {
 
     fn unhook(&mut self) {}
 
-    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {
-        // OSC sequences (title changes, etc.) — ignore for now
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        let Some((&code, args)) = params.split_first() else {
+            return;
+        };
+        // Arguments may themselves contain ';' (titles, URIs)
+        let joined = |parts: &[&[u8]]| {
+            parts
+                .iter()
+                .map(|p| String::from_utf8_lossy(p))
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+        match code {
+            // Icon name + window title / window title
+            b"0" | b"2" => {
+                let title: String = joined(args).chars().filter(|c| !c.is_control()).collect();
+                self.grid.title = Some(title).filter(|t| !t.trim().is_empty());
+            }
+            // Current directory as a file:// URL
+            b"7" => {
+                if let Some(dir) = args.first().and_then(|url| parse_file_url(url)) {
+                    self.grid.cwd = Some(dir);
+                }
+            }
+            // Hyperlink: OSC 8 ; params ; URI — an empty URI ends the link
+            b"8" => {
+                let uri = args.get(1..).map(joined).unwrap_or_default();
+                self.grid.current_link = if uri.is_empty() {
+                    None
+                } else {
+                    Some(Arc::from(uri))
+                };
+            }
+            // Clipboard write: OSC 52 ; selection ; base64. Reading the
+            // clipboard ("?") is never answered.
+            b"52" => {
+                if let Some(&data) = args.get(1)
+                    && data != b"?"
+                    && let Ok(bytes) =
+                        base64::Engine::decode(&base64::engine::general_purpose::STANDARD, data)
+                    && let Ok(text) = String::from_utf8(bytes)
+                {
+                    self.grid.clipboard_request = Some(text);
+                }
+            }
+            // Shell integration: A marks the start of a prompt
+            b"133" => {
+                if args.first().is_some_and(|a| a.first() == Some(&b'A')) {
+                    self.grid.mark_prompt();
+                }
+            }
+            _ => {}
+        }
     }
 
     fn csi_dispatch(
@@ -176,10 +231,12 @@ impl vte::Perform for VteHandler<'_> {
             // DECSC/DECRC via CSI
             's' => self.grid.save_cursor(),
             'u' => self.grid.restore_cursor(),
-            // SM/RM — set/reset mode (handle cursor visibility)
+            // SM/RM — set/reset mode (DEC private modes only)
             'h' | 'l' => {
-                if intermediates == b"?" && p0 == 25 {
-                    self.grid.cursor_visible = action == 'h';
+                if intermediates == b"?" {
+                    for &mode in &params {
+                        self.set_private_mode(mode, action == 'h');
+                    }
                 }
             }
             _ => {}
@@ -205,6 +262,7 @@ impl vte::Perform for VteHandler<'_> {
                 self.grid.clear_screen();
                 self.grid.set_cursor(1, 1);
                 self.grid.reset_attrs();
+                self.grid.reset_modes();
                 self.grid.reset_scroll_region();
             }
             _ => {}
@@ -213,6 +271,45 @@ impl vte::Perform for VteHandler<'_> {
 }
 
 impl VteHandler<'_> {
+    /// Apply a DEC private mode (CSI ? n h / CSI ? n l)
+    fn set_private_mode(&mut self, mode: u16, enable: bool) {
+        match mode {
+            // DECTCEM — cursor visibility
+            25 => self.grid.cursor_visible = enable,
+            // Alternate screen without cursor save
+            47 | 1047 => {
+                if enable {
+                    self.grid.enter_alt_screen();
+                } else {
+                    self.grid.exit_alt_screen();
+                }
+            }
+            // Alternate screen with cursor save/restore (vim, less, htop)
+            1049 => {
+                if enable {
+                    self.grid.save_cursor();
+                    self.grid.enter_alt_screen();
+                } else {
+                    self.grid.exit_alt_screen();
+                    self.grid.restore_cursor();
+                }
+            }
+            // Mouse tracking: press/release, then button motion
+            1000 | 1002 => {
+                self.grid.mouse_mode = match (enable, mode) {
+                    (false, _) => MouseMode::Off,
+                    (true, 1000) => MouseMode::Press,
+                    (true, _) => MouseMode::ButtonMotion,
+                };
+            }
+            // SGR extended mouse coordinates
+            1006 => self.grid.mouse_sgr = enable,
+            // Bracketed paste
+            2004 => self.grid.bracketed_paste = enable,
+            _ => {}
+        }
+    }
+
     /// Handle SGR (Select Graphic Rendition) parameters
     fn handle_sgr(&mut self, params: &[u16]) {
         if params.is_empty() {
@@ -225,11 +322,23 @@ impl VteHandler<'_> {
             match params[i] {
                 0 => self.grid.reset_attrs(),
                 1 => self.grid.current_bold = true,
+                2 => self.grid.current_dim = true,
+                3 => self.grid.current_italic = true,
+                // Underline (21 = double underline, drawn as single)
+                4 | 21 => self.grid.current_underline = true,
                 // Reverse video
                 7 => self.grid.current_reverse = true,
-                22 => self.grid.current_bold = false,
+                9 => self.grid.current_strikethrough = true,
+                // Normal intensity: neither bold nor dim
+                22 => {
+                    self.grid.current_bold = false;
+                    self.grid.current_dim = false;
+                }
+                23 => self.grid.current_italic = false,
+                24 => self.grid.current_underline = false,
                 // Reset reverse video
                 27 => self.grid.current_reverse = false,
+                29 => self.grid.current_strikethrough = false,
                 // Foreground 30-37
                 30..=37 => self.grid.current_fg = ansi_to_color(params[i] as u8 - 30),
                 // Bright foreground 90-97
@@ -297,6 +406,7 @@ impl VteHandler<'_> {
 
 #[cfg(test)]
 mod tests {
+    use super::super::grid::CellWidth;
     use super::*;
 
     fn process_bytes(grid: &mut TerminalGrid, bytes: &[u8]) {
@@ -383,4 +493,186 @@ mod tests {
         assert_eq!(grid.cells[0][0].ch, 'X');
         assert_eq!(grid.cells[0][0].fg, Color::from_rgb(100, 150, 200));
     }
+
+    #[test]
+    fn test_alt_screen_1049() {
+        let mut grid = TerminalGrid::new(10, 3);
+        process_bytes(&mut grid, b"shell$ \x1b[?1049h");
+        assert!(grid.alt_screen_active());
+        assert_eq!(grid.cells[0][0].ch, ' ');
+
+        // Full-screen app output scrolls without touching the scrollback
+        process_bytes(&mut grid, b"\x1b[HA\r\nB\r\nC\r\nD");
+        assert_eq!(grid.scrollback_len(), 0);
+
+        process_bytes(&mut grid, b"\x1b[?1049l");
+        assert!(!grid.alt_screen_active());
+        assert_eq!(grid.cells[0][0].ch, 's');
+        // Cursor restored to where the shell left it
+        assert_eq!(grid.cursor_row, 0);
+        assert_eq!(grid.cursor_col, 7);
+    }
+
+    #[test]
+    fn test_alt_screen_47() {
+        let mut grid = TerminalGrid::new(10, 3);
+        process_bytes(&mut grid, b"X\x1b[?47hY");
+        assert_eq!(grid.cells[0][1].ch, 'Y');
+        process_bytes(&mut grid, b"\x1b[?47l");
+        assert_eq!(grid.cells[0][0].ch, 'X');
+        assert_eq!(grid.cells[0][1].ch, ' ');
+    }
+
+    #[test]
+    fn test_bracketed_paste_mode() {
+        let mut grid = TerminalGrid::new(80, 24);
+        assert!(!grid.bracketed_paste);
+        process_bytes(&mut grid, b"\x1b[?2004h");
+        assert!(grid.bracketed_paste);
+        process_bytes(&mut grid, b"\x1b[?2004l");
+        assert!(!grid.bracketed_paste);
+    }
+
+    #[test]
+    fn test_mouse_modes() {
+        let mut grid = TerminalGrid::new(80, 24);
+        // Several modes in one sequence, as TUI frameworks send them
+        process_bytes(&mut grid, b"\x1b[?1000;1006h");
+        assert_eq!(grid.mouse_mode, MouseMode::Press);
+        assert!(grid.mouse_sgr);
+
+        process_bytes(&mut grid, b"\x1b[?1002h");
+        assert_eq!(grid.mouse_mode, MouseMode::ButtonMotion);
+
+        process_bytes(&mut grid, b"\x1b[?1002;1006l");
+        assert_eq!(grid.mouse_mode, MouseMode::Off);
+        assert!(!grid.mouse_sgr);
+    }
+
+    #[test]
+    fn test_reset_clears_modes() {
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(&mut grid, b"\x1b[?1049h\x1b[?2004h\x1b[?1000h\x1b[?25l");
+        process_bytes(&mut grid, b"\x1bc");
+        assert!(!grid.alt_screen_active());
+        assert!(!grid.bracketed_paste);
+        assert_eq!(grid.mouse_mode, MouseMode::Off);
+        assert!(grid.cursor_visible);
+    }
+
+    #[test]
+    fn test_sgr_text_attributes() {
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(&mut grid, b"\x1b[2;3;4;9mA\x1b[22;23mB\x1b[24;29mC");
+        let a = &grid.cells[0][0];
+        assert!(a.dim && a.italic && a.underline && a.strikethrough);
+        let b = &grid.cells[0][1];
+        assert!(!b.dim && !b.italic && b.underline && b.strikethrough);
+        let c = &grid.cells[0][2];
+        assert!(!c.underline && !c.strikethrough);
+    }
+
+    #[test]
+    fn test_wide_chars() {
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(&mut grid, "漢字x".as_bytes());
+        assert_eq!(grid.cells[0][0].ch, '漢');
+        assert_eq!(grid.cells[0][0].width, CellWidth::Wide);
+        assert_eq!(grid.cells[0][1].width, CellWidth::Spacer);
+        assert_eq!(grid.cells[0][2].ch, '字');
+        assert_eq!(grid.cells[0][4].ch, 'x');
+        assert_eq!(grid.cursor_col, 5);
+    }
+
+    #[test]
+    fn test_wide_char_wraps_at_last_column() {
+        let mut grid = TerminalGrid::new(3, 2);
+        process_bytes(&mut grid, "ab漢".as_bytes());
+        assert_eq!(grid.cells[0][2].width, CellWidth::Padding);
+        assert!(grid.cells[0][2].wrapped);
+        assert_eq!(grid.cells[1][0].ch, '漢');
+    }
+
+    #[test]
+    fn test_overwriting_half_of_wide_char() {
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(&mut grid, "漢".as_bytes());
+        // Overwrite the spacer half: the leading half must not linger
+        process_bytes(&mut grid, b"\x1b[1;2Hx");
+        assert_eq!(grid.cells[0][0].ch, ' ');
+        assert_eq!(grid.cells[0][0].width, CellWidth::Single);
+        assert_eq!(grid.cells[0][1].ch, 'x');
+    }
+
+    #[test]
+    fn test_combining_marks_and_zwj() {
+        let mut grid = TerminalGrid::new(80, 24);
+        // "e" + COMBINING ACUTE ACCENT, then WOMAN + ZWJ + LAPTOP
+        process_bytes(&mut grid, "e\u{301}\u{1F469}\u{200D}\u{1F4BB}!".as_bytes());
+        assert_eq!(grid.cells[0][0].text(), "e\u{301}");
+        assert_eq!(grid.cells[0][1].text(), "\u{1F469}\u{200D}\u{1F4BB}");
+        assert_eq!(grid.cells[0][1].width, CellWidth::Wide);
+        assert_eq!(grid.cells[0][3].ch, '!');
+    }
+
+    #[test]
+    fn test_osc_title() {
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(&mut grid, b"\x1b]0;vim main.rs\x07");
+        assert_eq!(grid.title.as_deref(), Some("vim main.rs"));
+        process_bytes(&mut grid, b"\x1b]2;a;b\x1b\\");
+        assert_eq!(grid.title.as_deref(), Some("a;b"));
+        process_bytes(&mut grid, b"\x1b]2;\x07");
+        assert_eq!(grid.title, None);
+    }
+
+    #[test]
+    fn test_osc7_cwd() {
+        use std::path::PathBuf;
+
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(&mut grid, b"\x1b]7;file://myhost/home/me/My%20Project\x07");
+        assert_eq!(grid.cwd, Some(PathBuf::from("/home/me/My Project")));
+        // Not a file URL: ignored
+        process_bytes(&mut grid, b"\x1b]7;http://x/y\x07");
+        assert_eq!(grid.cwd, Some(PathBuf::from("/home/me/My Project")));
+    }
+
+    #[test]
+    fn test_osc8_hyperlink() {
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(
+            &mut grid,
+            b"\x1b]8;;https://example.com/a;b\x1b\\link\x1b]8;;\x1b\\ plain",
+        );
+        assert_eq!(
+            grid.cells[0][0].link.as_deref(),
+            Some("https://example.com/a;b")
+        );
+        assert_eq!(
+            grid.cells[0][3].link.as_deref(),
+            Some("https://example.com/a;b")
+        );
+        assert_eq!(grid.cells[0][5].link, None);
+    }
+
+    #[test]
+    fn test_osc52_clipboard() {
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(&mut grid, b"\x1b]52;c;aGVsbG8=\x07");
+        assert_eq!(grid.clipboard_request.as_deref(), Some("hello"));
+        // Clipboard queries are ignored
+        grid.clipboard_request = None;
+        process_bytes(&mut grid, b"\x1b]52;c;?\x07");
+        assert_eq!(grid.clipboard_request, None);
+    }
+
+    #[test]
+    fn test_osc133_prompt_mark_survives_prompt_text() {
+        let mut grid = TerminalGrid::new(80, 24);
+        process_bytes(&mut grid, b"out\r\n\x1b]133;A\x07$ ls\x1b]133;B\x07");
+        assert!(!grid.cells[0][0].prompt);
+        assert!(grid.cells[1][0].prompt);
+        assert_eq!(grid.cells[1][0].ch, '$');
+    }
 }
@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use fltk::{
@@ -13,43 +14,119 @@ use fltk::{
     window::Window,
 };
 
+use super::controllers::editor_events::EditorEventTracker;
 use super::controllers::file::{FileAction, FileController};
 use super::controllers::highlight::{HighlightController, HighlightWidgets};
 use super::controllers::hook_dispatch::{self, HookContext};
+use super::controllers::navigation::{NavLocation, NavigationHistory};
+use super::controllers::panes::{EditorLayout, PaneController, PaneId, SplitOrientation};
 use super::controllers::plugin::PluginController;
 use super::controllers::preview::PreviewController;
-use super::controllers::session::SessionController;
+use super::controllers::quick_open::{FileIndex, OpenTab, QuickOpenSources};
+use super::controllers::recent::{ClosedTab, RecentController};
+use super::controllers::session::{RestoreResult, SessionController};
 use super::controllers::tabs::{GroupId, TabManager};
+use super::controllers::task::TaskController;
 use super::controllers::update::UpdateController;
 use super::controllers::view::ViewController;
 use super::controllers::widget::WidgetController;
-use super::domain::document::DocumentId;
+use super::domain::document::{DocumentId, PartialFileInfo, ViewState};
 use super::domain::messages::Message;
 use super::domain::settings::{self, AppSettings, SyntaxTheme, ThemeMode};
-use super::infrastructure::buffer::buffer_text_no_leak;
+use super::infrastructure::buffer::{buffer_text_no_leak, selection_text_no_leak};
 use super::infrastructure::defer::defer_send;
+use super::infrastructure::display::{scroll_position, scrolled_to_bottom};
 use super::infrastructure::platform::detect_system_dark_mode;
 use super::mcp::McpResponses;
 use super::plugins::HookResult;
-use super::plugins::{PluginHook, PluginManager, get_plugin_dir};
-use super::services::session;
+use super::plugins::security::find_project_root;
+use super::plugins::{
+    AnnotationColor, GutterMark, LineAnnotation, PluginHook, PluginManager, get_plugin_dir,
+};
+use super::services::bookmarks;
+use super::services::follow::{FOLLOW_POLL_INTERVAL, FollowState, FollowUpdate};
+use super::services::portable_session;
+use super::services::session::{self, SessionData, SessionRestore};
 use super::services::shortcut_registry::ShortcutRegistry;
+use super::services::syntax::extra_assets::{self, ExtraAssets};
+use super::services::syntax::symbols::Symbol;
+use crate::ui::dialogs::bookmarks::{BookmarkItem, show_bookmarks_dialog};
 use crate::ui::dialogs::settings_dialog::show_settings_dialog;
-use crate::ui::editor_container::EditorContainer;
+use crate::ui::editor_container::{EditorContainer, copy_editor_style, install_editor_handlers};
+use crate::ui::file_dialogs::{
+    native_folder_dialog, native_pick_file_dialog, native_save_file_dialog,
+};
 use crate::ui::tab_bar::TabBar;
 #[cfg(target_os = "windows")]
 use crate::ui::theme::set_windows_titlebar_theme;
 use crate::ui::theme::{apply_syntax_theme_colors, apply_theme};
 #[cfg(target_os = "macos")]
 use crate::ui::theme::{set_macos_titlebar_color, update_macos_title_label};
+use crate::ui::toast::ToastLevel;
+
+/// Largest selection (in bytes) whose text is passed to `on_selection_changed`.
+const MAX_SELECTION_HOOK_BYTES: i32 = 1024 * 1024;
+
+/// Load syntaxes/themes from enabled plugins and the user config dir
+/// (user files last, so they win name clashes). Broken files are logged
+/// and summarized in a toast.
+fn load_syntax_assets(plugins: &PluginManager, sender: &Sender<Message>) -> ExtraAssets {
+    let mut roots: Vec<std::path::PathBuf> = if plugins.is_enabled() {
+        plugins
+            .list_plugins()
+            .iter()
+            .filter(|p| p.enabled)
+            .map(|p| p.path.clone())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    roots.extend(extra_assets::user_asset_root());
+
+    let assets = extra_assets::load_extra_assets(&roots);
+    for error in &assets.errors {
+        eprintln!("[syntax] Failed to load {}", error);
+    }
+    if let Some(first) = assets.errors.first() {
+        let more = match assets.errors.len() {
+            1 => String::new(),
+            n => format!(" (and {} more)", n - 1),
+        };
+        sender.send(Message::ToastShow(
+            crate::ui::toast::ToastLevel::Warning,
+            format!("Invalid syntax/theme file: {}{}", first, more),
+        ));
+    }
+    assets
+}
+
+/// Put a document's cursor, selection and scroll position back in `editor`,
+/// which must already show its buffer. Without a saved scroll position
+/// (first time shown, older session) the cursor is scrolled into view.
+fn restore_view(editor: &mut TextEditor, cursor: i32, view: ViewState) {
+    editor.set_insert_position(cursor);
+    if let Some((start, end)) = view.selection
+        && let Some(mut buffer) = editor.buffer()
+    {
+        // The file may have changed on disk since the selection was saved
+        let len = buffer.length();
+        buffer.select(start.min(len), end.min(len));
+    }
+    if view.top_line > 0 {
+        editor.scroll(view.top_line, view.horiz_offset);
+    } else {
+        editor.show_insert_position();
+    }
+}
 
 pub struct AppState {
     pub tab_manager: TabManager,
     pub tabs_enabled: bool,
     pub tab_bar: Option<TabBar>,
-    #[allow(dead_code)] // Holds ownership; editor clone is extracted in constructor
     pub editor_container: EditorContainer,
+    /// Editor of the focused pane
     pub editor: TextEditor,
+    pub panes: PaneController,
     pub window: Window,
     pub menu: MenuBar,
     pub flex: Flex,
@@ -66,10 +143,20 @@ pub struct AppState {
     pub plugin_coord: PluginController,
     pub file: FileController,
     pub widget: WidgetController,
+    pub task: TaskController,
+    pub recent: RecentController,
+    /// Project files for Quick Open
+    pub file_index: FileIndex,
     /// Pending text change for debounced OnTextChanged hook: (doc_id, pos, inserted, deleted)
     pending_text_change: Option<(DocumentId, i32, i32, i32)>,
     /// Whether a DoTextChangeHook timer is active
     text_change_timer_active: bool,
+    /// Whether a FollowPoll timer is active
+    follow_timer_active: bool,
+    /// Pending cursor/selection/tab/focus/session/rename events for plugin hooks
+    editor_events: EditorEventTracker,
+    /// Back/forward cursor locations
+    pub navigation: NavigationHistory,
     /// MCP response channels keyed by request_id
     pub mcp_responses: McpResponses,
     /// Pending diff reviews from MCP tools (session_id → (file_path, decision_fifo))
@@ -112,7 +199,7 @@ impl AppState {
         let font_size = settings.borrow().font_size_clamped();
         let highlighting_enabled = settings.borrow().highlighting_enabled;
         let syntax_theme = settings.borrow().current_syntax_theme(dark_mode);
-        let highlight =
+        let mut highlight =
             HighlightController::new(syntax_theme, font, font_size, highlighting_enabled);
 
         let preview = PreviewController::new();
@@ -148,21 +235,31 @@ impl AppState {
             });
         }
 
+        // User/plugin syntaxes and themes (needs the enabled plugin list)
+        highlight.set_extra_assets(load_syntax_assets(&plugins, &sender));
+        highlight.set_theme(
+            syntax_theme,
+            settings.borrow().current_custom_syntax_theme(dark_mode),
+        );
+
         let shortcut_registry =
             ShortcutRegistry::from_settings(&settings.borrow().shortcut_overrides);
 
         let editor = editor_container.editor().clone();
+        let mut panes = PaneController::new();
+        panes.set_doc(0, tab_manager.active_id());
 
         let view = ViewController::new(editor.clone(), dark_mode, show_linenumbers, word_wrap);
         let session = SessionController::with_session_name(session_name);
         let plugin_coord = PluginController::new(menu.clone(), sender);
 
-        Self {
+        let mut state = Self {
             tab_manager,
             tabs_enabled,
             tab_bar,
             editor_container,
             editor,
+            panes,
             window,
             menu,
             flex,
@@ -179,11 +276,19 @@ impl AppState {
             plugin_coord,
             file: FileController::default(),
             widget: WidgetController::new(sender),
+            task: TaskController::new(),
             pending_text_change: None,
             text_change_timer_active: false,
+            follow_timer_active: false,
+            editor_events: EditorEventTracker::new(),
+            navigation: NavigationHistory::new(),
             mcp_responses: Default::default(),
             pending_diff_reviews: HashMap::new(),
-        }
+            recent: RecentController::new(),
+            file_index: FileIndex::default(),
+        };
+        state.rebuild_recent_menu();
+        state
     }
 
     /// Get the active document's buffer
@@ -195,6 +300,8 @@ impl AppState {
 
     /// Bind the active document's buffer to the editor
     pub fn bind_active_buffer(&mut self) {
+        let focused = self.panes.focused_index();
+        self.panes.set_doc(focused, self.tab_manager.active_id());
         if let Some(doc) = self.tab_manager.active_doc() {
             // Save dirty state before rebinding (set_buffer may trigger modify callback)
             let was_dirty = doc.is_dirty();
@@ -210,6 +317,8 @@ impl AppState {
             }
         }
         self.update_linenumber_width();
+        self.sync_pane_editors();
+        self.refresh_bookmark_marks();
     }
 
     /// Update the window title based on active document
@@ -238,14 +347,40 @@ impl AppState {
 
     /// Switch the editor to display a different document
     pub fn switch_to_document(&mut self, id: DocumentId) {
-        // Save current doc's cursor/scroll state
-        if let Some(current) = self.tab_manager.active_doc_mut() {
-            current.cursor_position = self.editor.insert_position();
+        self.save_active_view();
+        self.show_document(id);
+    }
+
+    /// Remember the active document's cursor, scroll position and selection
+    /// before another document takes over the editor
+    fn save_active_view(&mut self) {
+        let cursor = self.editor.insert_position();
+        let (top_line, horiz_offset) = scroll_position(self.editor.as_widget_ptr());
+        let shown = self.editor.buffer();
+        if let Some(doc) = self.tab_manager.active_doc_mut() {
+            doc.cursor_position = cursor;
+            // The editor may already show an empty buffer (e.g. while
+            // switching sessions); its scroll position is not this document's
+            if shown.is_some_and(|b| b == doc.buffer) {
+                doc.view = ViewState {
+                    top_line,
+                    horiz_offset,
+                    selection: doc.buffer.selection_position(),
+                };
+            }
         }
+    }
+
+    /// Show a document in the focused pane at its saved cursor, scroll
+    /// position and selection
+    fn show_document(&mut self, id: DocumentId) {
+        let tab_changed = self.tab_manager.active_id() != Some(id);
 
         // Set new active and expand collapsed group if needed
         self.tab_manager.set_active(id);
         self.tab_manager.expand_group_of(id);
+        let focused = self.panes.focused_index();
+        self.panes.set_doc(focused, Some(id));
 
         // Bind new buffer and restore state
         if let Some(doc) = self.tab_manager.active_doc_mut() {
@@ -259,12 +394,19 @@ impl AppState {
 
             let buffer = doc.buffer.clone();
             let cursor = doc.cursor_position;
+            let view = doc.view;
             let style_buf = doc.style_buffer.clone();
-            self.editor.set_buffer(buffer);
-            let table = self.highlight.style_table();
-            self.editor.set_highlight_data_ext(style_buf, table);
-            self.editor.set_insert_position(cursor);
-            self.editor.show_insert_position();
+            // A pane that already shows the buffer keeps its own cursor (and
+            // any drag in progress when focus follows a click)
+            let already_bound = self.editor.buffer().is_some_and(|b| b == buffer);
+            if !already_bound {
+                self.editor.set_buffer(buffer);
+                let table = self.highlight.style_table();
+                self.editor.set_highlight_data_ext(style_buf, table);
+                restore_view(&mut self.editor, cursor, view);
+            } else {
+                self.editor.show_insert_position();
+            }
 
             // Restore dirty state (binding shouldn't mark document dirty)
             if !was_dirty {
@@ -274,6 +416,7 @@ impl AppState {
 
         self.update_linenumber_width();
         self.update_window_title();
+        self.refresh_bookmark_marks();
 
         // Restore diagnostics for the new active document (or hide panel if never linted)
         if let Some(diagnostics) = self.get_active_diagnostics() {
@@ -290,6 +433,14 @@ impl AppState {
         // YAML/JSON files get a new tree; other files close the stale tree.
         self.widget
             .refresh_tree_view_for_active_doc(&self.tab_manager);
+        if tab_changed {
+            self.widget.close_non_persistent_table_view();
+        }
+
+        if tab_changed && self.plugins.has_subscribers("on_tab_activated") {
+            let armed = self.editor_events.tab_activated();
+            self.arm_editor_event_timer(armed);
+        }
     }
 
     /// Execute the tree view refresh (called directly or from deferred message).
@@ -331,6 +482,7 @@ impl AppState {
 
         // Update built-in menus
         crate::ui::menu::update_preview_menu(&mut self.menu, file_path);
+        self.update_follow_menu();
     }
 
     /// Rebuild the tab bar UI from current documents
@@ -351,40 +503,96 @@ impl AppState {
 
     /// Close a tab by id. Returns true if the app should exit (no tabs remaining).
     pub fn close_tab(&mut self, id: DocumentId) -> bool {
-        // Check if document is dirty
-        if let Some(doc) = self.tab_manager.doc_by_id(id)
-            && doc.is_dirty()
-        {
-            let name = doc.display_name.clone();
-            let choice = dialog::choice2_default(
-                &format!("\"{}\" has unsaved changes.", name),
-                "Save",
-                "Discard",
-                "Cancel",
-            );
+        if !self.confirm_close(&[id]) {
+            return false;
+        }
+        self.discard_tab(id)
+    }
+
+    /// Close several tabs at once. Unsaved documents among them share one
+    /// Save All / Discard / Cancel prompt. Returns true if no tabs remain.
+    pub fn close_tabs(&mut self, ids: &[DocumentId]) -> bool {
+        if !self.confirm_close(ids) {
+            return false;
+        }
+        for &id in ids {
+            if self.discard_tab(id) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Ask what to do with the unsaved documents among `ids`. Returns true
+    /// if closing may go ahead (saved or discarded), false on cancel or a
+    /// failed save.
+    fn confirm_close(&mut self, ids: &[DocumentId]) -> bool {
+        let dirty: Vec<(DocumentId, String)> = ids
+            .iter()
+            .filter_map(|&id| self.tab_manager.doc_by_id(id))
+            .filter(|d| d.is_dirty())
+            .map(|d| (d.id, d.display_name.clone()))
+            .collect();
+
+        let (message, save_label) = match dirty.as_slice() {
+            [] => return true,
+            [(_, name)] => (format!("\"{}\" has unsaved changes.", name), "Save"),
+            _ => {
+                const MAX_LISTED: usize = 10;
+                let names: Vec<&str> = dirty
+                    .iter()
+                    .take(MAX_LISTED)
+                    .map(|(_, name)| name.as_str())
+                    .collect();
+                let mut listed = names.join("\n");
+                if dirty.len() > MAX_LISTED {
+                    listed.push_str(&format!("\n...and {} more", dirty.len() - MAX_LISTED));
+                }
+                (
+                    format!("{} tabs have unsaved changes:\n\n{}", dirty.len(), listed),
+                    "Save All",
+                )
+            }
+        };
 
-            match choice {
-                Some(0) => {
-                    let was_active = self.tab_manager.active_id();
-                    if was_active != Some(id) {
-                        self.switch_to_document(id);
+        let choice = dialog::choice2_default(&message, save_label, "Discard", "Cancel");
+        match choice {
+            Some(0) => {
+                let was_active = self.tab_manager.active_id();
+                for (id, _) in &dirty {
+                    if self.tab_manager.active_id() != Some(*id) {
+                        self.switch_to_document(*id);
                     }
                     self.file_save();
-                    if let Some(doc) = self.tab_manager.doc_by_id(id)
-                        && doc.is_dirty()
+                    if self
+                        .tab_manager
+                        .doc_by_id(*id)
+                        .is_some_and(|d| d.is_dirty())
                     {
                         if let Some(prev) = was_active
-                            && prev != id
+                            && prev != *id
                         {
                             self.switch_to_document(prev);
                         }
                         return false;
                     }
                 }
-                Some(1) => {}
-                _ => return false,
+                if let Some(prev) = was_active
+                    && self.tab_manager.active_id() != Some(prev)
+                {
+                    self.switch_to_document(prev);
+                }
+                true
             }
+            Some(1) => true,
+            _ => false,
         }
+    }
+
+    /// Close a tab without asking about unsaved changes.
+    /// Returns true if no tabs remain.
+    fn discard_tab(&mut self, id: DocumentId) -> bool {
+        self.remember_closed_tab(id);
 
         // Call plugin hook before closing
         let close_path = self
@@ -394,13 +602,18 @@ impl AppState {
         self.plugins
             .call_hook(PluginHook::OnDocumentClose { path: close_path });
 
+        // Panes that showed the document close with it
+        let refocused = self.drop_doc_from_panes(id);
         self.tab_manager.remove(id);
+        self.navigation.remove_document(id);
 
         if self.tab_manager.count() == 0 {
             return true;
         }
 
-        if let Some(active_id) = self.tab_manager.active_id() {
+        if let Some(pane_doc) = refocused {
+            self.show_document(pane_doc);
+        } else if let Some(active_id) = self.tab_manager.active_id() {
             self.switch_to_document(active_id);
         }
         self.rebuild_tab_bar();
@@ -409,6 +622,408 @@ impl AppState {
         false
     }
 
+    // --- Editor panes ---
+
+    /// Split the focused pane. The new pane shows the same document and
+    /// takes the focus.
+    pub fn split_editor(&mut self, orientation: SplitOrientation) {
+        let Some(id) = self.tab_manager.active_id() else {
+            return;
+        };
+        self.save_active_view();
+        let index = self.panes.split(orientation);
+        self.add_pane_editor(index);
+        self.refocus_editor();
+        self.show_document(id);
+        self.sync_pane_editors();
+        self.editor.take_focus().ok();
+        self.rebuild_tab_bar();
+        self.session.mark_dirty();
+    }
+
+    /// Close the focused pane. Its document stays open as a tab.
+    pub fn close_editor_pane(&mut self) {
+        let index = self.panes.focused_index();
+        if !self.panes.close(index) {
+            return;
+        }
+        self.editor_container.remove_pane(index);
+        self.follow_focused_pane();
+        self.session.mark_dirty();
+    }
+
+    /// Move the focus to the next pane, wrapping around
+    pub fn focus_next_pane(&mut self) {
+        if let Some(next) = self.panes.neighbour() {
+            let pane = self.panes.panes()[next].id;
+            self.focus_pane(pane);
+        }
+    }
+
+    /// Focus follows the pane whose editor got keyboard focus
+    pub fn focus_pane(&mut self, pane: PaneId) {
+        let Some(index) = self.panes.index_of(pane) else {
+            return;
+        };
+        self.save_active_view();
+        if self.panes.focus(index) {
+            self.follow_focused_pane();
+        }
+    }
+
+    /// Show a tab in the other pane (splitting first when there is only one)
+    /// and focus it. The pane it came from moves on to a neighbouring tab.
+    pub fn move_tab_to_other_pane(&mut self, id: DocumentId) {
+        if self.tab_manager.doc_by_id(id).is_none() {
+            return;
+        }
+        let source = self.panes.focused_index();
+        let replacement = if self.panes.focused().doc == Some(id) {
+            self.neighbour_tab(id)
+        } else {
+            None
+        };
+        match self.panes.neighbour() {
+            Some(target) => {
+                let pane = self.panes.panes()[target].id;
+                self.focus_pane(pane);
+            }
+            None => self.split_editor(SplitOrientation::default()),
+        }
+        self.switch_to_document(id);
+        if let Some(other) = replacement
+            && let Some(cursor) = self.tab_manager.doc_by_id(other).map(|d| d.cursor_position)
+        {
+            self.show_in_pane(source, other, cursor);
+        }
+        self.editor.take_focus().ok();
+        self.rebuild_tab_bar();
+        self.session.mark_dirty();
+    }
+
+    /// Give the other panes the focused editor's look and rebind their
+    /// highlighting (the style table changes with the theme and font)
+    pub fn sync_pane_editors(&mut self) {
+        if !self.panes.is_split() {
+            return;
+        }
+        let focused = self.panes.focused_index();
+        let table = self.highlight.style_table();
+        for (index, pane) in self.panes.panes().iter().enumerate() {
+            if index == focused {
+                continue;
+            }
+            let Some(mut editor) = self.editor_container.editor_at(index).cloned() else {
+                continue;
+            };
+            copy_editor_style(&self.editor, &mut editor, self.view.word_wrap);
+            if let Some(doc) = pane.doc.and_then(|id| self.tab_manager.doc_by_id(id)) {
+                editor.set_highlight_data_ext(doc.style_buffer.clone(), table.clone());
+            }
+        }
+    }
+
+    /// The split layout to save with the session (None when not split)
+    pub fn editor_layout(&self) -> Option<EditorLayout> {
+        if !self.panes.is_split() {
+            return None;
+        }
+        let panes = self
+            .panes
+            .panes()
+            .iter()
+            .zip(self.editor_container.editors())
+            .map(|(pane, editor)| (pane.doc, editor.insert_position()))
+            .collect();
+        Some(EditorLayout {
+            orientation: self.panes.orientation(),
+            panes,
+            focused: self.panes.focused_index(),
+        })
+    }
+
+    /// Recreate a saved split layout once the session's documents are open
+    fn restore_editor_layout(&mut self, layout: EditorLayout) {
+        self.collapse_panes();
+        let panes: Vec<(DocumentId, i32)> = layout
+            .panes
+            .into_iter()
+            .filter_map(|(doc, cursor)| Some((doc?, cursor)))
+            .filter(|(id, _)| self.tab_manager.doc_by_id(*id).is_some())
+            .collect();
+        if panes.len() < 2 {
+            return;
+        }
+        for (index, &(id, cursor)) in panes.iter().enumerate() {
+            if index > 0 {
+                self.panes.split(layout.orientation);
+                self.add_pane_editor(index);
+            }
+            self.show_in_pane(index, id, cursor);
+        }
+        self.panes.focus(layout.focused.min(panes.len() - 1));
+        self.follow_focused_pane();
+        self.sync_pane_editors();
+    }
+
+    /// Go back to a single pane (the focused one)
+    fn collapse_panes(&mut self) {
+        for index in self.panes.collapse() {
+            self.editor_container.remove_pane(index);
+        }
+        self.refocus_editor();
+    }
+
+    /// Close the panes showing a document that is being closed. Returns the
+    /// document to show when the focus moved to another pane.
+    fn drop_doc_from_panes(&mut self, id: DocumentId) -> Option<DocumentId> {
+        let focused = self.panes.focused().id;
+        let removed = self.panes.remove_doc(id);
+        if removed.is_empty() {
+            return None;
+        }
+        for index in removed {
+            self.editor_container.remove_pane(index);
+        }
+        self.refocus_editor();
+        if self.panes.focused().id == focused {
+            return None;
+        }
+        let doc = self.panes.focused().doc?;
+        let cursor = self.editor.insert_position();
+        if let Some(d) = self.tab_manager.doc_by_id_mut(doc) {
+            d.cursor_position = cursor;
+        }
+        self.editor.take_focus().ok();
+        Some(doc)
+    }
+
+    /// Create the editor widget for the pane at `index`
+    fn add_pane_editor(&mut self, index: usize) {
+        let pane = self.panes.panes()[index].id;
+        let mut editor = self
+            .editor_container
+            .insert_pane(index, self.panes.orientation());
+        install_editor_handlers(&mut editor, pane, self.settings.clone(), self.sender);
+        copy_editor_style(&self.editor, &mut editor, self.view.word_wrap);
+    }
+
+    /// Point `editor` (and the view) at the focused pane's editor
+    fn refocus_editor(&mut self) {
+        if let Some(editor) = self.editor_container.editor_at(self.panes.focused_index()) {
+            self.editor = editor.clone();
+            self.view.set_editor(self.editor.clone());
+        }
+    }
+
+    /// Make the focused pane's editor and document current, keeping the
+    /// pane's own cursor position
+    fn follow_focused_pane(&mut self) {
+        self.refocus_editor();
+        let Some(id) = self.panes.focused().doc else {
+            return;
+        };
+        let cursor = self.editor.insert_position();
+        if let Some(doc) = self.tab_manager.doc_by_id_mut(id) {
+            doc.cursor_position = cursor;
+        }
+        self.show_document(id);
+        self.rebuild_tab_bar();
+        self.editor.take_focus().ok();
+    }
+
+    /// Bind a document to the pane at `index` without focusing it
+    fn show_in_pane(&mut self, index: usize, id: DocumentId, cursor: i32) {
+        let (Some(editor), Some(doc)) = (
+            self.editor_container.editor_at(index),
+            self.tab_manager.doc_by_id(id),
+        ) else {
+            return;
+        };
+        let mut editor = editor.clone();
+        let was_dirty = doc.is_dirty();
+        editor.set_buffer(doc.buffer.clone());
+        editor.set_highlight_data_ext(doc.style_buffer.clone(), self.highlight.style_table());
+        editor.set_insert_position(cursor);
+        editor.show_insert_position();
+        if !was_dirty {
+            doc.mark_clean();
+        }
+        self.panes.set_doc(index, Some(id));
+    }
+
+    /// The tab after `id`, or before it when it is the last one
+    fn neighbour_tab(&self, id: DocumentId) -> Option<DocumentId> {
+        let docs = self.tab_manager.documents();
+        let idx = docs.iter().position(|d| d.id == id)?;
+        docs.get(idx + 1)
+            .or_else(|| idx.checked_sub(1).and_then(|i| docs.get(i)))
+            .map(|d| d.id)
+    }
+
+    // --- Recent files and closed tabs ---
+
+    /// Push a tab that is about to close onto the closed-tab stack and
+    /// update the file history with its cursor position
+    fn remember_closed_tab(&mut self, id: DocumentId) {
+        let Some(doc) = self.tab_manager.doc_by_id(id) else {
+            return;
+        };
+        let cursor_position = if self.tab_manager.active_id() == Some(id) {
+            self.editor.insert_position()
+        } else {
+            doc.cursor_position
+        };
+        let content = doc
+            .file_path
+            .is_none()
+            .then(|| buffer_text_no_leak(&doc.buffer));
+        self.recent.record_close(ClosedTab {
+            file_path: doc.file_path.clone(),
+            cursor_position,
+            content,
+        });
+        self.rebuild_recent_menu();
+    }
+
+    pub fn rebuild_recent_menu(&mut self) {
+        crate::ui::menu::rebuild_recent_menu(&mut self.menu, &self.sender, self.recent.files());
+    }
+
+    /// Open a file from the history at its remembered cursor position
+    pub fn open_recent(&mut self, path: &str) {
+        let cursor = self.recent.files().get(path).map(|f| f.cursor_position);
+        if !self.open_at(path, cursor.unwrap_or(0)) {
+            self.recent.forget(path);
+            self.rebuild_recent_menu();
+        }
+    }
+
+    pub fn clear_recent(&mut self) {
+        self.recent.clear();
+        self.rebuild_recent_menu();
+    }
+
+    /// Bring back the most recently closed tab, with the unsaved text of
+    /// an untitled tab
+    pub fn reopen_closed_tab(&mut self) {
+        let Some(tab) = self.recent.pop_closed() else {
+            return;
+        };
+        match tab.file_path {
+            Some(ref path) => {
+                self.open_at(path, tab.cursor_position);
+            }
+            None => {
+                let actions = self.file.file_new(&mut self.tab_manager, self.tabs_enabled);
+                self.dispatch_file_actions(actions);
+                if let Some(text) = tab.content {
+                    self.active_buffer().set_text(&text);
+                }
+                self.place_cursor(tab.cursor_position);
+            }
+        }
+        self.session.mark_dirty();
+    }
+
+    /// Project of the active file, or of the working directory for untitled
+    /// documents
+    fn active_project_root(&self) -> Option<PathBuf> {
+        let active_path = self
+            .tab_manager
+            .active_doc()
+            .and_then(|d| d.file_path.clone());
+        match active_path {
+            Some(path) => find_project_root(Path::new(&path))
+                .or_else(|| Path::new(&path).parent().map(Path::to_path_buf)),
+            None => std::env::current_dir()
+                .ok()
+                .and_then(|dir| find_project_root(&dir)),
+        }
+    }
+
+    /// Open (or switch to) a file and put the cursor at `cursor` when it was
+    /// not open yet. Returns false, after a toast, when the file is gone.
+    fn open_at(&mut self, path: &str, cursor: i32) -> bool {
+        if !Path::new(path).exists() {
+            self.sender.send(Message::ToastShow(
+                crate::ui::toast::ToastLevel::Warning,
+                format!("File not found: {}", path),
+            ));
+            return false;
+        }
+        let was_open = self.tab_manager.find_by_path(path).is_some();
+        let theme_bg = self.highlight.highlighter().theme_background();
+        let actions = self.file.open_file(
+            path.to_string(),
+            &mut self.tab_manager,
+            &self.settings,
+            theme_bg,
+            self.tabs_enabled,
+        );
+        self.dispatch_file_actions(actions);
+        if !was_open
+            && self
+                .tab_manager
+                .active_doc()
+                .and_then(|d| d.file_path.as_deref())
+                == Some(path)
+        {
+            self.place_cursor(cursor);
+        }
+        true
+    }
+
+    fn place_cursor(&mut self, cursor: i32) {
+        let len = self.active_buffer().length();
+        self.editor.set_insert_position(cursor.clamp(0, len));
+        self.editor.show_insert_position();
+    }
+
+    // --- Quick Open ---
+
+    /// Open tabs and recent files for Quick Open. Also starts listing the
+    /// project of the active document (or of the working directory) in the
+    /// background.
+    pub fn quick_open_sources(&mut self) -> QuickOpenSources {
+        if let Some(root) = self.active_project_root() {
+            self.file_index.refresh(&root);
+        }
+
+        QuickOpenSources {
+            tabs: self
+                .tab_manager
+                .documents()
+                .iter()
+                .map(|d| OpenTab {
+                    id: d.id,
+                    name: d.display_name.clone(),
+                    path: d.file_path.clone(),
+                })
+                .collect(),
+            recent: self
+                .recent
+                .files()
+                .files
+                .iter()
+                .map(|f| f.path.clone())
+                .collect(),
+        }
+    }
+
+    /// Symbols of the active document (empty without a syntax)
+    pub fn active_symbols(&mut self) -> Vec<Symbol> {
+        let Some(syntax) = self
+            .tab_manager
+            .active_doc()
+            .and_then(|d| d.syntax_name.clone())
+        else {
+            return Vec::new();
+        };
+        let text = buffer_text_no_leak(&self.active_buffer());
+        self.highlight.symbols(&text, &syntax)
+    }
+
     // --- File operations (delegated to FileController) ---
 
     /// Run OnDocumentOpen plugin hooks for a file.
@@ -437,6 +1052,7 @@ impl AppState {
                         &mut self.tab_manager,
                         &self.sender,
                     );
+                    self.refresh_bookmark_marks();
                 }
                 FileAction::BindHighlightData(id) => {
                     if let Some(doc) = self.tab_manager.doc_by_id(id) {
@@ -444,6 +1060,7 @@ impl AppState {
                         let table = self.highlight.style_table();
                         self.editor.set_highlight_data_ext(style_buf, table);
                     }
+                    self.refresh_bookmark_marks();
                 }
                 FileAction::UpdateWindowTitle => self.update_window_title(),
                 FileAction::UpdateMenusForFileType => self.update_menus_for_file_type(),
@@ -468,6 +1085,16 @@ impl AppState {
                     };
                     hook_dispatch::dispatch_lint_result(*result, &mut ctx);
                 }
+                FileAction::NotifyRenamed { old_path, new_path } => {
+                    if self.plugins.has_subscribers("on_file_renamed") {
+                        let armed = self.editor_events.file_renamed(old_path, new_path);
+                        self.arm_editor_event_timer(armed);
+                    }
+                }
+                FileAction::RecordRecent(path) => {
+                    self.recent.record_open(&path);
+                    self.rebuild_recent_menu();
+                }
                 FileAction::UpdatePreviewFile { doc_id, path, text } => {
                     FileController::update_preview_file(
                         &mut self.preview,
@@ -513,6 +1140,7 @@ impl AppState {
                     if let Some(doc) = self.tab_manager.doc_by_id_mut(doc_id) {
                         doc.has_unsaved_changes.set(true);
                         doc.disk_mtime = None;
+                        doc.disk_len = None;
                     }
                     all_actions.push(FileAction::RebuildTabBar);
                 }
@@ -571,6 +1199,10 @@ impl AppState {
     /// Restore session from disk. Call after bind_active_buffer and apply_settings.
     pub fn restore_session(&mut self) {
         let session_name = self.session.current_session_name().to_string();
+        if let Some(data) = self.project_session_seed(&session_name) {
+            self.restore_session_data(data);
+            return;
+        }
         let result = match SessionController::restore(
             &mut self.tab_manager,
             &self.settings,
@@ -580,7 +1212,36 @@ impl AppState {
             Some(r) => r,
             None => return,
         };
+        self.finish_restore(result);
+    }
+
+    /// Restore session data that did not come from the session directory
+    /// (an imported or project session). It has no unsaved changes to read.
+    fn restore_session_data(&mut self, data: SessionData) {
+        let session_name = self.session.current_session_name().to_string();
+        let result = SessionController::restore_data(
+            &mut self.tab_manager,
+            &self.settings,
+            data,
+            SessionRestore::SavedFiles,
+            &session_name,
+        );
+        self.finish_restore(result);
+    }
 
+    /// On startup inside a project with a `.ferrispad/session.json`, the
+    /// session to start from, unless the project's named session already
+    /// has saved state of its own
+    fn project_session_seed(&mut self, session_name: &str) -> Option<SessionData> {
+        let file = self.session.take_project_file()?;
+        let restores = self.settings.borrow().session_restore != SessionRestore::Off;
+        if !self.tabs_enabled || (restores && session::session_exists(session_name)) {
+            return None;
+        }
+        self.load_portable_session(&file)
+    }
+
+    fn finish_restore(&mut self, result: RestoreResult) {
         self.file.last_open_directory = result.last_open_directory;
 
         // Apply syntax highlighting for each restored document
@@ -591,9 +1252,10 @@ impl AppState {
 
         self.bind_active_buffer();
         if let Some(doc) = self.tab_manager.active_doc() {
-            let cursor = doc.cursor_position;
-            self.editor.set_insert_position(cursor);
-            self.editor.show_insert_position();
+            restore_view(&mut self.editor, doc.cursor_position, doc.view);
+        }
+        if let Some(layout) = result.editor_layout {
+            self.restore_editor_layout(layout);
         }
         self.update_window_title();
         self.rebuild_tab_bar();
@@ -625,29 +1287,52 @@ impl AppState {
         let session_mode = self.settings.borrow().session_restore;
         let _ = session::save_session(
             &self.tab_manager,
+            self.editor_layout().as_ref(),
             session_mode,
             self.file.last_open_directory.as_deref(),
             self.session.current_session_name(),
         );
     }
 
+    /// Auto-save the session when it is due (called on every event loop
+    /// iteration), with the active document's current view
+    pub fn auto_save_session(&mut self) {
+        if !self.session.auto_save_due() {
+            return;
+        }
+        self.save_active_view();
+        self.session.auto_save_if_needed(
+            &self.tab_manager,
+            self.editor_layout().as_ref(),
+            &self.settings,
+            self.file.last_open_directory.as_deref(),
+        );
+    }
+
     /// Switch to a different named session.
     /// Saves current session, closes all tabs, loads the target session.
     pub fn switch_session(&mut self, name: &str) {
-        // 1. Save current cursor position
-        if let Some(current) = self.tab_manager.active_doc_mut() {
-            current.cursor_position = self.editor.insert_position();
-        }
+        self.enter_session(name, None);
+    }
+
+    /// Replace the open tabs with the session `name`, restored from `data`
+    /// when given instead of from its saved state
+    fn enter_session(&mut self, name: &str, data: Option<SessionData>) {
+        // 1. Save current cursor and scroll position
+        self.save_active_view();
 
         // 2. Force-save current session
         self.session.force_save(
             &self.tab_manager,
+            self.editor_layout().as_ref(),
             &self.settings,
             self.file.last_open_directory.as_deref(),
         );
 
         // 3. Close all documents (content is saved in temp files, no prompts needed)
+        self.collapse_panes();
         self.tab_manager.clear();
+        self.navigation.clear();
 
         // 4. Unbind editor buffer (no active doc)
         let empty_buf = fltk::text::TextBuffer::default();
@@ -667,26 +1352,180 @@ impl AppState {
         self.tab_manager.add_untitled();
 
         // 6. Restore new session (will remove the untitled doc if session has content)
-        self.restore_session();
+        match data {
+            Some(data) => self.restore_session_data(data),
+            None => self.restore_session(),
+        }
 
         // 7. Rebuild UI
         self.bind_active_buffer();
         if let Some(doc) = self.tab_manager.active_doc() {
-            let cursor = doc.cursor_position;
-            self.editor.set_insert_position(cursor);
-            self.editor.show_insert_position();
+            restore_view(&mut self.editor, doc.cursor_position, doc.view);
         }
         self.update_window_title();
         self.rebuild_tab_bar();
+
+        if self.plugins.has_subscribers("on_session_switched") {
+            let armed = self.editor_events.session_switched(name);
+            self.arm_editor_event_timer(armed);
+        }
+    }
+
+    /// Export the open saved files as a portable session, with paths
+    /// relative to a project root the user picks
+    pub fn export_session(&mut self) {
+        self.save_active_view();
+        let data = session::snapshot_session(&self.tab_manager, self.editor_layout().as_ref());
+        if data.documents.is_empty() {
+            self.toast(ToastLevel::Info, "No saved files to export".to_string());
+            return;
+        }
+        let start = self
+            .active_project_root()
+            .map(|root| root.to_string_lossy().to_string())
+            .or_else(|| self.file.last_open_directory.clone());
+        let Some(root) = native_folder_dialog("Export Session: Project Root", start.as_deref())
+        else {
+            return;
+        };
+        let root = PathBuf::from(root);
+        let (portable, left_out) = portable_session::to_portable(&data, &root);
+        if portable.documents.is_empty() {
+            self.toast(
+                ToastLevel::Warning,
+                format!("None of the open files are inside {}", root.display()),
+            );
+            return;
+        }
+
+        let mut message = format!(
+            "Export {} file(s) with paths relative to\n{}",
+            portable.documents.len(),
+            root.display()
+        );
+        if left_out > 0 {
+            message.push_str(&format!("\n({} file(s) outside it are left out)", left_out));
+        }
+        message.push_str("\n\nThe project session opens when FerrisPad is started in this folder.");
+        let dest =
+            match dialog::choice2_default(&message, "Project Session", "Save As...", "Cancel") {
+                Some(0) => portable_session::project_session_path(&root),
+                Some(1) => {
+                    let dir = root.to_string_lossy().to_string();
+                    match native_save_file_dialog(
+                        "Export Session",
+                        Some(&dir),
+                        portable_session::PROJECT_SESSION_FILE,
+                    ) {
+                        Some(path) => PathBuf::from(path),
+                        None => return,
+                    }
+                }
+                _ => return,
+            };
+        // The file dialog asks before overwriting; the fixed project path doesn't
+        if dest.exists()
+            && dialog::choice2_default(
+                &format!("{} already exists.\nReplace it?", dest.display()),
+                "Cancel",
+                "Replace",
+                "",
+            ) != Some(1)
+        {
+            return;
+        }
+
+        match portable_session::write_portable(&dest, &portable) {
+            Ok(()) => self.toast(
+                ToastLevel::Info,
+                format!("Session exported to {}", dest.display()),
+            ),
+            Err(e) => self.toast(
+                ToastLevel::Error,
+                format!("Failed to export session: {}", e),
+            ),
+        }
+    }
+
+    /// Import a portable session file. It replaces the open tabs and is kept
+    /// as the named session of its project.
+    pub fn import_session(&mut self) {
+        if !self.tabs_enabled {
+            self.toast(
+                ToastLevel::Warning,
+                "Importing a session requires tabbed editing".to_string(),
+            );
+            return;
+        }
+        let Some(path) =
+            native_pick_file_dialog("Import Session", self.file.last_open_directory.as_deref())
+        else {
+            return;
+        };
+        let path = PathBuf::from(path);
+        let Some(data) = self.load_portable_session(&path) else {
+            return;
+        };
+        let root = portable_session::session_file_root(&path);
+        self.enter_session(&portable_session::project_session_name(&root), Some(data));
+    }
+
+    /// Read a portable session file. When some of its files no longer exist,
+    /// asks whether to open the others.
+    fn load_portable_session(&self, path: &Path) -> Option<SessionData> {
+        const MAX_LISTED: usize = 10;
+
+        let portable = match portable_session::read_portable(path) {
+            Ok(portable) => portable,
+            Err(e) => {
+                self.toast(
+                    ToastLevel::Error,
+                    format!("Cannot read session {}: {}", path.display(), e),
+                );
+                return None;
+            }
+        };
+        let root = portable_session::session_file_root(path);
+        let (data, missing) = portable_session::from_portable(&portable, &root);
+        if data.documents.is_empty() {
+            self.toast(
+                ToastLevel::Warning,
+                format!("None of the session's files exist in {}", root.display()),
+            );
+            return None;
+        }
+        if !missing.is_empty() {
+            let mut list: Vec<String> = missing
+                .iter()
+                .take(MAX_LISTED)
+                .map(|p| format!("    {}", p))
+                .collect();
+            if missing.len() > MAX_LISTED {
+                list.push(format!("    ... and {} more", missing.len() - MAX_LISTED));
+            }
+            let message = format!(
+                "{} file(s) of this session were not found in {}:\n\n{}\n\nOpen the other {} file(s)?",
+                missing.len(),
+                root.display(),
+                list.join("\n"),
+                data.documents.len()
+            );
+            if dialog::choice2_default(&message, "Open", "Cancel", "") != Some(0) {
+                return None;
+            }
+        }
+        Some(data)
+    }
+
+    fn toast(&self, level: ToastLevel, message: String) {
+        self.sender.send(Message::ToastShow(level, message));
     }
 
     /// Handle quit request. Returns `true` if the app should exit.
     pub fn file_quit(&mut self) -> bool {
         let session_mode = self.settings.borrow().session_restore;
 
-        if let Some(current) = self.tab_manager.active_doc_mut() {
-            current.cursor_position = self.editor.insert_position();
-        }
+        self.save_active_view();
 
         let should_quit = if self.tabs_enabled {
             let dirty_docs: Vec<DocumentId> = self
@@ -756,6 +1595,7 @@ impl AppState {
 
             let _ = session::save_session(
                 &self.tab_manager,
+                self.editor_layout().as_ref(),
                 session_mode,
                 self.file.last_open_directory.as_deref(),
                 self.session.current_session_name(),
@@ -788,11 +1628,13 @@ impl AppState {
         self.view.dark_mode = !self.view.dark_mode;
 
         // Set syntax theme first to get the background color
-        let theme = self
-            .settings
-            .borrow()
-            .current_syntax_theme(self.view.dark_mode);
-        self.highlight.set_theme(theme);
+        {
+            let settings = self.settings.borrow();
+            self.highlight.set_theme(
+                settings.current_syntax_theme(self.view.dark_mode),
+                settings.current_custom_syntax_theme(self.view.dark_mode),
+            );
+        }
 
         // Get syntax theme colors
         let bg = self.highlight.highlighter().theme_background();
@@ -875,7 +1717,10 @@ impl AppState {
     pub fn open_settings(&mut self) {
         let current = self.settings.borrow().clone();
         let theme_bg = self.highlight.highlighter().theme_background();
-        if let Some(new_settings) = show_settings_dialog(&current, &self.sender, theme_bg) {
+        let custom_themes = self.highlight.highlighter().custom_theme_names();
+        if let Some(new_settings) =
+            show_settings_dialog(&current, &self.sender, theme_bg, &custom_themes)
+        {
             if let Err(e) = new_settings.save() {
                 dialog::alert_default(&format!("Failed to save settings: {}", e));
                 return;
@@ -931,8 +1776,8 @@ impl AppState {
     }
 
     /// Preview a syntax theme (called from settings dialog for live preview)
-    pub fn preview_syntax_theme(&mut self, theme: SyntaxTheme) {
-        self.highlight.set_theme(theme);
+    pub fn preview_syntax_theme(&mut self, theme: SyntaxTheme, custom: Option<&str>) {
+        self.highlight.set_theme(theme, custom);
 
         // Apply theme background/foreground colors to editor
         let bg = self.highlight.highlighter().theme_background();
@@ -944,6 +1789,27 @@ impl AppState {
         self.bind_active_buffer();
     }
 
+    /// Reload user/plugin syntaxes and themes (after plugins are toggled or reloaded).
+    pub fn reload_syntax_assets(&mut self) {
+        let assets = load_syntax_assets(&self.plugins, &self.sender);
+        let has_assets = assets.syntax_set.is_some() || !assets.themes.is_empty();
+        if !has_assets && !self.highlight.highlighter().has_extra_assets() {
+            // Nothing before, nothing now: skip the full rehighlight
+            return;
+        }
+        self.highlight.set_extra_assets(assets);
+        let (theme, custom) = {
+            let settings = self.settings.borrow();
+            (
+                settings.current_syntax_theme(self.view.dark_mode),
+                settings
+                    .current_custom_syntax_theme(self.view.dark_mode)
+                    .map(str::to_string),
+            )
+        };
+        self.preview_syntax_theme(theme, custom.as_deref());
+    }
+
     pub fn set_font(&mut self, name: &str) {
         let font = settings::resolve_font(name);
         self.editor.set_text_font(font);
@@ -983,7 +1849,10 @@ impl AppState {
 
         // Set syntax theme first to get background color
         let syntax_theme = new_settings.current_syntax_theme(is_dark);
-        self.highlight.set_theme(syntax_theme);
+        self.highlight.set_theme(
+            syntax_theme,
+            new_settings.current_custom_syntax_theme(is_dark),
+        );
         self.highlight.set_font(font, new_font_size);
 
         // Get syntax theme colors
@@ -1059,6 +1928,7 @@ impl AppState {
                 .rehighlight_all_documents(&mut self.tab_manager, &self.sender);
             self.bind_active_buffer();
         }
+        self.sync_pane_editors();
     }
 
     fn update_menu_checkbox(&self, path: &str, checked: bool) {
@@ -1154,6 +2024,113 @@ impl AppState {
         hook_dispatch::dispatch_hook_result(result, plugin_name, &mut ctx);
     }
 
+    /// Observe cursor and selection changes (called every event loop iteration).
+    /// Costs nothing unless a plugin subscribes to the cursor or selection hooks.
+    pub fn observe_editor_events(&mut self) {
+        let track_cursor = self.plugins.has_subscribers("on_cursor_moved");
+        let track_selection = self.plugins.has_subscribers("on_selection_changed");
+        if !track_cursor && !track_selection {
+            return;
+        }
+        let Some(id) = self.tab_manager.active_id() else {
+            return;
+        };
+        let mut armed = false;
+        if track_cursor {
+            armed |= self
+                .editor_events
+                .observe_cursor(id, self.editor.insert_position());
+        }
+        if track_selection && let Some(buf) = self.editor.buffer() {
+            let selection = buf.selection_position().filter(|(s, e)| s != e);
+            armed |= self.editor_events.observe_selection(id, selection);
+        }
+        self.arm_editor_event_timer(armed);
+    }
+
+    /// Queue the window-focus hook (from `Message::WindowFocusGained`).
+    pub fn queue_window_focus_event(&mut self) {
+        if self.plugins.has_subscribers("on_window_focus") {
+            let armed = self.editor_events.focus_gained();
+            self.arm_editor_event_timer(armed);
+        }
+    }
+
+    /// Start the debounce timer for editor event hooks if the tracker asks for it.
+    fn arm_editor_event_timer(&self, armed: bool) {
+        if armed {
+            defer_send(self.sender, 0.3, Message::DoEditorEventHooks);
+        }
+    }
+
+    /// Fire the debounced editor event hooks to plugins.
+    pub fn do_pending_editor_event_hooks(&mut self) {
+        let events = self.editor_events.take();
+        if events.is_empty() {
+            return;
+        }
+
+        let path = self
+            .tab_manager
+            .active_doc()
+            .and_then(|d| d.file_path.clone());
+        let mut hooks = Vec::new();
+
+        if let Some(name) = events.session_switched {
+            hooks.push(PluginHook::OnSessionSwitched { name });
+        }
+        for (old_path, new_path) in events.renamed {
+            hooks.push(PluginHook::OnFileRenamed { old_path, new_path });
+        }
+        if events.tab_activated {
+            hooks.push(PluginHook::OnTabActivated { path: path.clone() });
+        }
+        if events.focus_gained {
+            hooks.push(PluginHook::OnWindowFocus { path: path.clone() });
+        }
+        if let Some(buf) = self.editor.buffer() {
+            if let Some(position) = events.cursor {
+                let position = position.clamp(0, buf.length());
+                let line = buf.count_lines(0, position) as u32 + 1;
+                let column = (position - buf.line_start(position)) as u32 + 1;
+                hooks.push(PluginHook::OnCursorMoved {
+                    path: path.clone(),
+                    position,
+                    line,
+                    column,
+                });
+            }
+            if let Some((start, end)) = events.selection {
+                // Very large selections report the range only
+                let text = if start != end && end - start <= MAX_SELECTION_HOOK_BYTES {
+                    selection_text_no_leak(&buf)
+                } else {
+                    String::new()
+                };
+                hooks.push(PluginHook::OnSelectionChanged {
+                    path: path.clone(),
+                    start,
+                    end,
+                    text,
+                });
+            }
+        }
+
+        for hook in hooks {
+            let result = self.plugins.call_hook(hook);
+            let approved = self.approved_commands_for_source(&result);
+            self.widget.process_widget_requests(&result, "", &approved);
+            let mut ctx = HookContext {
+                tab_manager: &mut self.tab_manager,
+                view: &mut self.view,
+                widget_manager: &mut self.widget.widget_manager,
+                sender: self.sender,
+                approved_commands: approved,
+            };
+            hook_dispatch::dispatch_hook_result(result, "", &mut ctx);
+        }
+    }
+
     pub fn do_pending_rehighlight(&mut self) {
         self.highlight.do_pending_rehighlight(
             &mut self.tab_manager,
@@ -1165,6 +2142,7 @@ impl AppState {
                 window: &mut self.window,
             },
         );
+        self.refresh_bookmark_marks();
     }
 
     pub fn continue_chunked_highlight(&mut self) {
@@ -1178,16 +2156,15 @@ impl AppState {
                 window: &mut self.window,
             },
         );
+        self.refresh_bookmark_marks();
     }
 
     // --- Tab Group handlers ---
 
     pub fn handle_group_close(&mut self, group_id: GroupId) {
         let doc_ids = self.tab_manager.group_doc_ids(group_id);
-        for id in doc_ids {
-            if self.close_tab(id) {
-                return;
-            }
+        if self.close_tabs(&doc_ids) || !self.tab_manager.group_doc_ids(group_id).is_empty() {
+            return;
         }
         self.tab_manager.delete_group(group_id);
         self.rebuild_tab_bar();
@@ -1207,10 +2184,397 @@ impl AppState {
 
     /// Navigate to a specific line number (1-indexed)
     pub fn goto_line(&mut self, line: u32) {
+        self.record_jump();
         let buf = self.active_buffer();
         self.view.goto_line(&buf, line);
     }
 
+    // --- Navigation history ---
+
+    fn current_location(&self) -> Option<NavLocation> {
+        let doc = self.tab_manager.active_doc()?;
+        // The history is only shifted by replayed edits; a cursor position
+        // taken ahead of them would be shifted again
+        if doc.has_queued_edits() {
+            return None;
+        }
+        Some(NavLocation {
+            doc: doc.id,
+            pos: self.editor.insert_position(),
+        })
+    }
+
+    /// Watch the cursor for document switches and big moves (called on every
+    /// event loop iteration, like `observe_editor_events`)
+    pub fn observe_navigation(&mut self) {
+        let Some(location) = self.current_location() else {
+            return;
+        };
+        if self.navigation.is_at(location) {
+            return;
+        }
+        let Some(buf) = self.editor.buffer() else {
+            return;
+        };
+        let line = buf.count_lines(0, location.pos.clamp(0, buf.length())) as u32;
+        self.navigation.observe(location, line);
+    }
+
+    /// Remember the cursor location before a goto command moves it, so even
+    /// a short jump can be gone back from
+    pub fn record_jump(&mut self) {
+        // A tab switch made just before the goto is recorded first
+        self.observe_navigation();
+        if let Some(location) = self.current_location() {
+            self.navigation.record(location);
+        }
+    }
+
+    /// Go back (or forward) to the previous (next) location in the
+    /// navigation history
+    pub fn navigate(&mut self, forward: bool) {
+        let Some(current) = self.current_location() else {
+            return;
+        };
+        loop {
+            let target = if forward {
+                self.navigation.go_forward(current)
+            } else {
+                self.navigation.go_back(current)
+            };
+            let Some(target) = target else {
+                return;
+            };
+            if self.tab_manager.doc_by_id(target.doc).is_none() {
+                continue;
+            }
+            if self.tab_manager.active_id() != Some(target.doc) {
+                self.switch_to_document(target.doc);
+            }
+            self.place_cursor(target.pos);
+            return;
+        }
+    }
+
+    // --- Follow mode ---
+
+    /// Start or stop following appends to the active document's file
+    pub fn toggle_follow(&mut self) {
+        let Some(doc) = self.tab_manager.active_doc_mut() else {
+            return;
+        };
+        let name = doc.tab_label().to_string();
+        let (level, message) = if doc.follow.take().is_some() {
+            (ToastLevel::Info, format!("Stopped following {}", name))
+        } else if doc.file_path.is_none() {
+            (
+                ToastLevel::Warning,
+                "Save the document to a file to follow it".to_string(),
+            )
+        } else if matches!(doc.partial_info, PartialFileInfo::Chunk { .. }) {
+            (
+                ToastLevel::Warning,
+                "A line range of a file cannot be followed".to_string(),
+            )
+        } else {
+            let id = doc.id;
+            match self.restart_follow(id) {
+                Ok(()) => (ToastLevel::Info, format!("Following {}", name)),
+                Err(e) => (ToastLevel::Error, format!("Cannot follow {}: {}", name, e)),
+            }
+        };
+        self.toast(level, message);
+        self.update_follow_menu();
+        self.arm_follow_timer();
+    }
+
+    /// (Re)start following a document's file from where it was last read or
+    /// saved, e.g. after the document was saved or reopened
+    fn restart_follow(&mut self, id: DocumentId) -> std::io::Result<()> {
+        let Some(doc) = self.tab_manager.doc_by_id_mut(id) else {
+            return Ok(());
+        };
+        let Some(path) = doc.file_path.clone() else {
+            return Ok(());
+        };
+        let len = doc.buffer.length();
+        let ends_with_newline =
+            len == 0 || doc.buffer.text_range(len - 1, len).as_deref() == Some("\n");
+        doc.follow = Some(FollowState::start(
+            Path::new(&path),
+            doc.disk_len,
+            ends_with_newline,
+        )?);
+        Ok(())
+    }
+
+    /// Restart following the active document after it was saved, since the
+    /// file now ends where the buffer does
+    pub fn restart_follow_after_save(&mut self) {
+        if let Some(doc) = self.tab_manager.active_doc()
+            && doc.follow.is_some()
+            && !doc.is_dirty()
+        {
+            let id = doc.id;
+            if self.restart_follow(id).is_err()
+                && let Some(doc) = self.tab_manager.doc_by_id_mut(id)
+            {
+                doc.follow = None;
+            }
+            self.update_follow_menu();
+        }
+    }
+
+    fn update_follow_menu(&self) {
+        let following = self
+            .tab_manager
+            .active_doc()
+            .is_some_and(|d| d.follow.is_some());
+        self.update_menu_checkbox("View/Follow File", following);
+    }
+
+    fn arm_follow_timer(&mut self) {
+        let any_followed = self
+            .tab_manager
+            .documents()
+            .iter()
+            .any(|d| d.follow.is_some());
+        if any_followed && !self.follow_timer_active {
+            self.follow_timer_active = true;
+            defer_send(self.sender, FOLLOW_POLL_INTERVAL, Message::FollowPoll);
+        }
+    }
+
+    /// Append what was written to followed files since the last poll
+    pub fn poll_followed_files(&mut self) {
+        self.follow_timer_active = false;
+        let ids: Vec<DocumentId> = self
+            .tab_manager
+            .documents()
+            .iter()
+            .filter(|d| d.follow.is_some())
+            .map(|d| d.id)
+            .collect();
+        for id in ids {
+            let Some(doc) = self.tab_manager.doc_by_id_mut(id) else {
+                continue;
+            };
+            let (Some(path), Some(follow)) = (doc.file_path.clone(), doc.follow.as_mut()) else {
+                continue;
+            };
+            match follow.poll(Path::new(&path)) {
+                Ok(FollowUpdate::Unchanged) => {}
+                Ok(FollowUpdate::Appended(text)) => self.append_followed(id, &path, &text),
+                Ok(FollowUpdate::Replaced) => self.reopen_followed(id),
+                Err(e) => {
+                    doc.follow = None;
+                    let name = doc.tab_label().to_string();
+                    self.toast(
+                        ToastLevel::Warning,
+                        format!("Stopped following {}: {}", name, e),
+                    );
+                    self.update_follow_menu();
+                }
+            }
+        }
+        self.arm_follow_timer();
+    }
+
+    /// Append followed text without moving the cursor or dirtying the
+    /// document. Keeps the view at the bottom if it was there.
+    fn append_followed(&mut self, id: DocumentId, path: &str, text: &str) {
+        let shown = self.editor.buffer().is_some_and(|b| {
+            self.tab_manager
+                .doc_by_id(id)
+                .is_some_and(|d| d.buffer == b)
+        });
+        let at_bottom = shown && scrolled_to_bottom(self.editor.as_widget_ptr());
+        let Some(doc) = self.tab_manager.doc_by_id_mut(id) else {
+            return;
+        };
+        let was_dirty = doc.is_dirty();
+        doc.buffer.append(text);
+        if !was_dirty {
+            doc.mark_clean();
+        }
+        // The focus check for external changes should not reload it
+        doc.disk_mtime = std::fs::metadata(path).ok().and_then(|m| m.modified().ok());
+        doc.disk_len = doc.follow.as_ref().map(|f| f.offset);
+        if at_bottom {
+            let lines = doc.buffer.count_lines(0, doc.buffer.length());
+            let (_, horiz_offset) = scroll_position(self.editor.as_widget_ptr());
+            self.editor.scroll(lines + 1, horiz_offset);
+        }
+    }
+
+    /// Reopen a followed file that was truncated or rotated. Unsaved edits
+    /// are not thrown away; following stops instead.
+    fn reopen_followed(&mut self, id: DocumentId) {
+        let Some(doc) = self.tab_manager.doc_by_id_mut(id) else {
+            return;
+        };
+        let name = doc.tab_label().to_string();
+        if doc.is_dirty() {
+            doc.follow = None;
+            self.toast(
+                ToastLevel::Warning,
+                format!(
+                    "Stopped following {}: the file was replaced and the document has unsaved changes",
+                    name
+                ),
+            );
+            self.update_follow_menu();
+            return;
+        }
+        let actions = self.file.reopen_followed(id, &mut self.tab_manager);
+        self.dispatch_file_actions(actions);
+        if self.restart_follow(id).is_err()
+            && let Some(doc) = self.tab_manager.doc_by_id_mut(id)
+        {
+            doc.follow = None;
+            self.update_follow_menu();
+        }
+        if self.tab_manager.active_id() == Some(id) {
+            let buf = self.active_buffer();
+            self.editor.scroll(buf.count_lines(0, buf.length()) + 1, 0);
+        }
+        self.toast(
+            ToastLevel::Info,
+            format!("{} was truncated or rotated and has been reopened", name),
+        );
+    }
+
+    // --- Bookmarks ---
+
+    /// Bring the positions that follow edits (bookmarks and the navigation
+    /// history) up to date with a `BufferModified` message. See
+    /// `Document::replay_edit`.
+    pub fn replay_edit(&mut self, id: DocumentId, pos: i32, inserted: i32, deleted: i32) {
+        self.navigation.edited(id, pos, inserted, deleted);
+        let Some(doc) = self.tab_manager.doc_by_id_mut(id) else {
+            return;
+        };
+        // Text typed on a bookmarked line starts out unstyled
+        if doc.replay_edit(pos, inserted, deleted) && self.tab_manager.active_id() == Some(id) {
+            self.refresh_bookmark_marks();
+        }
+    }
+
+    /// Draw the active document's bookmarks as gutter marks. Called after
+    /// anything that rewrites or rebinds its style buffer.
+    pub fn refresh_bookmark_marks(&mut self) {
+        let Some(doc) = self.tab_manager.active_doc() else {
+            return;
+        };
+        if doc.bookmarks.is_empty() {
+            return;
+        }
+        let annotations = doc
+            .bookmark_lines()
+            .into_iter()
+            .map(|line| LineAnnotation {
+                line,
+                gutter: Some(GutterMark {
+                    color: AnnotationColor::Info,
+                }),
+                inline: Vec::new(),
+            })
+            .collect();
+        self.highlight
+            .update_annotations(annotations, &self.tab_manager, &mut self.editor);
+    }
+
+    pub fn toggle_bookmark(&mut self) {
+        let pos = self.editor.insert_position();
+        let Some(doc) = self.tab_manager.active_doc_mut() else {
+            return;
+        };
+        let id = doc.id;
+        if doc.toggle_bookmark(pos) {
+            self.refresh_bookmark_marks();
+        } else {
+            let line_start = doc.buffer.line_start(pos);
+            self.unmark_line(id, line_start);
+        }
+        self.session.mark_dirty();
+    }
+
+    /// Give a line whose bookmark was removed its normal style back
+    fn unmark_line(&mut self, id: DocumentId, line_start: i32) {
+        let highlighted = self.highlight.highlighting_enabled
+            && self
+                .tab_manager
+                .doc_by_id(id)
+                .is_some_and(|d| d.syntax_name.is_some());
+        if highlighted {
+            self.schedule_rehighlight(id, line_start);
+        } else if let Some(doc) = self.tab_manager.doc_by_id_mut(id) {
+            let line_end = doc
+                .buffer
+                .find_char_forward(line_start, '\n')
+                .map_or(doc.buffer.length(), |newline| newline + 1);
+            let plain = "A".repeat((line_end - line_start) as usize);
+            doc.style_buffer.replace(line_start, line_end, &plain);
+            self.editor.redraw();
+        }
+    }
+
+    /// Move the cursor to the next (or previous) bookmark of the active
+    /// document, wrapping around at either end
+    pub fn goto_adjacent_bookmark(&mut self, forward: bool) {
+        let pos = self.editor.insert_position();
+        let Some(doc) = self.tab_manager.active_doc() else {
+            return;
+        };
+        let line_start = doc.buffer.line_start(pos);
+        let target = if forward {
+            bookmarks::next_after(&doc.bookmarks, line_start)
+        } else {
+            bookmarks::previous_before(&doc.bookmarks, line_start)
+        };
+        match target {
+            Some(target) => {
+                self.record_jump();
+                self.place_cursor(target);
+            }
+            None => self.toast(
+                ToastLevel::Info,
+                "No bookmarks in this document".to_string(),
+            ),
+        }
+    }
+
+    /// List the bookmarks of all open documents and go to the chosen one
+    pub fn show_bookmarks(&mut self) {
+        let mut targets = Vec::new();
+        let mut items = Vec::new();
+        for doc in self.tab_manager.documents() {
+            for (&pos, line) in doc.bookmarks.iter().zip(doc.bookmark_lines()) {
+                let line_end = doc
+                    .buffer
+                    .find_char_forward(pos, '\n')
+                    .unwrap_or(doc.buffer.length());
+                targets.push((doc.id, pos));
+                items.push(BookmarkItem {
+                    document: doc.tab_label().to_string(),
+                    line,
+                    text: doc.buffer.text_range(pos, line_end).unwrap_or_default(),
+                });
+            }
+        }
+        if items.is_empty() {
+            self.toast(ToastLevel::Info, "No bookmarks set".to_string());
+            return;
+        }
+        let theme_bg = self.highlight.highlighter().theme_background();
+        if let Some(index) = show_bookmarks_dialog(&self.window, &items, theme_bg) {
+            let (id, pos) = targets[index];
+            self.record_jump();
+            self.switch_to_document(id);
+            self.place_cursor(pos);
+        }
+    }
+
     // --- Annotations (delegates to HighlightController) ---
 
     /// Request manual highlight from plugins (Ctrl+Shift+L / Run All Checks)
@@ -1246,6 +2610,7 @@ impl AppState {
                     if plugin_result.status_message.is_some() {
                         combined.status_message = plugin_result.status_message;
                     }
+                    combined.status_items.extend(plugin_result.status_items);
                 }
             }
             combined
@@ -7,26 +7,37 @@ use fltk::prelude::*;
 
 use ferris_pad::split_parent;
 
+use crate::app::controllers::command_palette::{CommandAction, remember_command};
 use crate::app::controllers::plugin::PluginController;
+use crate::app::controllers::quick_open::QuickOpenTarget;
+use crate::app::controllers::task::{self, TaskController};
 use crate::app::controllers::update::BannerWidgets;
+use crate::app::domain::document::DocumentId;
 use crate::app::domain::messages::Message;
 use crate::app::domain::settings::TreePanelPosition;
 use crate::app::infrastructure::defer::defer_send;
 use crate::app::mcp;
-use crate::app::plugins::widgets::SplitDisplayMode;
+use crate::app::plugins::Diagnostic;
+use crate::app::plugins::widgets::{SplitDisplayMode, TerminalViewRequest};
 use crate::app::services::session;
+use crate::app::services::tasks::{PROJECT_TASKS_FILE, TaskDefinition, TaskRunMode};
 use crate::app::services::updater::current_timestamp;
 use crate::app::state::AppState;
 use crate::ui::dialogs::about::show_about_dialog;
+use crate::ui::dialogs::command_palette::show_command_palette;
 use crate::ui::dialogs::find::{show_find_dialog, show_replace_dialog};
 use crate::ui::dialogs::goto_line::show_goto_line_dialog;
+use crate::ui::dialogs::quick_open::{QuickOpenChoice, show_quick_open};
 use crate::ui::dialogs::session_picker::{
     SessionPickerResult, show_new_session_dialog, show_session_picker,
 };
+use crate::ui::dialogs::task_picker::show_task_picker;
 use crate::ui::dialogs::update::check_for_updates_ui;
 use crate::ui::main_window::LayoutWidgets;
+use crate::ui::menu::{palette_commands, trigger_menu_item};
 use crate::ui::tab_bar::TAB_BAR_HEIGHT;
 use crate::ui::theme::DIVIDER_WIDTH;
+use crate::ui::toast::ToastLevel;
 
 /// Result from a dispatch handler that may request quit.
 pub enum DispatchResult {
@@ -55,6 +66,11 @@ pub fn handle_file(msg: Message, state: &mut AppState) -> DispatchResult {
             state.file_reload_all();
             state.session.mark_dirty();
         }
+        Message::FileOpenRecent(path) => {
+            state.open_recent(&path);
+            state.session.mark_dirty();
+        }
+        Message::FileClearRecent => state.clear_recent(),
         Message::FileOpen => {
             let theme_bg = state.highlight.highlighter().theme_background();
             let actions = state.file.file_open(
@@ -66,8 +82,22 @@ pub fn handle_file(msg: Message, state: &mut AppState) -> DispatchResult {
             state.dispatch_file_actions(actions);
             state.session.mark_dirty();
         }
+        Message::FileOpenRange => {
+            let theme_bg = state.highlight.highlighter().theme_background();
+            let actions =
+                state
+                    .file
+                    .file_open_range(&mut state.tab_manager, theme_bg, state.tabs_enabled);
+            state.dispatch_file_actions(actions);
+            state.session.mark_dirty();
+        }
+        Message::FileOpenHex => {
+            let theme_bg = state.highlight.highlighter().theme_background();
+            state.file.file_open_hex(&state.settings, theme_bg);
+        }
         Message::FileSave => {
             state.file_save();
+            state.restart_follow_after_save();
             state.session.mark_dirty();
         }
         Message::FileSaveAs => {
@@ -76,6 +106,7 @@ pub fn handle_file(msg: Message, state: &mut AppState) -> DispatchResult {
                     .file
                     .file_save_as(&mut state.tab_manager, &state.plugins, state.tabs_enabled);
             state.dispatch_file_actions(actions);
+            state.restart_follow_after_save();
             state.session.mark_dirty();
         }
         Message::FileQuit | Message::WindowClose => {
@@ -127,11 +158,103 @@ pub fn handle_tab(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) ->
             state.session.mark_dirty();
         }
         Message::TabCloseActive => {
-            if let Some(id) = state.tab_manager.active_id() {
+            // Pinned tabs only close from the tab context menu
+            if let Some(doc) = state.tab_manager.active_doc()
+                && !doc.pinned
+            {
+                let id = doc.id;
                 state.close_tab(id);
                 state.session.mark_dirty();
             }
         }
+        Message::TabCloseOthers(_)
+        | Message::TabCloseToRight(_)
+        | Message::TabCloseSaved
+        | Message::TabCloseAll => {
+            // Bulk closes leave pinned tabs alone
+            let docs = state.tab_manager.documents();
+            let ids: Vec<DocumentId> = match msg {
+                Message::TabCloseOthers(keep) => docs
+                    .iter()
+                    .filter(|d| !d.pinned && d.id != keep)
+                    .map(|d| d.id)
+                    .collect(),
+                Message::TabCloseToRight(from) => docs
+                    .iter()
+                    .skip_while(|d| d.id != from)
+                    .skip(1)
+                    .filter(|d| !d.pinned)
+                    .map(|d| d.id)
+                    .collect(),
+                Message::TabCloseSaved => docs
+                    .iter()
+                    .filter(|d| !d.pinned && !d.is_dirty())
+                    .map(|d| d.id)
+                    .collect(),
+                _ => docs.iter().filter(|d| !d.pinned).map(|d| d.id).collect(),
+            };
+            if let Message::TabCloseOthers(keep) = msg
+                && state.tab_manager.active_id() != Some(keep)
+            {
+                state.switch_to_document(keep);
+            }
+            if !ids.is_empty() {
+                state.close_tabs(&ids);
+                state.session.mark_dirty();
+            }
+            state.rebuild_tab_bar();
+        }
+        Message::TabCopyPath(id) | Message::TabCopyRelativePath(id) => {
+            let Some(path) = state
+                .tab_manager
+                .doc_by_id(id)
+                .and_then(|d| d.file_path.clone())
+            else {
+                state.sender.send(Message::ToastShow(
+                    ToastLevel::Info,
+                    "This tab has not been saved to a file yet".to_string(),
+                ));
+                return DispatchResult::Continue;
+            };
+            let text = if matches!(msg, Message::TabCopyRelativePath(_)) {
+                project_relative_path(&path)
+            } else {
+                path
+            };
+            crate::app::infrastructure::platform::copy_to_clipboard(&text);
+        }
+        Message::TabRevealInTree(id) => {
+            let revealed = state
+                .tab_manager
+                .doc_by_id(id)
+                .and_then(|d| d.file_path.as_deref())
+                .is_some_and(|path| lw.tree_panel.reveal_path(path));
+            if !revealed {
+                state.sender.send(Message::ToastShow(
+                    ToastLevel::Info,
+                    "Open a file tree containing this file to reveal it".to_string(),
+                ));
+            }
+        }
+        Message::TabReopenClosed => {
+            if state.recent.has_closed() {
+                state.reopen_closed_tab();
+            } else {
+                state.sender.send(Message::ToastShow(
+                    ToastLevel::Info,
+                    "No recently closed tabs".to_string(),
+                ));
+            }
+        }
+        Message::TabTogglePin(id) => {
+            let pinned = state.tab_manager.doc_by_id(id).is_some_and(|d| d.pinned);
+            state.tab_manager.set_pinned(id, !pinned);
+            state.rebuild_tab_bar();
+            if let Some(ref mut tab_bar) = state.tab_bar {
+                tab_bar.ensure_active_visible(state.tab_manager.active_id());
+            }
+            state.session.mark_dirty();
+        }
         Message::TabMove(from, to) => {
             state.tab_manager.move_tab(from, to);
             state.rebuild_tab_bar();
@@ -188,6 +311,10 @@ pub fn handle_tab(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) ->
             state.session.mark_dirty();
         }
         Message::TabGroupByDrag(source_id, target_id) => {
+            let pinned = |id| state.tab_manager.doc_by_id(id).is_some_and(|d| d.pinned);
+            if pinned(source_id) || pinned(target_id) {
+                return DispatchResult::Continue;
+            }
             let target_group = state
                 .tab_manager
                 .documents()
@@ -251,9 +378,16 @@ pub fn handle_edit(msg: Message, state: &mut AppState) {
             show_replace_dialog(&state.active_buffer(), &mut state.editor, theme_bg);
         }
         Message::ShowGoToLine => {
+            state.record_jump();
             let theme_bg = state.highlight.highlighter().theme_background();
             show_goto_line_dialog(&state.active_buffer(), &mut state.editor, theme_bg);
         }
+        Message::BookmarkToggle => state.toggle_bookmark(),
+        Message::BookmarkNext => state.goto_adjacent_bookmark(true),
+        Message::BookmarkPrevious => state.goto_adjacent_bookmark(false),
+        Message::ShowBookmarks => state.show_bookmarks(),
+        Message::NavigateBack => state.navigate(false),
+        Message::NavigateForward => state.navigate(true),
         _ => {}
     }
 }
@@ -264,8 +398,14 @@ pub fn handle_edit(msg: Message, state: &mut AppState) {
 
 pub fn handle_view(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) {
     match msg {
-        Message::ToggleLineNumbers => state.view.toggle_line_numbers(&state.tab_manager),
-        Message::ToggleWordWrap => state.view.toggle_word_wrap(),
+        Message::ToggleLineNumbers => {
+            state.view.toggle_line_numbers(&state.tab_manager);
+            state.sync_pane_editors();
+        }
+        Message::ToggleWordWrap => {
+            state.view.toggle_word_wrap();
+            state.sync_pane_editors();
+        }
         Message::ToggleDarkMode => {
             state.toggle_dark_mode();
             let theme_bg = state.highlight.highlighter().theme_background();
@@ -279,6 +419,9 @@ pub fn handle_view(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) {
                 lw.terminal_panel
                     .apply_theme(state.view.dark_mode, theme_bg);
             }
+            if lw.table_panel.is_visible() {
+                lw.table_panel.apply_theme(state.view.dark_mode, theme_bg);
+            }
             lw.diagnostic_panel.apply_theme(state.view.dark_mode);
             lw.status_bar.apply_theme(theme_bg);
             lw.toast.apply_theme(state.view.dark_mode);
@@ -290,7 +433,14 @@ pub fn handle_view(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) {
             }
         }
         Message::ToggleHighlighting => state.toggle_highlighting(),
+        Message::ToggleFollow => state.toggle_follow(),
+        Message::FollowPoll => state.poll_followed_files(),
         Message::TogglePreview => state.preview_in_browser(),
+        Message::SplitEditor(orientation) => state.split_editor(orientation),
+        Message::CloseEditorPane => state.close_editor_pane(),
+        Message::FocusNextPane => state.focus_next_pane(),
+        Message::MoveTabToOtherPane(id) => state.move_tab_to_other_pane(id),
+        Message::EditorPaneFocused(pane) => state.focus_pane(pane),
         Message::SetFont(name) => {
             state.set_font(&name);
             propagate_font_to_panels(state, lw);
@@ -343,6 +493,9 @@ pub fn handle_settings(msg: Message, state: &mut AppState, lw: &mut LayoutWidget
             if lw.tree_panel.is_visible() {
                 lw.tree_panel.apply_theme(state.view.dark_mode, theme_bg);
             }
+            if lw.table_panel.is_visible() {
+                lw.table_panel.apply_theme(state.view.dark_mode, theme_bg);
+            }
             // Update foreground after settings may have changed the theme
             if state.view.dark_mode {
                 fltk::app::foreground(230, 230, 230);
@@ -381,6 +534,7 @@ pub fn handle_highlight(msg: Message, state: &mut AppState) {
                 doc.cached_tree = None;
                 doc.cached_line_count = doc.buffer.count_lines(0, doc.buffer.length()) as usize;
             }
+            state.replay_edit(id, pos, inserted, deleted);
             state.schedule_rehighlight(id, pos);
             state.schedule_text_change_hook(id, pos, inserted, deleted);
             state.session.mark_dirty();
@@ -394,6 +548,9 @@ pub fn handle_highlight(msg: Message, state: &mut AppState) {
         Message::DoTextChangeHook => {
             state.do_pending_text_change_hook();
         }
+        Message::DoEditorEventHooks => {
+            state.do_pending_editor_event_hooks();
+        }
         _ => {}
     }
 }
@@ -441,7 +598,9 @@ pub fn handle_update(msg: Message, state: &mut AppState) {
                 window: &mut state.window,
             });
         }
-        Message::PreviewSyntaxTheme(theme) => state.preview_syntax_theme(theme),
+        Message::PreviewSyntaxTheme(theme, custom) => {
+            state.preview_syntax_theme(theme, custom.as_deref())
+        }
         _ => {}
     }
 }
@@ -461,6 +620,7 @@ pub fn handle_plugin(msg: Message, state: &mut AppState) {
                 &mut state.widget.widget_manager,
                 theme_bg,
             );
+            state.reload_syntax_assets();
         }
         Message::PluginToggle(name) => {
             state.plugin_coord.handle_toggle(
@@ -470,6 +630,7 @@ pub fn handle_plugin(msg: Message, state: &mut AppState) {
                 name,
                 &mut state.widget.widget_manager,
             );
+            state.reload_syntax_assets();
         }
         Message::PluginsReloadAll => {
             let theme_bg = state.highlight.highlighter().theme_background();
@@ -480,6 +641,7 @@ pub fn handle_plugin(msg: Message, state: &mut AppState) {
                 &mut state.widget.widget_manager,
                 theme_bg,
             );
+            state.reload_syntax_assets();
         }
         Message::CheckPluginPermissions => {
             let theme_bg = state.highlight.highlighter().theme_background();
@@ -550,19 +712,14 @@ pub fn handle_plugin(msg: Message, state: &mut AppState) {
 // ---------------------------------------------------------------------------
 
 pub fn handle_diagnostic(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) {
-    let sender = state.sender;
     match msg {
         Message::DiagnosticsUpdate(diagnostics) => {
-            let is_success = diagnostics.is_empty();
             state.store_diagnostics(diagnostics.clone());
-            lw.diagnostic_panel.update_diagnostics(diagnostics);
-            let height = lw.diagnostic_panel.current_height();
-            lw.flex.fixed(lw.diagnostic_panel.widget(), height);
-            lw.flex.recalc();
-            lw.wind.redraw();
-            if is_success {
-                defer_send(sender, 5.0, Message::DiagnosticsAutoDismiss);
-            }
+            show_diagnostics(diagnostics, state, lw);
+        }
+        Message::DiagnosticsClear if !state.task.diagnostics().is_empty() => {
+            // The document was never linted, but task problems stay listed
+            show_diagnostics(Vec::new(), state, lw);
         }
         Message::DiagnosticsClear => {
             lw.diagnostic_panel.clear();
@@ -581,8 +738,8 @@ pub fn handle_diagnostic(msg: Message, state: &mut AppState, lw: &mut LayoutWidg
             }
         }
         Message::DiagnosticGoto(_idx) => {
-            if let Some(line) = lw.diagnostic_panel.selected_line() {
-                state.goto_line(line);
+            if let Some((file, line)) = lw.diagnostic_panel.selected_location() {
+                goto_location(file, line, state);
             }
         }
         Message::DiagnosticOpenDocs(_idx) => {
@@ -607,6 +764,37 @@ pub fn handle_diagnostic(msg: Message, state: &mut AppState, lw: &mut LayoutWidg
     }
 }
 
+/// Show the active document's diagnostics in the panel, followed by the
+/// problems of the last task.
+fn show_diagnostics(mut diagnostics: Vec<Diagnostic>, state: &AppState, lw: &mut LayoutWidgets) {
+    diagnostics.extend(state.task.diagnostics().iter().cloned());
+    let is_success = diagnostics.is_empty();
+    lw.diagnostic_panel.update_diagnostics(diagnostics);
+    let height = lw.diagnostic_panel.current_height();
+    lw.flex.fixed(lw.diagnostic_panel.widget(), height);
+    lw.flex.recalc();
+    lw.wind.redraw();
+    if is_success {
+        defer_send(state.sender, 5.0, Message::DiagnosticsAutoDismiss);
+    }
+}
+
+/// Go to a diagnostic's line, opening its file first when it is not the
+/// active document.
+fn goto_location(file: Option<String>, line: u32, state: &mut AppState) {
+    let active = state
+        .tab_manager
+        .active_doc()
+        .and_then(|d| d.file_path.clone());
+    match file {
+        Some(path) if active.as_deref() != Some(path.as_str()) => {
+            state.sender.send(Message::DeferredOpenFile(path));
+            state.sender.send(Message::DeferredGotoLine(line as usize));
+        }
+        _ => state.goto_line(line),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Annotations
 // ---------------------------------------------------------------------------
@@ -622,6 +810,7 @@ pub fn handle_annotation(msg: Message, state: &mut AppState) {
             state
                 .highlight
                 .clear_annotations(&mut state.tab_manager, &mut state.editor);
+            state.refresh_bookmark_marks();
         }
         Message::ManualHighlight => {
             state.request_manual_highlight();
@@ -714,6 +903,7 @@ pub fn handle_deferred(
             state.dispatch_file_actions(actions);
         }
         Message::DeferredGotoLine(line) => {
+            state.record_jump();
             if let Some(doc) = state.tab_manager.active_doc() {
                 let text = crate::app::infrastructure::buffer::buffer_text_no_leak(&doc.buffer);
                 if let Some(pos) =
@@ -999,6 +1189,7 @@ pub fn handle_tree_view(msg: Message, state: &mut AppState, lw: &mut LayoutWidge
             session_id,
             node_path,
         } => {
+            state.record_jump();
             state.widget.handle_tree_view_node_click(
                 session_id,
                 node_path,
@@ -1079,6 +1270,7 @@ pub fn handle_window(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets)
                 tab_bar.handle_resize();
             }
             lw.terminal_panel.handle_resize();
+            lw.table_panel.handle_resize();
             if lw.split_panel.is_visible() && lw.split_panel.is_tab_mode() {
                 let parent = split_parent!(lw);
                 let full_height = parent.h() - TAB_BAR_HEIGHT;
@@ -1116,78 +1308,80 @@ pub fn handle_terminal_view(msg: Message, state: &mut AppState, lw: &mut LayoutW
             plugin_name: _,
             mut request,
         } => {
-            // Default working_dir to the project root from the active document
-            if request.working_dir.is_none()
-                && let Some(path) = state
-                    .tab_manager
-                    .active_doc()
-                    .and_then(|d| d.file_path.as_ref())
-            {
-                let project_root =
-                    crate::app::plugins::security::find_project_root(std::path::Path::new(path));
-                if let Some(root) = project_root {
-                    request.working_dir = Some(root.to_string_lossy().to_string());
-                } else if let Some(parent) = std::path::Path::new(path).parent() {
-                    request.working_dir = Some(parent.to_string_lossy().to_string());
-                }
+            if request.working_dir.is_none() {
+                request.working_dir = terminal_working_dir(state);
             }
 
+            let was_visible = lw.terminal_panel.is_visible();
             let theme_bg = state.highlight.highlighter().theme_background();
             lw.terminal_panel
                 .apply_theme(state.view.dark_mode, theme_bg);
             lw.terminal_panel.show_request(session_id, &request);
-
-            // Expand window to accommodate terminal panel
-            let term_width = lw.terminal_panel.current_width() + DIVIDER_WIDTH;
-            let (scr_x, _scr_y, scr_w, _scr_h) = fltk::app::screen_work_area(0);
-            let max_right = scr_x + scr_w;
-            let current_right = lw.wind.x() + lw.wind.w();
-            let available = (max_right - current_right).max(0);
-            let grow = term_width.min(available);
-            if grow > 0 {
-                lw.wind.set_size(lw.wind.w() + grow, lw.wind.h());
-                lw.flex.resize(0, 0, lw.wind.w(), lw.wind.h());
+            if !was_visible {
+                show_terminal_layout(state, lw);
             }
-
-            let width = lw.terminal_panel.current_width();
-            lw.content_row.fixed(lw.terminal_panel.widget(), width);
-            if let Some(ref mut div) = lw.terminal_panel.divider {
-                div.show();
-                lw.content_row.fixed(div, DIVIDER_WIDTH);
+        }
+        Message::TerminalToggle => {
+            if lw.terminal_panel.is_visible() {
+                lw.terminal_panel.hide();
+                hide_terminal_layout(state, lw);
+                return;
             }
-            lw.content_row.recalc();
-            if let Some(ref mut tb) = state.tab_bar {
-                tb.handle_resize();
+            let theme_bg = state.highlight.highlighter().theme_background();
+            lw.terminal_panel
+                .apply_theme(state.view.dark_mode, theme_bg);
+            if lw.terminal_panel.has_tabs() {
+                lw.terminal_panel.show();
+            } else {
+                lw.terminal_panel.new_shell_tab(terminal_working_dir(state));
             }
-            lw.wind.redraw();
+            show_terminal_layout(state, lw);
+        }
+        Message::TerminalNewTab => {
+            let was_visible = lw.terminal_panel.is_visible();
+            // Follow the active shell's directory (OSC 7) when it reports one
+            let dir = lw
+                .terminal_panel
+                .active_cwd()
+                .map(|p| p.to_string_lossy().to_string())
+                .or_else(|| terminal_working_dir(state));
+            lw.terminal_panel.new_shell_tab(dir);
+            if !was_visible {
+                show_terminal_layout(state, lw);
+            }
+        }
+        Message::TerminalSelectTab(tab_id) => {
+            lw.terminal_panel.select_tab(tab_id);
+        }
+        Message::TerminalCloseTab(tab_id) => {
+            close_terminal_tab(tab_id, state, lw);
+        }
+        Message::TerminalRenameTab { tab_id, title } => {
+            lw.terminal_panel.rename_tab(tab_id, &title);
         }
         Message::TerminalViewHide(_session_id) => {
-            // Shrink window back after closing terminal panel
-            let term_width = lw.terminal_panel.current_width() + DIVIDER_WIDTH;
-            let (scr_x, _scr_y, _scr_w, _scr_h) = fltk::app::screen_work_area(0);
-            let min_w = 400;
-            let new_w = (lw.wind.w() - term_width).max(min_w);
-            let new_x = lw.wind.x().max(scr_x);
-            lw.wind.resize(new_x, lw.wind.y(), new_w, lw.wind.h());
-            lw.flex.resize(0, 0, new_w, lw.wind.h());
-
-            lw.terminal_panel.close();
-            lw.content_row.fixed(lw.terminal_panel.widget(), 0);
-            if let Some(ref mut div) = lw.terminal_panel.divider {
-                div.hide();
-                lw.content_row.fixed(div, 0);
-            }
-            lw.content_row.recalc();
-            if let Some(ref mut tb) = state.tab_bar {
-                tb.handle_resize();
+            if lw.terminal_panel.is_visible() {
+                lw.terminal_panel.hide();
+                hide_terminal_layout(state, lw);
             }
-            lw.wind.redraw();
         }
         Message::TerminalOutput(_) => {
             lw.terminal_panel.process_output();
+            if let Some(text) = lw.terminal_panel.take_clipboard_request() {
+                if state.settings.borrow().terminal_clipboard_write {
+                    crate::app::infrastructure::platform::copy_to_clipboard(&text);
+                } else {
+                    eprintln!("[terminal] Ignored clipboard write (OSC 52 disabled in Settings)");
+                }
+            }
         }
-        Message::TerminalExited => {
-            eprintln!("[terminal] Child process exited");
+        Message::TerminalExited(tab_id) => {
+            eprintln!("[terminal] Child process of tab {} exited", tab_id);
+            if lw.terminal_panel.mark_exited(tab_id) {
+                close_terminal_tab(tab_id, state, lw);
+            } else {
+                finish_terminal_task(tab_id, state, lw);
+            }
         }
         Message::TerminalViewResize(mouse_x) => {
             let content_x = lw.content_row.x();
@@ -1206,6 +1400,403 @@ pub fn handle_terminal_view(msg: Message, state: &mut AppState, lw: &mut LayoutW
     }
 }
 
+/// A file's path relative to its project root (see `find_project_root`),
+/// or the path itself when it lies outside one.
+fn project_relative_path(path: &str) -> String {
+    let file = std::path::Path::new(path);
+    crate::app::plugins::security::find_project_root(file)
+        .and_then(|root| file.strip_prefix(&root).ok().map(|p| p.to_path_buf()))
+        .map_or_else(|| path.to_string(), |p| p.to_string_lossy().to_string())
+}
+
+/// Working directory for new terminals: the project root of the active
+/// document, else its parent directory, else FerrisPad's own cwd.
+fn terminal_working_dir(state: &AppState) -> Option<String> {
+    let path = state
+        .tab_manager
+        .active_doc()
+        .and_then(|d| d.file_path.as_ref())?;
+    let path = std::path::Path::new(path);
+    crate::app::plugins::security::find_project_root(path)
+        .or_else(|| path.parent().map(|p| p.to_path_buf()))
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Close a terminal tab, drop its widget session and hide the panel when the
+/// last tab is gone.
+fn close_terminal_tab(tab_id: u32, state: &mut AppState, lw: &mut LayoutWidgets) {
+    // A task closed while running still finishes, so it can be run again
+    finish_terminal_task(tab_id, state, lw);
+    if let Some(session_id) = lw.terminal_panel.close_tab(tab_id) {
+        state.widget.widget_manager.remove_session(session_id);
+    }
+    if !lw.terminal_panel.has_tabs() && lw.terminal_panel.is_visible() {
+        lw.terminal_panel.hide();
+        hide_terminal_layout(state, lw);
+    }
+}
+
+/// Report the output of a task tab's run, if it has one.
+fn finish_terminal_task(tab_id: u32, state: &AppState, lw: &mut LayoutWidgets) {
+    if let Some((run_id, output, exit_code)) = lw.terminal_panel.take_task_output(tab_id) {
+        state.sender.send(Message::TaskFinished {
+            run_id,
+            output,
+            exit_code,
+        });
+    }
+}
+
+/// Grow the window and give the (already shown) terminal panel its width.
+fn show_terminal_layout(state: &mut AppState, lw: &mut LayoutWidgets) {
+    // Expand window to accommodate terminal panel
+    let term_width = lw.terminal_panel.current_width() + DIVIDER_WIDTH;
+    let (scr_x, _scr_y, scr_w, _scr_h) = fltk::app::screen_work_area(0);
+    let max_right = scr_x + scr_w;
+    let current_right = lw.wind.x() + lw.wind.w();
+    let available = (max_right - current_right).max(0);
+    let grow = term_width.min(available);
+    if grow > 0 {
+        lw.wind.set_size(lw.wind.w() + grow, lw.wind.h());
+        lw.flex.resize(0, 0, lw.wind.w(), lw.wind.h());
+    }
+
+    let width = lw.terminal_panel.current_width();
+    lw.content_row.fixed(lw.terminal_panel.widget(), width);
+    if let Some(ref mut div) = lw.terminal_panel.divider {
+        div.show();
+        lw.content_row.fixed(div, DIVIDER_WIDTH);
+    }
+    lw.content_row.recalc();
+    lw.terminal_panel.handle_resize();
+    if let Some(ref mut tb) = state.tab_bar {
+        tb.handle_resize();
+    }
+    lw.wind.redraw();
+}
+
+/// Shrink the window back after the terminal panel was hidden.
+fn hide_terminal_layout(state: &mut AppState, lw: &mut LayoutWidgets) {
+    let term_width = lw.terminal_panel.current_width() + DIVIDER_WIDTH;
+    let (scr_x, _scr_y, _scr_w, _scr_h) = fltk::app::screen_work_area(0);
+    let min_w = 400;
+    let new_w = (lw.wind.w() - term_width).max(min_w);
+    let new_x = lw.wind.x().max(scr_x);
+    lw.wind.resize(new_x, lw.wind.y(), new_w, lw.wind.h());
+    lw.flex.resize(0, 0, new_w, lw.wind.h());
+
+    lw.content_row.fixed(lw.terminal_panel.widget(), 0);
+    if let Some(ref mut div) = lw.terminal_panel.divider {
+        div.hide();
+        lw.content_row.fixed(div, 0);
+    }
+    lw.content_row.recalc();
+    if let Some(ref mut tb) = state.tab_bar {
+        tb.handle_resize();
+    }
+    lw.wind.redraw();
+}
+
+// ---------------------------------------------------------------------------
+// Palettes
+// ---------------------------------------------------------------------------
+
+pub fn handle_palette(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) {
+    match msg {
+        Message::ShowQuickOpen => show_quick_open_palette(state, lw),
+        Message::ShowCommandPalette => show_command_palette_dialog(state, lw),
+        _ => {}
+    }
+}
+
+fn show_command_palette_dialog(state: &mut AppState, lw: &mut LayoutWidgets) {
+    let commands = palette_commands(
+        &state.menu,
+        &state.shortcut_registry,
+        &state.plugins,
+        state.tabs_enabled,
+    );
+    let recent = state.settings.borrow().recent_commands.clone();
+    let theme_bg = state.highlight.highlighter().theme_background();
+    let Some(idx) = show_command_palette(&lw.wind, &commands, &recent, theme_bg) else {
+        return;
+    };
+    let command = &commands[idx];
+    {
+        let mut s = state.settings.borrow_mut();
+        remember_command(&mut s.recent_commands, &command.id);
+        let _ = s.save();
+    }
+    match command.action {
+        CommandAction::Menu(ref path) => {
+            trigger_menu_item(&mut state.menu, path);
+        }
+        CommandAction::Plugin {
+            ref plugin_name,
+            ref action,
+        } => state.sender.send(Message::PluginMenuAction {
+            plugin_name: plugin_name.clone(),
+            action: action.clone(),
+        }),
+    }
+}
+
+fn show_quick_open_palette(state: &mut AppState, lw: &mut LayoutWidgets) {
+    let sources = state.quick_open_sources();
+    let index = state.file_index.clone();
+    let theme_bg = state.highlight.highlighter().theme_background();
+    let choice = show_quick_open(
+        &lw.wind,
+        &sources,
+        &index,
+        || state.active_symbols(),
+        theme_bg,
+    );
+    match choice {
+        Some(QuickOpenChoice::Line(line)) => state.goto_line(line),
+        Some(QuickOpenChoice::Open(QuickOpenTarget::Tab(id), line)) => {
+            handle_tab(Message::TabSwitch(id), state, lw);
+            if let Some(line) = line {
+                state.goto_line(line);
+            }
+        }
+        Some(QuickOpenChoice::Open(QuickOpenTarget::File(path), line)) => {
+            state.open_recent(&path);
+            if let Some(line) = line {
+                state.goto_line(line);
+            }
+            state.session.mark_dirty();
+        }
+        None => {}
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tasks
+// ---------------------------------------------------------------------------
+
+pub fn handle_task(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) {
+    match msg {
+        Message::RunTask => {
+            let root = task_root(state);
+            let global = state.settings.borrow().tasks.clone();
+            let (tasks, error) = TaskController::available_tasks(&root, &global);
+            if let Some(e) = error {
+                state
+                    .sender
+                    .send(Message::ToastShow(ToastLevel::Warning, e));
+            }
+            if tasks.is_empty() {
+                state.sender.send(Message::ToastShow(
+                    ToastLevel::Info,
+                    format!("No tasks defined. Add them to {}", PROJECT_TASKS_FILE),
+                ));
+                return;
+            }
+            let theme_bg = state.highlight.highlighter().theme_background();
+            if let Some(idx) = show_task_picker(&lw.wind, &tasks, theme_bg) {
+                run_task(&tasks[idx], &root, state, lw);
+            }
+        }
+        Message::RerunLastTask => match state.task.last_task() {
+            Some((task, root)) => run_task(&task, &root, state, lw),
+            None => state.sender.send(Message::RunTask),
+        },
+        Message::NextError => {
+            let Some((diag, pos)) = state.task.next_error() else {
+                state.sender.send(Message::ToastShow(
+                    ToastLevel::Info,
+                    "No problems from the last task".to_string(),
+                ));
+                return;
+            };
+            let (file, line, message) = (diag.file.clone(), diag.line, diag.message.clone());
+            let total = state.task.diagnostics().len();
+            lw.diagnostic_panel
+                .select_where(|d| d.file == file && d.line == line && d.message == message);
+            goto_location(file, line, state);
+            state.sender.send(Message::ToastShow(
+                ToastLevel::Info,
+                format!("Problem {}/{}: {}", pos, total, message),
+            ));
+        }
+        Message::TaskFinished {
+            run_id,
+            output,
+            exit_code,
+        } => {
+            let Some(outcome) = state.task.finish(run_id, &output) else {
+                return;
+            };
+            let (level, text) = match (outcome.errors, outcome.warnings, exit_code) {
+                (0, 0, Some(0) | None) => {
+                    (ToastLevel::Success, format!("{} finished", outcome.name))
+                }
+                (0, 0, Some(code)) => (
+                    ToastLevel::Error,
+                    format!("{} failed (exit code {})", outcome.name, code),
+                ),
+                (errors, warnings, _) => {
+                    let plural = |n: usize| if n == 1 { "" } else { "s" };
+                    (
+                        if errors > 0 {
+                            ToastLevel::Error
+                        } else {
+                            ToastLevel::Warning
+                        },
+                        format!(
+                            "{}: {} error{}, {} warning{}",
+                            outcome.name,
+                            errors,
+                            plural(errors),
+                            warnings,
+                            plural(warnings)
+                        ),
+                    )
+                }
+            };
+            state.sender.send(Message::ToastShow(level, text));
+            let doc_diagnostics = state.get_active_diagnostics().unwrap_or_default();
+            show_diagnostics(doc_diagnostics, state, lw);
+        }
+        _ => {}
+    }
+}
+
+/// Project root that tasks run for: that of the active document, else
+/// FerrisPad's own working directory.
+fn task_root(state: &AppState) -> std::path::PathBuf {
+    terminal_working_dir(state)
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default()
+}
+
+/// Start a task in a terminal tab or in the background.
+fn run_task(
+    task: &TaskDefinition,
+    root: &std::path::Path,
+    state: &mut AppState,
+    lw: &mut LayoutWidgets,
+) {
+    let (run_id, dir) = match state.task.start(task, root) {
+        Ok(run) => run,
+        Err(e) => {
+            state
+                .sender
+                .send(Message::ToastShow(ToastLevel::Warning, e));
+            return;
+        }
+    };
+    match task.run_in {
+        TaskRunMode::Background => {
+            task::spawn_background(run_id, task.command.clone(), dir, state.sender);
+            state.sender.send(Message::ToastShow(
+                ToastLevel::Info,
+                format!("Running {}...", task.name),
+            ));
+        }
+        TaskRunMode::Terminal => {
+            let request = TerminalViewRequest {
+                title: task.name.clone(),
+                command: Some(task.command.clone()),
+                working_dir: Some(dir.to_string_lossy().into_owned()),
+                ..Default::default()
+            };
+            let was_visible = lw.terminal_panel.is_visible();
+            let theme_bg = state.highlight.highlighter().theme_background();
+            lw.terminal_panel
+                .apply_theme(state.view.dark_mode, theme_bg);
+            if !lw.terminal_panel.run_task(run_id, &request) {
+                state.task.cancel(run_id);
+                state.sender.send(Message::ToastShow(
+                    ToastLevel::Error,
+                    format!("Failed to start {}", task.name),
+                ));
+            }
+            if !was_visible {
+                show_terminal_layout(state, lw);
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Table View
+// ---------------------------------------------------------------------------
+
+pub fn handle_table_view(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets) {
+    match msg {
+        Message::TableViewShow {
+            session_id,
+            plugin_name,
+            request,
+        } => {
+            state.widget.show_table_view(
+                session_id,
+                &plugin_name,
+                &request,
+                &mut lw.table_panel,
+                &state.highlight,
+                &state.view,
+            );
+            let height = lw.table_panel.current_height();
+            lw.flex.fixed(lw.table_panel.widget(), height);
+            lw.flex.recalc();
+            lw.table_panel.handle_resize();
+            if let Some(ref mut tb) = state.tab_bar {
+                tb.handle_resize();
+            }
+            lw.wind.redraw();
+        }
+        Message::TableViewHide(session_id) => {
+            state
+                .widget
+                .hide_table_view(session_id, &mut lw.table_panel);
+            lw.flex.fixed(lw.table_panel.widget(), 0);
+            lw.flex.recalc();
+            if let Some(ref mut tb) = state.tab_bar {
+                tb.handle_resize();
+            }
+            lw.wind.redraw();
+        }
+        Message::TableViewRowClicked { session_id, row_id } => {
+            state.widget.handle_table_view_action(
+                session_id,
+                "row_clicked".to_string(),
+                Some(row_id),
+                None,
+                &mut state.plugins,
+                &mut state.tab_manager,
+                &mut state.view,
+            );
+        }
+        Message::TableViewContextAction {
+            session_id,
+            action,
+            row_id,
+            input_text,
+        } => {
+            state.widget.handle_table_view_action(
+                session_id,
+                action,
+                row_id,
+                input_text,
+                &mut state.plugins,
+                &mut state.tab_manager,
+                &mut state.view,
+            );
+        }
+        Message::TableViewFilter { query } => {
+            lw.table_panel.apply_filter(&query);
+        }
+        Message::TableViewSort(column) => {
+            lw.table_panel.toggle_sort(column);
+        }
+        _ => {}
+    }
+}
+
 // ---------------------------------------------------------------------------
 // MCP
 // ---------------------------------------------------------------------------
@@ -1280,6 +1871,7 @@ pub fn handle_session(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets
                 state.session.set_session_name(sanitized);
                 state.session.force_save(
                     &state.tab_manager,
+                    state.editor_layout().as_ref(),
                     &state.settings,
                     state.file.last_open_directory.as_deref(),
                 );
@@ -1302,6 +1894,11 @@ pub fn handle_session(msg: Message, state: &mut AppState, lw: &mut LayoutWidgets
                     .spawn();
             }
         }
+        Message::SessionExport => state.export_session(),
+        Message::SessionImport => {
+            state.import_session();
+            lw.wind.redraw();
+        }
         _ => {}
     }
 }
@@ -1322,7 +1919,7 @@ pub fn sync_start_page(state: &mut AppState, lw: &mut LayoutWidgets) {
     if has_tabs && start_visible {
         // Tabs opened — hide start page, show editor + tab bar
         lw.start_page.hide();
-        state.editor.show();
+        state.editor_container.widget().show();
         if let Some(ref mut tab_bar) = state.tab_bar {
             tab_bar.widget.show();
         }
@@ -1342,7 +1939,7 @@ pub fn sync_start_page(state: &mut AppState, lw: &mut LayoutWidgets) {
         // No tabs — hide editor + tab bar, show start page
         // Set empty buffer so the hidden editor doesn't paint stale text
         state.editor.set_buffer(fltk::text::TextBuffer::default());
-        state.editor.hide();
+        state.editor_container.widget().hide();
         if let Some(ref mut tab_bar) = state.tab_bar {
             tab_bar.widget.hide();
         }
@@ -16,9 +16,9 @@ use std::rc::Rc;
 use crate::app::domain::settings::TreePanelPosition;
 use crate::app::infrastructure::defer::defer_send;
 use crate::app::services::editor_context::EditorContextWriter;
-use crate::app::services::session;
 use crate::app::services::shortcut_registry::ShortcutRegistry;
 use crate::app::services::updater::{UpdateCheckResult, check_for_updates, should_check_now};
+use crate::app::services::{portable_session, session};
 use crate::app::state::AppState;
 use crate::app::{AppSettings, Message, ThemeMode, detect_system_dark_mode};
 use crate::ui::main_window::{LayoutWidgets, build_main_window};
@@ -124,7 +124,8 @@ fn print_help() {
          OPTIONS:\n    \
              -l, --line <N>       Go to line N after opening (applies to last file)\n    \
              -n, --new            Open a new empty tab\n    \
-             -s, --session <NAME> Open a named session (default: \"default\")\n    \
+             -s, --session <NAME> Open a named session (default: the project's\n                         \
+             .ferrispad/session.json, else \"default\")\n    \
              --list-sessions      List available sessions and exit\n    \
              -v, --version        Print version and exit\n    \
              -h, --help           Print this help and exit\n    \
@@ -258,9 +259,22 @@ fn main() {
     // Initialize state
     let app_settings = Rc::new(RefCell::new(settings.clone()));
 
-    let session_name = cli_args
-        .session_name
-        .unwrap_or_else(|| session::DEFAULT_SESSION_NAME.to_string());
+    // Started inside a project that has a shared session: use the project's
+    // named session, seeded from `.ferrispad/session.json` the first time
+    let project_session = if cli_args.session_name.is_none() && settings.project_sessions {
+        std::env::current_dir()
+            .ok()
+            .and_then(|dir| portable_session::find_project_session(&dir))
+    } else {
+        None
+    };
+    let session_name = match (cli_args.session_name, &project_session) {
+        (Some(name), _) => name,
+        (None, Some(file)) => {
+            portable_session::project_session_name(&portable_session::session_file_root(file))
+        }
+        (None, None) => session::DEFAULT_SESSION_NAME.to_string(),
+    };
 
     let mut state = AppState::new(
         w.editor_container,
@@ -280,40 +294,20 @@ fn main() {
     if let Some(responses) = mcp_responses {
         state.mcp_responses = responses;
     }
+    if let Some(file) = project_session {
+        state.session.set_project_file(file);
+    }
 
     // Bind the initial document's buffer to the editor
     state.bind_active_buffer();
 
-    // Set up Tab key handler for "use spaces" feature.
-    // Replace FLTK's built-in Tab binding with a no-op so our handle() is the sole handler.
-    state.editor.add_key_binding(
-        fltk::enums::Key::Tab,
-        fltk::enums::Shortcut::None,
-        |_key, _editor| 1, // 1 = handled (do nothing, our handle() already inserted text)
+    // Tab key handling ("use spaces") and focus-follow for the first pane
+    ui::editor_container::install_editor_handlers(
+        &mut state.editor,
+        state.panes.focused().id,
+        app_settings.clone(),
+        sender,
     );
-    {
-        let settings_ref = app_settings.clone();
-        state.editor.handle(move |editor, event| {
-            if event == fltk::enums::Event::KeyDown
-                && fltk::app::event_key() == fltk::enums::Key::Tab
-            {
-                let s = settings_ref.borrow();
-                if let Some(mut buf) = editor.buffer() {
-                    let pos = editor.insert_position();
-                    if s.use_spaces {
-                        let spaces = " ".repeat(s.tab_size as usize);
-                        buf.insert(pos, &spaces);
-                        editor.set_insert_position(pos + s.tab_size as i32);
-                    } else {
-                        buf.insert(pos, "\t");
-                        editor.set_insert_position(pos + 1);
-                    }
-                }
-                return true;
-            }
-            false
-        });
-    }
 
     // Apply initial settings (theme, font, line numbers, word wrap)
     state.apply_settings(settings.clone());
@@ -448,6 +442,7 @@ fn main() {
         diagnostic_panel: w.diagnostic_panel,
         tree_panel: w.tree_panel,
         terminal_panel: w.terminal_panel,
+        table_panel: w.table_panel,
         toast: w.toast,
         status_bar: w.status_bar,
         content_row: w.content_row,
@@ -529,8 +524,12 @@ fn main() {
                 // File
                 Message::FileNew
                 | Message::FileOpen
+                | Message::FileOpenRange
+                | Message::FileOpenHex
                 | Message::FileReload
                 | Message::FileReloadAll
+                | Message::FileOpenRecent(_)
+                | Message::FileClearRecent
                 | Message::FileSave
                 | Message::FileSaveAs
                 | Message::FileQuit
@@ -543,6 +542,15 @@ fn main() {
                 | Message::TabMove(..)
                 | Message::TabNext
                 | Message::TabPrevious
+                | Message::TabCloseOthers(_)
+                | Message::TabCloseToRight(_)
+                | Message::TabCloseSaved
+                | Message::TabCloseAll
+                | Message::TabCopyPath(_)
+                | Message::TabCopyRelativePath(_)
+                | Message::TabRevealInTree(_)
+                | Message::TabTogglePin(_)
+                | Message::TabReopenClosed
                 | Message::TabGroupCreate(_)
                 | Message::TabGroupDelete(_)
                 | Message::TabGroupClose(_)
@@ -564,7 +572,13 @@ fn main() {
                 | Message::SelectAll
                 | Message::ShowFind
                 | Message::ShowReplace
-                | Message::ShowGoToLine => {
+                | Message::ShowGoToLine
+                | Message::BookmarkToggle
+                | Message::BookmarkNext
+                | Message::BookmarkPrevious
+                | Message::ShowBookmarks
+                | Message::NavigateBack
+                | Message::NavigateForward => {
                     if state.tab_manager.count() > 0 {
                         dispatch::handle_edit(msg, &mut state);
                     }
@@ -576,10 +590,17 @@ fn main() {
                 | Message::ToggleWordWrap
                 | Message::ToggleDarkMode
                 | Message::ToggleHighlighting
+                | Message::ToggleFollow
+                | Message::FollowPoll
                 | Message::TogglePreview
                 | Message::SetFont(_)
                 | Message::SetFontSize(_)
-                | Message::OpenFontPicker => {
+                | Message::OpenFontPicker
+                | Message::SplitEditor(_)
+                | Message::CloseEditorPane
+                | Message::FocusNextPane
+                | Message::MoveTabToOtherPane(_)
+                | Message::EditorPaneFocused(_) => {
                     dispatch::handle_view(msg, &mut state, &mut lw);
                     dispatch::DispatchResult::Continue
                 }
@@ -604,11 +625,18 @@ fn main() {
                     dispatch::DispatchResult::Continue
                 }
 
+                // Always delivered: the tick re-arms the editor event timer,
+                // and tab, session and focus events don't need a buffer
+                Message::DoEditorEventHooks => {
+                    dispatch::handle_highlight(msg, &mut state);
+                    dispatch::DispatchResult::Continue
+                }
+
                 // Background updates
                 Message::BackgroundUpdateResult(_)
                 | Message::ShowBannerUpdate
                 | Message::DismissBanner
-                | Message::PreviewSyntaxTheme(_) => {
+                | Message::PreviewSyntaxTheme(..) => {
                     dispatch::handle_update(msg, &mut state);
                     dispatch::DispatchResult::Continue
                 }
@@ -625,6 +653,25 @@ fn main() {
                 | Message::CheckPluginUpdates
                 | Message::PluginUpdatesChecked(_) => {
                     dispatch::handle_plugin(msg, &mut state);
+                    // Drop status items of plugins that were disabled or unloaded
+                    let plugins = &state.plugins;
+                    lw.status_bar.retain_plugins(|name| {
+                        plugins.is_enabled()
+                            && plugins
+                                .list_plugins()
+                                .iter()
+                                .any(|p| p.name == name && p.enabled)
+                    });
+                    // Close the table view if its plugin's session was cleared
+                    if let Some(id) = lw.table_panel.session_id()
+                        && state.widget.widget_manager.get_session(id).is_none()
+                    {
+                        state.sender.send(Message::TableViewHide(id));
+                    }
+                    dispatch::DispatchResult::Continue
+                }
+                Message::StatusItemsUpdate { plugin_name, items } => {
+                    lw.status_bar.set_plugin_items(&plugin_name, items);
                     dispatch::DispatchResult::Continue
                 }
 
@@ -692,12 +739,43 @@ fn main() {
                 Message::TerminalViewShow { .. }
                 | Message::TerminalViewHide(_)
                 | Message::TerminalOutput(_)
-                | Message::TerminalExited
+                | Message::TerminalExited(_)
+                | Message::TerminalToggle
+                | Message::TerminalNewTab
+                | Message::TerminalSelectTab(_)
+                | Message::TerminalCloseTab(_)
+                | Message::TerminalRenameTab { .. }
                 | Message::TerminalViewResize(_) => {
                     dispatch::handle_terminal_view(msg, &mut state, &mut lw);
                     dispatch::DispatchResult::Continue
                 }
 
+                // Palettes
+                Message::ShowQuickOpen | Message::ShowCommandPalette => {
+                    dispatch::handle_palette(msg, &mut state, &mut lw);
+                    dispatch::DispatchResult::Continue
+                }
+
+                // Tasks
+                Message::RunTask
+                | Message::RerunLastTask
+                | Message::NextError
+                | Message::TaskFinished { .. } => {
+                    dispatch::handle_task(msg, &mut state, &mut lw);
+                    dispatch::DispatchResult::Continue
+                }
+
+                // Table view
+                Message::TableViewShow { .. }
+                | Message::TableViewHide(_)
+                | Message::TableViewRowClicked { .. }
+                | Message::TableViewContextAction { .. }
+                | Message::TableViewFilter { .. }
+                | Message::TableViewSort(_) => {
+                    dispatch::handle_table_view(msg, &mut state, &mut lw);
+                    dispatch::DispatchResult::Continue
+                }
+
                 // MCP
                 Message::McpRequest { .. } => {
                     dispatch::handle_mcp(msg, &mut state);
@@ -716,7 +794,9 @@ fn main() {
                 | Message::SessionOpenInNewWindow(_)
                 | Message::SessionSaveAs(_)
                 | Message::SessionDelete(_)
-                | Message::SessionNewWindow => {
+                | Message::SessionNewWindow
+                | Message::SessionExport
+                | Message::SessionImport => {
                     dispatch::handle_session(msg, &mut state, &mut lw);
                     dispatch::DispatchResult::Continue
                 }
@@ -724,6 +804,7 @@ fn main() {
                 Message::WindowFocusGained => {
                     state.check_and_reload_external_changes();
                     state.refresh_tree_if_visible();
+                    state.queue_window_focus_event();
                     dispatch::DispatchResult::Continue
                 }
             };
@@ -753,12 +834,10 @@ fn main() {
             project_root.as_ref().and_then(|p| p.to_str()),
         );
         editor_context.update(&state.editor, file_path);
+        state.observe_editor_events();
+        state.observe_navigation();
 
-        state.session.auto_save_if_needed(
-            &state.tab_manager,
-            &state.settings,
-            state.file.last_open_directory.as_deref(),
-        );
+        state.auto_save_session();
     }
 
     // Clean up MCP port file and editor context file
@@ -770,6 +849,7 @@ fn main() {
         let session_mode = state.settings.borrow().session_restore;
         let _ = session::save_session(
             &state.tab_manager,
+            state.editor_layout().as_ref(),
             session_mode,
             state.file.last_open_directory.as_deref(),
             state.session.current_session_name(),
@@ -187,9 +187,14 @@ impl DiagnosticPanel {
                 };
 
                 // Format: @C<color> icon Line N:col - message (source)
+                // Problems from task output name their file instead
+                let location = match diag.file {
+                    Some(ref file) => format!("{}:{}", file_label(file), diag.line),
+                    None => format!("Line {}", diag.line),
+                };
                 let line = format!(
-                    "{} {} Line {}{}: {} [{}]",
-                    color, icon, diag.line, col_info, diag.message, diag.source
+                    "{} {} {}{}: {} [{}]",
+                    color, icon, location, col_info, diag.message, diag.source
                 );
                 self.browser.add(&line);
             }
@@ -217,16 +222,27 @@ impl DiagnosticPanel {
         self.container.visible()
     }
 
-    /// Get the selected diagnostic's line number (for goto)
-    pub fn selected_line(&self) -> Option<u32> {
+    /// Get the selected diagnostic's file (None = active document) and
+    /// line number (for goto)
+    pub fn selected_location(&self) -> Option<(Option<String>, u32)> {
         let idx = self.browser.value();
         if idx > 0 && (idx as usize) <= self.diagnostics.len() {
-            Some(self.diagnostics[idx as usize - 1].line)
+            let diag = &self.diagnostics[idx as usize - 1];
+            Some((diag.file.clone(), diag.line))
         } else {
             None
         }
     }
 
+    /// Select the first diagnostic matching `pred` and scroll it into view
+    pub fn select_where(&mut self, pred: impl Fn(&Diagnostic) -> bool) {
+        if let Some(idx) = self.diagnostics.iter().position(pred) {
+            let line = idx as i32 + 1;
+            self.browser.select(line);
+            self.browser.middle_line(line);
+        }
+    }
+
     /// Get the selected diagnostic's documentation URL (for double-click)
     pub fn selected_url(&self) -> Option<String> {
         let idx = self.browser.value();
@@ -279,6 +295,9 @@ impl DiagnosticPanel {
                         "Line {}: {}\nSource: {}",
                         diag.line, diag.message, diag.source
                     );
+                    if let Some(ref file) = diag.file {
+                        tooltip.push_str(&format!("\nFile: {}", file));
+                    }
                     if let Some(ref fix) = diag.fix_message {
                         tooltip.push_str(&format!("\n\nFix: {}", fix));
                     }
@@ -378,3 +397,17 @@ impl DiagnosticPanel {
         }
     }
 }
+
+/// Short label for a diagnostic's file: its name, with the parent directory
+/// when there is one (`src/main.rs`)
+fn file_label(path: &str) -> String {
+    let path = std::path::Path::new(path);
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string_lossy().into_owned());
+    match path.parent().and_then(|p| p.file_name()) {
+        Some(dir) => format!("{}/{}", dir.to_string_lossy(), name),
+        None => name,
+    }
+}
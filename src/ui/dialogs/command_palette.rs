@@ -0,0 +1,34 @@
+use fltk::window::Window;
+
+use crate::app::controllers::command_palette::{PaletteCommand, rank_commands};
+
+use super::DialogTheme;
+use super::filter_list::FilterList;
+
+/// Show the command palette. `recent` holds the ids of recently used
+/// commands, most recent first. Returns the index of the chosen command.
+pub fn show_command_palette(
+    parent: &Window,
+    commands: &[PaletteCommand],
+    recent: &[String],
+    theme_bg: (u8, u8, u8),
+) -> Option<usize> {
+    let theme = DialogTheme::from_theme_bg(theme_bg);
+    let mut list = FilterList::new(parent, "Command Palette", 360, 170, false, &theme);
+    list.right_align_details();
+
+    let mut rows: Vec<usize> = Vec::new();
+    while list.shown() {
+        if let Some(query) = list.take_query() {
+            rows = rank_commands(commands, recent, &query);
+            list.clear();
+            for &i in &rows {
+                list.add(&commands[i].label, &commands[i].shortcut);
+            }
+            list.finish();
+        }
+        list.wait();
+    }
+
+    rows.get(list.chosen()?).copied()
+}
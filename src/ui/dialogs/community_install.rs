@@ -33,8 +33,8 @@ pub struct CommunityInstallReview {
     pub permissions: Vec<String>,
     /// `true` when installing from a local archive (not from the community index).
     pub is_manual: bool,
-    /// `true` when the plugin registers an `on_text_changed` hook.
-    pub has_text_change_hook: bool,
+    /// High-frequency hooks the plugin registers (`on_text_changed`, `on_cursor_moved`, ...).
+    pub high_frequency_hooks: Vec<String>,
     /// Warnings produced by the pre-install source scan.
     pub scan_warnings: Vec<String>,
 }
@@ -60,8 +60,8 @@ pub fn show_community_install_dialog(review: &CommunityInstallReview, theme: &Di
         content_height += 16 * review.permissions.len() as i32;
     }
 
-    // Text change hook warning
-    if review.has_text_change_hook {
+    // High-frequency hook warning
+    if !review.high_frequency_hooks.is_empty() {
         content_height += 4 + 32;
     }
 
@@ -198,13 +198,14 @@ pub fn show_community_install_dialog(review: &CommunityInstallReview, theme: &Di
         }
     }
 
-    // ── Text change hook warning ──
-    if review.has_text_change_hook {
+    // ── High-frequency hook warning ──
+    if !review.high_frequency_hooks.is_empty() {
         Frame::default().with_size(content_width, 4);
         let mut hook_warn = Frame::default()
             .with_size(content_width, 32)
-            .with_label(concat!(
-                "\u{26A0} This plugin monitors every keystroke (on_text_changed).\n",
+            .with_label(&format!(
+                "\u{26A0} This plugin monitors every keystroke or cursor move ({}).\n{}",
+                review.high_frequency_hooks.join(", "),
                 "This may affect editor performance and gives the plugin access to all typed content."
             ));
         hook_warn.set_label_color(warning_color);
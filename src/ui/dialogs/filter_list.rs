@@ -0,0 +1,243 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fltk::{
+    app,
+    browser::HoldBrowser,
+    enums::{Align, CallbackTrigger, Event, FrameType, Key},
+    frame::Frame,
+    input::Input,
+    prelude::{BrowserExt, GroupExt, InputExt, WidgetBase, WidgetExt, WindowExt},
+    window::Window,
+};
+
+use super::DialogTheme;
+
+const DW: i32 = 560;
+
+/// A query input over a two-column list, shared by the Quick Open and
+/// Command Palette dialogs. Arrow and page keys move through the list while
+/// typing; Enter or a double click accepts the selected row.
+///
+/// Callers run their own event loop (instead of `run_dialog`) so the list
+/// is rebuilt outside the widget callbacks whenever `take_query` returns.
+pub(super) struct FilterList {
+    window: Window,
+    input: Input,
+    browser: HoldBrowser,
+    status: Option<Frame>,
+    accepted: Rc<Cell<bool>>,
+    query_changed: Rc<Cell<bool>>,
+    text_code: u32,
+    dim_code: u32,
+    right_align_details: bool,
+}
+
+impl FilterList {
+    /// Build and show the dialog, positioned over `parent`. `detail_width`
+    /// is the room left for the second column; `with_status` adds a hint
+    /// line under the list.
+    pub fn new(
+        parent: &Window,
+        title: &str,
+        height: i32,
+        detail_width: i32,
+        with_status: bool,
+        theme: &DialogTheme,
+    ) -> Self {
+        let mut window = Window::default()
+            .with_size(DW, height)
+            .with_label(title)
+            .center_screen();
+        window.set_color(theme.bg);
+
+        let mut input = Input::default().with_pos(10, 10).with_size(DW - 20, 28);
+        input.set_color(theme.input_bg);
+        input.set_text_color(theme.text);
+        input.set_selection_color(theme.button_bg);
+        input.set_trigger(CallbackTrigger::Changed);
+
+        let status_space = if with_status { 36 } else { 10 };
+        let mut browser = HoldBrowser::default()
+            .with_pos(10, 44)
+            .with_size(DW - 20, height - 44 - status_space);
+        browser.set_color(theme.input_bg);
+        browser.set_selection_color(theme.button_bg);
+        browser.set_frame(FrameType::FlatBox);
+        browser.set_column_widths(&[DW - detail_width]);
+        browser.set_column_char('\t');
+
+        let status = with_status.then(|| {
+            let mut status = Frame::default()
+                .with_pos(10, height - 32)
+                .with_size(DW - 20, 24)
+                .with_align(Align::Left | Align::Inside);
+            status.set_label_color(theme.text_dim);
+            status.set_label_size(12);
+            status
+        });
+
+        window.end();
+        window.make_resizable(false);
+        window.show();
+        theme.apply_titlebar(&window);
+        // Reposition to center on the parent AFTER show().
+        window.resize(
+            parent.x() + (parent.w() - DW) / 2,
+            parent.y() + (parent.h() - height) / 3,
+            DW,
+            height,
+        );
+        let _ = input.take_focus();
+
+        let accepted = Rc::new(Cell::new(false));
+        let query_changed = Rc::new(Cell::new(true));
+
+        input.set_callback({
+            let query_changed = query_changed.clone();
+            move |_| query_changed.set(true)
+        });
+
+        input.handle({
+            let mut browser = browser.clone();
+            let accepted = accepted.clone();
+            let mut dialog = window.clone();
+            move |_, ev| {
+                if ev != Event::KeyDown {
+                    return false;
+                }
+                let step = match app::event_key() {
+                    Key::Up => -1,
+                    Key::Down => 1,
+                    Key::PageUp => -10,
+                    Key::PageDown => 10,
+                    Key::Enter | Key::KPEnter => {
+                        accepted.set(browser.value() > 0);
+                        dialog.hide();
+                        return true;
+                    }
+                    _ => return false,
+                };
+                let size = browser.size();
+                if size > 0 {
+                    let line = (browser.value() + step).clamp(1, size);
+                    browser.select(line);
+                    browser.middle_line(line);
+                }
+                true
+            }
+        });
+
+        browser.handle({
+            let accepted = accepted.clone();
+            let mut dialog = window.clone();
+            move |b, ev| match ev {
+                Event::KeyDown if matches!(app::event_key(), Key::Enter | Key::KPEnter) => {
+                    accepted.set(b.value() > 0);
+                    dialog.hide();
+                    true
+                }
+                Event::Released if app::event_clicks() && b.value() > 0 => {
+                    accepted.set(true);
+                    dialog.hide();
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        window.set_callback(|d| d.hide());
+
+        let color_code =
+            |(r, g, b): (u8, u8, u8)| ((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8);
+
+        Self {
+            window,
+            input,
+            browser,
+            status,
+            accepted,
+            query_changed,
+            text_code: color_code(theme.text_rgb()),
+            dim_code: color_code(theme.text_dim.to_rgb()),
+            right_align_details: false,
+        }
+    }
+
+    /// Right-align the second column (used for shortcuts).
+    pub fn right_align_details(&mut self) {
+        self.right_align_details = true;
+    }
+
+    /// Whether the dialog is still open.
+    pub fn shown(&self) -> bool {
+        self.window.shown()
+    }
+
+    /// The current query, if it changed since the last call (or a refresh
+    /// was requested).
+    pub fn take_query(&self) -> Option<String> {
+        self.query_changed
+            .replace(false)
+            .then(|| self.input.value())
+    }
+
+    /// The current query text.
+    pub fn query(&self) -> String {
+        self.input.value()
+    }
+
+    /// Rebuild the list on the next `take_query`, e.g. when its source
+    /// changed in the background.
+    pub fn request_refresh(&self) {
+        self.query_changed.set(true);
+    }
+
+    pub fn clear(&mut self) {
+        self.browser.clear();
+    }
+
+    /// Append a row with a label and a dimmed detail column.
+    pub fn add(&mut self, label: &str, detail: &str) {
+        let align = if self.right_align_details { "@r" } else { "" };
+        self.browser.add(&format!(
+            "@C{} {}\t{}@C{} {}",
+            self.text_code,
+            label.replace('@', "@@"),
+            align,
+            self.dim_code,
+            detail.replace('@', "@@")
+        ));
+    }
+
+    pub fn set_status(&mut self, text: &str) {
+        if let Some(status) = &mut self.status {
+            status.set_label(text);
+            status.redraw();
+        }
+    }
+
+    /// Select the first row after the list was rebuilt.
+    pub fn finish(&mut self) {
+        if self.browser.size() > 0 {
+            self.browser.select(1);
+        }
+        self.browser.redraw();
+    }
+
+    /// Wait for the next event; closes the dialog when the app quits.
+    pub fn wait(&mut self) {
+        app::wait();
+        if app::should_program_quit() {
+            self.window.hide();
+        }
+    }
+
+    /// The 0-based row the user accepted, once the dialog has closed.
+    pub fn chosen(&self) -> Option<usize> {
+        if !self.accepted.get() {
+            return None;
+        }
+        Some((self.browser.value() - 1).max(0) as usize)
+    }
+}
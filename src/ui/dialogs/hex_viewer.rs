@@ -0,0 +1,934 @@
+//! Hex viewer for binary files.
+//!
+//! Shows a memory-mapped file one page at a time as rows of offset, hex
+//! bytes and ASCII, so multi-GB binaries open instantly. Supports go to
+//! offset, hex pattern search, an inspector showing the selected bytes as
+//! integers and floats, and overwriting bytes (typed as hex digits or
+//! ASCII) with an in-place save. Searches run in a background thread over
+//! the file with the unsaved edits applied; Escape stops them.
+
+use fltk::{
+    app,
+    button::Button,
+    dialog,
+    enums::{Align, Event, Font, FrameType, Key, Shortcut},
+    frame::Frame,
+    group::{Flex, FlexType},
+    input::Input,
+    prelude::*,
+    text::{Cursor, TextBuffer, TextDisplay},
+    window::Window,
+};
+use memmap2::Mmap;
+use std::cell::RefCell;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+
+use super::{DialogTheme, SCROLLBAR_SIZE};
+use crate::app::infrastructure::display::style_scrollbars;
+use crate::app::services::file_size::format_size;
+use crate::app::services::hex::{
+    BYTES_PER_ROW, ByteSearch, HexEdits, HexLayout, find_bytes, interpret, parse_hex_pattern,
+    parse_offset, rfind_bytes,
+};
+
+/// Bytes shown per page
+const PAGE_BYTES: usize = 64 * 1024;
+
+/// Bytes interpreted by the inspector (the widest type)
+const INSPECT_BYTES: usize = 8;
+
+/// What to do with a file that looks binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryFileAction {
+    /// Open it in the hex viewer
+    HexView,
+    /// Open it in an editor tab anyway
+    OpenAsText,
+    /// Don't open it
+    Cancel,
+}
+
+/// Ask how to open a file that looks binary
+pub fn show_binary_file_prompt(path: &Path) -> BinaryFileAction {
+    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+    let msg = format!(
+        "\"{}\" looks like a binary file.\n\n\
+        Open it in the hex viewer?",
+        filename
+    );
+    match dialog::choice2_default(&msg, "Hex Viewer", "Open as Text", "Cancel") {
+        Some(0) => BinaryFileAction::HexView,
+        Some(1) => BinaryFileAction::OpenAsText,
+        _ => BinaryFileAction::Cancel,
+    }
+}
+
+/// The widgets updated by the viewer
+#[derive(Clone)]
+struct HexWidgets {
+    display: TextDisplay,
+    buffer: TextBuffer,
+    inspector: TextBuffer,
+    pos_label: Frame,
+    size_label: Frame,
+}
+
+/// A search running in a background thread
+struct HexSearch {
+    rx: mpsc::Receiver<ByteSearch>,
+    progress: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    /// Bytes the search may have to go through
+    total: usize,
+    pattern_len: usize,
+    forward: bool,
+    /// Shown when there is no match
+    not_found: &'static str,
+    /// The position label to put back when there is no match or the
+    /// search is stopped
+    label: String,
+}
+
+/// Shared state for the viewer
+struct HexViewerState {
+    path: PathBuf,
+    mmap: Arc<Mmap>,
+    file_size: usize,
+    layout: HexLayout,
+    total_pages: usize,
+    current_page: usize,
+    edits: HexEdits,
+    /// Start of the current search match (Find Next continues after it)
+    match_start: Option<usize>,
+    search: Option<HexSearch>,
+}
+
+impl HexViewerState {
+    fn new(path: PathBuf, mmap: Mmap, file_size: usize) -> Self {
+        Self {
+            path,
+            mmap: Arc::new(mmap),
+            file_size,
+            layout: HexLayout::for_size(file_size as u64),
+            total_pages: file_size.div_ceil(PAGE_BYTES),
+            current_page: 0,
+            edits: HexEdits::new(),
+            match_start: None,
+            search: None,
+        }
+    }
+
+    fn page_start(&self) -> usize {
+        self.current_page * PAGE_BYTES
+    }
+
+    fn page_end(&self) -> usize {
+        (self.page_start() + PAGE_BYTES).min(self.file_size)
+    }
+
+    /// Buffer characters per row, with the newline
+    fn row_stride(&self) -> usize {
+        self.layout.row_len() + 1
+    }
+
+    /// Bytes `start..end` with the unsaved edits applied
+    fn bytes(&self, start: usize, end: usize) -> Vec<u8> {
+        let mut bytes = self.mmap[start..end].to_vec();
+        self.edits.apply(start as u64, &mut bytes);
+        bytes
+    }
+
+    fn load_current_page(&mut self, w: &mut HexWidgets) {
+        let start = self.page_start();
+        let end = self.page_end();
+        let bytes = self.bytes(start, end);
+
+        let mut text =
+            String::with_capacity(bytes.len().div_ceil(BYTES_PER_ROW) * self.row_stride());
+        for (i, row) in bytes.chunks(BYTES_PER_ROW).enumerate() {
+            if i > 0 {
+                text.push('\n');
+            }
+            let offset = (start + i * BYTES_PER_ROW) as u64;
+            text.push_str(&self.layout.format_row(offset, row));
+        }
+        w.buffer.set_text(&text);
+        w.display.set_insert_position(self.position(start, false));
+        w.display.show_insert_position();
+
+        w.pos_label.set_label(&format!(
+            "Page {}/{} | Offset {:X}-{:X}",
+            self.current_page + 1,
+            self.total_pages,
+            start,
+            end - 1
+        ));
+        self.update_inspector(w);
+    }
+
+    fn go_to_page(&mut self, page: usize, w: &mut HexWidgets) {
+        if page < self.total_pages {
+            self.current_page = page;
+            self.load_current_page(w);
+        }
+    }
+
+    fn prev_page(&mut self, w: &mut HexWidgets) {
+        if self.current_page > 0 {
+            self.go_to_page(self.current_page - 1, w);
+        }
+    }
+
+    fn next_page(&mut self, w: &mut HexWidgets) {
+        self.go_to_page(self.current_page + 1, w);
+    }
+
+    /// Buffer position of byte `offset` on the current page: its first hex
+    /// digit, or its ASCII gutter character
+    fn position(&self, offset: usize, ascii: bool) -> i32 {
+        let local = offset - self.page_start();
+        let index = local % BYTES_PER_ROW;
+        let column = if ascii {
+            self.layout.ascii_column(index)
+        } else {
+            self.layout.hex_column(index)
+        };
+        (local / BYTES_PER_ROW * self.row_stride() + column) as i32
+    }
+
+    /// The byte under buffer position `pos`, whether `pos` is its low hex
+    /// digit, and whether it is in the ASCII gutter
+    fn byte_at_position(&self, pos: i32) -> Option<(usize, bool, bool)> {
+        let pos = pos.max(0) as usize;
+        let column = pos % self.row_stride();
+        let (index, low) = self.layout.byte_at_column(column)?;
+        let offset = self.page_start() + pos / self.row_stride() * BYTES_PER_ROW + index;
+        (offset < self.page_end()).then_some((offset, low, column >= self.layout.ascii_column(0)))
+    }
+
+    /// Show the page containing `offset` and select `len` bytes from it
+    fn select_bytes(&mut self, offset: usize, len: usize, w: &mut HexWidgets) {
+        let page = offset / PAGE_BYTES;
+        if page != self.current_page {
+            self.go_to_page(page, w);
+        }
+        // A selection running past the page end stops there
+        let last = (offset + len.max(1) - 1).min(self.page_end() - 1);
+        let start = self.position(offset, false);
+        w.buffer.select(start, self.position(last, false) + 2);
+        w.display.set_insert_position(start);
+        w.display.show_insert_position();
+        self.update_inspector(w);
+    }
+
+    fn go_to_offset(&mut self, offset: usize, w: &mut HexWidgets) -> bool {
+        if offset >= self.file_size {
+            return false;
+        }
+        self.select_bytes(offset, 1, w);
+        true
+    }
+
+    /// Start looking for `pattern` after the current match (or from the
+    /// start of the file) or before it, in a background thread that
+    /// `poll_search` collects. Returns the message to show when there is
+    /// nothing to search.
+    fn start_search(
+        &mut self,
+        pattern: Vec<u8>,
+        from_start: bool,
+        forward: bool,
+        w: &mut HexWidgets,
+    ) -> Option<&'static str> {
+        let (from, not_found) = match (from_start, forward, self.match_start) {
+            (true, _, _) => (0, "Bytes not found."),
+            (false, true, start) => (start.map_or(0, |s| s + 1), "No more matches found."),
+            (false, false, Some(start)) => (start, "No previous matches found."),
+            (false, false, None) => return Some("No previous matches found."),
+        };
+        let label = match self.search.take() {
+            Some(search) => {
+                search.cancelled.store(true, Ordering::Relaxed);
+                search.label
+            }
+            None => w.pos_label.label(),
+        };
+
+        let progress = Arc::new(AtomicUsize::new(0));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        self.search = Some(HexSearch {
+            rx,
+            progress: Arc::clone(&progress),
+            cancelled: Arc::clone(&cancelled),
+            total: if forward {
+                self.file_size.saturating_sub(from)
+            } else {
+                from
+            },
+            pattern_len: pattern.len(),
+            forward,
+            not_found,
+            label,
+        });
+        let mmap = Arc::clone(&self.mmap);
+        let edits = self.edits.clone();
+        std::thread::spawn(move || {
+            let result = if forward {
+                find_bytes(&mmap, &edits, &pattern, from, &progress, &cancelled)
+            } else {
+                rfind_bytes(&mmap, &edits, &pattern, from, &progress, &cancelled)
+            };
+            let _ = tx.send(result);
+            app::awake();
+        });
+        w.pos_label.set_label("Searching... (Esc to stop)");
+        None
+    }
+
+    /// Show the result of the running search once it arrives, or its
+    /// progress until then. Returns the message to show when there was no
+    /// match.
+    fn poll_search(&mut self, w: &mut HexWidgets) -> Option<&'static str> {
+        let search = self.search.as_ref()?;
+        let result = match search.rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => {
+                let done = search.progress.load(Ordering::Relaxed);
+                w.pos_label.set_label(&format!(
+                    "Searching {}%... (Esc to stop)",
+                    done * 100 / search.total.max(1)
+                ));
+                return None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => ByteSearch::Cancelled,
+        };
+        let search = self.search.take()?;
+        w.pos_label.set_label(&search.label);
+        match result {
+            ByteSearch::Found(found) => {
+                self.match_start = Some(found);
+                self.select_bytes(found, search.pattern_len, w);
+                None
+            }
+            ByteSearch::NotFound => {
+                // Find Next starts over from the top after this
+                if search.forward {
+                    self.match_start = None;
+                }
+                Some(search.not_found)
+            }
+            ByteSearch::Cancelled => None,
+        }
+    }
+
+    /// Stop the running search. Returns false if none was running.
+    fn stop_search(&mut self, w: &mut HexWidgets) -> bool {
+        let Some(search) = self.search.take() else {
+            return false;
+        };
+        search.cancelled.store(true, Ordering::Relaxed);
+        w.pos_label.set_label(&search.label);
+        true
+    }
+
+    /// Show the selected bytes (or the bytes from the cursor) as numbers
+    fn update_inspector(&self, w: &mut HexWidgets) {
+        let cursor = self.byte_at_position(w.display.insert_position());
+        let (first, last) = match w.buffer.selection_position() {
+            Some((start, end)) if end > start => (
+                self.byte_at_position(start).or(cursor),
+                self.byte_at_position(end - 1),
+            ),
+            _ => (cursor, None),
+        };
+        let Some((offset, ..)) = first else {
+            w.inspector.set_text("");
+            return;
+        };
+
+        let selected = last.map_or(1, |(end, ..)| end.saturating_sub(offset) + 1);
+        let len = if selected > 1 {
+            selected.min(INSPECT_BYTES)
+        } else {
+            INSPECT_BYTES
+        };
+        let bytes = self.bytes(offset, (offset + len).min(self.file_size));
+
+        let mut text = format!("Offset {:X} ({})", offset, offset);
+        if selected > 1 {
+            text.push_str(&format!(", {} bytes selected", selected));
+        }
+        text.push_str(&format!(
+            "\n\n{:<6}{:<28}{}",
+            "Type", "Little endian", "Big endian"
+        ));
+        for value in interpret(&bytes) {
+            text.push_str(&format!(
+                "\n{:<6}{:<28}{}",
+                value.name, value.little_endian, value.big_endian
+            ));
+        }
+        w.inspector.set_text(&text);
+    }
+
+    fn update_size_label(&self, w: &mut HexWidgets) {
+        let total = format!("Total: {}", format_size(self.file_size as u64));
+        if self.edits.is_empty() {
+            w.size_label.set_label(&total);
+        } else {
+            w.size_label
+                .set_label(&format!("{} | {} bytes modified", total, self.edits.len()));
+        }
+    }
+
+    /// Overwrite the byte under the cursor with a typed character: a hex
+    /// digit in the hex columns, a printable character in the ASCII gutter.
+    /// Returns false when the character is no edit there.
+    fn type_char(&mut self, ch: char, w: &mut HexWidgets) -> bool {
+        let Some((offset, low, ascii)) = self.byte_at_position(w.display.insert_position()) else {
+            return false;
+        };
+        let original = self.mmap[offset];
+        if ascii {
+            if !ch.is_ascii_graphic() && ch != ' ' {
+                return false;
+            }
+            self.edits.set(offset as u64, ch as u8, original);
+        } else {
+            let Some(digit) = ch.to_digit(16) else {
+                return false;
+            };
+            let current = self.edits.get(offset as u64).unwrap_or(original);
+            self.edits
+                .set_nibble(offset as u64, current, original, low, digit as u8);
+        }
+        self.refresh_row(offset, w);
+
+        // Advance to the low digit, or to the next byte on this page
+        let next = if !ascii && !low {
+            self.position(offset, false) + 1
+        } else if offset + 1 < self.page_end() {
+            self.position(offset + 1, ascii)
+        } else {
+            self.position(offset, ascii)
+        };
+        w.buffer.unselect();
+        w.display.set_insert_position(next);
+        w.display.show_insert_position();
+        self.update_inspector(w);
+        self.update_size_label(w);
+        true
+    }
+
+    /// Redraw the row containing byte `offset` with the current edits
+    fn refresh_row(&self, offset: usize, w: &mut HexWidgets) {
+        let row_offset = offset - offset % BYTES_PER_ROW;
+        let row_end = (row_offset + BYTES_PER_ROW).min(self.file_size);
+        let text = self
+            .layout
+            .format_row(row_offset as u64, &self.bytes(row_offset, row_end));
+        let start = self.position(row_offset, false) - self.layout.hex_column(0) as i32;
+        w.buffer.replace(start, start + text.len() as i32, &text);
+    }
+
+    /// Move the cursor by `delta` buffer characters
+    fn move_cursor(&self, delta: i32, w: &mut HexWidgets) {
+        let pos = (w.display.insert_position() + delta).clamp(0, w.buffer.length());
+        w.buffer.unselect();
+        w.display.set_insert_position(pos);
+        w.display.show_insert_position();
+        self.update_inspector(w);
+    }
+
+    /// Write the edits into the file. Reports a failure and returns false.
+    fn save(&mut self, w: &mut HexWidgets) -> bool {
+        if self.edits.is_empty() {
+            return true;
+        }
+        match self.edits.save(&self.path) {
+            Ok(()) => {
+                // The map now shows the saved bytes, as the display already does
+                self.update_size_label(w);
+                true
+            }
+            Err(e) => {
+                dialog::alert_default(&format!("Failed to save file: {}", e));
+                false
+            }
+        }
+    }
+}
+
+/// Ask to save unsaved edits before closing. Returns false if the user
+/// cancelled or the save failed.
+fn confirm_close(state: &Rc<RefCell<HexViewerState>>, w: &mut HexWidgets) -> bool {
+    let modified = state.borrow().edits.len();
+    if modified == 0 {
+        return true;
+    }
+    let msg = format!(
+        "{} bytes were modified.\n\nSave the changes before closing?",
+        modified
+    );
+    match dialog::choice2_default(&msg, "Save", "Discard", "Cancel") {
+        Some(0) => state.borrow_mut().save(w),
+        Some(1) => true,
+        _ => false,
+    }
+}
+
+/// Page Up/Down, Ctrl+Home/End, Ctrl+S and Escape (stopping a search),
+/// from the display or the window
+fn handle_shortcut(state: &Rc<RefCell<HexViewerState>>, w: &mut HexWidgets) -> bool {
+    let ctrl = app::event_state().contains(Shortcut::Ctrl);
+    let mut s = state.borrow_mut();
+    match app::event_key() {
+        Key::PageUp => s.prev_page(w),
+        Key::PageDown => s.next_page(w),
+        Key::Home if ctrl => s.go_to_page(0, w),
+        Key::End if ctrl => {
+            let last = s.total_pages - 1;
+            s.go_to_page(last, w);
+        }
+        key if ctrl && key == Key::from_char('s') => {
+            s.save(w);
+        }
+        Key::Escape => return s.stop_search(w),
+        _ => return false,
+    }
+    true
+}
+
+/// Open the hex viewer for a file.
+///
+/// This creates a modal window with:
+/// - Memory-mapped file access, one 64 KiB page at a time
+/// - Offset column, hex bytes and ASCII gutter
+/// - Go to offset (decimal, `0x1F` or `1Fh`)
+/// - Hex pattern search
+/// - An inspector showing the selected bytes as integers and floats
+/// - Byte overwriting by typing hex digits or ASCII, saved in place
+pub fn show_hex_viewer(path: &Path, theme_bg: (u8, u8, u8), code_font: Font, code_font_size: i32) {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            dialog::alert_default(&format!("Failed to open file: {}", e));
+            return;
+        }
+    };
+
+    let file_size = match file.metadata() {
+        Ok(m) => m.len() as usize,
+        Err(e) => {
+            dialog::alert_default(&format!("Failed to read file metadata: {}", e));
+            return;
+        }
+    };
+    if file_size == 0 {
+        dialog::alert_default("File is empty.");
+        return;
+    }
+
+    // SAFETY: The file is mapped read-only. The viewer itself only changes
+    // it by overwriting bytes in place, which never changes its length.
+    let mmap = match unsafe { Mmap::map(&file) } {
+        Ok(m) => m,
+        Err(e) => {
+            dialog::alert_default(&format!("Failed to memory-map file: {}", e));
+            return;
+        }
+    };
+
+    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+    let state = Rc::new(RefCell::new(HexViewerState::new(
+        path.to_path_buf(),
+        mmap,
+        file_size,
+    )));
+
+    let theme = DialogTheme::from_theme_bg(theme_bg);
+
+    let title = format!("{} (Hex Viewer)", filename);
+    let mut window = Window::new(100, 100, 900, 760, None);
+    window.set_label(&title);
+    window.make_modal(true);
+    window.set_color(theme.bg);
+
+    let mut main_flex = Flex::new(5, 5, 890, 750, None);
+    main_flex.set_type(FlexType::Column);
+    main_flex.set_spacing(5);
+
+    // Top toolbar: go to offset and hex pattern search
+    let mut toolbar = Flex::default();
+    toolbar.set_type(FlexType::Row);
+    toolbar.set_spacing(5);
+
+    let mut offset_input = Input::default();
+    offset_input.set_tooltip("Offset: decimal, 0x1F or 1Fh");
+    offset_input.set_color(theme.input_bg);
+    offset_input.set_text_color(theme.text);
+    offset_input.set_frame(FrameType::FlatBox);
+    toolbar.fixed(&offset_input, 120);
+
+    let mut offset_btn = Button::default().with_label("Go to Offset");
+    offset_btn.set_frame(FrameType::RFlatBox);
+    offset_btn.set_color(theme.button_bg);
+    offset_btn.set_label_color(theme.text);
+    toolbar.fixed(&offset_btn, 100);
+
+    let mut spacer = Frame::default();
+    spacer.set_frame(FrameType::FlatBox);
+    spacer.set_color(theme.bg);
+
+    let mut search_input = Input::default();
+    search_input.set_tooltip("Hex bytes to find (e.g., DE AD BE EF)");
+    search_input.set_color(theme.input_bg);
+    search_input.set_text_color(theme.text);
+    search_input.set_frame(FrameType::FlatBox);
+    toolbar.fixed(&search_input, 200);
+
+    let mut search_btn = Button::default().with_label("Find");
+    search_btn.set_tooltip("Find from start");
+    search_btn.set_frame(FrameType::RFlatBox);
+    search_btn.set_color(theme.button_bg);
+    search_btn.set_label_color(theme.text);
+    toolbar.fixed(&search_btn, 60);
+
+    let mut search_prev_btn = Button::default().with_label("@<");
+    search_prev_btn.set_tooltip("Find previous");
+    search_prev_btn.set_frame(FrameType::RFlatBox);
+    search_prev_btn.set_color(theme.button_bg);
+    search_prev_btn.set_label_color(theme.text);
+    toolbar.fixed(&search_prev_btn, 30);
+
+    let mut search_next_btn = Button::default().with_label("@>");
+    search_next_btn.set_tooltip("Find next");
+    search_next_btn.set_frame(FrameType::RFlatBox);
+    search_next_btn.set_color(theme.button_bg);
+    search_next_btn.set_label_color(theme.text);
+    toolbar.fixed(&search_next_btn, 30);
+
+    toolbar.end();
+    main_flex.fixed(&toolbar, 30);
+
+    // Hex rows of the current page
+    let mut display = TextDisplay::default();
+    let buffer = TextBuffer::default();
+    display.set_buffer(buffer.clone());
+    display.set_text_font(code_font);
+    display.set_text_size(code_font_size.clamp(6, 96));
+    display.set_linenumber_width(0);
+    display.set_frame(FrameType::FlatBox);
+    display.set_color(theme.input_bg);
+    display.set_text_color(theme.text);
+    display.set_cursor_color(theme.text);
+    display.set_cursor_style(Cursor::Block);
+    display.show_cursor(true);
+    display.set_scrollbar_size(SCROLLBAR_SIZE);
+    style_scrollbars(
+        display.as_widget_ptr(),
+        theme.scroll_track,
+        theme.scroll_thumb,
+    );
+
+    // Value inspector for the selection
+    let mut inspector_display = TextDisplay::default();
+    let inspector = TextBuffer::default();
+    inspector_display.set_buffer(inspector.clone());
+    inspector_display.set_text_font(code_font);
+    inspector_display.set_text_size(code_font_size.clamp(6, 96));
+    inspector_display.set_frame(FrameType::FlatBox);
+    inspector_display.set_color(theme.bg);
+    inspector_display.set_text_color(theme.text_dim);
+    inspector_display.set_scrollbar_size(SCROLLBAR_SIZE);
+    style_scrollbars(
+        inspector_display.as_widget_ptr(),
+        theme.scroll_track,
+        theme.scroll_thumb,
+    );
+    main_flex.fixed(&inspector_display, 13 * (code_font_size.clamp(6, 96) + 4));
+
+    // Status bar
+    let mut status_flex = Flex::default();
+    status_flex.set_type(FlexType::Row);
+
+    let mut pos_label = Frame::default();
+    pos_label.set_align(Align::Left | Align::Inside);
+    pos_label.set_label_color(theme.text_dim);
+    pos_label.set_frame(FrameType::FlatBox);
+    pos_label.set_color(theme.bg);
+
+    let mut size_label = Frame::default();
+    size_label.set_align(Align::Right | Align::Inside);
+    size_label.set_label_color(theme.text_dim);
+    size_label.set_frame(FrameType::FlatBox);
+    size_label.set_color(theme.bg);
+    status_flex.fixed(&size_label, 300);
+
+    status_flex.end();
+    main_flex.fixed(&status_flex, 25);
+
+    // Bottom row: [<< Prev] [Next >>] ... [Save] [Close]
+    let mut btn_row = Flex::default();
+    btn_row.set_type(FlexType::Row);
+    btn_row.set_spacing(5);
+
+    let mut prev_btn = Button::default().with_label("@<  Prev Page");
+    prev_btn.set_tooltip("Previous page (Page Up)");
+    prev_btn.set_frame(FrameType::RFlatBox);
+    prev_btn.set_color(theme.button_bg);
+    prev_btn.set_label_color(theme.text);
+    btn_row.fixed(&prev_btn, 110);
+
+    let mut next_btn = Button::default().with_label("Next Page  @>");
+    next_btn.set_tooltip("Next page (Page Down)");
+    next_btn.set_frame(FrameType::RFlatBox);
+    next_btn.set_color(theme.button_bg);
+    next_btn.set_label_color(theme.text);
+    btn_row.fixed(&next_btn, 110);
+
+    let mut spacer_bottom = Frame::default();
+    spacer_bottom.set_frame(FrameType::FlatBox);
+    spacer_bottom.set_color(theme.bg);
+
+    let mut save_btn = Button::default().with_label("Save");
+    save_btn.set_tooltip("Write the modified bytes into the file (Ctrl+S)");
+    save_btn.set_frame(FrameType::RFlatBox);
+    save_btn.set_color(theme.button_bg);
+    save_btn.set_label_color(theme.text);
+    btn_row.fixed(&save_btn, 100);
+
+    let mut close_btn = Button::default().with_label("Close");
+    close_btn.set_frame(FrameType::RFlatBox);
+    close_btn.set_color(theme.secondary_button_bg());
+    close_btn.set_label_color(theme.text_dim);
+    btn_row.fixed(&close_btn, 100);
+
+    btn_row.end();
+    main_flex.fixed(&btn_row, 35);
+
+    main_flex.end();
+    window.end();
+    window.show();
+    theme.apply_titlebar(&window);
+
+    let widgets = HexWidgets {
+        display: display.clone(),
+        buffer,
+        inspector,
+        pos_label,
+        size_label,
+    };
+
+    // Load the first page
+    {
+        let mut w = widgets.clone();
+        let mut s = state.borrow_mut();
+        s.load_current_page(&mut w);
+        s.update_size_label(&mut w);
+    }
+    let _ = display.take_focus();
+
+    // Close button and window close ask about unsaved edits
+    let state_close = Rc::clone(&state);
+    let mut w_close = widgets.clone();
+    let mut win_close = window.clone();
+    close_btn.set_callback(move |_| {
+        if confirm_close(&state_close, &mut w_close) {
+            win_close.hide();
+        }
+    });
+    let mut close_btn_win = close_btn.clone();
+    window.set_callback(move |_| {
+        close_btn_win.do_callback();
+    });
+
+    // Save button
+    let state_save = Rc::clone(&state);
+    let mut w_save = widgets.clone();
+    save_btn.set_callback(move |_| {
+        state_save.borrow_mut().save(&mut w_save);
+    });
+
+    // Previous / next page buttons
+    let state_prev = Rc::clone(&state);
+    let mut w_prev = widgets.clone();
+    prev_btn.set_callback(move |_| {
+        state_prev.borrow_mut().prev_page(&mut w_prev);
+    });
+    let state_next = Rc::clone(&state);
+    let mut w_next = widgets.clone();
+    next_btn.set_callback(move |_| {
+        state_next.borrow_mut().next_page(&mut w_next);
+    });
+
+    // Go to offset button (Enter in the input too)
+    let state_offset = Rc::clone(&state);
+    let mut w_offset = widgets.clone();
+    let offset_input_val = offset_input.clone();
+    offset_btn.set_callback(move |_| {
+        let Some(offset) = parse_offset(&offset_input_val.value()) else {
+            dialog::message_default("Enter an offset such as 4096, 0x1000 or 1000h");
+            return;
+        };
+        let found = usize::try_from(offset).is_ok_and(|offset| {
+            state_offset
+                .borrow_mut()
+                .go_to_offset(offset, &mut w_offset)
+        });
+        if !found {
+            dialog::message_default("Offset is past the end of the file.");
+        }
+    });
+    let mut offset_btn_enter = offset_btn.clone();
+    offset_input.set_trigger(fltk::enums::CallbackTrigger::EnterKey);
+    offset_input.set_callback(move |_| {
+        offset_btn_enter.do_callback();
+    });
+
+    // Search buttons: Find (from start), previous and next
+    let search_pattern = {
+        let search_input = search_input.clone();
+        move || {
+            let pattern = parse_hex_pattern(&search_input.value());
+            if pattern.is_none() {
+                dialog::message_default("Enter hex bytes to find, such as DE AD BE EF");
+            }
+            pattern
+        }
+    };
+
+    let state_search = Rc::clone(&state);
+    let mut w_search = widgets.clone();
+    let pattern_search = search_pattern.clone();
+    search_btn.set_callback(move |_| {
+        let Some(pattern) = pattern_search() else {
+            return;
+        };
+        let message = state_search
+            .borrow_mut()
+            .start_search(pattern, true, true, &mut w_search);
+        if let Some(message) = message {
+            dialog::message_default(message);
+        }
+    });
+    let mut search_btn_enter = search_btn.clone();
+    search_input.set_trigger(fltk::enums::CallbackTrigger::EnterKey);
+    search_input.set_callback(move |_| {
+        search_btn_enter.do_callback();
+    });
+
+    let state_search_next = Rc::clone(&state);
+    let mut w_search_next = widgets.clone();
+    let pattern_next = search_pattern.clone();
+    search_next_btn.set_callback(move |_| {
+        let Some(pattern) = pattern_next() else {
+            return;
+        };
+        let message =
+            state_search_next
+                .borrow_mut()
+                .start_search(pattern, false, true, &mut w_search_next);
+        if let Some(message) = message {
+            dialog::message_default(message);
+        }
+    });
+
+    let state_search_prev = Rc::clone(&state);
+    let mut w_search_prev = widgets.clone();
+    search_prev_btn.set_callback(move |_| {
+        let Some(pattern) = search_pattern() else {
+            return;
+        };
+        let message =
+            state_search_prev
+                .borrow_mut()
+                .start_search(pattern, false, false, &mut w_search_prev);
+        if let Some(message) = message {
+            dialog::message_default(message);
+        }
+    });
+
+    // Typing in the display overwrites bytes; arrows move by character
+    // (the display itself would only scroll)
+    let state_display = Rc::clone(&state);
+    let mut w_display = widgets.clone();
+    display.handle(move |_, ev| {
+        if ev != Event::KeyDown {
+            return false;
+        }
+        if handle_shortcut(&state_display, &mut w_display) {
+            return true;
+        }
+        let stride = state_display.borrow().row_stride() as i32;
+        let delta = match app::event_key() {
+            Key::Left => -1,
+            Key::Right => 1,
+            Key::Up => -stride,
+            Key::Down => stride,
+            _ => 0,
+        };
+        if delta != 0 {
+            state_display.borrow().move_cursor(delta, &mut w_display);
+            return true;
+        }
+        // Leave Ctrl+C and Ctrl+A to the display
+        if app::event_state().intersects(Shortcut::Ctrl | Shortcut::Command | Shortcut::Alt) {
+            return false;
+        }
+        match app::event_text().chars().next() {
+            Some(ch) => state_display.borrow_mut().type_char(ch, &mut w_display),
+            None => false,
+        }
+    });
+
+    // The same shortcuts when an input has focus
+    let state_key = Rc::clone(&state);
+    let mut w_key = widgets.clone();
+    window.handle(move |_, ev| match ev {
+        Event::KeyDown => handle_shortcut(&state_key, &mut w_key),
+        _ => false,
+    });
+
+    // Run the dialog event loop; mouse selections update the inspector
+    // once the display has handled them, and a running search reports its
+    // progress and result
+    let mut w_loop = widgets.clone();
+    let mut last_selection = None;
+    while window.shown() {
+        app::wait_for(0.1).ok();
+
+        let not_found = state.borrow_mut().poll_search(&mut w_loop);
+        // Shown with the state released, the dialog runs its own loop
+        if let Some(message) = not_found {
+            dialog::message_default(message);
+        }
+
+        let selection = (
+            w_loop.display.insert_position(),
+            w_loop.buffer.selection_position(),
+        );
+        if last_selection != Some(selection) {
+            last_selection = Some(selection);
+            state.borrow().update_inspector(&mut w_loop);
+        }
+
+        if app::should_program_quit() {
+            if confirm_close(&state, &mut w_loop) {
+                window.hide();
+            } else {
+                app::program_should_quit(false);
+            }
+        }
+    }
+
+    // Stop a search still running when the viewer closes
+    if let Some(search) = state.borrow_mut().search.take() {
+        search.cancelled.store(true, Ordering::Relaxed);
+    }
+}
@@ -1,17 +1,24 @@
 pub mod about;
+pub mod bookmarks;
+pub mod command_palette;
 pub mod community_install;
+pub mod filter_list;
 pub mod find;
 pub mod font_picker;
 pub mod goto_line;
+pub mod hex_viewer;
 pub mod large_file;
+pub mod open_range;
 pub mod plugin_config;
 pub mod plugin_manager;
 pub mod plugin_permissions;
 pub mod plugin_settings;
+pub mod quick_open;
 pub mod readonly_viewer;
 pub mod session_picker;
 pub mod settings_dialog;
 pub mod shortcut_dialog;
+pub mod task_picker;
 pub mod update;
 
 use fltk::{
@@ -337,6 +344,23 @@ impl DialogTheme {
         }
     }
 
+    /// Background for secondary buttons (Close, Cancel): a step away from
+    /// the tab bar shade so they read as less prominent than `button_bg`.
+    pub fn secondary_button_bg(&self) -> Color {
+        let (r, g, b) = self.theme_bg;
+        let (bg_r, bg_g, bg_b) = if self.is_dark {
+            darken(r, g, b, 0.65)
+        } else {
+            darken(r, g, b, 0.85)
+        };
+        let (cr, cg, cb) = if self.is_dark {
+            lighten(bg_r, bg_g, bg_b, 0.10)
+        } else {
+            darken(bg_r, bg_g, bg_b, 0.90)
+        };
+        Color::from_rgb(cr, cg, cb)
+    }
+
     /// Error/warning text color adapted to the theme brightness.
     /// Light red on dark backgrounds for readability, dark red on light backgrounds.
     pub fn error_color(&self) -> Color {
@@ -0,0 +1,128 @@
+use fltk::{
+    button::{Button, RadioRoundButton},
+    dialog,
+    enums::{Align, CallbackTrigger},
+    frame::Frame,
+    group::Group,
+    input::Input,
+    prelude::*,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::DialogTheme;
+use crate::app::services::file_size::{OpenRange, format_size, parse_range};
+
+/// Show the Open Range dialog for a file: a line range (inclusive) or a
+/// byte range (end exclusive) to open as an editable chunk.
+pub fn show_open_range_dialog(
+    filename: &str,
+    file_size: u64,
+    theme_bg: (u8, u8, u8),
+) -> Option<OpenRange> {
+    let theme = DialogTheme::from_theme_bg(theme_bg);
+    let result: Rc<RefCell<Option<OpenRange>>> = Rc::new(RefCell::new(None));
+
+    let mut dialog_win = Window::default()
+        .with_size(360, 190)
+        .with_label("Open Range")
+        .center_screen();
+    dialog_win.set_color(theme.bg);
+
+    let mut info = Frame::default()
+        .with_pos(20, 10)
+        .with_size(320, 30)
+        .with_label(&format!("\"{}\" ({})", filename, format_size(file_size)));
+    info.set_align(Align::Left | Align::Inside | Align::Clip);
+    info.set_label_color(theme.text);
+
+    let kind_group = Group::default().with_pos(20, 45).with_size(320, 25);
+    let mut lines_radio = RadioRoundButton::default()
+        .with_pos(20, 45)
+        .with_size(100, 25)
+        .with_label("Lines");
+    lines_radio.set_label_color(theme.text);
+    lines_radio.set_color(theme.bg);
+    lines_radio.set_value(true);
+    let mut bytes_radio = RadioRoundButton::default()
+        .with_pos(130, 45)
+        .with_size(100, 25)
+        .with_label("Bytes");
+    bytes_radio.set_label_color(theme.text);
+    bytes_radio.set_color(theme.bg);
+    kind_group.end();
+
+    let mut label = Frame::default()
+        .with_pos(20, 80)
+        .with_size(70, 30)
+        .with_label("Range:");
+    label.set_label_color(theme.text);
+    let mut range_input = Input::default().with_pos(100, 80).with_size(240, 30);
+    range_input.set_value("1-10000");
+    range_input.set_tooltip("Lines: first-last (inclusive)\nBytes: start-end (end exclusive)");
+    range_input.set_color(theme.input_bg);
+    range_input.set_text_color(theme.text);
+    range_input.set_selection_color(theme.button_bg);
+
+    let mut open_btn = Button::default()
+        .with_pos(170, 135)
+        .with_size(80, 30)
+        .with_label("Open");
+    open_btn.set_color(theme.button_bg);
+    open_btn.set_label_color(theme.text);
+    let mut cancel_btn = Button::default()
+        .with_pos(260, 135)
+        .with_size(80, 30)
+        .with_label("Cancel");
+    cancel_btn.set_color(theme.button_bg);
+    cancel_btn.set_label_color(theme.text);
+
+    dialog_win.end();
+    dialog_win.make_resizable(false);
+    dialog_win.show();
+    theme.apply_titlebar(&dialog_win);
+    let _ = range_input.take_focus();
+
+    let result_open = result.clone();
+    let dialog_open = dialog_win.clone();
+    let range_input_open = range_input.clone();
+    let lines_open = lines_radio.clone();
+    open_btn.set_callback(move |_| {
+        let Some((start, end)) = parse_range(&range_input_open.value()) else {
+            dialog::message_default("Enter a range such as 100-200");
+            return;
+        };
+        let range = if lines_open.value() {
+            if start == 0 {
+                dialog::message_default("Line numbers start at 1");
+                return;
+            }
+            OpenRange::Lines(start as usize, end as usize)
+        } else {
+            OpenRange::Bytes(start, end)
+        };
+        *result_open.borrow_mut() = Some(range);
+        dialog_open.clone().hide();
+    });
+
+    // Enter key on input triggers Open
+    let mut open_btn2 = open_btn.clone();
+    range_input.set_trigger(CallbackTrigger::EnterKey);
+    range_input.set_callback(move |_| {
+        open_btn2.do_callback();
+    });
+
+    let dialog_close = dialog_win.clone();
+    cancel_btn.set_callback(move |_| {
+        dialog_close.clone().hide();
+    });
+
+    let dialog_x = dialog_win.clone();
+    dialog_win.set_callback(move |_| {
+        dialog_x.clone().hide();
+    });
+
+    super::run_dialog(&dialog_win);
+    result.borrow_mut().take()
+}
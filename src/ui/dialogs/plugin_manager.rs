@@ -28,7 +28,7 @@ use crate::app::services::plugin_registry::{
     parse_github_url,
 };
 use crate::app::services::plugin_verify::{
-    LuaScanResult, detects_text_change_hook, scan_lua_source,
+    LuaScanResult, detect_high_frequency_hooks, scan_lua_source,
 };
 
 use super::community_install::{CommunityInstallReview, show_community_install_dialog};
@@ -757,7 +757,7 @@ pub fn show_plugin_manager_dialog(
                 .as_ref()
                 .map(|m| m.permissions.execute.clone())
                 .unwrap_or_default();
-            let has_text_change = detects_text_change_hook(&plugin_toml);
+            let high_frequency_hooks = detect_high_frequency_hooks(&plugin_toml);
             let scan_warnings = match scan_lua_source(&plugin_toml) {
                 LuaScanResult::Warnings(w) => w,
                 _ => vec![],
@@ -771,7 +771,7 @@ pub fn show_plugin_manager_dialog(
                 source_url: url.clone(),
                 permissions,
                 is_manual: true,
-                has_text_change_hook: has_text_change,
+                high_frequency_hooks,
                 scan_warnings,
             };
 
@@ -1551,8 +1551,8 @@ fn create_community_plugin_row(
             .map(|m| m.permissions.execute.clone())
             .unwrap_or_default();
 
-        // 3. Check for text change hook
-        let has_text_change = detects_text_change_hook(&plugin_toml);
+        // 3. Check for high-frequency hooks
+        let high_frequency_hooks = detect_high_frequency_hooks(&plugin_toml);
 
         // 4. Preliminary scan (on plugin.toml -- actual init.lua scan happens in install)
         let scan_warnings = match scan_lua_source(&plugin_toml) {
@@ -1568,7 +1568,7 @@ fn create_community_plugin_row(
             source_url: info.repo.clone(),
             permissions,
             is_manual: false,
-            has_text_change_hook: has_text_change,
+            high_frequency_hooks,
             scan_warnings,
         };
 
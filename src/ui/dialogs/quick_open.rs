@@ -0,0 +1,112 @@
+use fltk::window::Window;
+
+use crate::app::controllers::quick_open::{
+    FileIndex, QuickOpenEntry, QuickOpenQuery, QuickOpenSources, QuickOpenTarget, collect_entries,
+    parse_query, rank_entries, rank_symbols,
+};
+use crate::app::services::syntax::symbols::Symbol;
+
+use super::DialogTheme;
+use super::filter_list::FilterList;
+
+/// What the user picked in Quick Open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuickOpenChoice {
+    /// A tab or file, optionally at a 1-based line
+    Open(QuickOpenTarget, Option<u32>),
+    /// A line (or symbol) in the current file
+    Line(u32),
+}
+
+/// What a row of the list stands for
+enum Row {
+    Entry(usize),
+    Symbol(usize),
+    Line(u32),
+}
+
+const HINT: &str = "Type a file name, name:line, :line or @symbol";
+
+/// Show the Quick Open palette. `symbols` is only called once the query
+/// starts with `@`. The list follows `index` as it finishes listing the
+/// project in the background.
+pub fn show_quick_open(
+    parent: &Window,
+    sources: &QuickOpenSources,
+    index: &FileIndex,
+    mut symbols: impl FnMut() -> Vec<Symbol>,
+    theme_bg: (u8, u8, u8),
+) -> Option<QuickOpenChoice> {
+    let theme = DialogTheme::from_theme_bg(theme_bg);
+    let mut list = FilterList::new(parent, "Quick Open", 380, 120, true, &theme);
+
+    let mut entries: Vec<QuickOpenEntry> = Vec::new();
+    let mut symbol_list: Option<Vec<Symbol>> = None;
+    let mut rows: Vec<Row> = Vec::new();
+    let mut seen_generation = None;
+    let mut indexing = false;
+
+    while list.shown() {
+        let snapshot = index.snapshot();
+        if seen_generation != Some(snapshot.generation) || indexing != snapshot.indexing {
+            seen_generation = Some(snapshot.generation);
+            indexing = snapshot.indexing;
+            entries = collect_entries(sources, snapshot.root.as_deref(), &snapshot.files);
+            list.request_refresh();
+        }
+
+        if let Some(query) = list.take_query() {
+            rows.clear();
+            list.clear();
+            match parse_query(&query) {
+                QuickOpenQuery::Symbols(text) => {
+                    let found = symbol_list.get_or_insert_with(&mut symbols);
+                    for i in rank_symbols(found, text) {
+                        let s = &found[i];
+                        list.add(&s.name, &format!("{}  {}", s.kind, s.line));
+                        rows.push(Row::Symbol(i));
+                    }
+                    list.set_status(if found.is_empty() {
+                        "No symbols in this file"
+                    } else {
+                        HINT
+                    });
+                }
+                QuickOpenQuery::Files {
+                    text: "",
+                    line: Some(line),
+                } => {
+                    list.add(&format!("Go to line {}", line), "");
+                    rows.push(Row::Line(line));
+                    list.set_status(HINT);
+                }
+                QuickOpenQuery::Files { text, .. } => {
+                    for i in rank_entries(&entries, text) {
+                        list.add(&entries[i].label, entries[i].detail);
+                        rows.push(Row::Entry(i));
+                    }
+                    list.set_status(if indexing {
+                        "Indexing project files\u{2026}"
+                    } else {
+                        HINT
+                    });
+                }
+            }
+            list.finish();
+        }
+        list.wait();
+    }
+
+    let row = rows.get(list.chosen()?)?;
+    match *row {
+        Row::Line(line) => Some(QuickOpenChoice::Line(line)),
+        Row::Symbol(i) => Some(QuickOpenChoice::Line(symbol_list.as_ref()?[i].line)),
+        Row::Entry(i) => {
+            let line = match parse_query(&list.query()) {
+                QuickOpenQuery::Files { line, .. } => line,
+                QuickOpenQuery::Symbols(_) => None,
+            };
+            Some(QuickOpenChoice::Open(entries[i].target.clone(), line))
+        }
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! Uses memory-mapped file access to view files larger than 1.8GB
 //! without loading them entirely into memory. Supports navigation
-//! by page, line number, and search.
+//! by page and line number, plain/regex search with a result list, and
+//! a filter that shows only the matching lines. Searches are scanned a
+//! bounded step per event loop iteration, and Escape stops Find Next or
+//! Find Previous. A sparse line index is built in a background thread for
+//! fast go-to-line and the line count.
 
 use fltk::{
     app,
-    button::Button,
+    browser::HoldBrowser,
+    button::{Button, CheckButton},
     dialog,
     enums::{Align, Event, Font, FrameType, Key},
     frame::Frame,
@@ -21,28 +26,94 @@ use std::cell::RefCell;
 use std::fs::File;
 use std::path::Path;
 use std::rc::Rc;
-
-use super::{DialogTheme, SCROLLBAR_SIZE, darken, lighten};
-use crate::app::services::file_size::format_size;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+
+use super::{DialogTheme, SCROLLBAR_SIZE};
+use crate::app::services::file_size::{FileStamp, format_size};
+use crate::app::services::line_index::{
+    FindStep, LineIndex, LinePattern, count_newlines, find_next, find_previous, scan_line_offset,
+    scan_lines,
+};
 
 /// Size of each page in bytes (1MB chunks for smooth scrolling)
 const PAGE_SIZE: usize = 1024 * 1024;
 
+/// Bytes searched per event loop iteration by the searches and the filter
+const SCAN_STEP: usize = 4 * 1024 * 1024;
+
+/// Matching lines shown per filter page
+const FILTER_PAGE_LINES: usize = 5000;
+
+/// Find All stops after this many matching lines
+const MAX_FIND_RESULTS: usize = 10_000;
+
+/// Longest line text shown in the result list
+const RESULT_TEXT_CHARS: usize = 200;
+
+/// A filter showing only the lines that match a pattern, one page of
+/// matching lines at a time
+struct FilterView {
+    pattern: LinePattern,
+    /// (byte offset, line number) where each page visited so far starts
+    page_starts: Vec<(usize, usize)>,
+    /// Displayed page (index into `page_starts`)
+    page: usize,
+    /// Where the scan of the displayed page continues
+    next: (usize, usize),
+    /// Matching lines on the displayed page so far
+    shown: usize,
+    /// The displayed page is full or reached the end of the file
+    complete: bool,
+    /// The displayed page reached the end of the file
+    at_end: bool,
+    /// Digits of the line numbers in the line prefixes
+    digits: usize,
+}
+
+/// Find All in progress
+struct FindAllScan {
+    pattern: LinePattern,
+    next: (usize, usize),
+}
+
+/// Find, Find Next or Find Previous in progress
+struct FindScan {
+    pattern: LinePattern,
+    forward: bool,
+    /// Where the next step starts (forward) or ends (backward)
+    next: usize,
+    /// Shown when there is no match
+    not_found: &'static str,
+    /// The position label to put back when there is no match or the
+    /// search is stopped
+    label: String,
+}
+
 /// Shared state for the viewer
 struct ViewerState {
-    mmap: Mmap,
+    mmap: Arc<Mmap>,
     file_size: usize,
     total_pages: usize,
     current_page: usize,
     search_pos: usize,
+    /// Start of the current search match (Find Previous searches before it)
+    match_start: usize,
     /// First line number of the currently displayed content (1-based)
     current_start_line: usize,
     /// Width of the line-number prefix (digits + separator) for current page
     current_prefix_width: usize,
+    /// Sparse line index, once the background build has finished
+    index: Option<LineIndex>,
+    filter: Option<FilterView>,
+    find: Option<FindScan>,
+    find_all: Option<FindAllScan>,
+    /// Line start offsets of the Find All results, in list order
+    result_offsets: Vec<usize>,
 }
 
 impl ViewerState {
-    fn new(mmap: Mmap, file_size: usize) -> Self {
+    fn new(mmap: Arc<Mmap>, file_size: usize) -> Self {
         let total_pages = file_size.div_ceil(PAGE_SIZE);
         Self {
             mmap,
@@ -50,8 +121,23 @@ impl ViewerState {
             total_pages,
             current_page: 0,
             search_pos: 0,
+            match_start: 0,
             current_start_line: 1,
             current_prefix_width: 0,
+            index: None,
+            filter: None,
+            find: None,
+            find_all: None,
+            result_offsets: Vec::new(),
+        }
+    }
+
+    /// 1-based line containing byte `offset`; counts from the start of the
+    /// file until the line index is ready
+    fn line_at(&self, offset: usize) -> usize {
+        match &self.index {
+            Some(index) => index.line_at(&self.mmap, offset),
+            None => count_newlines(&self.mmap[..offset]) + 1,
         }
     }
 
@@ -62,6 +148,7 @@ impl ViewerState {
         if start >= self.file_size {
             return;
         }
+        self.filter = None;
 
         // Get the slice from memory map
         let slice = &self.mmap[start..end];
@@ -70,9 +157,8 @@ impl ViewerState {
         let text = String::from_utf8_lossy(slice);
 
         // Count line numbers at start and end of current view
-        let lines_before: usize = self.mmap[..start].iter().filter(|&&b| b == b'\n').count();
-        let lines_in_page: usize = slice.iter().filter(|&&b| b == b'\n').count();
-        let start_line = lines_before + 1;
+        let start_line = self.line_at(start);
+        let lines_in_page: usize = count_newlines(slice);
         let end_line = start_line + lines_in_page;
 
         // Prepend real line numbers to each line
@@ -98,14 +184,27 @@ impl ViewerState {
     }
 
     fn prev_page(&mut self, buffer: &mut TextBuffer, pos_label: &mut Frame) {
-        if self.current_page > 0 {
+        if let Some(filter) = &mut self.filter {
+            if filter.page > 0 {
+                filter.page -= 1;
+                self.start_filter_page(buffer, pos_label);
+            }
+        } else if self.current_page > 0 {
             self.current_page -= 1;
             self.load_current_page(buffer, pos_label);
         }
     }
 
     fn next_page(&mut self, buffer: &mut TextBuffer, pos_label: &mut Frame) {
-        if self.current_page < self.total_pages - 1 {
+        if let Some(filter) = &mut self.filter {
+            if filter.complete && !filter.at_end {
+                if filter.page + 1 == filter.page_starts.len() {
+                    filter.page_starts.push(filter.next);
+                }
+                filter.page += 1;
+                self.start_filter_page(buffer, pos_label);
+            }
+        } else if self.current_page < self.total_pages - 1 {
             self.current_page += 1;
             self.load_current_page(buffer, pos_label);
         }
@@ -113,19 +212,10 @@ impl ViewerState {
 
     /// Find byte offset for a given line number
     fn find_line_offset(&self, target_line: usize) -> usize {
-        if target_line <= 1 {
-            return 0;
+        match &self.index {
+            Some(index) => index.line_offset(&self.mmap, target_line),
+            None => scan_line_offset(&self.mmap, 0, 1, target_line),
         }
-        let mut line_count = 1usize;
-        for (i, &byte) in self.mmap.iter().enumerate() {
-            if line_count >= target_line {
-                return i;
-            }
-            if byte == b'\n' {
-                line_count += 1;
-            }
-        }
-        self.file_size
     }
 
     fn go_to_line(&mut self, target_line: usize, buffer: &mut TextBuffer, pos_label: &mut Frame) {
@@ -133,7 +223,7 @@ impl ViewerState {
             return;
         }
         let byte_offset = self.find_line_offset(target_line);
-        let target_page = byte_offset / PAGE_SIZE;
+        let target_page = byte_offset.min(self.file_size - 1) / PAGE_SIZE;
         self.go_to_page(target_page, buffer, pos_label);
     }
 
@@ -150,11 +240,16 @@ impl ViewerState {
         }
 
         let start_offset = self.find_line_offset(start_line);
-        let end_offset = self.find_line_offset(end_line + 1); // Include end line
+        let end_offset = match &self.index {
+            Some(index) => index.line_offset(&self.mmap, end_line + 1),
+            // Continue from the start line instead of rescanning the file
+            None => scan_line_offset(&self.mmap, start_offset, start_line, end_line + 1),
+        }; // Include end line
 
         if start_offset >= self.file_size {
             return;
         }
+        self.filter = None;
 
         let end_offset = end_offset.min(self.file_size);
         let slice = &self.mmap[start_offset..end_offset];
@@ -172,112 +267,249 @@ impl ViewerState {
         pos_label.set_label(&format!("Lines {}-{}", start_line, end_line,));
     }
 
-    fn search(
+    /// Start looking for a match from the start of the file, after the
+    /// current match or before it; `step_scans` carries on the search
+    fn start_find(
         &mut self,
-        query: &str,
+        pattern: LinePattern,
         from_start: bool,
-        buffer: &mut TextBuffer,
-        pos_label: &mut Frame,
-        display: &mut TextDisplay,
-    ) -> bool {
-        if query.is_empty() {
-            return false;
-        }
-
-        let query_bytes = query.as_bytes();
-        let search_start = if from_start { 0 } else { self.search_pos };
+        forward: bool,
+        pos_label: &Frame,
+    ) {
+        let (next, not_found) = if from_start {
+            (0, "Text not found.")
+        } else if forward {
+            (self.search_pos, "No more matches found.")
+        } else if self.search_pos == 0 {
+            (0, "No previous matches found.")
+        } else {
+            (self.match_start, "No previous matches found.")
+        };
+        self.find = Some(FindScan {
+            pattern,
+            forward,
+            next,
+            not_found,
+            label: self
+                .find
+                .take()
+                .map_or_else(|| pos_label.label(), |find| find.label),
+        });
+    }
 
-        if search_start >= self.file_size {
+    /// Stop Find Next or Find Previous. Returns false if none was running.
+    fn stop_find(&mut self, pos_label: &mut Frame) -> bool {
+        let Some(find) = self.find.take() else {
             return false;
-        }
-
-        // Simple byte search
-        let slice = &self.mmap[search_start..];
-        if let Some(rel_pos) = slice
-            .windows(query_bytes.len())
-            .position(|w| w == query_bytes)
-        {
-            let found_pos = search_start + rel_pos;
-            self.search_pos = found_pos + query_bytes.len();
-
-            // Navigate to the page containing this match
-            let target_page = found_pos / PAGE_SIZE;
-            self.go_to_page(target_page, buffer, pos_label);
-
-            // Position cursor at the match in the prefixed buffer.
-            // We need to account for the line-number prefixes added to each line.
-            let page_start = self.current_page * PAGE_SIZE;
-            let local_byte_pos = found_pos - page_start;
-
-            // Count how many newlines precede local_byte_pos in the raw page text
-            let page_end = (page_start + PAGE_SIZE).min(self.file_size);
-            let page_slice = &self.mmap[page_start..page_end];
-            let newlines_before = page_slice[..local_byte_pos.min(page_slice.len())]
-                .iter()
-                .filter(|&&b| b == b'\n')
-                .count();
-
-            // Each line gets a prefix of current_prefix_width bytes.
-            // First line has 1 prefix, each newline adds another prefix for the next line.
-            let prefix_bytes_added = (newlines_before + 1) * self.current_prefix_width;
-            let adjusted_pos = (local_byte_pos + prefix_bytes_added) as i32;
-
-            display.set_insert_position(adjusted_pos);
-            display.show_insert_position();
-
-            return true;
-        }
-        false
+        };
+        pos_label.set_label(&find.label);
+        true
     }
 
-    fn search_backward(
+    /// Advance Find Next or Find Previous by one bounded step. Returns the
+    /// message to show when the search ended without a match.
+    fn step_find(
         &mut self,
-        query: &str,
         buffer: &mut TextBuffer,
         pos_label: &mut Frame,
         display: &mut TextDisplay,
-    ) -> bool {
-        if query.is_empty() || self.search_pos == 0 {
-            return false;
+    ) -> Option<&'static str> {
+        let find = self.find.as_mut()?;
+        let step = if find.forward {
+            find_next(&self.mmap, &find.pattern, find.next, SCAN_STEP)
+        } else {
+            find_previous(&self.mmap, &find.pattern, find.next, SCAN_STEP)
+        };
+        match step {
+            FindStep::Found(found_pos, found_end) => {
+                self.find = None;
+                self.match_start = found_pos;
+                // An empty match must not be found again
+                self.search_pos = found_end.max(found_pos + 1);
+                self.show_match(found_pos, found_end, buffer, pos_label, display);
+                None
+            }
+            FindStep::Continue(next) => {
+                find.next = next;
+                let done = if find.forward {
+                    next
+                } else {
+                    self.file_size - next
+                };
+                pos_label.set_label(&format!(
+                    "Searching {}%... (Esc to stop)",
+                    done * 100 / self.file_size.max(1)
+                ));
+                None
+            }
+            FindStep::NotFound => {
+                // Find Next starts over from the top after this
+                if find.forward {
+                    self.search_pos = 0;
+                }
+                pos_label.set_label(&find.label);
+                let not_found = find.not_found;
+                self.find = None;
+                Some(not_found)
+            }
         }
+    }
 
-        let query_bytes = query.as_bytes();
-        // Search backward from just before the current match
-        let search_end = self.search_pos.saturating_sub(query_bytes.len());
-        if search_end == 0 {
-            return false;
-        }
+    /// Show the page containing the match at `found_pos..found_end` and
+    /// select the match
+    fn show_match(
+        &mut self,
+        found_pos: usize,
+        found_end: usize,
+        buffer: &mut TextBuffer,
+        pos_label: &mut Frame,
+        display: &mut TextDisplay,
+    ) {
+        // Navigate to the page containing this match
+        let target_page = found_pos / PAGE_SIZE;
+        self.go_to_page(target_page, buffer, pos_label);
 
-        let slice = &self.mmap[..search_end];
-        // Find the LAST occurrence by scanning backward
-        if let Some(rel_pos) = slice
-            .windows(query_bytes.len())
-            .rposition(|w| w == query_bytes)
-        {
-            self.search_pos = rel_pos;
+        // Position cursor at the match in the prefixed buffer.
+        // We need to account for the line-number prefixes added to each line.
+        let page_start = self.current_page * PAGE_SIZE;
+        let local_byte_pos = found_pos - page_start;
+
+        // Count how many newlines precede local_byte_pos in the raw page text
+        let page_end = (page_start + PAGE_SIZE).min(self.file_size);
+        let page_slice = &self.mmap[page_start..page_end];
+        let newlines_before = count_newlines(&page_slice[..local_byte_pos.min(page_slice.len())]);
+
+        // Each line gets a prefix of current_prefix_width bytes.
+        // First line has 1 prefix, each newline adds another prefix for the next line.
+        let prefix_bytes_added = (newlines_before + 1) * self.current_prefix_width;
+        let adjusted_pos = (local_byte_pos + prefix_bytes_added) as i32;
+        // A match running past the page end is selected up to the end
+        let match_len = (found_end.min(page_end) - found_pos) as i32;
+
+        buffer.select(adjusted_pos, adjusted_pos + match_len);
+        display.set_insert_position(adjusted_pos);
+        display.show_insert_position();
+    }
 
-            let target_page = rel_pos / PAGE_SIZE;
-            self.go_to_page(target_page, buffer, pos_label);
+    /// Show only the lines matching `pattern`, starting with the first page
+    fn start_filter(
+        &mut self,
+        pattern: LinePattern,
+        buffer: &mut TextBuffer,
+        pos_label: &mut Frame,
+    ) {
+        // Line numbers can't exceed the byte count; the index knows better
+        let max_line = self
+            .index
+            .as_ref()
+            .map_or(self.file_size + 1, |index| index.total_lines());
+        self.filter = Some(FilterView {
+            pattern,
+            page_starts: vec![(0, 1)],
+            page: 0,
+            next: (0, 1),
+            shown: 0,
+            complete: false,
+            at_end: false,
+            digits: max_line.max(1).to_string().len(),
+        });
+        self.start_filter_page(buffer, pos_label);
+    }
 
-            let page_start = self.current_page * PAGE_SIZE;
-            let local_byte_pos = rel_pos - page_start;
+    /// Clear the display for the current filter page; `step_scans` fills it
+    fn start_filter_page(&mut self, buffer: &mut TextBuffer, pos_label: &mut Frame) {
+        let Some(filter) = &mut self.filter else {
+            return;
+        };
+        filter.next = filter.page_starts[filter.page];
+        filter.shown = 0;
+        filter.complete = false;
+        filter.at_end = false;
+        self.current_prefix_width = filter.digits + 4;
+        buffer.set_text("");
+        Self::update_filter_label(filter, pos_label);
+    }
 
-            let page_end = (page_start + PAGE_SIZE).min(self.file_size);
-            let page_slice = &self.mmap[page_start..page_end];
-            let newlines_before = page_slice[..local_byte_pos.min(page_slice.len())]
-                .iter()
-                .filter(|&&b| b == b'\n')
-                .count();
+    fn update_filter_label(filter: &FilterView, pos_label: &mut Frame) {
+        let state = if filter.complete {
+            ""
+        } else {
+            " (filtering...)"
+        };
+        pos_label.set_label(&format!(
+            "Filter page {} | {} matching lines{}",
+            filter.page + 1,
+            filter.shown,
+            state
+        ));
+    }
 
-            let prefix_bytes_added = (newlines_before + 1) * self.current_prefix_width;
-            let adjusted_pos = (local_byte_pos + prefix_bytes_added) as i32;
+    /// Whether a search or a filter page is still being scanned
+    fn scanning(&self) -> bool {
+        self.find.is_some()
+            || self.find_all.is_some()
+            || self.filter.as_ref().is_some_and(|f| !f.complete)
+    }
 
-            display.set_insert_position(adjusted_pos);
-            display.show_insert_position();
+    /// Advance the filter page and Find All by one bounded step each, so
+    /// the dialog stays responsive while they work through the file
+    fn step_scans(
+        &mut self,
+        buffer: &mut TextBuffer,
+        pos_label: &mut Frame,
+        results: &mut HoldBrowser,
+        results_label: &mut Frame,
+    ) {
+        if let Some(filter) = self.filter.as_mut().filter(|f| !f.complete) {
+            let scan = scan_lines(
+                &self.mmap,
+                &filter.pattern,
+                filter.next.0,
+                filter.next.1,
+                SCAN_STEP,
+                FILTER_PAGE_LINES - filter.shown,
+            );
+            let mut text = String::new();
+            for m in &scan.matches {
+                if filter.shown > 0 || !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&format!("{:>width$}│ ", m.line, width = filter.digits));
+                text.push_str(&m.text);
+            }
+            buffer.append(&text);
+            filter.shown += scan.matches.len();
+            filter.next = (scan.next_offset, scan.next_line);
+            filter.at_end = scan.done;
+            filter.complete = scan.done || filter.shown == FILTER_PAGE_LINES;
+            Self::update_filter_label(filter, pos_label);
+        }
 
-            return true;
+        if let Some(find_all) = &mut self.find_all {
+            let scan = scan_lines(
+                &self.mmap,
+                &find_all.pattern,
+                find_all.next.0,
+                find_all.next.1,
+                SCAN_STEP,
+                MAX_FIND_RESULTS - self.result_offsets.len(),
+            );
+            for m in &scan.matches {
+                let text: String = m.text.trim().chars().take(RESULT_TEXT_CHARS).collect();
+                results.add(&format!("{}\t{}", m.line, text.replace('@', "@@")));
+                self.result_offsets.push(m.offset);
+            }
+            find_all.next = (scan.next_offset, scan.next_line);
+            let count = self.result_offsets.len();
+            if scan.done || count == MAX_FIND_RESULTS {
+                self.find_all = None;
+                let more = if scan.done { "" } else { " (stopped)" };
+                results_label.set_label(&format!("{} matching lines{}", count, more));
+            } else {
+                let percent = scan.next_offset * 100 / self.file_size.max(1);
+                results_label.set_label(&format!("{} matching lines ({}%)", count, percent));
+            }
         }
-        false
     }
 
     /// Calculate the byte width of a line-number prefix for a given max line number.
@@ -325,6 +557,8 @@ pub struct ViewerOpenRequest {
     pub end_line: usize,
     pub start_byte: u64,
     pub end_byte: u64,
+    /// The file as it was when the viewer mapped it
+    pub stamp: FileStamp,
 }
 
 /// Open a read-only viewer for a large file.
@@ -332,9 +566,10 @@ pub struct ViewerOpenRequest {
 /// This creates a modal window with:
 /// - Memory-mapped file access (no full load into RAM)
 /// - Page-based navigation
-/// - Go to line number
-/// - Search functionality
-/// - Display of current position/total size
+/// - Go to line number (indexed in the background)
+/// - Plain or regex search, with a Find All result list
+/// - A filter showing only matching lines with their original numbers
+/// - Display of current position/total size and line count
 ///
 /// Returns a `ViewerOpenRequest` if the user clicked "Open" to edit the
 /// currently displayed lines, or `None` if the viewer was simply closed.
@@ -360,6 +595,7 @@ pub fn show_readonly_viewer(
         }
     };
 
+    let stamp = FileStamp::from_metadata(&metadata);
     let file_size = metadata.len() as usize;
     if file_size == 0 {
         dialog::alert_default("File is empty.");
@@ -380,7 +616,24 @@ pub fn show_readonly_viewer(
     let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
 
     // Create shared state
-    let state = Rc::new(RefCell::new(ViewerState::new(mmap, file_size)));
+    let mmap = Arc::new(mmap);
+    let state = Rc::new(RefCell::new(ViewerState::new(Arc::clone(&mmap), file_size)));
+
+    // Build the line index in the background; the viewer counts lines the
+    // slow way until it arrives
+    let index_progress = Arc::new(AtomicUsize::new(0));
+    let index_cancelled = Arc::new(AtomicBool::new(false));
+    let (index_tx, index_rx) = mpsc::channel::<LineIndex>();
+    {
+        let progress = Arc::clone(&index_progress);
+        let cancelled = Arc::clone(&index_cancelled);
+        std::thread::spawn(move || {
+            if let Some(index) = LineIndex::build(&mmap, &progress, &cancelled) {
+                let _ = index_tx.send(index);
+                app::awake();
+            }
+        });
+    }
 
     // Create viewer window
     let theme = DialogTheme::from_theme_bg(theme_bg);
@@ -465,6 +718,57 @@ pub fn show_readonly_viewer(
     toolbar.end();
     main_flex.fixed(&toolbar, 30);
 
+    // Second row: search options, Find All and the line filter
+    let mut options_row = Flex::default();
+    options_row.set_type(fltk::group::FlexType::Row);
+    options_row.set_spacing(5);
+
+    let mut case_check = CheckButton::default().with_label("Match case");
+    case_check.set_checked(true);
+    case_check.set_label_color(theme.text);
+    case_check.set_color(theme.bg);
+    case_check.set_selection_color(theme.button_bg);
+    options_row.fixed(&case_check, 100);
+
+    let mut regex_check = CheckButton::default().with_label("Regex");
+    regex_check.set_label_color(theme.text);
+    regex_check.set_color(theme.bg);
+    regex_check.set_selection_color(theme.button_bg);
+    options_row.fixed(&regex_check, 70);
+
+    let mut find_all_btn = Button::default().with_label("Find All");
+    find_all_btn.set_tooltip("List every line matching the search");
+    find_all_btn.set_frame(FrameType::RFlatBox);
+    find_all_btn.set_color(theme.button_bg);
+    find_all_btn.set_label_color(theme.text);
+    options_row.fixed(&find_all_btn, 80);
+
+    let mut spacer_options = Frame::default();
+    spacer_options.set_frame(FrameType::FlatBox);
+    spacer_options.set_color(theme.bg);
+
+    let mut filter_input = Input::default();
+    filter_input.set_tooltip("Show only lines matching this text (uses the search options)");
+    filter_input.set_color(theme.input_bg);
+    filter_input.set_text_color(theme.text);
+    filter_input.set_frame(FrameType::FlatBox);
+    options_row.fixed(&filter_input, 200);
+
+    let mut filter_btn = Button::default().with_label("Filter");
+    filter_btn.set_frame(FrameType::RFlatBox);
+    filter_btn.set_color(theme.button_bg);
+    filter_btn.set_label_color(theme.text);
+    options_row.fixed(&filter_btn, 60);
+
+    let mut clear_filter_btn = Button::default().with_label("Clear Filter");
+    clear_filter_btn.set_frame(FrameType::RFlatBox);
+    clear_filter_btn.set_color(theme.button_bg);
+    clear_filter_btn.set_label_color(theme.text);
+    options_row.fixed(&clear_filter_btn, 90);
+
+    options_row.end();
+    main_flex.fixed(&options_row, 25);
+
     // Text display area with line numbers
     let mut display = TextDisplay::default();
     let buffer = TextBuffer::default();
@@ -509,6 +813,17 @@ pub fn show_readonly_viewer(
     }
     display.wrap_mode(WrapMode::AtBounds, 0);
 
+    // Find All results (hidden until the first Find All)
+    let mut results_browser = HoldBrowser::default();
+    results_browser.set_color(theme.input_bg);
+    results_browser.set_selection_color(theme.button_bg);
+    results_browser.set_frame(FrameType::FlatBox);
+    results_browser.set_text_size(code_font_size.clamp(6, 96));
+    results_browser.set_column_widths(&[90]);
+    results_browser.set_column_char('\t');
+    main_flex.fixed(&results_browser, 160);
+    results_browser.hide();
+
     // Status bar
     let mut status_flex = Flex::default();
     status_flex.set_type(fltk::group::FlexType::Row);
@@ -518,15 +833,25 @@ pub fn show_readonly_viewer(
     position_label.set_label_color(theme.text_dim);
     position_label.set_frame(FrameType::FlatBox);
     position_label.set_color(theme.bg);
-    status_flex.fixed(&position_label, 500);
+    status_flex.fixed(&position_label, 350);
+
+    let mut results_label = Frame::default();
+    results_label.set_align(Align::Left | Align::Inside);
+    results_label.set_label_color(theme.text_dim);
+    results_label.set_frame(FrameType::FlatBox);
+    results_label.set_color(theme.bg);
 
     let mut size_label = Frame::default();
     size_label.set_align(Align::Right | Align::Inside);
-    size_label.set_label(&format!("Total: {}", format_size(file_size as u64)));
+    size_label.set_label(&format!(
+        "Total: {} | Indexing lines...",
+        format_size(file_size as u64)
+    ));
     size_label.set_label_color(theme.text_dim);
     size_label.set_frame(FrameType::FlatBox);
     size_label.set_color(theme.bg);
 
+    status_flex.fixed(&size_label, 250);
     status_flex.end();
     main_flex.fixed(&status_flex, 25);
 
@@ -565,23 +890,8 @@ pub fn show_readonly_viewer(
     spacer_right.set_color(theme.bg);
 
     let mut close_btn = Button::default().with_label("Close");
-    let (r, g, b) = theme_bg;
-    let brightness = (r as u32 + g as u32 + b as u32) / 3;
-    let is_dark = brightness < 128;
-    let (bg_r, bg_g, bg_b) = if is_dark {
-        darken(r, g, b, 0.65)
-    } else {
-        darken(r, g, b, 0.85)
-    };
-    let close_bg = if is_dark {
-        let (cr, cg, cb) = lighten(bg_r, bg_g, bg_b, 0.10);
-        fltk::enums::Color::from_rgb(cr, cg, cb)
-    } else {
-        let (cr, cg, cb) = darken(bg_r, bg_g, bg_b, 0.90);
-        fltk::enums::Color::from_rgb(cr, cg, cb)
-    };
     close_btn.set_frame(FrameType::RFlatBox);
-    close_btn.set_color(close_bg);
+    close_btn.set_color(theme.secondary_button_bg());
     close_btn.set_label_color(theme.text_dim);
     btn_row.fixed(&close_btn, 100);
 
@@ -675,63 +985,126 @@ pub fn show_readonly_viewer(
         }
     });
 
-    // Search button (from start)
+    // Search button (from start) — the event loop scans for the match
     let state_search = Rc::clone(&state);
-    let mut buf_search = buffer.clone();
-    let mut pos_label_search = position_label.clone();
-    let mut display_search = display.clone();
-    let search_input_val = search_input.clone();
+    let pos_label_search = position_label.clone();
+    let search_input_search = search_input.clone();
+    let case_search = case_check.clone();
+    let regex_search = regex_check.clone();
     search_btn.set_callback(move |_| {
-        let query = search_input_val.value();
-        let found = state_search.borrow_mut().search(
-            &query,
-            true,
-            &mut buf_search,
-            &mut pos_label_search,
-            &mut display_search,
-        );
-        if !found {
-            dialog::message_default("Text not found.");
-        }
+        let Some(pattern) =
+            build_pattern(&search_input_search.value(), &case_search, &regex_search)
+        else {
+            return;
+        };
+        state_search
+            .borrow_mut()
+            .start_find(pattern, true, true, &pos_label_search);
     });
 
-    // Search next button
-    let state_search_next = Rc::clone(&state);
-    let mut buf_search_next = buffer.clone();
-    let mut pos_label_search_next = position_label.clone();
-    let mut display_search_next = display.clone();
+    // Search next button — the event loop scans for the match
+    let state_next = Rc::clone(&state);
+    let pos_label_next = position_label.clone();
     let search_input_next = search_input.clone();
+    let case_next = case_check.clone();
+    let regex_next = regex_check.clone();
     search_next_btn.set_callback(move |_| {
-        let query = search_input_next.value();
-        let found = state_search_next.borrow_mut().search(
-            &query,
-            false,
-            &mut buf_search_next,
-            &mut pos_label_search_next,
-            &mut display_search_next,
-        );
-        if !found {
-            dialog::message_default("No more matches found.");
-            state_search_next.borrow_mut().search_pos = 0;
-        }
+        let Some(pattern) = build_pattern(&search_input_next.value(), &case_next, &regex_next)
+        else {
+            return;
+        };
+        state_next
+            .borrow_mut()
+            .start_find(pattern, false, true, &pos_label_next);
     });
 
-    // Search previous button
-    let state_search_prev = Rc::clone(&state);
-    let mut buf_search_prev = buffer.clone();
-    let mut pos_label_search_prev = position_label.clone();
-    let mut display_search_prev = display.clone();
+    // Search previous button — the event loop scans for the match
+    let state_prev = Rc::clone(&state);
+    let pos_label_prev = position_label.clone();
     let search_input_prev = search_input.clone();
+    let case_prev = case_check.clone();
+    let regex_prev = regex_check.clone();
     search_prev_btn.set_callback(move |_| {
-        let query = search_input_prev.value();
-        let found = state_search_prev.borrow_mut().search_backward(
-            &query,
-            &mut buf_search_prev,
-            &mut pos_label_search_prev,
-            &mut display_search_prev,
+        let Some(pattern) = build_pattern(&search_input_prev.value(), &case_prev, &regex_prev)
+        else {
+            return;
+        };
+        state_prev
+            .borrow_mut()
+            .start_find(pattern, false, false, &pos_label_prev);
+    });
+
+    // Find All button — list matching lines, collected by the event loop
+    let state_find_all = Rc::clone(&state);
+    let search_input_all = search_input.clone();
+    let case_all = case_check.clone();
+    let regex_all = regex_check.clone();
+    let mut results_all = results_browser.clone();
+    let mut results_label_all = results_label.clone();
+    let flex_all = main_flex.clone();
+    find_all_btn.set_callback(move |_| {
+        let Some(pattern) = build_pattern(&search_input_all.value(), &case_all, &regex_all) else {
+            return;
+        };
+        let mut s = state_find_all.borrow_mut();
+        s.find_all = Some(FindAllScan {
+            pattern,
+            next: (0, 1),
+        });
+        s.result_offsets.clear();
+        results_all.clear();
+        results_all.show();
+        results_label_all.set_label("Searching...");
+        flex_all.layout();
+    });
+
+    // Selecting a result shows its line
+    let state_result = Rc::clone(&state);
+    let mut buf_result = buffer.clone();
+    let mut pos_label_result = position_label.clone();
+    let mut display_result = display.clone();
+    results_browser.set_callback(move |b| {
+        let mut s = state_result.borrow_mut();
+        let Some(&offset) = (b.value() as usize)
+            .checked_sub(1)
+            .and_then(|i| s.result_offsets.get(i))
+        else {
+            return;
+        };
+        s.show_match(
+            offset,
+            offset,
+            &mut buf_result,
+            &mut pos_label_result,
+            &mut display_result,
         );
-        if !found {
-            dialog::message_default("No previous matches found.");
+    });
+
+    // Filter button — show only matching lines, streamed by the event loop
+    let state_filter = Rc::clone(&state);
+    let mut buf_filter = buffer.clone();
+    let mut pos_label_filter = position_label.clone();
+    let filter_input_val = filter_input.clone();
+    let case_filter = case_check.clone();
+    let regex_filter = regex_check.clone();
+    filter_btn.set_callback(move |_| {
+        let Some(pattern) = build_pattern(&filter_input_val.value(), &case_filter, &regex_filter)
+        else {
+            return;
+        };
+        state_filter
+            .borrow_mut()
+            .start_filter(pattern, &mut buf_filter, &mut pos_label_filter);
+    });
+
+    // Clear Filter button — back to the page the filter started from
+    let state_clear = Rc::clone(&state);
+    let mut buf_clear = buffer.clone();
+    let mut pos_label_clear = position_label.clone();
+    clear_filter_btn.set_callback(move |_| {
+        let mut s = state_clear.borrow_mut();
+        if s.filter.is_some() {
+            s.load_current_page(&mut buf_clear, &mut pos_label_clear);
         }
     });
 
@@ -741,11 +1114,15 @@ pub fn show_readonly_viewer(
     let mut win_open = window.clone();
     open_btn.set_callback(move |_| {
         let s = state_open.borrow();
+        if s.filter.is_some() {
+            dialog::message_default("Clear the filter to open lines for editing.");
+            return;
+        }
         let start_line = s.current_start_line;
         let page_start = s.current_page * PAGE_SIZE;
         let page_end = (page_start + PAGE_SIZE).min(s.file_size);
         let slice = &s.mmap[page_start..page_end];
-        let lines_in_page = slice.iter().filter(|&&b| b == b'\n').count();
+        let lines_in_page = count_newlines(slice);
         let end_line = start_line + lines_in_page;
 
         // Extract raw content directly from the mmap (no re-read needed)
@@ -757,6 +1134,7 @@ pub fn show_readonly_viewer(
             end_line,
             start_byte: page_start as u64,
             end_byte: page_end as u64,
+            stamp,
         });
         win_open.hide();
     });
@@ -794,23 +1172,95 @@ pub fn show_readonly_viewer(
                         .go_to_page(total - 1, &mut buf_key, &mut pos_label_key);
                     true
                 }
-                Key::Escape => {
-                    // Don't close on Escape
-                    false
-                }
+                // Stop a running Find Next or Find Previous; don't close on
+                // Escape
+                Key::Escape => state_key.borrow_mut().stop_find(&mut pos_label_key),
                 _ => false,
             }
         }
         _ => false,
     });
 
-    // Run the dialog event loop
+    // Run the dialog event loop. While a search or a filter page is being
+    // scanned, each iteration does one bounded step of it; otherwise it
+    // wakes up periodically to report the index build progress.
+    let mut buf_loop = buffer.clone();
+    let mut pos_label_loop = position_label.clone();
+    let mut display_loop = display.clone();
+    let mut results_loop = results_browser.clone();
+    let mut results_label_loop = results_label.clone();
+    let mut index_rx = Some(index_rx);
     while window.shown() {
-        app::wait();
+        let scanning = state.borrow().scanning();
+        if scanning {
+            app::wait_for(0.0).ok();
+            state.borrow_mut().step_scans(
+                &mut buf_loop,
+                &mut pos_label_loop,
+                &mut results_loop,
+                &mut results_label_loop,
+            );
+            let not_found =
+                state
+                    .borrow_mut()
+                    .step_find(&mut buf_loop, &mut pos_label_loop, &mut display_loop);
+            // Shown with the state released, the dialog runs its own loop
+            if let Some(message) = not_found {
+                dialog::message_default(message);
+            }
+        } else {
+            app::wait_for(0.1).ok();
+        }
+
+        if let Some(rx) = &index_rx {
+            match rx.try_recv() {
+                Ok(index) => {
+                    size_label.set_label(&format!(
+                        "Total: {} | {} lines",
+                        format_size(file_size as u64),
+                        index.total_lines()
+                    ));
+                    state.borrow_mut().index = Some(index);
+                    index_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    let done = index_progress.load(Ordering::Relaxed);
+                    size_label.set_label(&format!(
+                        "Total: {} | Indexing lines {}%",
+                        format_size(file_size as u64),
+                        done * 100 / file_size
+                    ));
+                }
+                Err(mpsc::TryRecvError::Disconnected) => index_rx = None,
+            }
+        }
+
         if app::should_program_quit() {
             window.hide();
         }
     }
 
+    // Stop the index build if the viewer closed before it finished
+    index_cancelled.store(true, Ordering::Relaxed);
+
     open_request.borrow_mut().take()
 }
+
+/// Build the search pattern from a query and the search options. Reports
+/// an invalid regex and returns None for it and for an empty query.
+fn build_pattern(
+    query: &str,
+    case_check: &CheckButton,
+    regex_check: &CheckButton,
+) -> Option<LinePattern> {
+    if query.is_empty() {
+        return None;
+    }
+    match LinePattern::new(query, regex_check.is_checked(), case_check.is_checked()) {
+        Ok(pattern) => Some(pattern),
+        Err(e) => {
+            dialog::alert_default(&format!("Invalid regular expression: {}", e));
+            None
+        }
+    }
+}
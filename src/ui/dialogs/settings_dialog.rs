@@ -28,10 +28,12 @@ const SECTION_GAP: i32 = 15;
 
 /// Show settings dialog and return updated settings if user clicked Save.
 /// The sender is used to send live preview messages for theme changes.
+/// `custom_themes` are the user/plugin theme names listed after the built-in themes.
 pub fn show_settings_dialog(
     current_settings: &AppSettings,
     sender: &Sender<Message>,
     theme_bg: (u8, u8, u8),
+    custom_themes: &[String],
 ) -> Option<AppSettings> {
     let theme = DialogTheme::from_theme_bg(theme_bg);
     let is_dark = theme.is_dark();
@@ -111,10 +113,12 @@ pub fn show_settings_dialog(
     theme_light_choice.set_text_color(theme.text);
     theme_light_choice.set_label_color(theme.text);
     theme_light_choice.set_selection_color(theme.button_bg);
-    for syntax_theme in SyntaxTheme::all() {
-        theme_light_choice.add_choice(syntax_theme.display_name());
-    }
-    theme_light_choice.set_value(theme_index(current_settings.syntax_theme_light));
+    add_theme_choices(&mut theme_light_choice, custom_themes);
+    theme_light_choice.set_value(theme_index(
+        current_settings.syntax_theme_light,
+        current_settings.custom_syntax_theme_light.as_deref(),
+        custom_themes,
+    ));
     y += 30;
 
     // Syntax Theme (Dark)
@@ -133,26 +137,36 @@ pub fn show_settings_dialog(
     theme_dark_choice.set_text_color(theme.text);
     theme_dark_choice.set_label_color(theme.text);
     theme_dark_choice.set_selection_color(theme.button_bg);
-    for syntax_theme in SyntaxTheme::all() {
-        theme_dark_choice.add_choice(syntax_theme.display_name());
-    }
-    theme_dark_choice.set_value(theme_index(current_settings.syntax_theme_dark));
+    add_theme_choices(&mut theme_dark_choice, custom_themes);
+    theme_dark_choice.set_value(theme_index(
+        current_settings.syntax_theme_dark,
+        current_settings.custom_syntax_theme_dark.as_deref(),
+        custom_themes,
+    ));
     y += 30 + SECTION_GAP;
 
     // Live preview callbacks for theme changes
     let sender_light = *sender;
     let is_dark_for_light = is_dark;
+    let fallback_light = current_settings.syntax_theme_light;
+    let customs_light = custom_themes.to_vec();
     theme_light_choice.set_callback(move |c| {
-        if !is_dark_for_light && let Some(theme) = index_to_theme(c.value()) {
-            sender_light.send(Message::PreviewSyntaxTheme(theme));
+        if !is_dark_for_light
+            && let Some((theme, custom)) = index_to_theme(c.value(), fallback_light, &customs_light)
+        {
+            sender_light.send(Message::PreviewSyntaxTheme(theme, custom));
         }
     });
 
     let sender_dark = *sender;
     let is_dark_for_dark = is_dark;
+    let fallback_dark = current_settings.syntax_theme_dark;
+    let customs_dark = custom_themes.to_vec();
     theme_dark_choice.set_callback(move |c| {
-        if is_dark_for_dark && let Some(theme) = index_to_theme(c.value()) {
-            sender_dark.send(Message::PreviewSyntaxTheme(theme));
+        if is_dark_for_dark
+            && let Some((theme, custom)) = index_to_theme(c.value(), fallback_dark, &customs_dark)
+        {
+            sender_dark.send(Message::PreviewSyntaxTheme(theme, custom));
         }
     });
 
@@ -465,6 +479,25 @@ pub fn show_settings_dialog(
     max_edit_input.set_color(theme.input_bg);
     max_edit_input.set_text_color(theme.text);
     max_edit_input.set_frame(FrameType::FlatBox);
+    y += ITEM_HEIGHT + SECTION_GAP;
+
+    // Terminal section
+    let mut terminal_label = Frame::default()
+        .with_pos(RIGHT_COL, y)
+        .with_size(COL_WIDTH, LABEL_HEIGHT)
+        .with_label("Terminal:")
+        .with_align(fltk::enums::Align::Left | fltk::enums::Align::Inside);
+    terminal_label.set_label_color(theme.text);
+    y += LABEL_HEIGHT + 5;
+
+    let mut check_clipboard_write = CheckButton::default()
+        .with_pos(RIGHT_COL + 10, y)
+        .with_size(COL_WIDTH - 10, ITEM_HEIGHT)
+        .with_label("Allow programs to set clipboard");
+    check_clipboard_write.set_label_color(theme.text);
+    check_clipboard_write.set_color(theme.bg);
+    check_clipboard_write.set_tooltip("OSC 52, used by tmux, vim and remote shells to copy text");
+    check_clipboard_write.set_value(current_settings.terminal_clipboard_write);
 
     vpack.end();
 
@@ -495,13 +528,36 @@ pub fn show_settings_dialog(
 
     // Store original theme for reverting on cancel
     let original_theme = current_settings.current_syntax_theme(is_dark);
+    let original_custom = current_settings
+        .current_custom_syntax_theme(is_dark)
+        .map(str::to_string);
+    let original_custom_close = original_custom.clone();
     let sender_cancel = *sender;
+    let customs_save = custom_themes.to_vec();
 
     let dialog_save = dialog.clone();
     let current = current_settings.clone();
     let warning_input_save = warning_input.clone();
     let max_edit_input_save = max_edit_input.clone();
     save_btn.set_callback(move |_| {
+        let (syntax_theme_light, custom_syntax_theme_light) = index_to_theme(
+            theme_light_choice.value(),
+            current.syntax_theme_light,
+            &customs_save,
+        )
+        .unwrap_or((
+            current.syntax_theme_light,
+            current.custom_syntax_theme_light.clone(),
+        ));
+        let (syntax_theme_dark, custom_syntax_theme_dark) = index_to_theme(
+            theme_dark_choice.value(),
+            current.syntax_theme_dark,
+            &customs_save,
+        )
+        .unwrap_or((
+            current.syntax_theme_dark,
+            current.custom_syntax_theme_dark.clone(),
+        ));
         // Parse large file thresholds with clamping
         let max_editable_mb = max_edit_input_save
             .value()
@@ -557,10 +613,10 @@ pub fn show_settings_dialog(
                 SessionRestore::Off
             },
             preview_enabled: current.preview_enabled,
-            syntax_theme_light: index_to_theme(theme_light_choice.value())
-                .unwrap_or(current.syntax_theme_light),
-            syntax_theme_dark: index_to_theme(theme_dark_choice.value())
-                .unwrap_or(current.syntax_theme_dark),
+            syntax_theme_light,
+            syntax_theme_dark,
+            custom_syntax_theme_light,
+            custom_syntax_theme_dark,
             tab_size: if tab_2.value() {
                 2
             } else if tab_8.value() {
@@ -584,6 +640,13 @@ pub fn show_settings_dialog(
             shortcut_overrides: current.shortcut_overrides.clone(),
             large_file_warning_mb,
             max_editable_size_mb: max_editable_mb,
+            terminal_clipboard_write: check_clipboard_write.value(),
+            // Preserve global tasks (edited in settings.json)
+            tasks: current.tasks.clone(),
+            // Preserve command palette history
+            recent_commands: current.recent_commands.clone(),
+            // Preserve project session opt-out (edited in settings.json)
+            project_sessions: current.project_sessions,
         };
 
         *result_save.borrow_mut() = Some(new_settings);
@@ -593,7 +656,10 @@ pub fn show_settings_dialog(
     let dialog_cancel = dialog.clone();
     cancel_btn.set_callback(move |_| {
         // Revert to original theme
-        sender_cancel.send(Message::PreviewSyntaxTheme(original_theme));
+        sender_cancel.send(Message::PreviewSyntaxTheme(
+            original_theme,
+            original_custom.clone(),
+        ));
         *result_cancel.borrow_mut() = None;
         dialog_cancel.clone().hide();
     });
@@ -602,7 +668,10 @@ pub fn show_settings_dialog(
     let sender_close = *sender;
     dialog.set_callback(move |w| {
         // Revert to original theme on close (X button)
-        sender_close.send(Message::PreviewSyntaxTheme(original_theme));
+        sender_close.send(Message::PreviewSyntaxTheme(
+            original_theme,
+            original_custom_close.clone(),
+        ));
         *result_close.borrow_mut() = None;
         w.hide();
     });
@@ -612,8 +681,27 @@ pub fn show_settings_dialog(
     result.borrow().clone()
 }
 
-/// Convert SyntaxTheme to dropdown index
-fn theme_index(theme: SyntaxTheme) -> i32 {
+/// Fill a theme dropdown: built-in themes first, then user/plugin themes.
+fn add_theme_choices(choice: &mut Choice, custom_themes: &[String]) {
+    for syntax_theme in SyntaxTheme::all() {
+        choice.add_choice(syntax_theme.display_name());
+    }
+    for name in custom_themes {
+        // Escape FLTK menu label metacharacters
+        let label = name
+            .replace('\\', "\\\\")
+            .replace('/', "\\/")
+            .replace('&', "&&");
+        choice.add_choice(&format!("{} (custom)", label));
+    }
+}
+
+/// Convert a theme selection to dropdown index. A custom theme that is not
+/// loaded falls back to the built-in theme's entry.
+fn theme_index(theme: SyntaxTheme, custom: Option<&str>, custom_themes: &[String]) -> i32 {
+    if let Some(i) = custom.and_then(|name| custom_themes.iter().position(|t| t == name)) {
+        return (SyntaxTheme::all().len() + i) as i32;
+    }
     SyntaxTheme::all()
         .iter()
         .position(|t| *t == theme)
@@ -621,10 +709,22 @@ fn theme_index(theme: SyntaxTheme) -> i32 {
         .unwrap_or(0)
 }
 
-/// Convert dropdown index to SyntaxTheme
-fn index_to_theme(index: i32) -> Option<SyntaxTheme> {
+/// Convert dropdown index to (built-in theme, custom theme name).
+/// Custom entries keep `fallback` as the built-in theme.
+fn index_to_theme(
+    index: i32,
+    fallback: SyntaxTheme,
+    custom_themes: &[String],
+) -> Option<(SyntaxTheme, Option<String>)> {
     if index < 0 {
         return None;
     }
-    SyntaxTheme::all().get(index as usize).copied()
+    let index = index as usize;
+    let builtins = SyntaxTheme::all();
+    match builtins.get(index) {
+        Some(theme) => Some((*theme, None)),
+        None => custom_themes
+            .get(index - builtins.len())
+            .map(|name| (fallback, Some(name.clone()))),
+    }
 }
@@ -0,0 +1,149 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    enums::{Event, FrameType, Key},
+    frame::Frame,
+    prelude::{BrowserExt, GroupExt, WidgetBase, WidgetExt, WindowExt},
+    window::Window,
+};
+
+use crate::app::services::tasks::{TaskDefinition, TaskRunMode};
+
+use super::DialogTheme;
+
+/// Show the task picker for Tasks > Run Task.
+/// `parent` is the main window used to center the dialog (reliable on Wayland).
+/// Returns the index of the chosen task, or None if cancelled.
+pub fn show_task_picker(
+    parent: &Window,
+    tasks: &[TaskDefinition],
+    theme_bg: (u8, u8, u8),
+) -> Option<usize> {
+    let theme = DialogTheme::from_theme_bg(theme_bg);
+    let result: Rc<RefCell<Option<usize>>> = Rc::new(RefCell::new(None));
+
+    const DW: i32 = 420;
+    const DH: i32 = 300;
+    let mut dialog_win = Window::default()
+        .with_size(DW, DH)
+        .with_label("Run Task")
+        .center_screen();
+    dialog_win.set_color(theme.bg);
+
+    let mut title = Frame::default()
+        .with_pos(20, 10)
+        .with_size(380, 25)
+        .with_label("Choose a task to run:");
+    title.set_label_color(theme.text);
+
+    let mut browser = HoldBrowser::default().with_pos(20, 40).with_size(380, 200);
+    browser.set_color(theme.input_bg);
+    browser.set_selection_color(theme.button_bg);
+    browser.set_frame(FrameType::FlatBox);
+
+    // Task name plus its command, dimmed (use @C format codes for dark themes)
+    let color_code =
+        |(r, g, b): (u8, u8, u8)| ((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8);
+    let text_code = color_code(theme.text_rgb());
+    let dim_code = color_code(theme.text_dim.to_rgb());
+    for task in tasks {
+        let mode = match task.run_in {
+            TaskRunMode::Terminal => "",
+            TaskRunMode::Background => " (background)",
+        };
+        browser.add(&format!(
+            "@C{} {}{}\t@C{} {}",
+            text_code,
+            task.name.replace('@', "@@"),
+            mode,
+            dim_code,
+            task.command.replace('@', "@@")
+        ));
+    }
+    browser.set_column_widths(&[170]);
+    browser.set_column_char('\t');
+    if !tasks.is_empty() {
+        browser.select(1);
+    }
+
+    let btn_y = 255;
+    let mut run_btn = Button::default()
+        .with_pos(DW - 200, btn_y)
+        .with_size(85, 30)
+        .with_label("Run");
+    run_btn.set_color(theme.button_bg);
+    run_btn.set_label_color(theme.text);
+
+    let mut cancel_btn = Button::default()
+        .with_pos(DW - 105, btn_y)
+        .with_size(85, 30)
+        .with_label("Cancel");
+    cancel_btn.set_color(theme.button_bg);
+    cancel_btn.set_label_color(theme.text);
+
+    dialog_win.end();
+    dialog_win.make_resizable(false);
+    dialog_win.show();
+    theme.apply_titlebar(&dialog_win);
+    // Reposition to center on the parent AFTER show().
+    dialog_win.resize(
+        parent.x() + (parent.w() - DW) / 2,
+        parent.y() + (parent.h() - DH) / 2,
+        DW,
+        DH,
+    );
+    let _ = browser.take_focus();
+
+    // Run button
+    {
+        let result = result.clone();
+        let dialog = dialog_win.clone();
+        let browser = browser.clone();
+        run_btn.set_callback(move |_| {
+            let sel = browser.value();
+            if sel > 0 {
+                *result.borrow_mut() = Some((sel - 1) as usize);
+                dialog.clone().hide();
+            }
+        });
+    }
+
+    // Cancel button
+    {
+        let dialog = dialog_win.clone();
+        cancel_btn.set_callback(move |_| {
+            dialog.clone().hide();
+        });
+    }
+
+    // Double-click or Enter on the list runs the task
+    {
+        let mut run_btn = run_btn.clone();
+        browser.handle(move |b, ev| match ev {
+            Event::KeyDown if fltk::app::event_key() == Key::Enter => {
+                run_btn.do_callback();
+                true
+            }
+            Event::Released if fltk::app::event_clicks() && b.value() > 0 => {
+                run_btn.do_callback();
+                true
+            }
+            _ => false,
+        });
+    }
+
+    // Window close
+    {
+        let dialog = dialog_win.clone();
+        dialog_win.set_callback(move |_| {
+            dialog.clone().hide();
+        });
+    }
+
+    super::run_dialog(&dialog_win);
+
+    *result.borrow()
+}
@@ -1,28 +1,160 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use fltk::{
-    enums::Color,
+    app::Sender,
+    enums::{Color, Event, Key, Shortcut},
     group::Flex,
     prelude::*,
-    text::{TextBuffer, TextEditor},
+    text::{TextBuffer, TextEditor, WrapMode},
 };
 
+use crate::app::controllers::panes::{PaneId, SplitOrientation};
+use crate::app::{AppSettings, Message};
+
+/// Gap between split editor panes (the parent's background shows through)
+const PANE_GAP: i32 = 4;
+
+/// The editor area: one `TextEditor` per pane, laid out in a row or column.
 pub struct EditorContainer {
-    editor: TextEditor,
+    flex: Flex,
+    editors: Vec<TextEditor>,
 }
 
 impl EditorContainer {
     pub fn new(_parent_flex: &Flex) -> Self {
-        let mut editor = TextEditor::new(0, 0, 0, 0, "");
-        editor.set_buffer(TextBuffer::default());
+        let mut flex = Flex::default().row();
+        flex.set_margin(0);
+        flex.set_pad(PANE_GAP);
+        let editor = new_editor();
+        flex.end();
 
-        // Enable native line numbers
-        editor.set_linenumber_width(40);
-        editor.set_linenumber_bgcolor(Color::from_rgb(240, 240, 240));
-        editor.set_linenumber_fgcolor(Color::from_rgb(100, 100, 100));
+        Self {
+            flex,
+            editors: vec![editor],
+        }
+    }
 
-        Self { editor }
+    /// The group holding all panes (shown/hidden with the start page)
+    pub fn widget(&mut self) -> &mut Flex {
+        &mut self.flex
     }
 
+    /// Editor of the first pane
     pub fn editor(&self) -> &TextEditor {
-        &self.editor
+        &self.editors[0]
+    }
+
+    pub fn editor_at(&self, index: usize) -> Option<&TextEditor> {
+        self.editors.get(index)
+    }
+
+    pub fn editors(&self) -> &[TextEditor] {
+        &self.editors
+    }
+
+    /// Create a pane editor at `index` and lay the panes out again
+    pub fn insert_pane(&mut self, index: usize, orientation: SplitOrientation) -> TextEditor {
+        self.set_orientation(orientation);
+        let editor = new_editor();
+        let index = index.min(self.editors.len());
+        self.flex.insert(&editor, index as i32);
+        self.editors.insert(index, editor.clone());
+        self.relayout();
+        editor
+    }
+
+    /// Delete the pane editor at `index` (the last one always stays)
+    pub fn remove_pane(&mut self, index: usize) {
+        if self.editors.len() <= 1 || index >= self.editors.len() {
+            return;
+        }
+        let editor = self.editors.remove(index);
+        self.flex.remove(&editor);
+        TextEditor::delete(editor);
+        self.relayout();
+    }
+
+    fn set_orientation(&mut self, orientation: SplitOrientation) {
+        match orientation {
+            SplitOrientation::Vertical => self.flex.set_type(fltk::group::FlexType::Row),
+            SplitOrientation::Horizontal => self.flex.set_type(fltk::group::FlexType::Column),
+        }
+    }
+
+    fn relayout(&mut self) {
+        self.flex.layout();
+        self.flex.redraw();
+    }
+}
+
+fn new_editor() -> TextEditor {
+    let mut editor = TextEditor::new(0, 0, 0, 0, "");
+    editor.set_buffer(TextBuffer::default());
+
+    // Enable native line numbers
+    editor.set_linenumber_width(40);
+    editor.set_linenumber_bgcolor(Color::from_rgb(240, 240, 240));
+    editor.set_linenumber_fgcolor(Color::from_rgb(100, 100, 100));
+    editor
+}
+
+/// Install the key handling shared by all pane editors: the Tab key honours
+/// the "use spaces" setting, and focusing a pane reports it so the rest of
+/// the app follows the focused pane.
+pub fn install_editor_handlers(
+    editor: &mut TextEditor,
+    pane: PaneId,
+    settings: Rc<RefCell<AppSettings>>,
+    sender: Sender<Message>,
+) {
+    // Replace FLTK's built-in Tab binding with a no-op so our handle() is the sole handler.
+    editor.add_key_binding(
+        Key::Tab,
+        Shortcut::None,
+        |_key, _editor| 1, // 1 = handled (do nothing, our handle() already inserted text)
+    );
+    editor.handle(move |editor, event| match event {
+        Event::KeyDown if fltk::app::event_key() == Key::Tab => {
+            let s = settings.borrow();
+            if let Some(mut buf) = editor.buffer() {
+                let pos = editor.insert_position();
+                if s.use_spaces {
+                    let spaces = " ".repeat(s.tab_size as usize);
+                    buf.insert(pos, &spaces);
+                    editor.set_insert_position(pos + s.tab_size as i32);
+                } else {
+                    buf.insert(pos, "\t");
+                    editor.set_insert_position(pos + 1);
+                }
+            }
+            true
+        }
+        Event::Focus => {
+            sender.send(Message::EditorPaneFocused(pane));
+            // Let FLTK accept the focus as usual
+            false
+        }
+        _ => false,
+    });
+}
+
+/// Copy the look of the focused pane's editor (theme colors, font, line
+/// numbers, wrapping) to another pane's editor.
+pub fn copy_editor_style(from: &TextEditor, to: &mut TextEditor, word_wrap: bool) {
+    to.set_color(from.color());
+    to.set_text_color(from.text_color());
+    to.set_cursor_color(from.cursor_color());
+    to.set_selection_color(from.selection_color());
+    to.set_text_font(from.text_font());
+    to.set_text_size(from.text_size());
+    to.set_linenumber_width(from.linenumber_width());
+    to.set_linenumber_bgcolor(from.linenumber_bgcolor());
+    to.set_linenumber_fgcolor(from.linenumber_fgcolor());
+    if word_wrap {
+        to.wrap_mode(WrapMode::AtBounds, 0);
+    } else {
+        to.wrap_mode(WrapMode::None, 0);
     }
+    to.redraw();
 }
@@ -47,3 +47,40 @@ pub fn native_save_dialog(directory: Option<&str>) -> Option<String> {
         Some(path.to_string_lossy().to_string())
     }
 }
+
+/// Pick a single file, with a custom title
+pub fn native_pick_file_dialog(title: &str, directory: Option<&str>) -> Option<String> {
+    let chooser = show_chooser(title, NativeFileChooserType::BrowseFile, directory);
+    chosen_path(&chooser)
+}
+
+/// Pick a directory
+pub fn native_folder_dialog(title: &str, directory: Option<&str>) -> Option<String> {
+    let chooser = show_chooser(title, NativeFileChooserType::BrowseDir, directory);
+    chosen_path(&chooser)
+}
+
+/// Choose where to save, suggesting `file_name`
+pub fn native_save_file_dialog(
+    title: &str,
+    directory: Option<&str>,
+    file_name: &str,
+) -> Option<String> {
+    let mut chooser = NativeFileChooser::new(NativeFileChooserType::BrowseSaveFile);
+    chooser.set_title(title);
+    if let Some(dir) = directory {
+        chooser.set_directory(&dir.to_string()).ok();
+    }
+    chooser.set_preset_file(file_name);
+    chooser.show();
+    chosen_path(&chooser)
+}
+
+fn chosen_path(chooser: &NativeFileChooser) -> Option<String> {
+    let path = chooser.filename();
+    if path.as_os_str().is_empty() {
+        None
+    } else {
+        Some(path.to_string_lossy().to_string())
+    }
+}
@@ -10,6 +10,7 @@ use super::split_panel::SplitPanel;
 use super::start_page::StartPage;
 use super::status_bar::{STATUS_BAR_HEIGHT, StatusBar};
 use super::tab_bar::{TAB_BAR_HEIGHT, TabBar};
+use super::table_panel::TablePanel;
 use super::terminal_panel::TerminalPanel;
 use super::toast::Toast;
 use super::tree_panel::TreePanel;
@@ -25,6 +26,7 @@ pub struct LayoutWidgets {
     pub diagnostic_panel: DiagnosticPanel,
     pub tree_panel: TreePanel,
     pub terminal_panel: TerminalPanel,
+    pub table_panel: TablePanel,
     pub toast: Toast,
     pub status_bar: StatusBar,
     pub content_row: Flex,
@@ -63,6 +65,7 @@ pub struct MainWidgets {
     pub diagnostic_panel: DiagnosticPanel,
     pub tree_panel: TreePanel,
     pub terminal_panel: TerminalPanel,
+    pub table_panel: TablePanel,
     pub status_bar: StatusBar,
     /// Inner row flex for left/right tree panel positioning
     pub content_row: Flex,
@@ -290,13 +293,18 @@ pub fn build_main_window(
         }
     }
 
+    // Table panel (full width above diagnostics, hidden until requested)
+    let mut table_panel = TablePanel::new(*sender);
+    table_panel.hide();
+    flex.fixed(table_panel.widget(), 0);
+
     // Diagnostic panel (below everything, initially hidden)
     let mut diagnostic_panel = DiagnosticPanel::new(*sender);
     diagnostic_panel.hide();
     flex.fixed(diagnostic_panel.widget(), 0);
 
     // Status bar (bottom of window, always visible)
-    let status_bar = StatusBar::new();
+    let status_bar = StatusBar::new(*sender);
     flex.fixed(status_bar.widget(), STATUS_BAR_HEIGHT);
 
     flex.end();
@@ -314,6 +322,7 @@ pub fn build_main_window(
         diagnostic_panel,
         tree_panel,
         terminal_panel,
+        table_panel,
         status_bar,
         content_row,
         right_col,
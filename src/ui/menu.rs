@@ -7,10 +7,21 @@ use fltk::{
     prelude::*,
 };
 
+use crate::app::controllers::command_palette::{CommandAction, PaletteCommand, command_label};
+use crate::app::controllers::panes::SplitOrientation;
 use crate::app::plugins::{PluginManager, plugin_display_name};
+use crate::app::services::recent_files::RecentFiles;
 use crate::app::services::shortcut_registry::{ShortcutRegistry, normalize_shortcut};
 use crate::app::{AppSettings, Message};
 
+/// Submenu listing recently used files
+const RECENT_MENU: &str = "File/Open Recent";
+
+/// How many recent files the submenu lists
+const MAX_RECENT_MENU_ITEMS: usize = 15;
+const CLEAR_RECENT_LABEL: &str = "Clear Recently Opened";
+const COMMAND_PALETTE_PATH: &str = "View/Command Palette...";
+
 /// Reserved keyboard shortcuts that plugins cannot override.
 /// These are built-in editor functions.
 const RESERVED_SHORTCUTS: &[&str] = &[
@@ -37,6 +48,21 @@ const RESERVED_SHORTCUTS: &[&str] = &[
     "ctrl+shift+n", // New Session Window
     "ctrl+r",
     "ctrl+shift+r",
+    "ctrl+`",        // Toggle Terminal
+    "ctrl+shift+b",  // Run Task
+    "ctrl+b",        // Re-run Last Task
+    "f8",            // Next Error
+    "ctrl+\\",       // Split Editor Right
+    "f6",            // Focus Next Pane
+    "ctrl+shift+t",  // Reopen Closed Tab
+    "ctrl+p",        // Quick Open
+    "ctrl+shift+p",  // Command Palette
+    "ctrl+f2",       // Toggle Bookmark
+    "f2",            // Next Bookmark
+    "shift+f2",      // Previous Bookmark
+    "ctrl+shift+f2", // Bookmarks
+    "alt+left",      // Go Back
+    "alt+right",     // Go Forward
 ];
 
 /// Built-in shortcuts: (menu_path/command_id, default_shortcut_string).
@@ -45,13 +71,15 @@ const RESERVED_SHORTCUTS: &[&str] = &[
 pub const BUILTIN_SHORTCUTS: &[(&str, &str)] = &[
     ("File/New", "Ctrl+T"), // Ctrl+N when tabs off
     ("File/Open...", "Ctrl+O"),
+    ("File/Quick Open...", "Ctrl+P"),
     ("File/Save", "Ctrl+S"),
     ("File/Save As...", "Ctrl+Shift+S"),
     ("File/Reload", "Ctrl+R"),
     ("File/Reload All", "Ctrl+Shift+R"),
-    ("File/Close Tab", "Ctrl+W"),            // tabs only
-    ("File/Next Tab", "Ctrl+Tab"),           // tabs only
-    ("File/Previous Tab", "Ctrl+Shift+Tab"), // tabs only
+    ("File/Close Tab", "Ctrl+W"),               // tabs only
+    ("File/Next Tab", "Ctrl+Tab"),              // tabs only
+    ("File/Previous Tab", "Ctrl+Shift+Tab"),    // tabs only
+    ("File/Reopen Closed Tab", "Ctrl+Shift+T"), // tabs only
     ("File/New Session Window...", "Ctrl+Shift+N"),
     ("File/Quit", "Ctrl+Q"),
     ("Edit/Undo", "Ctrl+Z"),
@@ -63,7 +91,20 @@ pub const BUILTIN_SHORTCUTS: &[(&str, &str)] = &[
     ("Edit/Find...", "Ctrl+F"),
     ("Edit/Replace...", "Ctrl+H"),
     ("Edit/Go To Line...", "Ctrl+G"),
+    ("Edit/Go Back", "Alt+Left"),
+    ("Edit/Go Forward", "Alt+Right"),
+    ("Edit/Bookmarks/Toggle Bookmark", "Ctrl+F2"),
+    ("Edit/Bookmarks/Next Bookmark", "F2"),
+    ("Edit/Bookmarks/Previous Bookmark", "Shift+F2"),
+    ("Edit/Bookmarks/List Bookmarks...", "Ctrl+Shift+F2"),
+    ("View/Command Palette...", "Ctrl+Shift+P"),
     ("View/Preview in Browser", "Ctrl+M"),
+    ("View/Terminal", "Ctrl+`"),
+    ("View/Split Editor Right", "Ctrl+\\"),
+    ("View/Focus Next Pane", "F6"),
+    ("Tasks/Run Task...", "Ctrl+Shift+B"),
+    ("Tasks/Re-run Last Task", "Ctrl+B"),
+    ("Tasks/Next Error", "F8"),
     ("Plugins/General/Run All Checks", "Ctrl+Shift+L"),
 ];
 
@@ -127,7 +168,12 @@ pub fn apply_shortcut_overrides(
 ) {
     for &(id, default) in BUILTIN_SHORTCUTS {
         // Skip tab-only shortcuts when tabs are disabled
-        if !tabs_enabled && matches!(id, "File/Close Tab" | "File/Next Tab" | "File/Previous Tab") {
+        if !tabs_enabled
+            && matches!(
+                id,
+                "File/Close Tab" | "File/Next Tab" | "File/Previous Tab" | "File/Reopen Closed Tab"
+            )
+        {
             continue;
         }
         // Handle File/New special case (Ctrl+N when tabs off)
@@ -267,10 +313,11 @@ fn parse_shortcut(s: &str) -> Option<Shortcut> {
                     _ => {}
                 }
 
-                // Single character key (A-Z, 0-9)
+                // Single character key (A-Z, 0-9, ` for the terminal toggle
+                // and \ for splitting the editor)
                 if part.len() == 1 {
                     let ch = part.chars().next().unwrap().to_ascii_lowercase();
-                    if ch.is_ascii_alphanumeric() {
+                    if ch.is_ascii_alphanumeric() || ch == '`' || ch == '\\' {
                         result = result | ch;
                         continue;
                     }
@@ -330,6 +377,35 @@ pub fn build_menu(
         let s = *s;
         move |_| s.send(Message::FileOpen)
     });
+    menu.add(
+        "File/Open Range...",
+        rs("File/Open Range..."),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::FileOpenRange)
+        },
+    );
+    menu.add(
+        "File/Open in Hex Viewer...",
+        rs("File/Open in Hex Viewer..."),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::FileOpenHex)
+        },
+    );
+    menu.add(
+        "File/Quick Open...",
+        rs("File/Quick Open..."),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::ShowQuickOpen)
+        },
+    );
+    // Filled by rebuild_recent_menu()
+    menu.add(RECENT_MENU, Shortcut::None, MenuFlag::Submenu, |_| {});
     menu.add("File/Save", rs("File/Save"), MenuFlag::Normal, {
         let s = *s;
         move |_| s.send(Message::FileSave)
@@ -369,6 +445,24 @@ pub fn build_menu(
             move |_| s.send(Message::SessionNewWindow)
         },
     );
+    menu.add(
+        "File/Export Session...",
+        Shortcut::None,
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::SessionExport)
+        },
+    );
+    menu.add(
+        "File/Import Session...",
+        Shortcut::None,
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::SessionImport)
+        },
+    );
     if tabs_enabled {
         menu.add("File/Close Tab", rs("File/Close Tab"), MenuFlag::Normal, {
             let s = *s;
@@ -387,6 +481,15 @@ pub fn build_menu(
                 move |_| s.send(Message::TabPrevious)
             },
         );
+        menu.add(
+            "File/Reopen Closed Tab",
+            rs("File/Reopen Closed Tab"),
+            MenuFlag::Normal,
+            {
+                let s = *s;
+                move |_| s.send(Message::TabReopenClosed)
+            },
+        );
     }
     menu.add("File/Settings...", Shortcut::None, MenuFlag::Normal, {
         let s = *s;
@@ -449,12 +552,70 @@ pub fn build_menu(
             move |_| s.send(Message::ShowGoToLine)
         },
     );
+    menu.add("Edit/Go Back", rs("Edit/Go Back"), MenuFlag::Normal, {
+        let s = *s;
+        move |_| s.send(Message::NavigateBack)
+    });
+    menu.add(
+        "Edit/Go Forward",
+        rs("Edit/Go Forward"),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::NavigateForward)
+        },
+    );
+    menu.add(
+        "Edit/Bookmarks/Toggle Bookmark",
+        rs("Edit/Bookmarks/Toggle Bookmark"),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::BookmarkToggle)
+        },
+    );
+    menu.add(
+        "Edit/Bookmarks/Next Bookmark",
+        rs("Edit/Bookmarks/Next Bookmark"),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::BookmarkNext)
+        },
+    );
+    menu.add(
+        "Edit/Bookmarks/Previous Bookmark",
+        rs("Edit/Bookmarks/Previous Bookmark"),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::BookmarkPrevious)
+        },
+    );
+    menu.add(
+        "Edit/Bookmarks/List Bookmarks...",
+        rs("Edit/Bookmarks/List Bookmarks..."),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::ShowBookmarks)
+        },
+    );
     menu.add("Edit/Key Shortcuts...", Shortcut::None, MenuFlag::Normal, {
         let s = *s;
         move |_| s.send(Message::ShowKeyShortcuts)
     });
 
     // View
+    menu.add(
+        COMMAND_PALETTE_PATH,
+        rs(COMMAND_PALETTE_PATH),
+        MenuFlag::MenuDivider,
+        {
+            let s = *s;
+            move |_| s.send(Message::ShowCommandPalette)
+        },
+    );
     let ln_flag = if settings.line_numbers_enabled {
         MenuFlag::Toggle | MenuFlag::Value
     } else {
@@ -496,6 +657,10 @@ pub fn build_menu(
             move |_| s.send(Message::ToggleHighlighting)
         },
     );
+    menu.add("View/Follow File", Shortcut::None, MenuFlag::Toggle, {
+        let s = *s;
+        move |_| s.send(Message::ToggleFollow)
+    });
     menu.add(
         "View/Preview in Browser",
         rs("View/Preview in Browser"),
@@ -514,6 +679,46 @@ pub fn build_menu(
             move |_| s.send(Message::ToggleDiagnosticsPanel)
         },
     );
+    menu.add("View/Terminal", rs("View/Terminal"), MenuFlag::Normal, {
+        let s = *s;
+        move |_| s.send(Message::TerminalToggle)
+    });
+    menu.add(
+        "View/Split Editor Right",
+        rs("View/Split Editor Right"),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::SplitEditor(SplitOrientation::Vertical))
+        },
+    );
+    menu.add(
+        "View/Split Editor Down",
+        Shortcut::None,
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::SplitEditor(SplitOrientation::Horizontal))
+        },
+    );
+    menu.add(
+        "View/Close Editor Pane",
+        Shortcut::None,
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::CloseEditorPane)
+        },
+    );
+    menu.add(
+        "View/Focus Next Pane",
+        rs("View/Focus Next Pane"),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::FocusNextPane)
+        },
+    );
 
     // Format
     menu.add(
@@ -561,6 +766,35 @@ pub fn build_menu(
         );
     }
 
+    // Tasks
+    menu.add(
+        "Tasks/Run Task...",
+        rs("Tasks/Run Task..."),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::RunTask)
+        },
+    );
+    menu.add(
+        "Tasks/Re-run Last Task",
+        rs("Tasks/Re-run Last Task"),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::RerunLastTask)
+        },
+    );
+    menu.add(
+        "Tasks/Next Error",
+        rs("Tasks/Next Error"),
+        MenuFlag::Normal,
+        {
+            let s = *s;
+            move |_| s.send(Message::NextError)
+        },
+    );
+
     // Plugins - General submenu with core functionality
     let plugins_flag = if settings.plugins_enabled {
         MenuFlag::Toggle | MenuFlag::Value
@@ -630,6 +864,162 @@ pub fn build_menu(
     );
 }
 
+/// Fill the File > Open Recent submenu from the recently used files
+pub fn rebuild_recent_menu(menu: &mut MenuBar, sender: &Sender<Message>, recent: &RecentFiles) {
+    let idx = menu.find_index(RECENT_MENU);
+    if idx < 0 {
+        return;
+    }
+    let _ = menu.clear_submenu(idx);
+
+    if recent.files.is_empty() {
+        menu.add(
+            &format!("{}/(none)", RECENT_MENU),
+            Shortcut::None,
+            MenuFlag::Inactive,
+            |_| {},
+        );
+        return;
+    }
+
+    let count = recent.files.len().min(MAX_RECENT_MENU_ITEMS);
+    for (i, file) in recent.files.iter().take(count).enumerate() {
+        // Numbered so equal labels never replace each other
+        let label = format!(
+            "{}/{} {}",
+            RECENT_MENU,
+            i + 1,
+            escape_menu_label(&shorten_home(&file.path))
+        );
+        let flag = if i + 1 == count {
+            MenuFlag::MenuDivider
+        } else {
+            MenuFlag::Normal
+        };
+        menu.add_emit(
+            &label,
+            Shortcut::None,
+            flag,
+            *sender,
+            Message::FileOpenRecent(file.path.clone()),
+        );
+    }
+    menu.add_emit(
+        &format!("{}/{}", RECENT_MENU, CLEAR_RECENT_LABEL),
+        Shortcut::None,
+        MenuFlag::Normal,
+        *sender,
+        Message::FileClearRecent,
+    );
+}
+
+/// Every action of the menu bar for the command palette, in menu order,
+/// with its effective shortcut. Items of enabled plugins are sent as
+/// `PluginMenuAction`; the Open Recent file entries are left to Quick Open.
+pub fn palette_commands(
+    menu: &MenuBar,
+    registry: &ShortcutRegistry,
+    plugins: &PluginManager,
+    tabs_enabled: bool,
+) -> Vec<PaletteCommand> {
+    let mut plugin_paths = HashSet::new();
+    let mut plugin_commands = Vec::new();
+    for plugin in plugins.list_plugins() {
+        let display = plugin_display_name(&plugin.name);
+        for item in &plugin.menu_items {
+            plugin_paths.insert(format!("Plugins/{}/{}", display, item.label));
+            if !plugin.enabled {
+                continue;
+            }
+            let id = plugin_command_id(&plugin.name, &item.action);
+            let default = item.shortcut.as_deref().unwrap_or("");
+            plugin_commands.push(PaletteCommand {
+                shortcut: registry.effective_shortcut(&id, default).to_string(),
+                id,
+                label: command_label(&format!("Plugins/{}/{}", display, item.label)),
+                action: CommandAction::Plugin {
+                    plugin_name: plugin.name.clone(),
+                    action: item.action.clone(),
+                },
+            });
+        }
+    }
+
+    let recent_prefix = format!("{}/", RECENT_MENU);
+    let mut commands = Vec::new();
+    for i in 0..menu.size() {
+        let Some(item) = menu.at(i) else {
+            continue;
+        };
+        if item.label().is_none() || item.is_submenu() || !item.visible() || !item.active() {
+            continue;
+        }
+        let Ok(path) = menu.item_pathname(Some(&item)) else {
+            continue;
+        };
+        if plugin_paths.contains(&path)
+            || path == COMMAND_PALETTE_PATH
+            || path.contains('\u{2500}')
+            || (path.starts_with(&recent_prefix) && !path.ends_with(CLEAR_RECENT_LABEL))
+        {
+            continue;
+        }
+        let default = BUILTIN_SHORTCUTS
+            .iter()
+            .find(|(id, _)| *id == path)
+            .map_or("", |(_, default)| *default);
+        let default = if path == "File/New" && !tabs_enabled {
+            "Ctrl+N"
+        } else {
+            default
+        };
+        commands.push(PaletteCommand {
+            shortcut: registry.effective_shortcut(&path, default).to_string(),
+            label: command_label(&path),
+            action: CommandAction::Menu(path.clone()),
+            id: path,
+        });
+    }
+    commands.extend(plugin_commands);
+    commands
+}
+
+/// Run a menu item as if it was clicked (check items flip first).
+/// Returns false if there is no such item.
+pub fn trigger_menu_item(menu: &mut MenuBar, path: &str) -> bool {
+    let idx = menu.find_index(path);
+    let Some(mut item) = (idx >= 0).then(|| menu.at(idx)).flatten() else {
+        return false;
+    };
+    if item.is_checkbox() {
+        if item.value() {
+            item.clear();
+        } else {
+            item.set();
+        }
+    }
+    item.do_callback(menu);
+    true
+}
+
+/// Escape FLTK menu label metacharacters
+fn escape_menu_label(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('/', "\\/")
+        .replace('&', "&&")
+}
+
+/// Show paths under the home directory as `~/...`
+fn shorten_home(path: &str) -> String {
+    if let Some(home) = dirs::home_dir()
+        && let Ok(rest) = std::path::Path::new(path).strip_prefix(&home)
+    {
+        return format!("~/{}", rest.to_string_lossy());
+    }
+    path.to_string()
+}
+
 /// Remove all menu entries for a plugin by name.
 /// This handles both flat toggles and submenu items.
 fn remove_plugin_menu_entries(menu: &mut MenuBar, name: &str) {
@@ -997,6 +1387,14 @@ mod tests {
         assert!(parse_shortcut("Ctrl+!!!").is_none());
     }
 
+    #[test]
+    fn test_parse_shortcut_backtick() {
+        let sc = parse_shortcut("Ctrl+`").unwrap();
+        assert_eq!(sc, Shortcut::Ctrl | '`');
+        let sc = parse_shortcut("Ctrl+\\").unwrap();
+        assert_eq!(sc, Shortcut::Ctrl | '\\');
+    }
+
     #[test]
     fn test_normalize_shortcut() {
         assert_eq!(normalize_shortcut("Ctrl+Shift+P"), "ctrl+shift+p");
@@ -8,6 +8,7 @@ pub mod split_panel;
 pub mod start_page;
 pub mod status_bar;
 pub mod tab_bar;
+pub mod table_panel;
 pub mod terminal_panel;
 pub mod theme;
 pub mod toast;
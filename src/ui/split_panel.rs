@@ -21,49 +21,19 @@ use fltk::{
 
 use super::dialogs::{DialogTheme, SCROLLBAR_SIZE};
 use crate::app::Message;
+use crate::app::infrastructure::display::scroll_position;
 use crate::app::plugins::widgets::{
     HighlightColor, LineHighlight, SplitDisplayMode, SplitViewAction, SplitViewRequest,
 };
 
-/// Get the vertical scrollbar value of a TextDisplay/TextEditor via FFI.
-/// Works with any widget that is a Fl_Group subclass with scrollbar children.
-/// The vertical scrollbar is typically at index 1.
-fn get_vscrollbar_value_raw(widget_ptr: fltk::app::WidgetPtr) -> f64 {
-    // SAFETY: widget_ptr is a valid Fl_Group subclass (TextDisplay/TextEditor).
-    // Fl_Group_children/Fl_Group_child are stable FLTK C API. We null-check
-    // the child pointer before reconstructing. The widget outlives this call.
-    unsafe extern "C" {
-        fn Fl_Group_children(grp: *mut std::ffi::c_void) -> std::ffi::c_int;
-        fn Fl_Group_child(
-            grp: *mut std::ffi::c_void,
-            index: std::ffi::c_int,
-        ) -> *mut std::ffi::c_void;
-    }
-    unsafe {
-        use fltk::valuator::Scrollbar;
-        let group_ptr = widget_ptr as *mut std::ffi::c_void;
-        let nchildren = Fl_Group_children(group_ptr);
-        // child[0] = horizontal scrollbar (wide, short)
-        // child[1] = vertical scrollbar (narrow, tall)
-        if nchildren > 1 {
-            let ptr = Fl_Group_child(group_ptr, 1);
-            if !ptr.is_null() {
-                let sb = Scrollbar::from_widget_ptr(ptr as fltk::app::WidgetPtr);
-                return sb.value();
-            }
-        }
-        0.0
-    }
-}
-
 /// Get the vertical scrollbar value of a TextDisplay.
 fn get_vscrollbar_value(display: &TextDisplay) -> f64 {
-    get_vscrollbar_value_raw(display.as_widget_ptr())
+    scroll_position(display.as_widget_ptr()).0 as f64
 }
 
 /// Get the vertical scrollbar value of a TextEditor.
 fn get_vscrollbar_value_editor(editor: &TextEditor) -> f64 {
-    get_vscrollbar_value_raw(editor.as_widget_ptr())
+    scroll_position(editor.as_widget_ptr()).0 as f64
 }
 
 /// Maximum style entries for the split view style table.
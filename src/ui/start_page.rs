@@ -4,7 +4,6 @@
 //! - Left: 2x2 button grid + stacked session/files cards
 //! - Right: donate button + changelog/sponsor card (aligned with left cards)
 
-use std::collections::HashSet;
 use std::path::Path;
 
 use fltk::{
@@ -18,7 +17,8 @@ use fltk::{
 };
 
 use crate::app::domain::messages::Message;
-use crate::app::services::session::{self, SessionRestore};
+use crate::app::services::recent_files;
+use crate::app::services::session;
 use crate::ui::dialogs::DialogTheme;
 
 const WIKI_FERRISPAD: &str = "https://github.com/fedro86/ferrispad/wiki";
@@ -494,24 +494,19 @@ fn shorten(path: &str) -> String {
         .unwrap_or_default()
 }
 
+/// Most recently used files that still exist, from the shared history
 fn gather_recent_files() -> Vec<(String, String)> {
-    let mut files = Vec::new();
-    let mut seen = HashSet::new();
-    for name in session::list_sessions() {
-        if let Some(data) = session::load_session(SessionRestore::SavedFiles, &name) {
-            for doc in &data.documents {
-                if let Some(ref p) = doc.file_path
-                    && seen.insert(p.clone())
-                {
-                    let display = Path::new(p)
-                        .file_name()
-                        .map(|f| f.to_string_lossy().to_string())
-                        .unwrap_or_else(|| p.clone());
-                    files.push((display, p.clone()));
-                }
-            }
-        }
-    }
-    files.truncate(MAX_RECENT);
-    files
+    recent_files::load()
+        .files
+        .into_iter()
+        .filter(|f| Path::new(&f.path).exists())
+        .take(MAX_RECENT)
+        .map(|f| {
+            let display = Path::new(&f.path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| f.path.clone());
+            (display, f.path)
+        })
+        .collect()
 }
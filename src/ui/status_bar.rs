@@ -2,17 +2,26 @@
 //!
 //! Displays the relative file path on the left and `Ln X, Col Y` with
 //! optional selection info on the right, composed into a single label.
+//! Plugin-contributed items sit after the path (left-aligned) or before
+//! the cursor position (right-aligned); clicking one with an action sends
+//! it to the plugin's `on_menu_action`.
 //! Updated on every event loop iteration but short-circuits when nothing
 //! has changed (0% CPU when idle).
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use fltk::{
-    enums::{Align, Color, Font, FrameType},
+    app::Sender,
+    enums::{Align, Color, Event, Font, FrameType},
     frame::Frame,
     prelude::*,
     text::TextEditor,
 };
 
 use super::dialogs::DialogTheme;
+use crate::app::Message;
+use crate::app::plugins::{StatusAlignment, StatusItem};
 
 /// Height of the status bar in pixels.
 pub const STATUS_BAR_HEIGHT: i32 = 20;
@@ -20,6 +29,21 @@ pub const STATUS_BAR_HEIGHT: i32 = 20;
 /// Approximate character width for Courier at size 12 (pixels).
 const CHAR_WIDTH: i32 = 7;
 
+/// Spacing between status bar segments.
+const SEGMENT_SEPARATOR: &str = "   ";
+
+/// Column range of a plugin item within the label, for click/hover hit testing.
+#[derive(Debug, Clone, PartialEq)]
+struct HitRegion {
+    /// First character column (inclusive)
+    start: usize,
+    /// Last character column (exclusive)
+    end: usize,
+    plugin_name: String,
+    action: Option<String>,
+    tooltip: Option<String>,
+}
+
 pub struct StatusBar {
     frame: Frame,
     last_label: String,
@@ -35,16 +59,16 @@ pub struct StatusBar {
     pos_text: String,
     /// Cached display path (left side, already relative + truncated).
     path_text: String,
-}
-
-impl Default for StatusBar {
-    fn default() -> Self {
-        Self::new()
-    }
+    /// Plugin-contributed items as (plugin name, item), in arrival order.
+    plugin_items: Vec<(String, StatusItem)>,
+    /// Plugin items changed since the label was last composed.
+    items_dirty: bool,
+    /// Where each plugin item landed in the current label (shared with the event handler).
+    hit_regions: Rc<RefCell<Vec<HitRegion>>>,
 }
 
 impl StatusBar {
-    pub fn new() -> Self {
+    pub fn new(sender: Sender<Message>) -> Self {
         let mut frame = Frame::default().with_size(0, STATUS_BAR_HEIGHT);
         frame.set_frame(FrameType::FlatBox);
         frame.set_color(Color::from_rgb(45, 45, 45));
@@ -53,6 +77,51 @@ impl StatusBar {
         frame.set_label_size(12);
         frame.set_align(Align::Left | Align::Inside);
 
+        let hit_regions: Rc<RefCell<Vec<HitRegion>>> = Rc::new(RefCell::new(Vec::new()));
+        let regions = hit_regions.clone();
+        frame.handle(move |f, ev| {
+            let column = || ((fltk::app::event_x() - f.x()).max(0) / CHAR_WIDTH) as usize;
+            match ev {
+                // Accept Enter so FLTK keeps sending Move events for tooltips
+                Event::Enter | Event::Move => {
+                    let col = column();
+                    let tooltip = regions
+                        .borrow()
+                        .iter()
+                        .find(|r| (r.start..r.end).contains(&col))
+                        .and_then(|r| r.tooltip.clone())
+                        .unwrap_or_default();
+                    if f.tooltip().unwrap_or_default() != tooltip {
+                        f.set_tooltip(&tooltip);
+                    }
+                    true
+                }
+                Event::Leave => {
+                    f.set_tooltip("");
+                    true
+                }
+                Event::Push if fltk::app::event_button() == 1 => {
+                    let col = column();
+                    let hit = regions
+                        .borrow()
+                        .iter()
+                        .find(|r| (r.start..r.end).contains(&col))
+                        .and_then(|r| Some((r.plugin_name.clone(), r.action.clone()?)));
+                    match hit {
+                        Some((plugin_name, action)) => {
+                            sender.send(Message::PluginMenuAction {
+                                plugin_name,
+                                action,
+                            });
+                            true
+                        }
+                        None => false,
+                    }
+                }
+                _ => false,
+            }
+        });
+
         Self {
             frame,
             last_label: String::new(),
@@ -62,6 +131,29 @@ impl StatusBar {
             last_width: 0,
             pos_text: String::new(),
             path_text: String::new(),
+            plugin_items: Vec::new(),
+            items_dirty: false,
+            hit_regions,
+        }
+    }
+
+    /// Replace all items contributed by a plugin (an empty list removes them).
+    pub fn set_plugin_items(&mut self, plugin_name: &str, items: Vec<StatusItem>) {
+        self.plugin_items.retain(|(name, _)| name != plugin_name);
+        self.plugin_items.extend(
+            items
+                .into_iter()
+                .map(|item| (plugin_name.to_string(), item)),
+        );
+        self.items_dirty = true;
+    }
+
+    /// Drop items from plugins that no longer pass `keep` (disabled or unloaded).
+    pub fn retain_plugins(&mut self, keep: impl Fn(&str) -> bool) {
+        let before = self.plugin_items.len();
+        self.plugin_items.retain(|(name, _)| keep(name));
+        if self.plugin_items.len() != before {
+            self.items_dirty = true;
         }
     }
 
@@ -86,7 +178,7 @@ impl StatusBar {
         let pos_changed = pos != self.last_pos || sel != self.last_selection;
         let width_changed = current_width != self.last_width;
 
-        if !path_changed && !pos_changed && !width_changed {
+        if !path_changed && !pos_changed && !width_changed && !self.items_dirty {
             return;
         }
 
@@ -137,53 +229,22 @@ impl StatusBar {
             };
         }
 
-        // --- Compose single label: "  path ...padding... Ln X, Col Y  " ---
-        if path_changed || pos_changed || width_changed {
-            self.last_width = current_width;
-
-            let total_chars = (current_width / CHAR_WIDTH).max(0) as usize;
-            let pos_len = self.pos_text.len();
-            let margin = 6; // 2 chars left + 4 chars right padding
-            let available_for_path = total_chars.saturating_sub(pos_len + margin);
-
-            // Truncate path from left if needed
-            let display_path = if self.path_text.is_empty() {
-                String::new()
-            } else if self.path_text.len() <= available_for_path {
-                self.path_text.clone()
-            } else if available_for_path > 5 {
-                // Find a `/` boundary to truncate at
-                let skip = self.path_text.len() - (available_for_path - 2); // 2 for "…/"
-                if let Some(slash_offset) = self.path_text[skip..].find('/') {
-                    format!("\u{2026}{}", &self.path_text[skip + slash_offset..])
-                } else {
-                    // No slash found — just hard-truncate
-                    format!("\u{2026}{}", &self.path_text[skip..])
-                }
-            } else {
-                String::new() // Too narrow to show anything useful
-            };
+        // --- Compose single label: "  path  items ...padding... items  Ln X, Col Y  " ---
+        self.last_width = current_width;
+        self.items_dirty = false;
 
-            let label = if display_path.is_empty() {
-                // Right-align with right padding
-                let inner = total_chars.saturating_sub(4);
-                format!("{:>width$}    ", self.pos_text, width = inner)
-            } else {
-                // Path on left, Ln/Col on right, fill with spaces
-                let gap = total_chars.saturating_sub(display_path.len() + pos_len + margin);
-                format!(
-                    "  {}{:gap$}{}    ",
-                    display_path,
-                    "",
-                    self.pos_text,
-                    gap = gap,
-                )
-            };
+        let total_chars = (current_width / CHAR_WIDTH).max(0) as usize;
+        let (label, regions) = compose_label(
+            total_chars,
+            &self.path_text,
+            &self.pos_text,
+            &self.plugin_items,
+        );
+        *self.hit_regions.borrow_mut() = regions;
 
-            if label != self.last_label {
-                self.frame.set_label(&label);
-                self.last_label = label;
-            }
+        if label != self.last_label {
+            self.frame.set_label(&label);
+            self.last_label = label;
         }
     }
 
@@ -194,3 +255,168 @@ impl StatusBar {
         self.frame.set_label_color(theme.text_dim);
     }
 }
+
+/// Truncate a path from the left (at a `/` boundary when possible) to fit `available` chars.
+fn truncate_path(path_text: &str, available: usize) -> String {
+    if path_text.is_empty() {
+        String::new()
+    } else if path_text.len() <= available {
+        path_text.to_string()
+    } else if available > 5 {
+        // Find a `/` boundary to truncate at
+        let mut skip = path_text.len() - (available - 2); // 2 for "…/"
+        while !path_text.is_char_boundary(skip) {
+            skip += 1;
+        }
+        if let Some(slash_offset) = path_text[skip..].find('/') {
+            format!("\u{2026}{}", &path_text[skip + slash_offset..])
+        } else {
+            // No slash found — just hard-truncate
+            format!("\u{2026}{}", &path_text[skip..])
+        }
+    } else {
+        String::new() // Too narrow to show anything useful
+    }
+}
+
+/// Lay out the path, plugin items and cursor position into one label of
+/// `total_chars` columns. Items are ordered by descending priority within
+/// each side; when space runs out, the lowest priority items are hidden first.
+fn compose_label(
+    total_chars: usize,
+    path_text: &str,
+    pos_text: &str,
+    items: &[(String, StatusItem)],
+) -> (String, Vec<HitRegion>) {
+    let margin = 6; // 2 chars left + 4 chars right padding
+    let pos_len = pos_text.chars().count();
+
+    // Keep the highest priority items that fit next to the cursor position
+    let mut by_priority: Vec<&(String, StatusItem)> = items.iter().collect();
+    by_priority.sort_by_key(|(_, item)| std::cmp::Reverse(item.priority));
+    let mut budget = total_chars.saturating_sub(pos_len + margin);
+    let mut shown = Vec::new();
+    for entry in by_priority {
+        let width = entry.1.text.chars().count() + SEGMENT_SEPARATOR.len();
+        if width <= budget {
+            budget -= width;
+            shown.push(entry);
+        }
+    }
+    let (left, right): (Vec<_>, Vec<_>) = shown
+        .into_iter()
+        .partition(|(_, item)| item.alignment == StatusAlignment::Left);
+
+    // Truncate path from left if needed
+    let display_path = truncate_path(path_text, budget);
+
+    let mut label = String::new();
+    let mut col = 0;
+    let mut regions = Vec::new();
+    let mut push = |label: &mut String, text: &str| {
+        label.push_str(text);
+        col += text.chars().count();
+        col
+    };
+    let region = |start: usize, end: usize, plugin_name: &str, item: &StatusItem| HitRegion {
+        start,
+        end,
+        plugin_name: plugin_name.to_string(),
+        action: item.action.clone(),
+        tooltip: item.tooltip.clone(),
+    };
+
+    // Left side: path, then left-aligned items
+    if !display_path.is_empty() || !left.is_empty() {
+        push(&mut label, "  ");
+        push(&mut label, &display_path);
+    }
+    for (i, (plugin_name, item)) in left.iter().enumerate() {
+        if i > 0 || !display_path.is_empty() {
+            push(&mut label, SEGMENT_SEPARATOR);
+        }
+        let start = push(&mut label, "");
+        let end = push(&mut label, &item.text);
+        regions.push(region(start, end, plugin_name, item));
+    }
+
+    // Right side: right-aligned items, then Ln/Col, filled with spaces
+    let right_len: usize = right
+        .iter()
+        .map(|(_, item)| item.text.chars().count() + SEGMENT_SEPARATOR.len())
+        .sum::<usize>()
+        + pos_len;
+    let current = push(&mut label, "");
+    let gap = total_chars.saturating_sub(current + right_len + 4);
+    push(&mut label, &" ".repeat(gap));
+    for (plugin_name, item) in &right {
+        let start = push(&mut label, "");
+        let end = push(&mut label, &item.text);
+        regions.push(region(start, end, plugin_name, item));
+        push(&mut label, SEGMENT_SEPARATOR);
+    }
+    push(&mut label, pos_text);
+    push(&mut label, "    ");
+
+    (label, regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, alignment: StatusAlignment, priority: i32) -> (String, StatusItem) {
+        (
+            "git".to_string(),
+            StatusItem {
+                id: id.to_string(),
+                text: id.to_string(),
+                tooltip: None,
+                alignment,
+                priority,
+                action: Some(format!("click_{}", id)),
+            },
+        )
+    }
+
+    #[test]
+    fn test_compose_label_without_items_matches_plain_layout() {
+        let (label, regions) = compose_label(40, "src/main.rs", "Ln 1, Col 1", &[]);
+        assert_eq!(label.chars().count(), 40);
+        assert!(label.starts_with("  src/main.rs "));
+        assert!(label.ends_with("Ln 1, Col 1    "));
+        assert!(regions.is_empty());
+    }
+
+    #[test]
+    fn test_compose_label_places_items_and_regions() {
+        let items = vec![
+            item("main", StatusAlignment::Left, 0),
+            item("3E", StatusAlignment::Right, 0),
+        ];
+        let (label, regions) = compose_label(60, "a.rs", "Ln 1, Col 1", &items);
+        assert_eq!(label.chars().count(), 60);
+        assert!(label.starts_with("  a.rs   main "));
+        assert!(label.ends_with("3E   Ln 1, Col 1    "));
+
+        assert_eq!(regions.len(), 2);
+        let chars: Vec<char> = label.chars().collect();
+        for r in &regions {
+            let text: String = chars[r.start..r.end].iter().collect();
+            assert_eq!(Some(format!("click_{}", text)), r.action);
+        }
+    }
+
+    #[test]
+    fn test_compose_label_drops_lowest_priority_when_narrow() {
+        let items = vec![
+            item("low", StatusAlignment::Left, -5),
+            item("high", StatusAlignment::Left, 10),
+        ];
+        // Room for "Ln 1, Col 1" + margin + one item only
+        let (label, regions) = compose_label(11 + 6 + 7, "", "Ln 1, Col 1", &items);
+        assert!(label.contains("high"));
+        assert!(!label.contains("low"));
+        assert_eq!(regions.len(), 1);
+    }
+}
@@ -16,6 +16,8 @@ pub const TAB_BAR_HEIGHT: i32 = 32;
 
 const MIN_TAB_WIDTH: i32 = 60;
 const MAX_TAB_WIDTH: i32 = 200;
+/// Pinned tabs are drawn compact, without a close button
+const PINNED_TAB_WIDTH: i32 = 80;
 const CLOSE_BTN_SIZE: i32 = 14;
 const CLOSE_BTN_MARGIN: i32 = 6;
 const TAB_H_PADDING: i32 = 10;
@@ -39,6 +41,9 @@ struct TabInfo {
     is_dirty: bool,
     is_active: bool,
     group_id: Option<GroupId>,
+    pinned: bool,
+    /// Whether the document is backed by a file (enables the path items)
+    has_path: bool,
 }
 
 #[derive(Clone)]
@@ -280,6 +285,8 @@ impl TabBar {
                 is_dirty: doc.is_dirty(),
                 is_active: !diff_tab_active && active_id == Some(doc.id),
                 group_id: doc.group_id,
+                pinned: doc.pinned,
+                has_path: doc.file_path.is_some(),
             });
         }
         for g in groups {
@@ -326,9 +333,14 @@ impl TabBar {
 
         let mut st = self.state.borrow_mut();
 
+        // Pinned tabs never scroll out of view
+        let mut i = pinned_count(&st);
+        if st.tabs[..i].iter().any(|t| t.id == active_id) {
+            return;
+        }
+
         // Find the scrollable item index for this tab
         let mut scrollable_idx = 0;
-        let mut i = 0;
         let mut current_group: Option<GroupId> = None;
 
         while i < st.tabs.len() {
@@ -494,6 +506,11 @@ struct ScrollableItem {
     group_label_width: i32,
 }
 
+/// Number of pinned tabs (TabManager keeps them at the front)
+fn pinned_count(st: &TabBarState) -> usize {
+    st.tabs.iter().take_while(|t| t.pinned).count()
+}
+
 fn compute_layout(st: &mut TabBarState) {
     st.layout.clear();
     let widget_w = st.widget_w;
@@ -507,10 +524,15 @@ fn compute_layout(st: &mut TabBarState) {
         return;
     }
 
+    // Pinned tabs come first and stay put at the left edge; everything
+    // after them scrolls.
+    let pinned = pinned_count(st);
+    let pinned_space = pinned as i32 * (PINNED_TAB_WIDTH + TAB_GAP);
+
     // First pass: build scrollable items (tabs + collapsed chips) with their group labels
     let mut scrollable_items: Vec<ScrollableItem> = Vec::new();
     let mut current_group: Option<GroupId> = None;
-    let mut i = 0;
+    let mut i = pinned;
 
     while i < st.tabs.len() {
         let tab_group = st.tabs[i].group_id;
@@ -595,6 +617,8 @@ fn compute_layout(st: &mut TabBarState) {
 
     // Calculate space needed for plus button
     let plus_btn_space = PLUS_BTN_WIDTH + PLUS_BTN_MARGIN * 2 + diff_tab_space;
+    // Everything that doesn't scroll
+    let fixed_space = plus_btn_space + pinned_space;
 
     // Check if we need scroll arrows
     // First, compute minimum width needed for all items at MIN_TAB_WIDTH
@@ -608,9 +632,9 @@ fn compute_layout(st: &mut TabBarState) {
             }
         })
         .sum::<i32>()
-        + plus_btn_space;
+        + fixed_space;
 
-    let needs_scroll = min_total_width > widget_w;
+    let needs_scroll = min_total_width > widget_w && !scrollable_items.is_empty();
 
     // Reserve space for arrows if needed
     // Left arrow: margin + width + margin (gap to tabs)
@@ -630,7 +654,7 @@ fn compute_layout(st: &mut TabBarState) {
     }
 
     // Calculate available width for tabs (excluding arrows and plus button)
-    let available_for_tabs = widget_w - arrow_space - plus_btn_space;
+    let available_for_tabs = widget_w - arrow_space - fixed_space;
 
     // Compute tab width and visible count
     let (tab_width, visible_count) = if !needs_scroll {
@@ -703,18 +727,26 @@ fn compute_layout(st: &mut TabBarState) {
 
     st.visible_items = visible_count;
 
-    // Build final layout
+    // Build final layout, starting with the pinned tabs
+    for index in 0..pinned {
+        st.layout.push(LayoutItem::Tab {
+            index,
+            x: index as i32 * (PINNED_TAB_WIDTH + TAB_GAP),
+            width: PINNED_TAB_WIDTH,
+        });
+    }
+
     // When scrolling: left margin + arrow width + right margin (gap to first tab)
     let mut cursor_x = if needs_scroll {
-        SCROLL_ARROW_MARGIN + SCROLL_ARROW_WIDTH + SCROLL_ARROW_MARGIN
+        pinned_space + SCROLL_ARROW_MARGIN + SCROLL_ARROW_WIDTH + SCROLL_ARROW_MARGIN
     } else {
-        0
+        pinned_space
     };
 
     // Add scroll left arrow if needed
     if needs_scroll {
         st.layout.push(LayoutItem::ScrollLeft {
-            x: SCROLL_ARROW_MARGIN,
+            x: pinned_space + SCROLL_ARROW_MARGIN,
             enabled: st.scroll_offset > 0,
         });
     }
@@ -1052,8 +1084,11 @@ fn draw_tab_bar(wid: &Widget, st: &TabBarState) {
                     tab.display_name.clone()
                 };
 
-                let text_area_width =
-                    tab_width - TAB_H_PADDING - CLOSE_BTN_MARGIN - CLOSE_BTN_SIZE - TAB_H_PADDING;
+                let text_area_width = if tab.pinned {
+                    tab_width - TAB_H_PADDING * 2
+                } else {
+                    tab_width - TAB_H_PADDING - CLOSE_BTN_MARGIN - CLOSE_BTN_SIZE - TAB_H_PADDING
+                };
                 let display_text = truncate_to_fit(&label, text_area_width);
 
                 draw::set_draw_color(text_color);
@@ -1062,6 +1097,10 @@ fn draw_tab_bar(wid: &Widget, st: &TabBarState) {
                 let text_y = wy + (wh + 12) / 2;
                 draw::draw_text(&display_text, text_x, text_y);
 
+                if tab.pinned {
+                    continue;
+                }
+
                 // Close button
                 let close_x = tx + tab_width - CLOSE_BTN_MARGIN - CLOSE_BTN_SIZE;
                 let close_y = wy + (wh - CLOSE_BTN_SIZE) / 2;
@@ -1525,6 +1564,56 @@ fn build_context_menu(
     if let Some(idx) = tab_index {
         let tab = &st.tabs[idx];
         let tab_id = tab.id;
+        let divider = MenuFlag::MenuDivider;
+        let path_flag = if tab.has_path { fl } else { MenuFlag::Inactive };
+
+        // The only close path for pinned tabs, which have no close button
+        menu.add_emit("Close", sc, fl, sender, Message::TabClose(tab_id));
+        menu.add_emit(
+            "Close Others",
+            sc,
+            fl,
+            sender,
+            Message::TabCloseOthers(tab_id),
+        );
+        menu.add_emit(
+            "Close to the Right",
+            sc,
+            fl,
+            sender,
+            Message::TabCloseToRight(tab_id),
+        );
+        menu.add_emit("Close Saved", sc, fl, sender, Message::TabCloseSaved);
+        menu.add_emit("Close All", sc, divider, sender, Message::TabCloseAll);
+        menu.add_emit(
+            "Copy Path",
+            sc,
+            path_flag,
+            sender,
+            Message::TabCopyPath(tab_id),
+        );
+        menu.add_emit(
+            "Copy Relative Path",
+            sc,
+            path_flag,
+            sender,
+            Message::TabCopyRelativePath(tab_id),
+        );
+        menu.add_emit(
+            "Reveal in File Tree",
+            sc,
+            path_flag | divider,
+            sender,
+            Message::TabRevealInTree(tab_id),
+        );
+        let pin_label = if tab.pinned { "Unpin Tab" } else { "Pin Tab" };
+        menu.add_emit(
+            pin_label,
+            sc,
+            divider,
+            sender,
+            Message::TabTogglePin(tab_id),
+        );
 
         if let Some(gid) = tab.group_id {
             menu.add_emit(
@@ -1543,7 +1632,8 @@ fn build_context_menu(
 
             menu.add_emit("Ungroup all", sc, fl, sender, Message::TabGroupDelete(gid));
             menu.add_emit("Close group", sc, fl, sender, Message::TabGroupClose(gid));
-        } else {
+        } else if !tab.pinned {
+            // Pinned tabs stay out of groups
             menu.add_emit(
                 "Add to new group",
                 sc,
@@ -1567,6 +1657,13 @@ fn build_context_menu(
                 );
             }
         }
+        menu.add_emit(
+            "Move to other pane",
+            sc,
+            fl,
+            sender,
+            Message::MoveTabToOtherPane(tab_id),
+        );
     } else if let Some(gid) = group_id {
         menu.add_emit("Rename group", sc, fl, sender, Message::TabGroupRename(gid));
 
@@ -1604,6 +1701,9 @@ fn handle_tab_bar(wid: &mut Widget, event: Event, state: &Rc<RefCell<TabBarState
                 HitResult::Tab { index, is_close } => {
                     let tab_id = st.tabs[index].id;
                     let sender = st.sender;
+                    // Pinned tabs have no close button and ignore middle-click
+                    let pinned = st.tabs[index].pinned;
+                    let is_close = is_close && !pinned;
 
                     if button == 3 {
                         // Right-click context menu — build menu while borrowed,
@@ -1619,7 +1719,9 @@ fn handle_tab_bar(wid: &mut Widget, event: Event, state: &Rc<RefCell<TabBarState
 
                     if button == 2 {
                         drop(st);
-                        sender.send(Message::TabClose(tab_id));
+                        if !pinned {
+                            sender.send(Message::TabClose(tab_id));
+                        }
                     } else if button == 1 {
                         if is_close {
                             drop(st);
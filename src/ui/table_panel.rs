@@ -0,0 +1,739 @@
+//! Table panel UI for displaying sortable, filterable multi-column data.
+//!
+//! Used for test results, TODO lists, query results and audits.
+//! Plugin-driven via the Widget API; styled to match the tree panel.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use fltk::{
+    app::Sender,
+    browser::HoldBrowser,
+    button::Button,
+    enums::{Align, Color, Event, Font, FrameType, Key, Shortcut},
+    frame::Frame,
+    group::Flex,
+    input::Input,
+    menu::{MenuButton, MenuFlag},
+    prelude::*,
+};
+
+use super::dialogs::{DialogTheme, SCROLLBAR_SIZE, darken, lighten};
+use crate::app::Message;
+use crate::app::plugins::widgets::{
+    ContextMenuItem, ContextMenuTarget, TableRow, TableSort, TableViewRequest, TreeClickMode,
+    column_widths, visible_rows,
+};
+
+/// Height of the table panel header (matches the tree panel header)
+const HEADER_HEIGHT: i32 = 32;
+
+/// Height of the search bar row
+const SEARCH_HEIGHT: i32 = 24;
+
+/// Height of the column header row
+const COLUMNS_HEIGHT: i32 = 22;
+
+/// Default height of the row list
+const DEFAULT_HEIGHT: i32 = 180;
+
+/// Narrowest a flexible column is allowed to get
+const MIN_COLUMN_WIDTH: i32 = 40;
+
+/// Placeholder text for the filter input (magnifying glass + hint)
+const SEARCH_PLACEHOLDER: &str = "\u{1F50D} Filter...";
+
+/// Rows, filter and sort shared between the panel and its event handlers
+#[derive(Default)]
+struct TableState {
+    request: TableViewRequest,
+    filter: String,
+    sort: Option<TableSort>,
+    /// Row indices in display order (browser line N = `visible[N - 1]`)
+    visible: Vec<usize>,
+}
+
+impl TableState {
+    /// Row shown on a 1-based browser line
+    fn row_at_line(&self, line: i32) -> Option<&TableRow> {
+        let idx = usize::try_from(line).ok()?.checked_sub(1)?;
+        self.visible
+            .get(idx)
+            .and_then(|&i| self.request.rows.get(i))
+    }
+}
+
+/// Table panel widget for showing plugin-provided rows
+pub struct TablePanel {
+    /// The outer container (Flex column)
+    pub container: Flex,
+    /// Header frame showing title and row count
+    header: Frame,
+    /// Close button in header
+    close_btn: Button,
+    /// Search row containing the filter input
+    search_row: Flex,
+    /// Filter input below header
+    search_input: Input,
+    /// Row of clickable column titles (sort toggles)
+    columns_row: Flex,
+    /// Column title buttons, rebuilt per request
+    column_btns: Vec<Button>,
+    /// Row list
+    browser: HoldBrowser,
+    /// Reusable context menu widget (created once, parented to container)
+    ctx_menu: MenuButton,
+    /// Message sender
+    sender: Sender<Message>,
+    /// Current session ID
+    session_id: Option<u32>,
+    /// Plugin that owns the current table (filter/sort survive its refreshes)
+    plugin_name: Option<String>,
+    /// Whether panel is currently visible
+    visible: bool,
+    /// Whether the current theme is dark (for resolving semantic row colors)
+    is_dark: bool,
+    /// Row text color when a row has no semantic color
+    /// (HoldBrowser has no text color setter, so it's applied per line)
+    item_fg: Color,
+    /// Request data, filter and sort
+    state: Rc<RefCell<TableState>>,
+}
+
+impl TablePanel {
+    /// Create a new table panel
+    pub fn new(sender: Sender<Message>) -> Self {
+        let mut container = Flex::default().column();
+        container.set_frame(FrameType::FlatBox);
+        container.set_margin(0);
+        container.set_pad(0);
+
+        // Header bar with title and close button
+        let mut header_row = Flex::default().row();
+        header_row.set_frame(FrameType::FlatBox);
+        header_row.set_margin(0);
+        header_row.set_pad(0);
+        header_row.set_color(Color::from_rgb(60, 60, 60));
+
+        let mut header = Frame::default();
+        header.set_frame(FrameType::FlatBox);
+        header.set_color(Color::from_rgb(60, 60, 60));
+        header.set_label_color(Color::White);
+        header.set_label_font(Font::HelveticaBold);
+        header.set_label_size(12);
+        header.set_align(Align::Left | Align::Inside);
+        header.set_label("  Table View");
+
+        let mut close_btn = Button::default().with_size(24, 24).with_label("X");
+        close_btn.set_frame(FrameType::FlatBox);
+        close_btn.set_color(Color::from_rgb(60, 60, 60));
+        close_btn.set_label_color(Color::from_rgb(180, 180, 180));
+        close_btn.set_label_size(11);
+
+        header_row.fixed(&close_btn, 24);
+        header_row.end();
+        container.fixed(&header_row, HEADER_HEIGHT);
+
+        // Filter row
+        let mut search_row = Flex::default().row();
+        search_row.set_frame(FrameType::FlatBox);
+        search_row.set_margin(0);
+        search_row.set_pad(0);
+        search_row.set_color(Color::from_rgb(50, 50, 50));
+
+        let mut search_input = Input::default();
+        search_input.set_frame(FrameType::FlatBox);
+        search_input.set_color(Color::from_rgb(50, 50, 50));
+        search_input.set_text_color(Color::from_rgb(160, 160, 160));
+        search_input.set_text_size(13);
+        search_input.set_value(SEARCH_PLACEHOLDER);
+        search_input.set_tooltip("Filter rows...");
+
+        search_row.end();
+        container.fixed(&search_row, SEARCH_HEIGHT);
+
+        // Column titles (buttons added per request)
+        let mut columns_row = Flex::default().row();
+        columns_row.set_frame(FrameType::FlatBox);
+        columns_row.set_margin(0);
+        columns_row.set_pad(0);
+        columns_row.end();
+        container.fixed(&columns_row, COLUMNS_HEIGHT);
+
+        // Row list — columns are tab-separated
+        let mut browser = HoldBrowser::default();
+        browser.set_frame(FrameType::FlatBox);
+        browser.set_color(Color::from_rgb(40, 40, 40));
+        browser.set_selection_color(Color::from_rgb(70, 100, 130));
+        browser.set_text_size(12);
+        browser.set_column_char('\t');
+
+        // Reusable context menu — parented to container so Wayland can
+        // anchor the popup to our window via xdg_positioner.
+        let mut ctx_menu = MenuButton::new(1, 1, 1, 1, None);
+        ctx_menu.hide();
+
+        container.end();
+        container.hide();
+
+        // Filter input callback — placeholder text + filter on each key press
+        let active_text_color = Color::from_rgb(220, 220, 220);
+        let dim_color = Color::from_rgb(160, 160, 160);
+        search_input.handle(move |input, ev| match ev {
+            Event::Focus => {
+                if input.value() == SEARCH_PLACEHOLDER {
+                    input.set_value("");
+                    input.set_text_color(active_text_color);
+                }
+                false
+            }
+            Event::Unfocus => {
+                if input.value().is_empty() {
+                    input.set_value(SEARCH_PLACEHOLDER);
+                    input.set_text_color(dim_color);
+                }
+                false
+            }
+            Event::KeyUp => {
+                let query = input.value();
+                if query != SEARCH_PLACEHOLDER {
+                    sender.send(Message::TableViewFilter { query });
+                }
+                false
+            }
+            _ => false,
+        });
+
+        Self {
+            container,
+            header,
+            close_btn,
+            search_row,
+            search_input,
+            columns_row,
+            column_btns: Vec::new(),
+            browser,
+            ctx_menu,
+            sender,
+            session_id: None,
+            plugin_name: None,
+            visible: false,
+            is_dark: true,
+            item_fg: Color::from_rgb(220, 220, 220),
+            state: Rc::new(RefCell::new(TableState::default())),
+        }
+    }
+
+    /// Get a reference to the container widget for layout
+    pub fn widget(&self) -> &Flex {
+        &self.container
+    }
+
+    /// Show the table panel with content from a plugin request.
+    /// A refresh from the same plugin keeps the user's filter and sort.
+    pub fn show_request(&mut self, session_id: u32, plugin_name: &str, request: &TableViewRequest) {
+        let is_refresh = self.visible && self.plugin_name.as_deref() == Some(plugin_name);
+        {
+            let mut state = self.state.borrow_mut();
+            let same_columns = state.request.columns == request.columns;
+            state.request = request.clone();
+            if !is_refresh || !same_columns {
+                state.filter = request.filter.clone().unwrap_or_default();
+                state.sort = request.sort;
+            }
+        }
+
+        self.session_id = Some(session_id);
+        self.plugin_name = Some(plugin_name.to_string());
+
+        let filter = self.state.borrow().filter.clone();
+        if filter.is_empty() {
+            self.search_input.set_value(SEARCH_PLACEHOLDER);
+        } else {
+            self.search_input.set_value(&filter);
+        }
+
+        self.rebuild_column_buttons();
+        self.setup_callbacks(session_id);
+        self.refresh_rows();
+
+        self.container.show();
+        self.visible = true;
+        self.container.redraw();
+    }
+
+    /// Recreate the column title buttons for the current columns
+    fn rebuild_column_buttons(&mut self) {
+        self.columns_row.clear();
+        self.column_btns.clear();
+
+        let columns = self.state.borrow().request.columns.clone();
+        self.columns_row.begin();
+        for (i, _) in columns.iter().enumerate() {
+            let mut btn = Button::default();
+            btn.set_frame(FrameType::FlatBox);
+            btn.set_label_size(12);
+            btn.set_label_font(Font::HelveticaBold);
+            btn.set_align(Align::Left | Align::Inside | Align::Clip);
+            btn.set_tooltip("Sort");
+            let sender = self.sender;
+            btn.set_callback(move |_| sender.send(Message::TableViewSort(i)));
+            self.column_btns.push(btn);
+        }
+        self.columns_row.end();
+        self.apply_column_colors();
+    }
+
+    /// Label each column title with a sort arrow on the sorted column
+    fn update_column_labels(&mut self) {
+        let state = self.state.borrow();
+        for (i, (btn, col)) in self
+            .column_btns
+            .iter_mut()
+            .zip(state.request.columns.iter())
+            .enumerate()
+        {
+            let arrow = match state.sort {
+                Some(s) if s.column == i && s.ascending => " \u{25B2}",
+                Some(s) if s.column == i => " \u{25BC}",
+                _ => "",
+            };
+            // '@' starts an FLTK label symbol; double it to print literally
+            btn.set_label(&format!(" {}{}", col.title.replace('@', "@@"), arrow));
+        }
+    }
+
+    /// Toggle sorting on a column: ascending first, then descending
+    pub fn toggle_sort(&mut self, column: usize) {
+        {
+            let mut state = self.state.borrow_mut();
+            if column >= state.request.columns.len() {
+                return;
+            }
+            state.sort = match state.sort {
+                Some(s) if s.column == column => Some(TableSort {
+                    column,
+                    ascending: !s.ascending,
+                }),
+                _ => Some(TableSort {
+                    column,
+                    ascending: true,
+                }),
+            };
+        }
+        self.refresh_rows();
+    }
+
+    /// Filter rows to those with a cell containing `query` (case-insensitive)
+    pub fn apply_filter(&mut self, query: &str) {
+        self.state.borrow_mut().filter = query.to_string();
+        self.refresh_rows();
+    }
+
+    /// Resolve a semantic row color name to an FLTK Color, theme-aware.
+    fn resolve_row_color(is_dark: bool, name: &str) -> Option<Color> {
+        let (r, g, b) = if is_dark {
+            match name {
+                "error" | "failed" => (224, 108, 117),
+                "warning" | "modified" => (229, 192, 123),
+                "success" | "passed" | "added" => (152, 195, 121),
+                "info" => (97, 175, 239),
+                "muted" | "ignored" | "skipped" => (120, 120, 120),
+                _ => return None,
+            }
+        } else {
+            match name {
+                "error" | "failed" => (190, 50, 50),
+                "warning" | "modified" => (190, 140, 40),
+                "success" | "passed" | "added" => (60, 140, 50),
+                "info" => (30, 100, 190),
+                "muted" | "ignored" | "skipped" => (160, 160, 160),
+                _ => return None,
+            }
+        };
+        Some(Color::from_rgb(r, g, b))
+    }
+
+    /// Browser line for a row: one `@.`-escaped column per cell, with an
+    /// optional color prefix so plugin text is never read as format codes.
+    fn format_row(row: &TableRow, columns: usize, color: Option<Color>) -> String {
+        let prefix = color.map(|c| format!("@C{}", c.bits())).unwrap_or_default();
+        (0..columns)
+            .map(|i| format!("{}@.{}", prefix, row.cell(i).display()))
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+
+    /// Re-run filter and sort, then repopulate the browser
+    fn refresh_rows(&mut self) {
+        let selected_id = self.selected_row_id();
+        let (is_dark, item_fg) = (self.is_dark, self.item_fg);
+        let mut state = self.state.borrow_mut();
+        state.visible = visible_rows(&state.request.rows, &state.filter, state.sort);
+
+        self.browser.clear();
+        let columns = state.request.columns.len();
+        let mut reselect = 0;
+        for (line, &i) in state.visible.iter().enumerate() {
+            let row = &state.request.rows[i];
+            let color = row
+                .color
+                .as_deref()
+                .and_then(|c| Self::resolve_row_color(is_dark, c))
+                .unwrap_or(item_fg);
+            self.browser
+                .add(&Self::format_row(row, columns, Some(color)));
+            if selected_id.as_deref() == Some(row.id.as_str()) {
+                reselect = line as i32 + 1;
+            }
+        }
+        if reselect > 0 {
+            self.browser.select(reselect);
+        }
+
+        let total = state.request.rows.len();
+        let shown = state.visible.len();
+        let title = if state.request.title.is_empty() {
+            "Table View"
+        } else {
+            state.request.title.as_str()
+        };
+        let count = if shown == total {
+            format!("{}", total)
+        } else {
+            format!("{} of {}", shown, total)
+        };
+        self.header
+            .set_label(&format!("  {}  ({})", title.replace('@', "@@"), count));
+        drop(state);
+
+        self.update_column_labels();
+        self.container.redraw();
+    }
+
+    /// Id of the currently selected row
+    fn selected_row_id(&self) -> Option<String> {
+        let state = self.state.borrow();
+        state
+            .row_at_line(self.browser.value())
+            .map(|r| r.id.clone())
+    }
+
+    /// Recompute column widths for the current panel width.
+    /// Call after the panel is laid out or resized.
+    pub fn handle_resize(&mut self) {
+        if !self.visible {
+            return;
+        }
+        let state = self.state.borrow();
+        if state.request.columns.is_empty() {
+            return;
+        }
+        let available = (self.browser.w() - SCROLLBAR_SIZE).max(MIN_COLUMN_WIDTH);
+        let widths = column_widths(&state.request.columns, available, MIN_COLUMN_WIDTH);
+        drop(state);
+
+        self.browser.set_column_widths(&widths);
+        for (btn, w) in self.column_btns.iter().zip(widths.iter()) {
+            self.columns_row.fixed(btn, *w);
+        }
+        self.columns_row.layout();
+        self.container.redraw();
+    }
+
+    /// Set up click, Enter and context menu handlers for the current session
+    fn setup_callbacks(&mut self, session_id: u32) {
+        let sender = self.sender;
+        let state = self.state.clone();
+        let ctx_menu_ptr = self.ctx_menu.as_widget_ptr();
+
+        self.browser.handle(move |b, ev| {
+            let (has_click_action, double_click) = {
+                let s = state.borrow();
+                (
+                    s.request.on_click_action.is_some(),
+                    s.request.click_mode == TreeClickMode::DoubleClick,
+                )
+            };
+            let send_click = |line: i32| {
+                if let Some(row) = state.borrow().row_at_line(line) {
+                    sender.send(Message::TableViewRowClicked {
+                        session_id,
+                        row_id: row.id.clone(),
+                    });
+                }
+            };
+
+            match ev {
+                // Right-click: clear the selection so FLTK selects the row under
+                // the mouse (or nothing on empty space), then open the menu on release
+                Event::Push if fltk::app::event_button() == 3 => {
+                    let current = b.value();
+                    if current > 0 {
+                        b.deselect(current);
+                    }
+                    false
+                }
+                Event::Released if fltk::app::event_button() == 3 => {
+                    let row = state.borrow().row_at_line(b.value()).cloned();
+                    let menu = state.borrow().request.context_menu.clone();
+                    Self::show_context_menu(session_id, sender, row.as_ref(), &menu, ctx_menu_ptr);
+                    true
+                }
+                Event::Push if double_click && fltk::app::event_clicks() => {
+                    if has_click_action {
+                        send_click(b.value());
+                    }
+                    false
+                }
+                Event::Released if !double_click && fltk::app::event_button() == 1 => {
+                    if has_click_action {
+                        send_click(b.value());
+                    }
+                    false
+                }
+                Event::KeyDown if fltk::app::event_key() == Key::Enter => {
+                    if has_click_action && b.value() > 0 {
+                        send_click(b.value());
+                        return true;
+                    }
+                    false
+                }
+                _ => false,
+            }
+        });
+
+        let close_sender = self.sender;
+        self.close_btn.set_callback(move |_| {
+            close_sender.send(Message::TableViewHide(session_id));
+        });
+    }
+
+    /// Show the right-click context menu built from plugin-defined items.
+    /// Row items appear on rows; empty-area items when no row is under the mouse.
+    fn show_context_menu(
+        session_id: u32,
+        sender: Sender<Message>,
+        row: Option<&TableRow>,
+        context_menu: &[ContextMenuItem],
+        ctx_menu_ptr: fltk::app::WidgetPtr,
+    ) {
+        let click_target = if row.is_some() {
+            ContextMenuTarget::Row
+        } else {
+            ContextMenuTarget::Empty
+        };
+        let visible_items: Vec<&ContextMenuItem> = context_menu
+            .iter()
+            .filter(|item| item.target == click_target || item.target == ContextMenuTarget::All)
+            .filter(|item| !item.clipboard || row.is_some())
+            .collect();
+        if visible_items.is_empty() {
+            return;
+        }
+
+        // SAFETY: ctx_menu_ptr is a valid MenuButton widget pointer created in
+        // the constructor and stored for the lifetime of this panel.
+        let mut menu = unsafe { MenuButton::from_widget_ptr(ctx_menu_ptr) };
+        menu.clear();
+        menu.resize(fltk::app::event_x(), fltk::app::event_y(), 1, 1);
+        for item in &visible_items {
+            menu.add(&item.label, Shortcut::None, MenuFlag::Normal, |_| {});
+        }
+
+        let Some(chosen) = menu.popup() else {
+            return;
+        };
+        let chosen_label = chosen.label().unwrap_or_default();
+        let Some(item) = visible_items.iter().find(|d| d.label == chosen_label) else {
+            return;
+        };
+        let row_id = row.map(|r| r.id.clone());
+
+        if item.clipboard {
+            // Copy the row's path cell, or the whole row as tab-separated text
+            if let Some(row) = row {
+                let text = row
+                    .path()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| row.to_tsv());
+                crate::app::infrastructure::platform::copy_to_clipboard(&text);
+            }
+        } else if let Some(ref prompt) = item.input_prompt {
+            if let Some(input) = fltk::dialog::input_default(prompt, "") {
+                let input = input.trim().to_string();
+                if !input.is_empty() {
+                    sender.send(Message::TableViewContextAction {
+                        session_id,
+                        action: item.action.clone(),
+                        row_id,
+                        input_text: Some(input),
+                    });
+                }
+            }
+        } else if let Some(ref prompt) = item.confirm_prompt {
+            let choice = fltk::dialog::choice2_default(prompt, "Cancel", "OK", "");
+            if choice == Some(1) {
+                sender.send(Message::TableViewContextAction {
+                    session_id,
+                    action: item.action.clone(),
+                    row_id,
+                    input_text: None,
+                });
+            }
+        } else {
+            sender.send(Message::TableViewContextAction {
+                session_id,
+                action: item.action.clone(),
+                row_id,
+                input_text: None,
+            });
+        }
+    }
+
+    /// Hide the table panel
+    pub fn hide(&mut self) {
+        self.container.hide();
+        self.visible = false;
+        self.session_id = None;
+        self.plugin_name = None;
+        self.browser.clear();
+        *self.state.borrow_mut() = TableState::default();
+        self.search_input.set_value(SEARCH_PLACEHOLDER);
+    }
+
+    /// Check if the panel is visible
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Session currently shown in the panel
+    pub fn session_id(&self) -> Option<u32> {
+        self.session_id
+    }
+
+    /// Get the current panel height for flex layout
+    pub fn current_height(&self) -> i32 {
+        if self.visible {
+            HEADER_HEIGHT + SEARCH_HEIGHT + COLUMNS_HEIGHT + DEFAULT_HEIGHT
+        } else {
+            0
+        }
+    }
+
+    /// Header colors for the column title buttons
+    fn apply_column_colors(&mut self) {
+        let bg = self.search_row.color();
+        let fg = self.header.label_color();
+        self.columns_row.set_color(bg);
+        for btn in &mut self.column_btns {
+            btn.set_color(bg);
+            btn.set_label_color(fg);
+        }
+    }
+
+    /// Apply theme colors, matching the tree panel
+    pub fn apply_theme(&mut self, is_dark: bool, theme_bg: (u8, u8, u8)) {
+        self.is_dark = is_dark;
+        let theme = DialogTheme::from_theme_bg(theme_bg);
+        let (r, g, b) = theme_bg;
+
+        // Row list background matches the editor background
+        self.browser.set_color(Color::from_rgb(r, g, b));
+        self.item_fg = theme.text;
+
+        self.header.set_color(theme.bg);
+        self.header.set_label_color(theme.text);
+        self.close_btn.set_color(theme.bg);
+        self.close_btn.set_label_color(theme.text_dim);
+        self.search_row.set_color(theme.bg);
+
+        let search_bg = if theme.is_dark() {
+            let (sr, sg, sb) = lighten(r, g, b, 0.05);
+            Color::from_rgb(sr, sg, sb)
+        } else {
+            let (sr, sg, sb) = darken(r, g, b, 0.95);
+            Color::from_rgb(sr, sg, sb)
+        };
+        self.search_input.set_color(search_bg);
+        if self.search_input.value() == SEARCH_PLACEHOLDER {
+            self.search_input.set_text_color(theme.text_dim);
+        } else {
+            self.search_input.set_text_color(theme.text);
+        }
+
+        let selection = if theme.is_dark() {
+            let (sr, sg, sb) = lighten(r, g, b, 0.10);
+            Color::from_rgb(sr, sg, sb)
+        } else {
+            let (sr, sg, sb) = darken(r, g, b, 0.90);
+            Color::from_rgb(sr, sg, sb)
+        };
+        self.browser.set_selection_color(selection);
+        self.apply_column_colors();
+
+        // Style context menu to match the main menu bar
+        let mc = super::theme::menu_colors_from_bg(theme_bg);
+        self.ctx_menu.set_frame(FrameType::FlatBox);
+        self.ctx_menu.set_down_frame(FrameType::FlatBox);
+        self.ctx_menu.set_text_size(fltk::app::font_size());
+        self.ctx_menu.set_color(mc.color);
+        self.ctx_menu.set_text_color(mc.text_color);
+        self.ctx_menu.set_selection_color(mc.selection_color);
+
+        // Style scrollbars to match the editor
+        self.browser.set_scrollbar_size(SCROLLBAR_SIZE);
+        let mut sb = self.browser.scrollbar();
+        sb.set_frame(FrameType::FlatBox);
+        sb.set_color(theme.scroll_track);
+        sb.set_slider_frame(FrameType::FlatBox);
+        sb.set_selection_color(theme.scroll_thumb);
+        let mut hsb = self.browser.hscrollbar();
+        hsb.set_frame(FrameType::FlatBox);
+        hsb.set_color(theme.scroll_track);
+        hsb.set_slider_frame(FrameType::FlatBox);
+        hsb.set_selection_color(theme.scroll_thumb);
+
+        // Re-resolve semantic row colors for the new theme
+        if self.visible {
+            self.refresh_rows();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::plugins::widgets::CellValue;
+
+    #[test]
+    fn test_format_row_escapes_cells() {
+        let row = TableRow {
+            id: "1".into(),
+            cells: vec![CellValue::Text("@bold".into()), CellValue::Number(4.0)],
+            color: None,
+        };
+        // Extra columns are padded with empty cells
+        assert_eq!(TablePanel::format_row(&row, 3, None), "@.@bold\t@.4\t@.");
+    }
+
+    #[test]
+    fn test_row_at_line_maps_through_visible_order() {
+        let mut state = TableState::default();
+        state.request.rows = vec![
+            TableRow {
+                id: "a".into(),
+                ..Default::default()
+            },
+            TableRow {
+                id: "b".into(),
+                ..Default::default()
+            },
+        ];
+        state.visible = vec![1, 0];
+        assert_eq!(state.row_at_line(1).map(|r| r.id.as_str()), Some("b"));
+        assert_eq!(state.row_at_line(2).map(|r| r.id.as_str()), Some("a"));
+        assert!(state.row_at_line(0).is_none());
+        assert!(state.row_at_line(3).is_none());
+    }
+}
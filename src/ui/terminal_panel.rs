@@ -1,30 +1,49 @@
 //! Terminal panel UI for embedded terminal emulation.
 //!
 //! Provides a PTY-backed terminal widget with VTE parsing.
-//! Lazy-loaded: zero PTY/grid/parser until first show().
-//! Plugin-driven via the Widget API.
-
+//! Lazy-loaded: zero PTY/grid/parser until the first tab is opened.
+//! Each tab owns its own PTY session, grid and scrollback. Tabs are opened
+//! either by plugins via the Widget API or as plain shells from View > Terminal.
+//! Output can be mouse-selected, searched (Ctrl+Shift+F), and Ctrl+clicked to
+//! follow URLs and `file:line` locations. Shell integration sequences (OSC)
+//! update the tab title and working directory and mark prompts, which
+//! Ctrl+Shift+Up/Down jump between.
+
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
+use std::time::Duration;
 
 use fltk::{
     app::Sender,
     button::Button,
-    enums::{Align, Color, Cursor, Event, Font, FrameType, Key},
+    enums::{Align, Color, Cursor, Event, Font, FrameType, Key, Shortcut},
     frame::Frame,
     group::Flex,
+    input::Input,
+    menu::{MenuButton, MenuFlag},
     prelude::*,
 };
 
 use super::dialogs::DialogTheme;
 use crate::app::Message;
 use crate::app::plugins::widgets::TerminalViewRequest;
-use crate::app::services::terminal::grid::{Cell, TerminalGrid};
-use crate::app::services::terminal::pty::PtySession;
+use crate::app::services::terminal::grid::{Cell, CellWidth, TerminalGrid};
+use crate::app::services::terminal::input::{
+    MOUSE_MOD_ALT, MOUSE_MOD_CTRL, MouseMode, MouseReport, encode_mouse, encode_paste,
+};
+use crate::app::services::terminal::links::{LinkTarget, link_at, resolve_link_path};
+use crate::app::services::terminal::pty::{ArgvMode, PtySession, PtyWriter};
+use crate::app::services::terminal::selection::{
+    SearchMatch, Selection, find_matches, line_at, prompt_lines, selected_text, word_bounds,
+};
 
 /// Height of the terminal panel header
 const HEADER_HEIGHT: i32 = 32;
 
+/// Height of the search bar (shown with Ctrl+Shift+F)
+const SEARCH_HEIGHT: i32 = 28;
+
 /// Default width of the terminal panel (right-side position)
 const DEFAULT_WIDTH: i32 = 550;
 
@@ -37,11 +56,26 @@ const DEFAULT_TERM_FONT: Font = Font::Courier;
 /// Internal padding around the terminal content (px)
 const TERM_PAD: i32 = 4;
 
+/// Title given to shell tabs opened from View > Terminal or the "+" button
+const SHELL_TAB_TITLE: &str = "Shell";
+
+/// Min/max width of a tab button in the tab strip
+const TAB_MIN_WIDTH: i32 = 60;
+const TAB_MAX_WIDTH: i32 = 180;
+
+/// Most output kept from a task tab for problem matching (older output is dropped)
+const TASK_OUTPUT_LIMIT: usize = 8 * 1024 * 1024;
+
 /// Colors derived from the editor theme for terminal rendering.
 #[derive(Clone, Copy)]
 struct TerminalTheme {
     /// Terminal content background (slightly darker than editor)
     bg: Color,
+    /// Background of selected text
+    selection: Color,
+    /// Background of search matches, and of the current match
+    search_match: Color,
+    search_current: Color,
 }
 
 impl TerminalTheme {
@@ -65,7 +99,33 @@ impl TerminalTheme {
                 b.saturating_sub(15),
             )
         };
-        Self { bg }
+        let (selection, search_match, search_current) = if is_dark {
+            (
+                Color::from_rgb(60, 90, 140),
+                Color::from_rgb(110, 90, 30),
+                Color::from_rgb(200, 120, 40),
+            )
+        } else {
+            (
+                Color::from_rgb(173, 214, 255),
+                Color::from_rgb(255, 230, 120),
+                Color::from_rgb(255, 170, 60),
+            )
+        };
+        Self {
+            bg,
+            selection,
+            search_match,
+            search_current,
+        }
+    }
+
+    /// Blend a foreground halfway towards the background (SGR 2 dim/faint)
+    fn dimmed(fg: Color, bg: Color) -> Color {
+        let (fr, fg_g, fb) = fg.to_rgb();
+        let (br, bg_g, bb) = bg.to_rgb();
+        let mix = |a: u8, b: u8| ((a as u16 + b as u16) / 2) as u8;
+        Color::from_rgb(mix(fr, br), mix(fg_g, bg_g), mix(fb, bb))
     }
 
     /// Compute the effective fg color for a cell, applying contrast adjustment.
@@ -99,47 +159,256 @@ struct GridSnapshot {
     cursor_row: usize,
     cursor_col: usize,
     cursor_visible: bool,
-    /// Scrollback lines (oldest first) for scroll-up viewing
+    /// Scrollback lines (oldest first) for scroll-up viewing.
+    /// Left empty while the alternate screen is active.
     scrollback: Vec<Vec<Cell>>,
+    /// Input modes requested by the application
+    mouse_mode: MouseMode,
+    mouse_sgr: bool,
+    bracketed_paste: bool,
+    alt_screen: bool,
+}
+
+impl GridSnapshot {
+    /// Index of the first visible line in scrollback + screen for a scroll offset
+    fn first_visible(&self, offset: usize) -> usize {
+        (self.scrollback.len() + self.rows).saturating_sub(self.rows + offset)
+    }
+}
+
+/// Selection and search state of the active tab, shared by the draw
+/// callback, the input handler and the search bar
+#[derive(Default)]
+struct TerminalView {
+    selection: Option<Selection>,
+    /// Search matches, oldest first
+    matches: Vec<SearchMatch>,
+    current_match: Option<usize>,
+    /// Directory that relative `file:line` links are resolved against
+    cwd: Option<PathBuf>,
+}
+
+/// Search bar between the header and the canvas. Cheap to clone (widget
+/// handles and shared state), so the canvas and its own callbacks can drive it.
+#[derive(Clone)]
+struct TerminalSearch {
+    container: Flex,
+    row: Flex,
+    input: Input,
+    count: Frame,
+    canvas: Frame,
+    snapshot: Arc<Mutex<Option<GridSnapshot>>>,
+    view: Arc<Mutex<TerminalView>>,
+    scroll_offset: Arc<Mutex<usize>>,
+}
+
+impl TerminalSearch {
+    fn is_open(&self) -> bool {
+        self.row.visible()
+    }
+
+    /// Show the search bar and focus its input
+    fn open(&mut self) {
+        if !self.is_open() {
+            self.row.show();
+            self.container.layout();
+        }
+        let _ = self.input.take_focus();
+        self.run(true);
+    }
+
+    /// Hide the search bar and clear the match highlights
+    fn close(&mut self) {
+        self.row.hide();
+        self.container.layout();
+        if let Ok(mut v) = self.view.lock() {
+            v.matches.clear();
+            v.current_match = None;
+        }
+        let _ = self.canvas.take_focus();
+        self.canvas.redraw();
+    }
+
+    /// Re-run the query against the snapshot. With `jump`, select the newest
+    /// match and scroll to it; otherwise keep the current position.
+    fn run(&mut self, jump: bool) {
+        let query = self.input.value();
+        let matches = match self.snapshot.lock() {
+            Ok(snap) => snap
+                .as_ref()
+                .map(|g| find_matches(&g.scrollback, &g.cells, &query))
+                .unwrap_or_default(),
+            Err(_) => return,
+        };
+        if let Ok(mut v) = self.view.lock() {
+            v.current_match = if matches.is_empty() {
+                None
+            } else if jump {
+                Some(matches.len() - 1)
+            } else {
+                v.current_match.map(|i| i.min(matches.len() - 1))
+            };
+            v.matches = matches;
+        }
+        if jump {
+            self.reveal_current();
+        }
+        self.update_count();
+        self.canvas.redraw();
+    }
+
+    /// Move to the next (or previous) match, wrapping around
+    fn step(&mut self, forward: bool) {
+        if let Ok(mut v) = self.view.lock() {
+            let len = v.matches.len();
+            if len == 0 {
+                return;
+            }
+            v.current_match = Some(match (v.current_match, forward) {
+                (Some(i), true) => (i + 1) % len,
+                (Some(i), false) => (i + len - 1) % len,
+                (None, _) => len - 1,
+            });
+        }
+        self.reveal_current();
+        self.update_count();
+        self.canvas.redraw();
+    }
+
+    /// Scroll so the current match is on screen
+    fn reveal_current(&self) {
+        let line = match self.view.lock() {
+            Ok(v) => match v.current_match.and_then(|i| v.matches.get(i)) {
+                Some(m) => m.line,
+                None => return,
+            },
+            Err(_) => return,
+        };
+        let Ok(snap) = self.snapshot.lock() else {
+            return;
+        };
+        let Some(grid) = snap.as_ref() else {
+            return;
+        };
+        let Ok(mut off) = self.scroll_offset.lock() else {
+            return;
+        };
+        let first = grid.first_visible(*off);
+        if line < first || line >= first + grid.rows {
+            // Put the match a third of the way down the screen
+            let top = line
+                .saturating_sub(grid.rows / 3)
+                .min(grid.scrollback.len());
+            *off = grid.scrollback.len() - top;
+        }
+    }
+
+    fn update_count(&mut self) {
+        let label = match self.view.lock() {
+            Ok(v) if v.matches.is_empty() && !self.input.value().is_empty() => {
+                "No results".to_string()
+            }
+            Ok(v) => match v.current_match {
+                Some(i) => format!("{}/{}", i + 1, v.matches.len()),
+                None => String::new(),
+            },
+            Err(_) => return,
+        };
+        self.count.set_label(&label);
+    }
 }
 
 /// Terminal panel widget for embedded terminal emulation
 pub struct TerminalPanel {
     /// The outer container (Flex column)
     pub container: Flex,
-    /// Header frame showing title
-    header: Frame,
-    /// Close button in header
-    _close_btn: Button,
+    /// Header row holding the tab strip and buttons
+    header_row: Flex,
+    /// Tab strip (one button per terminal tab), rebuilt on every tab change
+    tab_strip: Flex,
+    /// "+" button that opens a new shell tab
+    new_btn: Button,
+    /// Close button in header (hides the panel, shells keep running)
+    close_btn: Button,
     /// Canvas frame for custom-drawn terminal surface
     canvas: Frame,
+    /// Search bar (hidden until Ctrl+Shift+F)
+    search: TerminalSearch,
+    /// Search bar buttons: previous, next, close
+    search_buttons: [Button; 3],
+    /// Reusable context menu for tab rename/close
+    ctx_menu: MenuButton,
     /// Message sender
     sender: Sender<Message>,
-    /// Current session ID
-    session_id: Option<u32>,
     /// Whether the panel is visible
     visible: bool,
     /// Optional divider frame for resizing
     pub divider: Option<Frame>,
-    /// Terminal state (lazy-loaded: None until first show)
-    state: Option<Box<TerminalState>>,
-    /// Shared output buffer — reader thread pushes, FLTK event loop drains
-    output_buf: Arc<Mutex<Vec<u8>>>,
+    /// Open terminal tabs, in display order
+    tabs: Vec<TerminalTab>,
+    /// ID of the active tab
+    active: Option<u32>,
+    /// Next tab ID to hand out
+    next_tab_id: u32,
     /// Whether the terminal is in dark mode
     is_dark: bool,
     /// Editor theme background (r, g, b) — used to derive terminal bg
     theme_bg: (u8, u8, u8),
-    /// Shared grid snapshot for the draw callback
+    /// Shared grid snapshot of the active tab for the draw callback
     snapshot: Arc<Mutex<Option<GridSnapshot>>>,
-    /// Scroll offset from bottom (0 = at bottom, shared with draw callback)
+    /// Scroll offset of the active tab from bottom (0 = at bottom, shared with draw callback)
     scroll_offset: Arc<Mutex<usize>>,
     /// Code font + size, shared with the draw callback so font changes
     /// (via `set_code_font`) are picked up on the next redraw without
     /// rebuilding the closure.
     font: Arc<Mutex<(Font, i32)>>,
+    /// Input writer of the active tab, shared with the canvas input handler
+    active_writer: Arc<Mutex<Option<PtyWriter>>>,
+    /// Selection and search highlights of the active tab
+    view: Arc<Mutex<TerminalView>>,
+    /// Latest OSC 52 clipboard write from any tab, for the caller to apply
+    clipboard_request: Option<String>,
+}
+
+/// A single terminal tab: one PTY session with its own grid and scrollback
+struct TerminalTab {
+    id: u32,
+    title: String,
+    /// Title set by the running program (OSC 0/2), shown instead of `title`
+    app_title: Option<String>,
+    /// Whether the user renamed the tab (program titles are then ignored)
+    renamed: bool,
+    /// Widget session that opened this tab (None for built-in shells)
+    session_id: Option<u32>,
+    /// Terminal state (None if the PTY failed to spawn)
+    state: Option<Box<TerminalState>>,
+    /// Shared output buffer — reader thread pushes, FLTK event loop drains
+    output_buf: Arc<Mutex<Vec<u8>>>,
+    /// Saved scroll offset while the tab is in the background
+    scroll_offset: usize,
+    /// Directory the process was started in, then as reported by the shell (OSC 7)
+    cwd: Option<PathBuf>,
+    /// Whether the child process has exited
+    exited: bool,
+    /// Task shown in this tab (task tabs are reused by name and stay open)
+    task_name: Option<String>,
+    /// Run ID of the task while it is running
+    task_run: Option<u32>,
+    /// Raw output of the running task, for problem matching
+    task_output: Vec<u8>,
+}
+
+impl TerminalTab {
+    /// Title shown in the tab strip
+    fn display_title(&self) -> &str {
+        match self.app_title {
+            Some(ref t) if !self.renamed => t,
+            _ => &self.title,
+        }
+    }
 }
 
-/// Internal terminal state, created lazily on first show
+/// Internal terminal state, created when a tab is opened
 struct TerminalState {
     grid: TerminalGrid,
     pty: PtySession,
@@ -148,62 +417,158 @@ struct TerminalState {
 }
 
 impl TerminalPanel {
-    /// Create a new terminal panel (container + header + canvas only, no PTY).
+    /// Create a new terminal panel (container + tab strip + canvas only, no PTY).
     pub fn new(sender: Sender<Message>) -> Self {
         let mut container = Flex::default().column();
         container.set_margin(0);
         container.set_pad(0);
 
-        // Header row
+        // Header row: [tab strip][+][x]
         let mut header_row = Flex::default().row();
         header_row.set_margin(0);
         header_row.set_pad(0);
 
-        let mut header = Frame::default();
-        header.set_frame(FrameType::FlatBox);
-        header.set_label("Terminal");
-        header.set_label_size(13);
-        header.set_align(Align::Left | Align::Inside);
+        let mut tab_strip = Flex::default().row();
+        tab_strip.set_margin(0);
+        tab_strip.set_pad(1);
+        tab_strip.set_frame(FrameType::FlatBox);
+        tab_strip.end();
+
+        let mut new_btn = Button::default().with_label("+");
+        new_btn.set_frame(FrameType::FlatBox);
+        new_btn.set_label_size(16);
+        new_btn.set_tooltip("New terminal tab");
+        header_row.fixed(&new_btn, 30);
 
         let mut close_btn = Button::default().with_label("\u{2715}");
         close_btn.set_frame(FrameType::FlatBox);
         close_btn.set_label_size(14);
+        close_btn.set_tooltip("Hide terminal");
         header_row.fixed(&close_btn, 30);
 
         header_row.end();
         container.fixed(&header_row, HEADER_HEIGHT);
 
+        // Search row: [query][count][prev][next][x], hidden until Ctrl+Shift+F
+        let mut search_row = Flex::default().row();
+        search_row.set_margin(2);
+        search_row.set_pad(2);
+        search_row.set_frame(FrameType::FlatBox);
+        let mut search_input = Input::default();
+        search_input.set_trigger(fltk::enums::CallbackTrigger::Changed);
+        search_input.set_tooltip("Search terminal output (Enter: next, Shift+Enter: previous)");
+        let mut search_count = Frame::default();
+        search_count.set_label_size(12);
+        search_row.fixed(&search_count, 80);
+        let mut search_buttons = ["\u{25B2}", "\u{25BC}", "\u{2715}"].map(|label| {
+            let mut btn = Button::default().with_label(label);
+            btn.set_frame(FrameType::FlatBox);
+            btn.set_label_size(12);
+            search_row.fixed(&btn, 26);
+            btn
+        });
+        search_buttons[0].set_tooltip("Previous match");
+        search_buttons[1].set_tooltip("Next match");
+        search_buttons[2].set_tooltip("Close search");
+        search_row.end();
+        search_row.hide();
+        container.fixed(&search_row, SEARCH_HEIGHT);
+
         // Canvas for terminal rendering
         let canvas = Frame::default();
 
+        // Reusable context menu — parented to container so Wayland can
+        // anchor the popup to our window via xdg_positioner.
+        let mut ctx_menu = MenuButton::new(1, 1, 1, 1, None);
+        ctx_menu.hide();
+
         container.end();
         container.hide();
 
-        // Close button callback
-        {
-            let s = sender;
-            close_btn.set_callback(move |_| {
-                s.send(Message::TerminalViewHide(0));
-            });
-        }
+        new_btn.set_callback(move |_| sender.send(Message::TerminalNewTab));
+        close_btn.set_callback(move |_| sender.send(Message::TerminalViewHide(0)));
+
+        let snapshot = Arc::new(Mutex::new(None));
+        let scroll_offset = Arc::new(Mutex::new(0));
+        let view = Arc::new(Mutex::new(TerminalView::default()));
+        let search = TerminalSearch {
+            container: container.clone(),
+            row: search_row,
+            input: search_input.clone(),
+            count: search_count,
+            canvas: canvas.clone(),
+            snapshot: Arc::clone(&snapshot),
+            view: Arc::clone(&view),
+            scroll_offset: Arc::clone(&scroll_offset),
+        };
+        Self::setup_search_callbacks(&search, &mut search_input, &mut search_buttons);
 
-        Self {
+        let mut panel = Self {
             container,
-            header,
-            _close_btn: close_btn,
+            header_row,
+            tab_strip,
+            new_btn,
+            close_btn,
             canvas,
+            search,
+            search_buttons,
+            ctx_menu,
             sender,
-            session_id: None,
             visible: false,
             divider: None,
-            state: None,
-            output_buf: Arc::new(Mutex::new(Vec::new())),
+            tabs: Vec::new(),
+            active: None,
+            next_tab_id: 1,
             is_dark: true,
             theme_bg: (40, 44, 52),
-            snapshot: Arc::new(Mutex::new(None)),
-            scroll_offset: Arc::new(Mutex::new(0)),
+            snapshot,
+            scroll_offset,
             font: Arc::new(Mutex::new((DEFAULT_TERM_FONT, DEFAULT_TERM_FONT_SIZE))),
-        }
+            active_writer: Arc::new(Mutex::new(None)),
+            view,
+            clipboard_request: None,
+        };
+        panel.setup_draw_callback();
+        panel.setup_input_handler();
+        panel
+    }
+
+    /// Wire the search bar: live search while typing, Enter/Shift+Enter to
+    /// step through matches, Escape to close.
+    fn setup_search_callbacks(
+        search: &TerminalSearch,
+        input: &mut Input,
+        buttons: &mut [Button; 3],
+    ) {
+        let mut s = search.clone();
+        input.set_callback(move |_| s.run(true));
+
+        let mut s = search.clone();
+        input.handle(move |_, ev| {
+            if ev != Event::KeyDown {
+                return false;
+            }
+            match fltk::app::event_key() {
+                Key::Enter | Key::KPEnter => {
+                    let back = fltk::app::event_state().contains(Shortcut::Shift);
+                    s.step(!back);
+                    true
+                }
+                Key::Escape => {
+                    s.close();
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        let [prev, next, close] = buttons;
+        let mut s = search.clone();
+        prev.set_callback(move |_| s.step(false));
+        let mut s = search.clone();
+        next.set_callback(move |_| s.step(true));
+        let mut s = search.clone();
+        close.set_callback(move |_| s.close());
     }
 
     /// Update the code font + size used to render terminal content.
@@ -242,23 +607,126 @@ impl TerminalPanel {
         div
     }
 
-    /// Show a terminal from a plugin request. Lazy-initializes PTY on first call.
+    /// Show a terminal from a plugin request.
+    ///
+    /// Each widget session gets its own tab. A repeated request for a live
+    /// session just activates its tab; an exited one is respawned in place.
     pub fn show_request(&mut self, session_id: u32, request: &TerminalViewRequest) {
-        self.session_id = Some(session_id);
+        let existing = self
+            .tabs
+            .iter()
+            .position(|t| t.session_id == Some(session_id));
+        let tab_id = match existing {
+            Some(idx) if !self.tabs[idx].exited && self.tabs[idx].state.is_some() => {
+                self.tabs[idx].title = request.title.clone();
+                self.tabs[idx].id
+            }
+            Some(idx) => {
+                let id = self.tabs[idx].id;
+                let (state, output_buf) = self.spawn_state(id, request);
+                let tab = &mut self.tabs[idx];
+                tab.title = request.title.clone();
+                tab.app_title = None;
+                tab.state = state;
+                tab.output_buf = output_buf;
+                tab.scroll_offset = 0;
+                tab.cwd = request.working_dir.as_ref().map(PathBuf::from);
+                tab.exited = false;
+                id
+            }
+            None => self.open_tab(request.title.clone(), Some(session_id), request),
+        };
+        self.activate(tab_id);
+        self.show();
+    }
 
-        // Update header
-        self.header.set_label(&request.title);
+    /// Run a task in its own tab, reusing the tab of an earlier run of the
+    /// same task. Returns false if the process could not be started.
+    pub fn run_task(&mut self, run_id: u32, request: &TerminalViewRequest) -> bool {
+        let existing = self
+            .tabs
+            .iter()
+            .position(|t| t.task_name.as_deref() == Some(request.title.as_str()));
+        let tab_id = match existing {
+            Some(idx) => {
+                let id = self.tabs[idx].id;
+                let (state, output_buf) = self.spawn_state(id, request);
+                let tab = &mut self.tabs[idx];
+                tab.app_title = None;
+                tab.exited = state.is_none();
+                tab.state = state;
+                tab.output_buf = output_buf;
+                tab.scroll_offset = 0;
+                tab.cwd = request.working_dir.as_ref().map(PathBuf::from);
+                id
+            }
+            None => self.open_tab(request.title.clone(), None, request),
+        };
 
-        // If we already have a live terminal state, just show the container
-        if self.state.is_some() {
-            self.container.show();
-            self.visible = true;
-            self.update_snapshot();
-            self.canvas.redraw();
-            return;
+        let mut started = false;
+        if let Some(tab) = self.tabs.iter_mut().find(|t| t.id == tab_id) {
+            started = !tab.exited;
+            tab.task_name = Some(request.title.clone());
+            tab.task_run = started.then_some(run_id);
+            tab.task_output.clear();
         }
+        self.activate(tab_id);
+        self.show();
+        started
+    }
+
+    /// Open a new interactive shell tab in `working_dir` and make it active.
+    pub fn new_shell_tab(&mut self, working_dir: Option<String>) {
+        let titles: Vec<&str> = self.tabs.iter().map(|t| t.title.as_str()).collect();
+        let title = next_shell_title(&titles);
+        let request = TerminalViewRequest {
+            title: title.clone(),
+            working_dir,
+            ..Default::default()
+        };
+        let tab_id = self.open_tab(title, None, &request);
+        self.activate(tab_id);
+        self.show();
+    }
 
-        // Lazy init: calculate grid dimensions from canvas size (minus padding)
+    /// Spawn a PTY for `request` and append it as a new tab. Returns the tab ID.
+    fn open_tab(
+        &mut self,
+        title: String,
+        session_id: Option<u32>,
+        request: &TerminalViewRequest,
+    ) -> u32 {
+        let id = self.next_tab_id;
+        self.next_tab_id += 1;
+        let (state, output_buf) = self.spawn_state(id, request);
+        let exited = state.is_none();
+        self.tabs.push(TerminalTab {
+            id,
+            title,
+            app_title: None,
+            renamed: false,
+            session_id,
+            state,
+            output_buf,
+            scroll_offset: 0,
+            cwd: request.working_dir.as_ref().map(PathBuf::from),
+            exited,
+            task_name: None,
+            task_run: None,
+            task_output: Vec::new(),
+        });
+        id
+    }
+
+    /// Spawn the PTY + reader thread for a tab. Returns None state on spawn failure.
+    fn spawn_state(
+        &mut self,
+        tab_id: u32,
+        request: &TerminalViewRequest,
+    ) -> (Option<Box<TerminalState>>, Arc<Mutex<Vec<u8>>>) {
+        let output_buf = Arc::new(Mutex::new(Vec::new()));
+
+        // Calculate grid dimensions from canvas size (minus padding)
         let canvas_w = if self.canvas.w() > 0 {
             self.canvas.w() - TERM_PAD * 2
         } else {
@@ -274,52 +742,48 @@ impl TerminalPanel {
         let cols = (canvas_w / char_w).max(20) as usize;
         let rows = (canvas_h / char_h).max(5) as usize;
 
-        // Spawn PTY
+        let mode = if request.exec {
+            ArgvMode::Exec
+        } else {
+            ArgvMode::Shell
+        };
         let pty_result = PtySession::spawn(
             request.command.as_deref(),
             &request.args,
+            mode,
             request.working_dir.as_deref(),
+            &request.env,
             cols as u16,
             rows as u16,
         );
 
         match pty_result {
             Ok((pty, reader)) => {
-                let grid = TerminalGrid::new(cols, rows);
-                let parser = vte::Parser::new();
-
                 // Start background reader thread
-                let output_buf = Arc::clone(&self.output_buf);
+                let buf = Arc::clone(&output_buf);
                 let sender = self.sender;
                 let reader_thread = std::thread::spawn(move || {
-                    Self::reader_loop(reader, output_buf, sender);
+                    Self::reader_loop(tab_id, reader, buf, sender);
                 });
 
-                self.state = Some(Box::new(TerminalState {
-                    grid,
+                let state = Box::new(TerminalState {
+                    grid: TerminalGrid::new(cols, rows),
                     pty,
-                    parser,
+                    parser: vte::Parser::new(),
                     _reader_thread: reader_thread,
-                }));
-
-                // Set up canvas draw callback and input handling
-                self.setup_draw_callback();
-                self.setup_input_handler();
-
-                self.container.show();
-                self.visible = true;
+                });
+                (Some(state), output_buf)
             }
             Err(e) => {
                 eprintln!("[terminal] Failed to spawn PTY: {}", e);
-                self.header.set_label(&format!("{} (error)", request.title));
-                self.container.show();
-                self.visible = true;
+                (None, output_buf)
             }
         }
     }
 
     /// Background reader thread: reads PTY output, buffers it, wakes FLTK
     fn reader_loop(
+        tab_id: u32,
         mut reader: Box<dyn std::io::Read + Send>,
         output_buf: Arc<Mutex<Vec<u8>>>,
         sender: Sender<Message>,
@@ -337,56 +801,349 @@ impl TerminalPanel {
                 }
             }
         }
-        sender.send(Message::TerminalExited);
+        sender.send(Message::TerminalExited(tab_id));
         fltk::app::awake();
     }
 
-    /// Process buffered output from the reader thread
+    /// Process buffered output from all tabs' reader threads
     pub fn process_output(&mut self) {
-        let data = {
-            let mut ob = match self.output_buf.lock() {
-                Ok(ob) => ob,
-                Err(_) => return,
+        let mut active_changed = false;
+        let mut titles_changed = false;
+        let mut active_cwd = None;
+        for tab in &mut self.tabs {
+            let data = {
+                let mut ob = match tab.output_buf.lock() {
+                    Ok(ob) => ob,
+                    Err(_) => continue,
+                };
+                if ob.is_empty() {
+                    continue;
+                }
+                std::mem::take(&mut *ob)
             };
-            if ob.is_empty() {
-                return;
+
+            if tab.task_run.is_some() {
+                tab.task_output.extend_from_slice(&data);
+                let excess = tab.task_output.len().saturating_sub(TASK_OUTPUT_LIMIT);
+                tab.task_output.drain(..excess);
             }
-            std::mem::take(&mut *ob)
-        };
 
-        if let Some(ref mut ts) = self.state {
-            let mut handler =
-                crate::app::services::terminal::vte_handler::VteHandler::new(&mut ts.grid);
-            ts.parser.advance(&mut handler, &data);
+            if let Some(ref mut ts) = tab.state {
+                let mut handler =
+                    crate::app::services::terminal::vte_handler::VteHandler::new(&mut ts.grid);
+                ts.parser.advance(&mut handler, &data);
+                if Some(tab.id) == self.active {
+                    active_changed = true;
+                } else {
+                    tab.scroll_offset = 0;
+                }
+
+                // Pick up state reported through OSC sequences
+                if ts.grid.title != tab.app_title {
+                    tab.app_title = ts.grid.title.clone();
+                    titles_changed |= !tab.renamed;
+                }
+                if ts.grid.cwd.is_some() && ts.grid.cwd != tab.cwd {
+                    tab.cwd = ts.grid.cwd.clone();
+                    if Some(tab.id) == self.active {
+                        active_cwd = tab.cwd.clone();
+                    }
+                }
+                if let Some(text) = ts.grid.clipboard_request.take() {
+                    self.clipboard_request = Some(text);
+                }
+            }
+        }
+
+        if titles_changed {
+            self.rebuild_tab_strip();
+        }
+        if active_cwd.is_some()
+            && let Ok(mut v) = self.view.lock()
+        {
+            v.cwd = active_cwd;
+        }
+
+        if active_changed {
             self.update_snapshot();
+            // New output arrives → snap to bottom
+            if let Ok(mut off) = self.scroll_offset.lock() {
+                *off = 0;
+            }
+            if self.search.is_open() {
+                self.search.run(false);
+            }
             self.canvas.redraw();
         }
     }
 
-    /// Update the shared snapshot with current grid state
-    fn update_snapshot(&self) {
-        if let Some(ref ts) = self.state
-            && let Ok(mut snap) = self.snapshot.lock()
+    /// Make `tab_id` the active tab: swap the snapshot, writer and scroll offset.
+    fn activate(&mut self, tab_id: u32) {
+        // Save the outgoing tab's scroll position
+        let current = self.scroll_offset.lock().map(|g| *g).unwrap_or(0);
+        if let Some(prev) = self.active
+            && let Some(tab) = self.tabs.iter_mut().find(|t| t.id == prev)
         {
-            let mut scrollback = Vec::with_capacity(ts.grid.scrollback_len());
+            tab.scroll_offset = current;
+        }
+
+        self.active = Some(tab_id);
+        let tab = self.tabs.iter().find(|t| t.id == tab_id);
+        let offset = tab.map(|t| t.scroll_offset).unwrap_or(0);
+        let writer = tab.and_then(|t| t.state.as_ref()).map(|ts| ts.pty.writer());
+        if let Ok(mut off) = self.scroll_offset.lock() {
+            *off = offset;
+        }
+        if let Ok(mut w) = self.active_writer.lock() {
+            *w = writer;
+        }
+        if let Ok(mut v) = self.view.lock() {
+            *v = TerminalView {
+                cwd: tab.and_then(|t| t.cwd.clone()),
+                ..TerminalView::default()
+            };
+        }
+
+        self.update_snapshot();
+        if self.search.is_open() {
+            self.search.run(true);
+        }
+        self.rebuild_tab_strip();
+        self.canvas.redraw();
+    }
+
+    /// Switch to the tab with the given ID
+    pub fn select_tab(&mut self, tab_id: u32) {
+        if self.active != Some(tab_id) && self.tabs.iter().any(|t| t.id == tab_id) {
+            self.activate(tab_id);
+            let _ = self.canvas.take_focus();
+        }
+    }
+
+    /// Close a tab and kill its process. Returns the widget session ID that
+    /// opened the tab, if any, so the caller can drop the session.
+    pub fn close_tab(&mut self, tab_id: u32) -> Option<u32> {
+        let idx = self.tabs.iter().position(|t| t.id == tab_id)?;
+        let tab = self.tabs.remove(idx);
+        if let Some(ts) = tab.state {
+            ts.pty.kill();
+        }
+
+        if self.active == Some(tab_id) {
+            self.active = None;
+            if let Some(next) = neighbor_after_close(self.tabs.len(), idx) {
+                let next_id = self.tabs[next].id;
+                self.activate(next_id);
+            } else {
+                if let Ok(mut w) = self.active_writer.lock() {
+                    *w = None;
+                }
+                if let Ok(mut snap) = self.snapshot.lock() {
+                    *snap = None;
+                }
+                if let Ok(mut v) = self.view.lock() {
+                    *v = TerminalView::default();
+                }
+                self.rebuild_tab_strip();
+                self.canvas.redraw();
+            }
+        } else {
+            self.rebuild_tab_strip();
+        }
+        tab.session_id
+    }
+
+    /// Rename a tab
+    pub fn rename_tab(&mut self, tab_id: u32, title: &str) {
+        let title = title.trim();
+        if title.is_empty() {
+            return;
+        }
+        if let Some(tab) = self.tabs.iter_mut().find(|t| t.id == tab_id) {
+            tab.title = title.to_string();
+            tab.renamed = true;
+            self.rebuild_tab_strip();
+        }
+    }
+
+    /// Mark a tab's process as exited. Returns true if the tab should be
+    /// closed: built-in shells go away with their process, while plugin
+    /// command and task tabs stay open so their output can still be read.
+    pub fn mark_exited(&mut self, tab_id: u32) -> bool {
+        // Drain any output that raced the exit signal
+        self.process_output();
+        let Some(tab) = self.tabs.iter_mut().find(|t| t.id == tab_id) else {
+            return false;
+        };
+        tab.exited = true;
+        if tab.session_id.is_none() && tab.task_name.is_none() {
+            return true;
+        }
+        self.rebuild_tab_strip();
+        false
+    }
+
+    /// Take the captured output of a task tab's run, with the run ID and the
+    /// exit code if known. Returns None for other tabs or when already taken.
+    pub fn take_task_output(&mut self, tab_id: u32) -> Option<(u32, Vec<u8>, Option<u32>)> {
+        let tab = self.tabs.iter_mut().find(|t| t.id == tab_id)?;
+        let run_id = tab.task_run.take()?;
+        let exit_code = tab.state.as_ref().and_then(|ts| {
+            if tab.exited {
+                ts.pty.wait_exit(Duration::from_millis(200))
+            } else {
+                ts.pty.try_wait()
+            }
+        });
+        Some((run_id, std::mem::take(&mut tab.task_output), exit_code))
+    }
+
+    /// Whether any terminal tab is open
+    pub fn has_tabs(&self) -> bool {
+        !self.tabs.is_empty()
+    }
+
+    /// Current directory of the active tab's shell, if known
+    pub fn active_cwd(&self) -> Option<PathBuf> {
+        self.active
+            .and_then(|id| self.tabs.iter().find(|t| t.id == id))
+            .and_then(|t| t.cwd.clone())
+    }
+
+    /// Take the latest clipboard write requested by a program (OSC 52).
+    /// The caller decides whether it is allowed.
+    pub fn take_clipboard_request(&mut self) -> Option<String> {
+        self.clipboard_request.take()
+    }
+
+    /// Update the shared snapshot with the active tab's grid state
+    fn update_snapshot(&self) {
+        let Ok(mut snap) = self.snapshot.lock() else {
+            return;
+        };
+        let state = self
+            .active
+            .and_then(|id| self.tabs.iter().find(|t| t.id == id))
+            .and_then(|t| t.state.as_ref());
+        let Some(ts) = state else {
+            *snap = None;
+            return;
+        };
+        let alt_screen = ts.grid.alt_screen_active();
+        let mut scrollback = Vec::new();
+        if !alt_screen {
+            scrollback.reserve(ts.grid.scrollback_len());
             for i in 0..ts.grid.scrollback_len() {
                 if let Some(line) = ts.grid.scrollback_line(i) {
                     scrollback.push(line.to_vec());
                 }
             }
-            *snap = Some(GridSnapshot {
-                cells: ts.grid.cells.clone(),
-                rows: ts.grid.rows,
-                cols: ts.grid.cols,
-                cursor_row: ts.grid.cursor_row,
-                cursor_col: ts.grid.cursor_col,
-                cursor_visible: ts.grid.cursor_visible,
-                scrollback,
+        }
+        *snap = Some(GridSnapshot {
+            cells: ts.grid.cells.clone(),
+            rows: ts.grid.rows,
+            cols: ts.grid.cols,
+            cursor_row: ts.grid.cursor_row,
+            cursor_col: ts.grid.cursor_col,
+            cursor_visible: ts.grid.cursor_visible,
+            scrollback,
+            mouse_mode: ts.grid.mouse_mode,
+            mouse_sgr: ts.grid.mouse_sgr,
+            bracketed_paste: ts.grid.bracketed_paste,
+            alt_screen,
+        });
+    }
+
+    /// Rebuild the tab strip buttons from the current tab list.
+    /// Re-uses the existing `self.tab_strip` Flex (which is parented to the header row).
+    fn rebuild_tab_strip(&mut self) {
+        let theme = DialogTheme::from_theme_bg(self.theme_bg);
+
+        self.tab_strip.clear();
+        self.tab_strip.set_color(theme.bg);
+        self.tab_strip.begin();
+
+        fltk::draw::set_font(Font::Helvetica, 12);
+        for tab in &self.tabs {
+            let label = if tab.exited {
+                format!("{} [exited]", tab.display_title())
+            } else {
+                tab.display_title().to_string()
+            };
+            let mut btn = Button::default();
+            btn.set_label(&label.replace('@', "@@"));
+            btn.set_frame(FrameType::FlatBox);
+            btn.set_label_size(12);
+            btn.set_label_color(if tab.exited {
+                theme.text_dim
+            } else {
+                theme.text
+            });
+            btn.set_color(if Some(tab.id) == self.active {
+                theme.tab_active_bg
+            } else {
+                theme.bg
+            });
+            btn.set_tooltip(tab.display_title());
+
+            let tab_id = tab.id;
+            let title = tab.display_title().to_string();
+            let sender = self.sender;
+            let ctx_menu_ptr = self.ctx_menu.as_widget_ptr();
+            btn.handle(move |_, ev| {
+                if ev != Event::Push {
+                    return false;
+                }
+                match fltk::app::event_mouse_button() {
+                    fltk::app::MouseButton::Right => {
+                        Self::show_tab_menu(tab_id, &title, sender, ctx_menu_ptr);
+                    }
+                    fltk::app::MouseButton::Middle => {
+                        sender.send(Message::TerminalCloseTab(tab_id));
+                    }
+                    _ => sender.send(Message::TerminalSelectTab(tab_id)),
+                }
+                true
             });
+
+            let width = (fltk::draw::width(&label) as i32 + 20).clamp(TAB_MIN_WIDTH, TAB_MAX_WIDTH);
+            self.tab_strip.fixed(&btn, width);
         }
-        // New output arrives → snap to bottom
-        if let Ok(mut off) = self.scroll_offset.lock() {
-            *off = 0;
+        Frame::default(); // spacer (flexible, fills the rest of the strip)
+
+        self.tab_strip.end();
+        self.tab_strip.redraw();
+    }
+
+    /// Pop up the Rename/Close context menu for a tab
+    fn show_tab_menu(
+        tab_id: u32,
+        title: &str,
+        sender: Sender<Message>,
+        ctx_menu_ptr: fltk::app::WidgetPtr,
+    ) {
+        // SAFETY: ctx_menu_ptr is a valid MenuButton widget pointer created in
+        // the constructor and stored for the lifetime of this panel.
+        let mut menu = unsafe { MenuButton::from_widget_ptr(ctx_menu_ptr) };
+        menu.clear();
+        menu.resize(fltk::app::event_x(), fltk::app::event_y(), 1, 1);
+        menu.add("Rename...", Shortcut::None, MenuFlag::Normal, |_| {});
+        menu.add("Close", Shortcut::None, MenuFlag::Normal, |_| {});
+
+        let Some(chosen) = menu.popup() else {
+            return;
+        };
+        match chosen.label().unwrap_or_default().as_str() {
+            "Rename..." => {
+                if let Some(new_title) = fltk::dialog::input_default("Tab name:", title) {
+                    sender.send(Message::TerminalRenameTab {
+                        tab_id,
+                        title: new_title,
+                    });
+                }
+            }
+            "Close" => sender.send(Message::TerminalCloseTab(tab_id)),
+            _ => {}
         }
     }
 
@@ -395,6 +1152,7 @@ impl TerminalPanel {
         let snapshot = Arc::clone(&self.snapshot);
         let scroll_offset = Arc::clone(&self.scroll_offset);
         let font = Arc::clone(&self.font);
+        let view = Arc::clone(&self.view);
         let term_theme = TerminalTheme::from_theme(self.is_dark, self.theme_bg);
         let bg_color = term_theme.bg;
 
@@ -418,6 +1176,10 @@ impl TerminalPanel {
             };
 
             let offset = scroll_offset.lock().map(|g| *g).unwrap_or(0);
+            let view = match view.lock() {
+                Ok(v) => v,
+                Err(_) => return,
+            };
 
             let (term_font, term_font_size) = font
                 .lock()
@@ -430,11 +1192,7 @@ impl TerminalPanel {
             // Build a combined view: scrollback + visible grid
             // When offset=0, show the bottom (normal view = just grid.cells)
             // When offset>0, shift up into scrollback
-            let sb_len = grid.scrollback.len();
-            let total_lines = sb_len + grid.rows;
-
-            // First visible line index in the combined buffer
-            let first_visible = total_lines.saturating_sub(grid.rows + offset);
+            let first_visible = grid.first_visible(offset);
 
             let ox = x + TERM_PAD;
             let oy = y + TERM_PAD;
@@ -446,18 +1204,13 @@ impl TerminalPanel {
                 }
 
                 let line_idx = first_visible + screen_row;
-                let line: Option<&[Cell]> = if line_idx < sb_len {
-                    Some(&grid.scrollback[line_idx])
-                } else {
-                    let grid_row = line_idx - sb_len;
-                    if grid_row < grid.cells.len() {
-                        Some(&grid.cells[grid_row])
-                    } else {
-                        None
-                    }
+                let Some(line) = line_at(&grid.scrollback, &grid.cells, line_idx) else {
+                    continue;
                 };
 
-                let Some(line) = line else { continue };
+                // Search matches on this line (matches are sorted by line)
+                let first_match = view.matches.partition_point(|m| m.line < line_idx);
+                let last_match = view.matches.partition_point(|m| m.line <= line_idx);
 
                 for col in 0..grid.cols {
                     let px = ox + (col as i32) * char_w;
@@ -468,29 +1221,86 @@ impl TerminalPanel {
                         continue;
                     }
                     let cell = &line[col];
+                    // The right half of a wide char is drawn with its left half
+                    if cell.width == CellWidth::Spacer {
+                        continue;
+                    }
+                    let cell_w = if cell.width == CellWidth::Wide {
+                        char_w * 2
+                    } else {
+                        char_w
+                    };
+
+                    let highlight = if view
+                        .selection
+                        .is_some_and(|sel| sel.contains(line_idx, col))
+                    {
+                        Some(term_theme.selection)
+                    } else {
+                        (first_match..last_match)
+                            .find(|&i| {
+                                let m = &view.matches[i];
+                                col >= m.start && col < m.end
+                            })
+                            .map(|i| {
+                                if Some(i) == view.current_match {
+                                    term_theme.search_current
+                                } else {
+                                    term_theme.search_match
+                                }
+                            })
+                    };
+                    let cell_bg = highlight.unwrap_or(cell.bg);
 
-                    if cell.bg != Color::TransparentBg {
-                        fltk::draw::set_draw_color(cell.bg);
-                        fltk::draw::draw_rectf(px, py, char_w, char_h);
+                    if cell_bg != Color::TransparentBg {
+                        fltk::draw::set_draw_color(cell_bg);
+                        fltk::draw::draw_rectf(px, py, cell_w, char_h);
+                    }
+
+                    let mut fg = TerminalTheme::effective_fg(cell.fg, cell_bg, bg_color);
+                    if cell.dim {
+                        let cell_bg = if cell_bg != Color::TransparentBg {
+                            cell_bg
+                        } else {
+                            bg_color
+                        };
+                        fg = TerminalTheme::dimmed(fg, cell_bg);
                     }
+                    fltk::draw::set_draw_color(fg);
 
-                    if cell.ch != ' ' {
-                        let fg = TerminalTheme::effective_fg(cell.fg, cell.bg, bg_color);
-                        fltk::draw::set_draw_color(fg);
-                        // Bold cells are rendered in regular weight: FLTK only has
-                        // bold variants for its 16 built-in fonts, and we now allow
-                        // arbitrary system fonts where no bold variant is reachable
-                        // by name lookup.
+                    if cell.ch != ' ' || cell.combining.is_some() {
+                        let styled = styled_font(term_font, cell.bold, cell.italic);
+                        if styled != term_font {
+                            fltk::draw::set_font(styled, term_font_size);
+                        }
                         let mut buf = [0u8; 4];
-                        let s = cell.ch.encode_utf8(&mut buf);
+                        let text;
+                        let s = if cell.combining.is_some() {
+                            text = cell.text();
+                            text.as_str()
+                        } else {
+                            cell.ch.encode_utf8(&mut buf)
+                        };
                         fltk::draw::draw_text2(
                             s,
                             px,
                             py,
-                            char_w,
+                            cell_w,
                             char_h,
                             Align::Left | Align::Inside,
                         );
+                        if styled != term_font {
+                            fltk::draw::set_font(term_font, term_font_size);
+                        }
+                    }
+                    // OSC 8 hyperlinks are underlined like links in a browser
+                    if cell.underline || cell.link.is_some() {
+                        let ly = py + char_h - 2;
+                        fltk::draw::draw_line(px, ly, px + cell_w - 1, ly);
+                    }
+                    if cell.strikethrough {
+                        let ly = py + char_h / 2;
+                        fltk::draw::draw_line(px, ly, px + cell_w - 1, ly);
                     }
                 }
             }
@@ -504,103 +1314,340 @@ impl TerminalPanel {
                 && grid.cursor_row < grid.cells.len()
                 && grid.cursor_col < grid.cells[grid.cursor_row].len()
             {
-                let cx = ox + (grid.cursor_col as i32) * char_w;
+                // On the right half of a wide char, cover the whole char
+                let mut col = grid.cursor_col;
+                if col > 0 && grid.cells[grid.cursor_row][col].width == CellWidth::Spacer {
+                    col -= 1;
+                }
+                let cell = &grid.cells[grid.cursor_row][col];
+                let cell_w = if cell.width == CellWidth::Wide {
+                    char_w * 2
+                } else {
+                    char_w
+                };
+                let cx = ox + (col as i32) * char_w;
                 let cy = oy + (grid.cursor_row as i32) * char_h;
-                let cell = &grid.cells[grid.cursor_row][grid.cursor_col];
                 let cursor_color = TerminalTheme::effective_fg(cell.fg, cell.bg, bg_color);
                 fltk::draw::set_draw_color(cursor_color);
-                fltk::draw::draw_rectf(cx, cy, char_w, char_h);
+                fltk::draw::draw_rectf(cx, cy, cell_w, char_h);
 
                 if cell.ch != ' ' {
                     fltk::draw::set_draw_color(bg_color);
-                    let mut buf = [0u8; 4];
-                    let s = cell.ch.encode_utf8(&mut buf);
-                    fltk::draw::draw_text2(s, cx, cy, char_w, char_h, Align::Left | Align::Inside);
+                    let text = cell.text();
+                    fltk::draw::draw_text2(
+                        &text,
+                        cx,
+                        cy,
+                        cell_w,
+                        char_h,
+                        Align::Left | Align::Inside,
+                    );
                 }
             }
         });
     }
 
-    /// Set up input handler to forward key events to PTY
+    /// Set up input handler to forward key, mouse and paste events to the
+    /// active tab's PTY, honouring the modes the application enabled.
+    ///
+    /// Mouse events the application does not take drive the terminal's own
+    /// selection (drag, double-click for a word) and Ctrl+click on links.
     fn setup_input_handler(&mut self) {
-        if let Some(ref ts) = self.state {
-            let writer = Arc::new(Mutex::new(PtyWriteHandle {
-                pty: &ts.pty as *const PtySession,
-            }));
-            let scroll_offset = Arc::clone(&self.scroll_offset);
-            let snapshot = Arc::clone(&self.snapshot);
-
-            let canvas_for_paste = self.canvas.clone();
-            self.canvas.handle({
-                let writer = writer.clone();
-                move |f, ev| match ev {
-                    Event::Push => {
-                        let _ = f.take_focus();
-                        true
+        let writer = Arc::clone(&self.active_writer);
+        let scroll_offset = Arc::clone(&self.scroll_offset);
+        let snapshot = Arc::clone(&self.snapshot);
+        let font = Arc::clone(&self.font);
+        let view = Arc::clone(&self.view);
+        let mut search = self.search.clone();
+        let sender = self.sender;
+        // Whether a left-button selection is in progress, and whether it has
+        // grown beyond the clicked cell (a plain click clears the selection)
+        let mut selecting = false;
+        let mut extended = false;
+
+        let write = move |bytes: &[u8]| {
+            if !bytes.is_empty()
+                && let Ok(w) = writer.lock()
+                && let Some(ref w) = *w
+            {
+                w.write(bytes);
+            }
+        };
+
+        let canvas_for_paste = self.canvas.clone();
+        self.canvas.handle(move |f, ev| {
+            // Modes of the active tab, read fresh for every event
+            let (mouse_mode, mouse_sgr, bracketed_paste, alt_screen) = snapshot
+                .lock()
+                .ok()
+                .and_then(|s| {
+                    s.as_ref()
+                        .map(|g| (g.mouse_mode, g.mouse_sgr, g.bracketed_paste, g.alt_screen))
+                })
+                .unwrap_or((MouseMode::Off, false, false, false));
+
+            // Report a mouse event to the application. Shift bypasses
+            // reporting so the user can still interact with the terminal itself.
+            let report_mouse = |f: &Frame, report: MouseReport| -> bool {
+                let state = fltk::app::event_state();
+                if mouse_mode == MouseMode::Off || state.contains(Shortcut::Shift) {
+                    return false;
+                }
+                let (col, row) = mouse_cell(f, &font);
+                let mut modifiers = 0;
+                if state.contains(Shortcut::Alt) {
+                    modifiers |= MOUSE_MOD_ALT;
+                }
+                if state.contains(Shortcut::Ctrl) {
+                    modifiers |= MOUSE_MOD_CTRL;
+                }
+                write(&encode_mouse(
+                    mouse_mode, mouse_sgr, report, col, row, modifiers,
+                ));
+                true
+            };
+            let button = || match fltk::app::event_mouse_button() {
+                fltk::app::MouseButton::Middle => 1,
+                fltk::app::MouseButton::Right => 2,
+                _ => 0,
+            };
+
+            // (line, col) under the mouse in the combined scrollback + screen
+            let point_under_mouse = |f: &Frame| -> Option<(usize, usize)> {
+                let (col, row) = mouse_cell(f, &font);
+                let offset = scroll_offset.lock().map(|g| *g).unwrap_or(0);
+                let snap = snapshot.lock().ok()?;
+                let grid = snap.as_ref()?;
+                let row = row.min(grid.rows.saturating_sub(1));
+                Some((
+                    grid.first_visible(offset) + row,
+                    col.min(grid.cols.saturating_sub(1)),
+                ))
+            };
+            let link_under_mouse = |f: &Frame| {
+                let (line, col) = point_under_mouse(f)?;
+                let snap = snapshot.lock().ok()?;
+                let grid = snap.as_ref()?;
+                link_at(line_at(&grid.scrollback, &grid.cells, line)?, col)
+            };
+            // Copy the selection to the clipboard, or to the primary
+            // selection (middle-click paste) on platforms that have one
+            let copy_selection = |clipboard: bool| {
+                let (Ok(snap), Ok(v)) = (snapshot.lock(), view.lock()) else {
+                    return;
+                };
+                let (Some(grid), Some(sel)) = (snap.as_ref(), v.selection.as_ref()) else {
+                    return;
+                };
+                let text = selected_text(&grid.scrollback, &grid.cells, sel);
+                if text.is_empty() {
+                    return;
+                }
+                if clipboard {
+                    crate::app::infrastructure::platform::copy_to_clipboard(&text);
+                } else {
+                    fltk::app::copy2(&text);
+                }
+            };
+
+            match ev {
+                Event::Push => {
+                    let _ = f.take_focus();
+                    if report_mouse(f, MouseReport::Press(button())) || button() != 0 {
+                        return true;
                     }
-                    Event::Focus | Event::Unfocus => true,
-                    Event::MouseWheel => {
-                        let dy = fltk::app::event_dy();
-                        if let Ok(mut off) = scroll_offset.lock() {
-                            let max_scroll = snapshot
-                                .lock()
-                                .ok()
-                                .and_then(|s| s.as_ref().map(|g| g.scrollback.len()))
-                                .unwrap_or(0);
-                            match dy {
-                                fltk::app::MouseWheel::Up => {
-                                    *off = (*off + 3).min(max_scroll);
-                                }
-                                fltk::app::MouseWheel::Down => {
-                                    *off = off.saturating_sub(3);
+                    if fltk::app::event_state().contains(Shortcut::Ctrl) {
+                        if let Some(link) = link_under_mouse(f) {
+                            let cwd = view.lock().ok().and_then(|v| v.cwd.clone());
+                            open_link(&link.target, cwd.as_deref(), sender);
+                        }
+                        return true;
+                    }
+                    let Some((line, col)) = point_under_mouse(f) else {
+                        return true;
+                    };
+                    let double_click = fltk::app::event_clicks();
+                    if let (Ok(snap), Ok(mut v)) = (snapshot.lock(), view.lock()) {
+                        let cells = snap
+                            .as_ref()
+                            .and_then(|g| line_at(&g.scrollback, &g.cells, line));
+                        v.selection = Some(match cells {
+                            Some(cells) if double_click => {
+                                let (start, end) = word_bounds(cells, col);
+                                Selection {
+                                    anchor: (line, start),
+                                    head: (line, end),
                                 }
-                                _ => {}
                             }
-                        }
+                            _ => Selection::new((line, col)),
+                        });
+                    }
+                    selecting = true;
+                    extended = double_click;
+                    if double_click {
+                        copy_selection(false);
+                    }
+                    f.redraw();
+                    true
+                }
+                Event::Drag => {
+                    if report_mouse(f, MouseReport::Drag(button())) || !selecting {
+                        return true;
+                    }
+                    if let Some(point) = point_under_mouse(f)
+                        && let Ok(mut v) = view.lock()
+                        && let Some(ref mut sel) = v.selection
+                        && sel.head != point
+                    {
+                        sel.head = point;
+                        extended = true;
                         f.redraw();
-                        true
                     }
-                    Event::Paste => {
-                        // Clipboard paste: FLTK delivers clipboard content via event_text()
-                        let clip = fltk::app::event_text();
-                        if !clip.is_empty()
-                            && let Ok(w) = writer.lock()
-                        {
-                            unsafe { (*w.pty).write(clip.as_bytes()) };
-                        }
-                        true
+                    true
+                }
+                Event::Released => {
+                    if report_mouse(f, MouseReport::Release(button())) || !selecting {
+                        return true;
                     }
-                    Event::KeyDown => {
-                        let key = fltk::app::event_key();
-                        let text = fltk::app::event_text();
-                        let state = fltk::app::event_state();
-
-                        // Ctrl+Shift+V: paste from clipboard (terminal convention)
-                        if state.contains(fltk::enums::Shortcut::Ctrl)
-                            && state.contains(fltk::enums::Shortcut::Shift)
-                            && (key == fltk::enums::Key::from_char('v')
-                                || key == fltk::enums::Key::from_char('V'))
-                        {
-                            fltk::app::paste_text(&canvas_for_paste);
+                    selecting = false;
+                    if extended {
+                        copy_selection(false);
+                    } else if let Ok(mut v) = view.lock() {
+                        v.selection = None;
+                        f.redraw();
+                    }
+                    true
+                }
+                Event::Enter => true,
+                Event::Move => {
+                    // Hand cursor over links while Ctrl is held
+                    let on_link = fltk::app::event_state().contains(Shortcut::Ctrl)
+                        && mouse_mode == MouseMode::Off
+                        && link_under_mouse(f).is_some();
+                    if let Some(mut win) = f.window() {
+                        win.set_cursor(if on_link {
+                            Cursor::Hand
                         } else {
-                            let bytes = encode_key(key, &text);
-                            if !bytes.is_empty()
-                                && let Ok(w) = writer.lock()
-                            {
-                                // SAFETY: pty pointer is valid as long as state exists
-                                unsafe { (*w.pty).write(&bytes) };
+                            Cursor::Default
+                        });
+                    }
+                    true
+                }
+                Event::Leave => {
+                    if let Some(mut win) = f.window() {
+                        win.set_cursor(Cursor::Default);
+                    }
+                    true
+                }
+                Event::Focus | Event::Unfocus => true,
+                Event::MouseWheel => {
+                    let dy = fltk::app::event_dy();
+                    let report = match dy {
+                        fltk::app::MouseWheel::Up => Some(MouseReport::WheelUp),
+                        fltk::app::MouseWheel::Down => Some(MouseReport::WheelDown),
+                        _ => None,
+                    };
+                    if let Some(report) = report
+                        && report_mouse(f, report)
+                    {
+                        return true;
+                    }
+                    if alt_screen {
+                        // No scrollback on the alternate screen: scroll the
+                        // application instead, like xterm's alternateScroll
+                        let arrow: &[u8] = match dy {
+                            fltk::app::MouseWheel::Up => b"\x1b[A",
+                            fltk::app::MouseWheel::Down => b"\x1b[B",
+                            _ => b"",
+                        };
+                        for _ in 0..3 {
+                            write(arrow);
+                        }
+                        return true;
+                    }
+                    if let Ok(mut off) = scroll_offset.lock() {
+                        let max_scroll = snapshot
+                            .lock()
+                            .ok()
+                            .and_then(|s| s.as_ref().map(|g| g.scrollback.len()))
+                            .unwrap_or(0);
+                        match dy {
+                            fltk::app::MouseWheel::Up => {
+                                *off = (*off + 3).min(max_scroll);
+                            }
+                            fltk::app::MouseWheel::Down => {
+                                *off = off.saturating_sub(3);
                             }
+                            _ => {}
                         }
-                        // Snap to bottom on keypress
-                        if let Ok(mut off) = scroll_offset.lock() {
-                            *off = 0;
+                    }
+                    f.redraw();
+                    true
+                }
+                Event::Paste => {
+                    // Clipboard paste: FLTK delivers clipboard content via event_text()
+                    let clip = fltk::app::event_text();
+                    if !clip.is_empty() {
+                        write(&encode_paste(&clip, bracketed_paste));
+                    }
+                    true
+                }
+                Event::KeyDown => {
+                    let key = fltk::app::event_key();
+                    let text = fltk::app::event_text();
+                    let state = fltk::app::event_state();
+                    let ctrl_shift = state.contains(fltk::enums::Shortcut::Ctrl)
+                        && state.contains(fltk::enums::Shortcut::Shift);
+                    let is_key = |c: char| {
+                        key == Key::from_char(c) || key == Key::from_char(c.to_ascii_uppercase())
+                    };
+
+                    // Ctrl+Shift+C / Ctrl+Shift+F: copy selection / search output.
+                    // Neither is sent to the PTY, nor snaps the view to the bottom.
+                    if ctrl_shift && is_key('c') {
+                        copy_selection(true);
+                        return true;
+                    }
+                    if ctrl_shift && is_key('f') {
+                        search.open();
+                        return true;
+                    }
+                    // Ctrl+Shift+Up/Down: jump to the previous/next prompt
+                    if ctrl_shift && (key == Key::Up || key == Key::Down) {
+                        if let Ok(snap) = snapshot.lock()
+                            && let Some(grid) = snap.as_ref()
+                            && let Ok(mut off) = scroll_offset.lock()
+                        {
+                            let prompts = prompt_lines(&grid.scrollback, &grid.cells);
+                            let first = grid.first_visible(*off);
+                            let target = if key == Key::Up {
+                                prompts.iter().rev().find(|&&l| l < first)
+                            } else {
+                                prompts.iter().find(|&&l| l > first)
+                            };
+                            if let Some(&line) = target {
+                                *off = grid.scrollback.len() - line.min(grid.scrollback.len());
+                            }
                         }
-                        true
+                        f.redraw();
+                        return true;
+                    }
+
+                    // Ctrl+Shift+V: paste from clipboard (terminal convention)
+                    if ctrl_shift && is_key('v') {
+                        fltk::app::paste_text(&canvas_for_paste);
+                    } else {
+                        write(&encode_key(key, &text));
                     }
-                    _ => false,
+                    // Snap to bottom on keypress
+                    if let Ok(mut off) = scroll_offset.lock() {
+                        *off = 0;
+                    }
+                    true
                 }
-            });
-        }
+                _ => false,
+            }
+        });
     }
 
     /// Get character metrics for the current terminal font/size.
@@ -616,41 +1663,46 @@ impl TerminalPanel {
         (char_w.max(1), char_h.max(1))
     }
 
-    /// Hide the terminal panel
+    /// Show the panel with its current tabs
+    pub fn show(&mut self) {
+        self.container.show();
+        self.visible = true;
+        let _ = self.canvas.take_focus();
+    }
+
+    /// Hide the terminal panel. Tabs and their processes keep running.
     pub fn hide(&mut self) {
         self.container.hide();
         self.visible = false;
     }
 
-    /// Close the terminal — kill PTY, drop state
-    pub fn close(&mut self) {
-        if let Some(ts) = self.state.take() {
-            ts.pty.kill();
-        }
-        self.session_id = None;
-        if let Ok(mut snap) = self.snapshot.lock() {
-            *snap = None;
-        }
-        self.hide();
-    }
-
     /// Apply theme colors
     pub fn apply_theme(&mut self, is_dark: bool, theme_bg: (u8, u8, u8)) {
         self.is_dark = is_dark;
         self.theme_bg = theme_bg;
         let theme = DialogTheme::from_theme_bg(theme_bg);
 
-        self.header.set_color(theme.bg);
-        self.header.set_label_color(theme.text);
+        self.header_row.set_color(theme.bg);
+        for btn in [&mut self.new_btn, &mut self.close_btn]
+            .into_iter()
+            .chain(self.search_buttons.iter_mut())
+        {
+            btn.set_color(theme.bg);
+            btn.set_label_color(theme.text);
+        }
+        self.search.row.set_color(theme.bg);
+        self.search.input.set_color(theme.input_bg);
+        self.search.input.set_text_color(theme.text);
+        self.search.input.set_cursor_color(theme.text);
+        self.search.count.set_label_color(theme.text_dim);
 
         if let Some(ref mut div) = self.divider {
             div.set_color(super::theme::divider_color_from_bg(theme_bg));
         }
 
         // Re-set the draw callback with updated dark mode
-        if self.state.is_some() {
-            self.setup_draw_callback();
-        }
+        self.setup_draw_callback();
+        self.rebuild_tab_strip();
 
         if self.visible {
             self.canvas.redraw();
@@ -667,10 +1719,12 @@ impl TerminalPanel {
         self.visible
     }
 
-    /// Get the current session ID
+    /// Get the widget session ID of the active tab
     #[allow(dead_code)]
     pub fn session_id(&self) -> Option<u32> {
-        self.session_id
+        self.active
+            .and_then(|id| self.tabs.iter().find(|t| t.id == id))
+            .and_then(|t| t.session_id)
     }
 
     /// Get the current width
@@ -682,25 +1736,27 @@ impl TerminalPanel {
         }
     }
 
-    /// Handle resize — recalculate grid dimensions and notify PTY
+    /// Handle resize — recalculate grid dimensions of every tab and notify their PTYs
     pub fn handle_resize(&mut self) {
         let (char_w, char_h) = self.char_metrics();
         let canvas_w = self.canvas.w();
         let canvas_h = self.canvas.h();
-        let mut needs_redraw = false;
-        if let Some(ref mut ts) = self.state {
-            if char_w <= 0 || char_h <= 0 || canvas_w <= 0 || canvas_h <= 0 {
-                return;
-            }
-            let usable_w = canvas_w - TERM_PAD * 2;
-            let usable_h = canvas_h - TERM_PAD * 2;
-            let new_cols = (usable_w / char_w).max(10) as usize;
-            let new_rows = (usable_h / char_h).max(3) as usize;
+        if char_w <= 0 || char_h <= 0 || canvas_w <= 0 || canvas_h <= 0 {
+            return;
+        }
+        let usable_w = canvas_w - TERM_PAD * 2;
+        let usable_h = canvas_h - TERM_PAD * 2;
+        let new_cols = (usable_w / char_w).max(10) as usize;
+        let new_rows = (usable_h / char_h).max(3) as usize;
 
-            if new_cols != ts.grid.cols || new_rows != ts.grid.rows {
+        let mut needs_redraw = false;
+        for tab in &mut self.tabs {
+            if let Some(ref mut ts) = tab.state
+                && (new_cols != ts.grid.cols || new_rows != ts.grid.rows)
+            {
                 ts.grid.resize(new_cols, new_rows);
                 ts.pty.resize(new_cols as u16, new_rows as u16);
-                needs_redraw = true;
+                needs_redraw |= Some(tab.id) == self.active;
             }
         }
         if needs_redraw {
@@ -709,14 +1765,118 @@ impl TerminalPanel {
         }
     }
 
-    /// Send raw input bytes to the terminal PTY (as if typed by the user).
+    /// Send raw input bytes to the active tab's PTY (as if typed by the user).
     pub fn send_input(&self, data: &[u8]) {
-        if let Some(ref ts) = self.state {
-            ts.pty.write(data);
+        if let Ok(w) = self.active_writer.lock()
+            && let Some(ref w) = *w
+        {
+            w.write(data);
         }
     }
 }
 
+/// Screen cell (col, row) under the mouse pointer
+fn mouse_cell(f: &Frame, font: &Mutex<(Font, i32)>) -> (usize, usize) {
+    let (term_font, term_font_size) = font
+        .lock()
+        .map(|f| *f)
+        .unwrap_or((DEFAULT_TERM_FONT, DEFAULT_TERM_FONT_SIZE));
+    fltk::draw::set_font(term_font, term_font_size);
+    let char_w = (fltk::draw::width("M") as i32).max(1);
+    let char_h = fltk::draw::height().max(1);
+    let col = ((fltk::app::event_x() - f.x() - TERM_PAD).max(0) / char_w) as usize;
+    let row = ((fltk::app::event_y() - f.y() - TERM_PAD).max(0) / char_h) as usize;
+    (col, row)
+}
+
+/// Follow a Ctrl+clicked link: URLs open in the default browser, file
+/// locations open in the editor at the given line.
+fn open_link(target: &LinkTarget, cwd: Option<&Path>, sender: Sender<Message>) {
+    match target {
+        LinkTarget::Url(url) => {
+            if let Err(e) = open::that(url) {
+                eprintln!("[terminal] Failed to open {}: {}", url, e);
+            }
+        }
+        LinkTarget::File { path, line, .. } => match resolve_link_path(path, cwd) {
+            Some(full) => {
+                sender.send(Message::DeferredOpenFile(
+                    full.to_string_lossy().into_owned(),
+                ));
+                if let Some(line) = line {
+                    sender.send(Message::DeferredGotoLine(*line));
+                }
+            }
+            None => eprintln!("[terminal] Link target not found: {}", path),
+        },
+    }
+}
+
+/// Bold/italic variant of the terminal font.
+///
+/// FLTK only has styled variants for its built-in font families; arbitrary
+/// system fonts (picked via the font picker) have no bold or italic variant
+/// reachable by name lookup, so those render in regular weight.
+fn styled_font(base: Font, bold: bool, italic: bool) -> Font {
+    let families = [
+        (
+            Font::Courier,
+            Font::CourierBold,
+            Font::CourierItalic,
+            Font::CourierBoldItalic,
+        ),
+        (
+            Font::Helvetica,
+            Font::HelveticaBold,
+            Font::HelveticaItalic,
+            Font::HelveticaBoldItalic,
+        ),
+        (
+            Font::Times,
+            Font::TimesBold,
+            Font::TimesItalic,
+            Font::TimesBoldItalic,
+        ),
+        (
+            Font::Screen,
+            Font::ScreenBold,
+            Font::Screen,
+            Font::ScreenBold,
+        ),
+    ];
+    families
+        .iter()
+        .find(|f| f.0 == base)
+        .map(|&(regular, b, i, bi)| match (bold, italic) {
+            (true, true) => bi,
+            (true, false) => b,
+            (false, true) => i,
+            (false, false) => regular,
+        })
+        .unwrap_or(base)
+}
+
+/// Pick a title for a new shell tab: "Shell", then "Shell 2", "Shell 3", ...
+fn next_shell_title(existing: &[&str]) -> String {
+    if !existing.contains(&SHELL_TAB_TITLE) {
+        return SHELL_TAB_TITLE.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} {}", SHELL_TAB_TITLE, n))
+        .find(|t| !existing.contains(&t.as_str()))
+        .unwrap_or_default()
+}
+
+/// Index of the tab to activate after closing the tab at `closed` from a list
+/// that now has `remaining` tabs: the right neighbor, else the left one.
+fn neighbor_after_close(remaining: usize, closed: usize) -> Option<usize> {
+    if remaining == 0 {
+        None
+    } else {
+        Some(closed.min(remaining - 1))
+    }
+}
+
 /// Encode an FLTK key event into terminal bytes.
 ///
 /// Supports modifier-aware sequences:
@@ -819,13 +1979,40 @@ fn encode_key(key: Key, text: &str) -> Vec<u8> {
     }
 }
 
-/// Helper to pass PTY write handle into callbacks.
-/// SAFETY: The PtySession pointer is valid as long as TerminalState exists,
-/// which is owned by TerminalPanel. The panel outlives all callbacks.
-struct PtyWriteHandle {
-    pty: *const PtySession,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_shell_title() {
+        assert_eq!(next_shell_title(&[]), "Shell");
+        assert_eq!(next_shell_title(&["cargo test"]), "Shell");
+        assert_eq!(next_shell_title(&["Shell"]), "Shell 2");
+        assert_eq!(
+            next_shell_title(&["Shell", "Shell 2", "Shell 4"]),
+            "Shell 3"
+        );
+    }
 
-// SAFETY: PtySession.write() is thread-safe (uses Arc<Mutex<Writer>>)
-unsafe impl Send for PtyWriteHandle {}
-unsafe impl Sync for PtyWriteHandle {}
+    #[test]
+    fn test_styled_font() {
+        assert_eq!(styled_font(Font::Courier, true, false), Font::CourierBold);
+        assert_eq!(
+            styled_font(Font::Courier, true, true),
+            Font::CourierBoldItalic
+        );
+        assert_eq!(styled_font(Font::Screen, false, true), Font::Screen);
+        // Fonts without built-in variants are left alone
+        assert_eq!(styled_font(Font::Symbol, true, true), Font::Symbol);
+    }
+
+    #[test]
+    fn test_neighbor_after_close() {
+        assert_eq!(neighbor_after_close(0, 0), None);
+        // Closing the middle of three → the tab that slid into its slot
+        assert_eq!(neighbor_after_close(2, 1), Some(1));
+        // Closing the last tab → its left neighbor
+        assert_eq!(neighbor_after_close(2, 2), Some(1));
+        assert_eq!(neighbor_after_close(1, 0), Some(0));
+    }
+}
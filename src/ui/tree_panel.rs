@@ -96,6 +96,8 @@ pub struct TreePanel {
     /// Maintained via TreeReason::Opened/Closed callbacks. Used to preserve
     /// expansion state across tree rebuilds (refresh, move, rename, etc.).
     expanded_paths: Rc<RefCell<HashSet<String>>>,
+    /// File waiting to be revealed once a lazily loaded folder arrives
+    pending_reveal: Option<String>,
 }
 
 impl TreePanel {
@@ -239,6 +241,7 @@ impl TreePanel {
             drag_source_path: Rc::new(RefCell::new(None)),
             dragging: Rc::new(Cell::new(false)),
             expanded_paths: Rc::new(RefCell::new(HashSet::new())),
+            pending_reveal: None,
         }
     }
 
@@ -346,6 +349,72 @@ impl TreePanel {
         self.container.show();
         self.visible = true;
         self.container.redraw();
+
+        // Finish a reveal that was waiting for a lazy folder to load
+        if let Some(path) = self.pending_reveal.take() {
+            self.reveal_path(&path);
+        }
+    }
+
+    /// Select and scroll to the item for `file_path`, opening the folders
+    /// above it. A lazily loaded folder on the way is requested from the
+    /// plugin first, and the reveal finishes when the refreshed tree arrives.
+    /// Returns false when the panel is hidden or the file lies outside the
+    /// tree's project.
+    pub fn reveal_path(&mut self, file_path: &str) -> bool {
+        self.pending_reveal = None;
+        if !self.visible {
+            return false;
+        }
+        let (Some(root), Some(session_id)) = (self.context_path.as_deref(), self.session_id) else {
+            return false;
+        };
+        let Ok(relative) = std::path::Path::new(file_path).strip_prefix(root) else {
+            return false;
+        };
+        let segments: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+
+        // The project root item is the only child of FLTK's hidden root
+        let Some(mut item) = self.tree.root().and_then(|r| r.child(0)) else {
+            return false;
+        };
+        for (depth, segment) in segments.iter().enumerate() {
+            let node_path = segments[..depth].to_vec();
+            self.expanded_paths
+                .borrow_mut()
+                .insert(Self::node_path_key(&node_path));
+            item.open();
+
+            let is_lazy = item
+                .child(0)
+                .and_then(|c| c.label())
+                .is_some_and(|l| l.contains(LAZY_PLACEHOLDER_LABEL));
+            if is_lazy {
+                self.pending_reveal = Some(file_path.to_string());
+                self.sender.send(Message::TreeViewNodeExpanded {
+                    session_id,
+                    node_path,
+                });
+                return true;
+            }
+
+            let child = (0..item.children())
+                .filter_map(|i| item.child(i))
+                .find(|c| c.label().is_some_and(|l| Self::strip_icon(&l) == *segment));
+            match child {
+                Some(child) => item = child,
+                None => return false,
+            }
+        }
+
+        self.tree.set_item_focus(&item);
+        let _ = self.tree.select_only(&item, false);
+        self.tree.show_item_middle(&item);
+        self.tree.redraw();
+        true
     }
 
     /// Show a "Loading..." placeholder in the tree panel.
@@ -870,6 +939,7 @@ impl TreePanel {
         *self.drag_source_path.borrow_mut() = None;
         self.dragging.set(false);
         self.expanded_paths.borrow_mut().clear();
+        self.pending_reveal = None;
     }
 
     /// Check if the panel is visible
@@ -35,6 +35,52 @@ fn uninstall_only_deletes_directory() {
     assert!(!plugins_dir.join("python-lint").exists());
 }
 
+#[test]
+fn uninstall_removes_plugin_storage() {
+    let tmp = TempDir::new().unwrap();
+    let plugins_dir = tmp.path().join("plugins");
+    let data_dir = tmp.path().join("plugin-data");
+
+    create_fake_plugin(&plugins_dir, "python-lint");
+    fs::create_dir_all(&data_dir).unwrap();
+    fs::write(data_dir.join("python-lint.json"), "{}").unwrap();
+    fs::write(data_dir.join("other-plugin.json"), "{}").unwrap();
+
+    let (deleted, errors) =
+        apply_plugin_uninstalls(&plugins_dir, &[], &["Python Lint".to_string()]);
+
+    assert!(errors.is_empty());
+    assert_eq!(deleted, vec!["Python Lint"]);
+    assert!(!data_dir.join("python-lint.json").exists());
+    assert!(
+        data_dir.join("other-plugin.json").exists(),
+        "Other plugins' storage must be left alone"
+    );
+}
+
+#[test]
+fn failed_uninstall_keeps_plugin_storage() {
+    let tmp = TempDir::new().unwrap();
+    let plugins_dir = tmp.path().join("plugins");
+    let data_dir = tmp.path().join("plugin-data");
+
+    // A file where the plugin directory should be can't be removed as one
+    fs::create_dir_all(&plugins_dir).unwrap();
+    fs::write(plugins_dir.join("python-lint"), "not a directory").unwrap();
+    fs::create_dir_all(&data_dir).unwrap();
+    fs::write(data_dir.join("python-lint.json"), "{}").unwrap();
+
+    let (deleted, errors) =
+        apply_plugin_uninstalls(&plugins_dir, &[], &["python-lint".to_string()]);
+
+    assert_eq!(errors.len(), 1);
+    assert!(deleted.is_empty());
+    assert!(
+        data_dir.join("python-lint.json").exists(),
+        "Storage must survive a failed uninstall"
+    );
+}
+
 #[test]
 fn install_only_does_not_delete() {
     let tmp = TempDir::new().unwrap();
@@ -1,6 +1,9 @@
 mod common;
 
-use ferris_pad::app::services::session::{DocumentSession, GroupSession, SessionData};
+use ferris_pad::app::controllers::panes::SplitOrientation;
+use ferris_pad::app::services::session::{
+    DocumentSession, EditorLayoutSession, GroupSession, PaneSession, SessionData,
+};
 use std::fs;
 use tempfile::tempdir;
 
@@ -20,6 +23,11 @@ fn test_session_roundtrip_via_disk() {
                 temp_file: None,
                 was_dirty: false,
                 group_index: Some(0),
+                pinned: true,
+                top_line: 40,
+                horiz_offset: 16,
+                selection: Some((50, 55)),
+                bookmarks: vec![2, 41],
             },
             DocumentSession {
                 file_path: None,
@@ -28,11 +36,30 @@ fn test_session_roundtrip_via_disk() {
                 temp_file: Some("deadbeef.tmp".to_string()),
                 was_dirty: true,
                 group_index: None,
+                pinned: false,
+                top_line: 0,
+                horiz_offset: 0,
+                selection: None,
+                bookmarks: Vec::new(),
             },
         ],
         last_open_directory: Some("/home/user".to_string()),
         groups: vec![],
         instance_id: Some("9999".to_string()),
+        editor_layout: Some(EditorLayoutSession {
+            orientation: SplitOrientation::Vertical,
+            panes: vec![
+                PaneSession {
+                    document_index: Some(0),
+                    cursor_position: 55,
+                },
+                PaneSession {
+                    document_index: Some(1),
+                    cursor_position: 3,
+                },
+            ],
+            focused: 0,
+        }),
     };
 
     let json = serde_json::to_string_pretty(&data).unwrap();
@@ -50,6 +77,14 @@ fn test_session_roundtrip_via_disk() {
     );
     assert_eq!(loaded.documents[0].cursor_position, 55);
     assert_eq!(loaded.documents[0].group_index, Some(0));
+    assert!(loaded.documents[0].pinned);
+    assert!(!loaded.documents[1].pinned);
+    assert_eq!(loaded.documents[0].top_line, 40);
+    assert_eq!(loaded.documents[0].horiz_offset, 16);
+    assert_eq!(loaded.documents[0].selection, Some((50, 55)));
+    assert!(loaded.documents[1].selection.is_none());
+    assert_eq!(loaded.documents[0].bookmarks, vec![2, 41]);
+    assert!(loaded.documents[1].bookmarks.is_empty());
     assert_eq!(loaded.documents[1].display_name, "Untitled");
     assert!(loaded.documents[1].was_dirty);
     assert_eq!(
@@ -58,6 +93,11 @@ fn test_session_roundtrip_via_disk() {
     );
     assert_eq!(loaded.last_open_directory.as_deref(), Some("/home/user"));
     assert_eq!(loaded.instance_id.as_deref(), Some("9999"));
+    let layout = loaded.editor_layout.unwrap();
+    assert_eq!(layout.orientation, SplitOrientation::Vertical);
+    assert_eq!(layout.panes.len(), 2);
+    assert_eq!(layout.panes[1].document_index, Some(1));
+    assert_eq!(layout.focused, 0);
 }
 
 #[test]
@@ -72,6 +112,11 @@ fn test_session_with_groups_roundtrip() {
             temp_file: None,
             was_dirty: false,
             group_index: Some(1),
+            pinned: false,
+            top_line: 0,
+            horiz_offset: 0,
+            selection: None,
+            bookmarks: Vec::new(),
         }],
         last_open_directory: None,
         groups: vec![
@@ -92,6 +137,7 @@ fn test_session_with_groups_roundtrip() {
             },
         ],
         instance_id: None,
+        editor_layout: None,
     };
 
     let json = serde_json::to_string(&data).unwrap();
@@ -143,6 +189,11 @@ fn test_session_unicode_paths() {
                 temp_file: None,
                 was_dirty: false,
                 group_index: None,
+                pinned: false,
+                top_line: 0,
+                horiz_offset: 0,
+                selection: None,
+                bookmarks: Vec::new(),
             },
             DocumentSession {
                 file_path: Some("/tmp/\u{1F600}emoji.md".to_string()),
@@ -151,11 +202,17 @@ fn test_session_unicode_paths() {
                 temp_file: None,
                 was_dirty: false,
                 group_index: None,
+                pinned: false,
+                top_line: 0,
+                horiz_offset: 0,
+                selection: None,
+                bookmarks: Vec::new(),
             },
         ],
         last_open_directory: Some("/home/user/\u{6587}\u{4EF6}\u{5939}".to_string()),
         groups: vec![],
         instance_id: None,
+        editor_layout: None,
     };
 
     let json = serde_json::to_string(&data).unwrap();
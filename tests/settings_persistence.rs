@@ -6,6 +6,7 @@ use ferris_pad::app::domain::settings::{
     AppSettings, PluginApprovals, ShortcutOverride, SyntaxTheme, ThemeMode,
 };
 use ferris_pad::app::services::session::SessionRestore;
+use ferris_pad::app::services::tasks::{MatcherSpec, TaskDefinition, TaskRunMode};
 use ferris_pad::app::services::updater::UpdateChannel;
 
 #[test]
@@ -29,6 +30,8 @@ fn test_settings_roundtrip_all_fields() {
         preview_enabled: true,
         syntax_theme_light: SyntaxTheme::InspiredGitHub,
         syntax_theme_dark: SyntaxTheme::SolarizedDark,
+        custom_syntax_theme_light: None,
+        custom_syntax_theme_dark: Some("midnight".to_string()),
         tab_size: 2,
         use_spaces: true,
         plugins_enabled: false,
@@ -42,6 +45,16 @@ fn test_settings_roundtrip_all_fields() {
         shortcut_overrides: HashMap::new(),
         large_file_warning_mb: 100,
         max_editable_size_mb: 200,
+        terminal_clipboard_write: true,
+        tasks: vec![TaskDefinition {
+            name: "build".to_string(),
+            command: "cargo build".to_string(),
+            cwd: None,
+            problem_matchers: vec![MatcherSpec::Builtin("rustc".to_string())],
+            run_in: TaskRunMode::Background,
+        }],
+        recent_commands: vec!["File/Save".to_string()],
+        project_sessions: false,
     };
 
     let json = serde_json::to_string_pretty(&settings).unwrap();